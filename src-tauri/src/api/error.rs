@@ -0,0 +1,170 @@
+//! Crate-wide structured API error.
+//!
+//! Every endpoint family used to declare its own `{error, code}` struct
+//! (`ErrorResponse`, `HistoryErrorResponse`, `ChangesErrorResponse`,
+//! `ToolErrorResponse`, `LatestErrorResponse`) — five identical shapes with
+//! five OpenAPI schema names. [`ApiError`] replaces all of them; the old names
+//! survive as type aliases so each module keeps its local vocabulary.
+//!
+//! On the wire an error serializes as:
+//!
+//! ```json
+//! {"error": "...", "code": 404, "category": "not_found", "requestId": "..."}
+//! ```
+//!
+//! - `code` is the numeric HTTP status (kept for backward compatibility).
+//! - `category` is a machine-readable classification derived from the status
+//!   (`validation`, `not_found`, `upstream`, ...) so clients can branch without
+//!   parsing message text.
+//! - `requestId` is the per-request correlation id assigned by
+//!   `request_id_middleware`, echoed in the `x-request-id` response header —
+//!   it lets users quote an id from an error payload and find the matching
+//!   access-log line.
+//!
+//! Handlers can either return `ApiError` directly (it implements
+//! `IntoResponse`) or keep the existing `(StatusCode, Json<ApiError>)` tuple
+//! style — both produce the same body.
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Serialize, Serializer};
+use std::borrow::Cow;
+
+tokio::task_local! {
+    /// Per-request correlation id. Scoped around each request by
+    /// `request_id_middleware` so error serialization (which has no access to
+    /// the request) can still stamp the id into the payload.
+    pub static REQUEST_ID: String;
+}
+
+/// Structured API error used by all handlers.
+///
+/// Construct via the category helpers (`validation`, `not_found`, `upstream`,
+/// `internal`) or — for the existing handler code — via a plain struct literal
+/// `ApiError { error, code }`. The category and request id are derived at
+/// serialization time, so literal construction stays two fields.
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    /// Human-readable error message
+    pub error: String,
+    /// Numeric HTTP status code
+    pub code: u16,
+}
+
+impl ApiError {
+    /// Generic constructor for arbitrary status codes.
+    pub fn new(code: u16, error: impl Into<String>) -> Self {
+        Self {
+            error: error.into(),
+            code,
+        }
+    }
+
+    /// 400 — the request itself is malformed (bad parameter, bad cursor, ...).
+    pub fn validation(error: impl Into<String>) -> Self {
+        Self::new(400, error)
+    }
+
+    /// 404 — the addressed resource does not exist.
+    pub fn not_found(error: impl Into<String>) -> Self {
+        Self::new(404, error)
+    }
+
+    /// 502 — a dependency (Jira, Gemini, git) failed.
+    pub fn upstream(error: impl Into<String>) -> Self {
+        Self::new(502, error)
+    }
+
+    /// 500 — something went wrong on our side.
+    pub fn internal(error: impl Into<String>) -> Self {
+        Self::new(500, error)
+    }
+
+    /// Machine-readable category derived from the status code.
+    pub fn category(&self) -> &'static str {
+        match self.code {
+            400 | 422 => "validation",
+            401 | 403 => "auth",
+            404 => "not_found",
+            409 => "conflict",
+            429 => "rate_limited",
+            502 | 503 | 504 => "upstream",
+            _ => "internal",
+        }
+    }
+}
+
+/// Wire shape of [`ApiError`]. Exists as a separate struct so the derived
+/// serializer and OpenAPI schema include the computed fields.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiErrorBody {
+    /// Human-readable error message
+    pub error: String,
+    /// Numeric HTTP status code
+    pub code: u16,
+    /// Machine-readable category: validation, auth, not_found, conflict, rate_limited, upstream, internal
+    pub category: &'static str,
+    /// Correlation id for this request (also in the x-request-id response header)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl Serialize for ApiError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ApiErrorBody {
+            error: self.error.clone(),
+            code: self.code,
+            category: self.category(),
+            request_id: REQUEST_ID.try_with(|id| id.clone()).ok(),
+        }
+        .serialize(serializer)
+    }
+}
+
+// Schema delegates to ApiErrorBody so `body = SomeErrorAlias` in utoipa path
+// macros documents the full wire shape under a single "ApiError" name.
+impl utoipa::PartialSchema for ApiError {
+    fn schema() -> utoipa::openapi::RefOr<utoipa::openapi::schema::Schema> {
+        <ApiErrorBody as utoipa::PartialSchema>::schema()
+    }
+}
+
+impl utoipa::ToSchema for ApiError {
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("ApiError")
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status =
+            StatusCode::from_u16(self.code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        (status, Json(self)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_category_mapping() {
+        assert_eq!(ApiError::validation("x").category(), "validation");
+        assert_eq!(ApiError::not_found("x").category(), "not_found");
+        assert_eq!(ApiError::upstream("x").category(), "upstream");
+        assert_eq!(ApiError::internal("x").category(), "internal");
+        assert_eq!(ApiError::new(418, "x").category(), "internal");
+    }
+
+    #[test]
+    fn test_serializes_with_category() {
+        let json = serde_json::to_value(ApiError::not_found("no such task")).unwrap();
+        assert_eq!(json["error"], "no such task");
+        assert_eq!(json["code"], 404);
+        assert_eq!(json["category"], "not_found");
+        // No request scope in tests — requestId is omitted entirely.
+        assert!(json.get("requestId").is_none());
+    }
+}
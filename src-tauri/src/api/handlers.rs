@@ -1,11 +1,14 @@
 use axum::{
-    extract::{Query, State},
+    extract::{Path, Query, State},
     http::StatusCode,
-    Json,
+    Extension, Json,
 };
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use crate::api::retry;
 use crate::state::{AccessLogEntry, InferenceLogEntry, AppState};
 use std::time::Instant;
 
@@ -22,6 +25,36 @@ pub struct ChatRequest {
     /// Optional model to use (defaults to "gemini-2.0-flash")
     #[serde(default)]
     pub model: Option<String>,
+    /// Optional image attachments (screenshots of errors, UI states, ...)
+    #[serde(default)]
+    pub images: Vec<ImageAttachment>,
+    /// When true, retrieve relevant excerpts from past tasks and inject
+    /// them as context before the message ("how did I fix this last week?")
+    #[serde(default)]
+    pub include_history_context: bool,
+    /// When true, expose the runtime's enabled tools to the model and loop
+    /// through its function calls (executed via the ToolRuntime choke-point)
+    /// until it produces a final text answer
+    #[serde(default)]
+    pub use_tools: bool,
+}
+
+/// An image attached to a chat message, as base64 data or a local file path.
+///
+/// Mapped to Gemini `inline_data` parts; an OpenAI provider would map the
+/// same attachment to an `image_url` content part.
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageAttachment {
+    /// Base64-encoded image bytes (without a `data:` URL prefix)
+    #[serde(default)]
+    pub data: Option<String>,
+    /// Local file path to read the image from (alternative to `data`)
+    #[serde(default)]
+    pub path: Option<String>,
+    /// MIME type, e.g. "image/png"; inferred from the path extension when omitted
+    #[serde(default)]
+    pub mime_type: Option<String>,
 }
 
 /// A single chat message in the conversation
@@ -40,23 +73,93 @@ pub struct ChatResponse {
     pub response: String,
     /// The updated conversation history
     pub history: Vec<ChatMessage>,
+    /// Tool calls executed while answering (empty for plain chat)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<ChatToolCall>,
+}
+
+/// A tool call the model made (and the runtime executed) during a chat turn
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatToolCall {
+    /// Operation ID, e.g. "get_jira_list"
+    pub operation_id: String,
+    /// Arguments the model supplied
+    pub args: serde_json::Value,
+    pub success: bool,
+    pub duration_ms: u64,
 }
 
 /// Gemini API request structures
 #[derive(Debug, Serialize)]
 struct GeminiRequest {
     contents: Vec<GeminiContent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<GeminiTool>>,
+    #[serde(rename = "toolConfig", skip_serializing_if = "Option::is_none")]
+    tool_config: Option<GeminiToolConfig>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 struct GeminiContent {
     role: String,
     parts: Vec<GeminiPart>,
 }
 
-#[derive(Debug, Serialize)]
-struct GeminiPart {
-    text: String,
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+enum GeminiPart {
+    Text { text: String },
+    InlineData { inline_data: GeminiInlineData },
+    FunctionCall {
+        #[serde(rename = "functionCall")]
+        function_call: GeminiFunctionCall,
+    },
+    FunctionResponse {
+        #[serde(rename = "functionResponse")]
+        function_response: GeminiFunctionResponse,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct GeminiInlineData {
+    mime_type: String,
+    data: String,
+}
+
+/// One tool group for the Gemini API; declarations come from
+/// [`crate::tool_runtime::ToolRuntime::function_declarations`]
+#[derive(Debug, Clone, Serialize)]
+struct GeminiTool {
+    #[serde(rename = "functionDeclarations")]
+    function_declarations: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct GeminiToolConfig {
+    #[serde(rename = "functionCallingConfig")]
+    function_calling_config: GeminiFunctionCallingConfig,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct GeminiFunctionCallingConfig {
+    /// "AUTO" | "ANY" | "NONE"
+    mode: String,
+}
+
+/// A function call requested by the model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GeminiFunctionCall {
+    name: String,
+    #[serde(default)]
+    args: serde_json::Value,
+}
+
+/// A tool result fed back to the model
+#[derive(Debug, Clone, Serialize)]
+struct GeminiFunctionResponse {
+    name: String,
+    response: serde_json::Value,
 }
 
 /// Gemini API response structures
@@ -76,9 +179,13 @@ struct GeminiResponseContent {
     parts: Vec<GeminiResponsePart>,
 }
 
+/// A response part: plain text, or a function call the model wants executed
 #[derive(Debug, Deserialize)]
 struct GeminiResponsePart {
-    text: String,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default, rename = "functionCall")]
+    function_call: Option<GeminiFunctionCall>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -117,12 +224,8 @@ pub struct JiraListResponse {
     pub jql: String,
 }
 
-/// Error response
-#[derive(Serialize, utoipa::ToSchema)]
-pub struct ErrorResponse {
-    pub error: String,
-    pub code: u16,
-}
+/// Error response — alias for the crate-wide structured error.
+pub type ErrorResponse = crate::api::error::ApiError;
 
 // ============ Gemini Models Types ============
 
@@ -162,6 +265,74 @@ pub struct GeminiModel {
     /// Top-K value for sampling
     #[serde(default)]
     pub top_k: Option<u32>,
+    // Annotations below are filled in by `annotate_models`; the upstream
+    // models API doesn't supply them
+    /// Provider that serves this model (always "gemini" today)
+    #[serde(default)]
+    pub provider: Option<String>,
+    /// Published pricing, when the model is on the known price list
+    #[serde(default)]
+    pub pricing: Option<ModelPricing>,
+    /// Usable context window (the input token limit)
+    #[serde(default)]
+    pub context_window: Option<u32>,
+    /// Set for the stable models we recommend for agent/chat use
+    #[serde(default)]
+    pub recommended: bool,
+}
+
+/// Published pricing in USD per one million tokens
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelPricing {
+    pub input_per_million: f64,
+    pub output_per_million: f64,
+}
+
+/// Stable model ids worth surfacing first in a picker
+const RECOMMENDED_MODELS: &[&str] = &["gemini-2.5-pro", "gemini-2.5-flash", "gemini-2.0-flash"];
+
+/// Published Gemini pricing (USD per 1M tokens) keyed by model id prefix.
+/// The models API doesn't expose pricing, so this table tracks the public
+/// price list; unknown models simply carry no pricing.
+fn pricing_for(model_id: &str) -> Option<ModelPricing> {
+    const TABLE: &[(&str, f64, f64)] = &[
+        ("gemini-2.5-pro", 1.25, 10.0),
+        ("gemini-2.5-flash-lite", 0.10, 0.40),
+        ("gemini-2.5-flash", 0.30, 2.50),
+        ("gemini-2.0-flash-lite", 0.075, 0.30),
+        ("gemini-2.0-flash", 0.10, 0.40),
+        ("gemini-1.5-pro", 1.25, 5.0),
+        ("gemini-1.5-flash", 0.075, 0.30),
+    ];
+    // Longest-prefix match so "-lite" variants aren't shadowed by their base
+    TABLE
+        .iter()
+        .filter(|(prefix, _, _)| model_id.starts_with(prefix))
+        .max_by_key(|(prefix, _, _)| prefix.len())
+        .map(|(_, input, output)| ModelPricing {
+            input_per_million: *input,
+            output_per_million: *output,
+        })
+}
+
+/// Fill in the provider/pricing/context-window/recommended annotations
+fn annotate_models(models: &mut [GeminiModel]) {
+    for model in models {
+        let id = model
+            .name
+            .strip_prefix("models/")
+            .unwrap_or(&model.name)
+            .to_string();
+        model.provider = Some("gemini".to_string());
+        model.pricing = pricing_for(&id);
+        model.context_window = model.input_token_limit;
+        model.recommended = RECOMMENDED_MODELS.contains(&id.as_str())
+            && model
+                .supported_generation_methods
+                .iter()
+                .any(|m| m == "generateContent");
+    }
 }
 
 /// Response from the list models endpoint
@@ -172,6 +343,11 @@ pub struct GeminiModelsResponse {
     pub models: Vec<GeminiModel>,
     /// Total count of models
     pub total: usize,
+    /// When this list was fetched from the Gemini API (RFC 3339)
+    pub fetched_at: String,
+    /// True when the list is older than the scheduler's refresh interval —
+    /// use POST /agent/models/refresh to force an update
+    pub expired: bool,
 }
 
 /// Internal struct for parsing Gemini API models list response
@@ -288,6 +464,210 @@ pub struct AccessLogsResponse {
     pub total: usize,
 }
 
+/// Get effective configuration
+///
+/// Returns the currently effective `config.toml` values (after hot reload),
+/// with secrets masked, plus the path of the file itself.
+#[utoipa::path(
+    get,
+    path = "/system/config",
+    responses(
+        (status = 200, description = "Effective configuration with secrets masked")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "system"
+)]
+pub async fn system_config_handler() -> Json<serde_json::Value> {
+    Json(crate::config::masked_effective())
+}
+
+/// Request body for updating provider keys
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct UpdateProvidersRequest {
+    /// New Gemini API key
+    pub gemini_api_key: Option<String>,
+}
+
+/// Response for the provider key update endpoint
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateProvidersResponse {
+    /// Providers whose keys were rotated
+    pub updated: Vec<String>,
+    pub message: String,
+}
+
+/// Set or rotate provider API keys
+///
+/// Persists the new key in the secrets store and swaps it into the running
+/// state atomically — requests built after the swap use the new key, no
+/// restart needed. A `GEMINI_API_KEY` environment variable still wins on
+/// the next startup.
+#[utoipa::path(
+    put,
+    path = "/system/providers",
+    request_body = UpdateProvidersRequest,
+    responses(
+        (status = 200, description = "Keys rotated", body = UpdateProvidersResponse),
+        (status = 400, description = "No keys in request", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "system"
+)]
+pub async fn update_providers_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<UpdateProvidersRequest>,
+) -> Result<Json<UpdateProvidersResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let key = request
+        .gemini_api_key
+        .as_deref()
+        .map(str::trim)
+        .filter(|k| !k.is_empty());
+    let Some(key) = key else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse::validation(
+                "No provider keys in request (expected gemini_api_key)",
+            )),
+        ));
+    };
+
+    if let Err(e) = crate::secrets::set("gemini_api_key", key) {
+        crate::audit::record(
+            "providers.update",
+            serde_json::json!({ "provider": "gemini" }),
+            false,
+            &e,
+        );
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse::internal(e)),
+        ));
+    }
+    state.set_gemini_api_key(key.to_string());
+    crate::audit::record(
+        "providers.update",
+        serde_json::json!({ "provider": "gemini" }),
+        true,
+        "Gemini API key rotated",
+    );
+    log::info!("Gemini API key rotated at runtime ({}...)", &key[..8.min(key.len())]);
+
+    Ok(Json(UpdateProvidersResponse {
+        updated: vec!["gemini".to_string()],
+        message: "Gemini API key rotated; in-flight requests finish with the old key".to_string(),
+    }))
+}
+
+/// Request body for backup restore
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct RestoreRequest {
+    /// Absolute path of a backup archive created by POST /system/backup
+    pub path: String,
+}
+
+/// Create a backup archive
+///
+/// Zips the config dir and all application data (notes, policies, fixtures,
+/// macros, profiles, caches) into `<config dir>/backups/` and returns the
+/// archive path. API tokens are never included.
+#[utoipa::path(
+    post,
+    path = "/system/backup",
+    responses(
+        (status = 200, description = "Backup archive written", body = crate::backup::BackupResponse),
+        (status = 500, description = "Backup failed", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "system"
+)]
+pub async fn system_backup_handler(
+) -> Result<Json<crate::backup::BackupResponse>, (StatusCode, Json<ErrorResponse>)> {
+    log::info!("REST API: system/backup called");
+    let result = tokio::task::spawn_blocking(|| crate::backup::create_backup(None)).await;
+    match result {
+        Ok(Ok(response)) => Ok(Json(response)),
+        Ok(Err(e)) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse { error: e, code: 500 }),
+        )),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Backup task panicked: {}", e),
+                code: 500,
+            }),
+        )),
+    }
+}
+
+/// Restore a backup archive
+///
+/// Validates the archive's manifest version, then extracts it over the
+/// config dir and application data dir. Archives from a newer app are
+/// rejected; an app-version mismatch is reported as a warning.
+#[utoipa::path(
+    post,
+    path = "/system/restore",
+    request_body = RestoreRequest,
+    responses(
+        (status = 200, description = "Backup restored", body = crate::backup::RestoreResponse),
+        (status = 400, description = "Invalid or incompatible archive", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "system"
+)]
+pub async fn system_restore_handler(
+    Json(request): Json<RestoreRequest>,
+) -> Result<Json<crate::backup::RestoreResponse>, (StatusCode, Json<ErrorResponse>)> {
+    log::info!("REST API: system/restore called for {}", request.path);
+    let params = serde_json::json!({ "path": request.path });
+    let result =
+        tokio::task::spawn_blocking(move || crate::backup::restore_backup(&request.path)).await;
+    match result {
+        Ok(Ok(response)) => {
+            crate::audit::record(
+                "system.restore",
+                params,
+                true,
+                &format!("{} entries restored", response.entries_restored),
+            );
+            Ok(Json(response))
+        }
+        Ok(Err(e)) => {
+            crate::audit::record("system.restore", params, false, &e);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse { error: e, code: 400 }),
+            ))
+        }
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: format!("Restore task panicked: {}", e),
+                code: 500,
+            }),
+        )),
+    }
+}
+
+/// Get background job status
+///
+/// Returns the scheduler's view of every periodic job: effective interval,
+/// last run time, duration, outcome and run/failure counts.
+#[utoipa::path(
+    get,
+    path = "/system/jobs",
+    responses(
+        (status = 200, description = "Background job status", body = crate::scheduler::JobsResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "system"
+)]
+pub async fn system_jobs_handler() -> Json<crate::scheduler::JobsResponse> {
+    Json(crate::scheduler::jobs_response())
+}
+
 /// Get access logs
 /// 
 /// Returns a list of all HTTP access log entries.
@@ -305,6 +685,278 @@ pub async fn access_logs_handler(State(state): State<Arc<AppState>>) -> Json<Acc
     Json(AccessLogsResponse { logs, total })
 }
 
+/// Response for the auth audit endpoint
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthAuditResponse {
+    /// Rejected requests, oldest first (last 500 retained)
+    pub entries: Vec<crate::api::middleware::AuthAuditEntry>,
+    pub total: usize,
+    /// Clients currently locked out after repeated failures
+    pub locked_clients: Vec<crate::api::middleware::LockedClient>,
+}
+
+/// Get auth audit log
+///
+/// Returns every rejected request since startup (missing header, invalid
+/// token or lockout) plus the clients currently locked out.
+#[utoipa::path(
+    get,
+    path = "/auth/audit",
+    responses(
+        (status = 200, description = "Auth audit log", body = AuthAuditResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "system"
+)]
+pub async fn auth_audit_handler() -> Json<AuthAuditResponse> {
+    let entries = crate::api::middleware::audit_entries();
+    let total = entries.len();
+    Json(AuthAuditResponse {
+        entries,
+        total,
+        locked_clients: crate::api::middleware::locked_clients(),
+    })
+}
+
+/// Query parameters for the audit trail endpoint
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct SystemAuditQuery {
+    /// Only include actions with this prefix (e.g. "changes.")
+    pub action: Option<String>,
+    /// Return at most the newest N entries
+    pub limit: Option<usize>,
+}
+
+/// Response for the audit trail endpoint
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemAuditResponse {
+    /// Audited actions, oldest first
+    pub entries: Vec<crate::audit::AuditEntry>,
+    pub total: usize,
+}
+
+/// Get the audit trail
+///
+/// Returns the recorded write-class actions (nuke, restore, apply, ignore
+/// updates, settings changes, fixture imports) with actor, parameters and
+/// outcome. The full history lives in `audit_log.jsonl`; this endpoint
+/// serves the newest 1000 entries.
+#[utoipa::path(
+    get,
+    path = "/system/audit",
+    params(SystemAuditQuery),
+    responses(
+        (status = 200, description = "Audit trail entries", body = SystemAuditResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "system"
+)]
+pub async fn system_audit_handler(
+    Query(params): Query<SystemAuditQuery>,
+) -> Json<SystemAuditResponse> {
+    let entries = crate::audit::entries(params.action.as_deref(), params.limit);
+    let total = entries.len();
+    Json(SystemAuditResponse { entries, total })
+}
+
+/// Query parameters for the access log summary endpoint
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct AccessLogSummaryQuery {
+    /// Only aggregate requests from the last N minutes (default: all retained)
+    pub window_minutes: Option<u64>,
+}
+
+/// Aggregated stats for one route (method + normalized path)
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteStats {
+    /// "METHOD /path" with numeric segments collapsed to `{id}`
+    pub route: String,
+    pub count: usize,
+    pub error_count: usize,
+    pub error_rate: f64,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub p99_ms: u64,
+}
+
+/// Aggregated stats for one client (key fingerprint, falling back to IP)
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientStats {
+    /// Client key fingerprint, or `ip:<addr>` for unauthenticated requests
+    pub client: String,
+    pub count: usize,
+    pub error_count: usize,
+    /// Most common User-Agent seen for this client
+    pub user_agent: Option<String>,
+    pub last_seen: String,
+}
+
+/// Response for the access log summary endpoint
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessLogSummaryResponse {
+    /// Window that was aggregated, when one was requested
+    pub window_minutes: Option<u64>,
+    pub total: usize,
+    pub error_count: usize,
+    pub error_rate: f64,
+    pub p50_ms: u64,
+    pub p95_ms: u64,
+    pub p99_ms: u64,
+    pub routes: Vec<RouteStats>,
+    pub clients: Vec<ClientStats>,
+}
+
+/// Collapse purely numeric path segments so per-task routes aggregate
+/// together ("/changes/tasks/1712345678901/diff" → "/changes/tasks/{id}/diff").
+fn normalize_route(path: &str) -> String {
+    path.split('/')
+        .map(|segment| {
+            if !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit()) {
+                "{id}"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Pick a percentile from a sorted duration list (nearest-rank on n-1)
+fn percentile(sorted: &[u64], pct: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let idx = ((pct / 100.0) * (sorted.len() - 1) as f64).round() as usize;
+    sorted[idx.min(sorted.len() - 1)]
+}
+
+/// Get access log summary
+///
+/// Aggregates the retained access log into per-route and per-client stats
+/// with error rates and latency percentiles, optionally limited to a
+/// rolling window.
+#[utoipa::path(
+    get,
+    path = "/access-logs/summary",
+    params(AccessLogSummaryQuery),
+    responses(
+        (status = 200, description = "Aggregated access log stats", body = AccessLogSummaryResponse)
+    ),
+    tag = "system"
+)]
+pub async fn access_logs_summary_handler(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<AccessLogSummaryQuery>,
+) -> Json<AccessLogSummaryResponse> {
+    let cutoff = params
+        .window_minutes
+        .map(|m| chrono::Utc::now() - chrono::Duration::minutes(m as i64));
+
+    let logs: Vec<AccessLogEntry> = state
+        .get_access_logs()
+        .into_iter()
+        .filter(|entry| match cutoff {
+            None => true,
+            Some(cutoff) => match chrono::DateTime::parse_from_rfc3339(&entry.timestamp) {
+                Ok(ts) => ts.with_timezone(&chrono::Utc) >= cutoff,
+                Err(_) => true, // unparseable timestamps stay included
+            },
+        })
+        .collect();
+
+    let total = logs.len();
+    let error_count = logs.iter().filter(|e| e.status_code >= 400).count();
+    let mut durations: Vec<u64> = logs.iter().map(|e| e.duration_ms).collect();
+    durations.sort_unstable();
+
+    // Per-route aggregation
+    let mut per_route: HashMap<String, Vec<&AccessLogEntry>> = HashMap::new();
+    for entry in &logs {
+        let route = format!("{} {}", entry.method, normalize_route(&entry.path));
+        per_route.entry(route).or_default().push(entry);
+    }
+    let mut routes: Vec<RouteStats> = per_route
+        .into_iter()
+        .map(|(route, entries)| {
+            let count = entries.len();
+            let error_count = entries.iter().filter(|e| e.status_code >= 400).count();
+            let mut durations: Vec<u64> = entries.iter().map(|e| e.duration_ms).collect();
+            durations.sort_unstable();
+            RouteStats {
+                route,
+                count,
+                error_count,
+                error_rate: error_count as f64 / count as f64,
+                p50_ms: percentile(&durations, 50.0),
+                p95_ms: percentile(&durations, 95.0),
+                p99_ms: percentile(&durations, 99.0),
+            }
+        })
+        .collect();
+    routes.sort_by(|a, b| b.count.cmp(&a.count));
+
+    // Per-client aggregation — key fingerprint when authenticated, IP otherwise
+    let mut per_client: HashMap<String, Vec<&AccessLogEntry>> = HashMap::new();
+    for entry in &logs {
+        let client = entry
+            .client_key_id
+            .clone()
+            .unwrap_or_else(|| format!("ip:{}", entry.client_ip));
+        per_client.entry(client).or_default().push(entry);
+    }
+    let mut clients: Vec<ClientStats> = per_client
+        .into_iter()
+        .map(|(client, entries)| {
+            let count = entries.len();
+            let error_count = entries.iter().filter(|e| e.status_code >= 400).count();
+            let mut agent_counts: HashMap<&str, usize> = HashMap::new();
+            for entry in &entries {
+                if let Some(ua) = &entry.user_agent {
+                    *agent_counts.entry(ua.as_str()).or_default() += 1;
+                }
+            }
+            let user_agent = agent_counts
+                .into_iter()
+                .max_by_key(|(_, n)| *n)
+                .map(|(ua, _)| ua.to_string());
+            // Entries are appended in order, so the last one is newest
+            let last_seen = entries
+                .last()
+                .map(|e| e.timestamp.clone())
+                .unwrap_or_default();
+            ClientStats {
+                client,
+                count,
+                error_count,
+                user_agent,
+                last_seen,
+            }
+        })
+        .collect();
+    clients.sort_by(|a, b| b.count.cmp(&a.count));
+
+    Json(AccessLogSummaryResponse {
+        window_minutes: params.window_minutes,
+        total,
+        error_count,
+        error_rate: if total > 0 {
+            error_count as f64 / total as f64
+        } else {
+            0.0
+        },
+        p50_ms: percentile(&durations, 50.0),
+        p95_ms: percentile(&durations, 95.0),
+        p99_ms: percentile(&durations, 99.0),
+        routes,
+        clients,
+    })
+}
+
 /// Clear access logs
 /// 
 /// Clears all HTTP access log entries.
@@ -366,12 +1018,111 @@ pub async fn clear_inference_logs_handler(State(state): State<Arc<AppState>>) ->
     StatusCode::OK
 }
 
+/// Response for the inference log detail endpoint
+#[derive(Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InferenceLogDetailResponse {
+    /// The log entry itself (same shape as the list endpoint)
+    pub entry: InferenceLogEntry,
+    /// Full request/response bodies, when body capture was enabled for
+    /// this call (`logging.capture_inference_bodies`)
+    pub exchange: Option<crate::state::InferenceExchange>,
+}
+
+/// Get a single inference log entry with the full exchange
+///
+/// Returns the log entry plus the complete (size-capped, redacted) request
+/// and response bodies when `logging.capture_inference_bodies` was enabled
+/// at the time of the call. Useful for debugging prompt issues that the
+/// 100-char previews in the list endpoint can't show.
+#[utoipa::path(
+    get,
+    path = "/inference-logs/{id}",
+    params(
+        ("id" = u64, Path, description = "Inference log entry ID")
+    ),
+    responses(
+        (status = 200, description = "Log entry with optional full exchange", body = InferenceLogDetailResponse),
+        (status = 404, description = "No log entry with this ID", body = ErrorResponse)
+    ),
+    tag = "system"
+)]
+pub async fn inference_log_detail_handler(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<u64>,
+) -> Result<Json<InferenceLogDetailResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let entry = state.get_inference_log(id).ok_or((
+        StatusCode::NOT_FOUND,
+        Json(ErrorResponse {
+            error: format!("No inference log entry with id {}", id),
+            code: 404,
+        }),
+    ))?;
+    let exchange = state.get_inference_exchange(id);
+    Ok(Json(InferenceLogDetailResponse { entry, exchange }))
+}
+
 // ============ Agent/Chat Handlers ============
 
+/// Resolve an [`ImageAttachment`] to inline data for the Gemini API.
+///
+/// Base64 data is passed through; a file path is read and encoded. The MIME
+/// type falls back to the path extension, then to "image/png".
+fn resolve_image_attachment(image: &ImageAttachment) -> Result<GeminiInlineData, String> {
+    use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+
+    let (data, inferred_mime) = match (&image.data, &image.path) {
+        (Some(data), _) => (data.clone(), None),
+        (None, Some(path)) => {
+            let bytes = std::fs::read(path)
+                .map_err(|e| format!("Failed to read image '{}': {}", path, e))?;
+            let inferred = std::path::Path::new(path)
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(mime_for_extension);
+            (BASE64.encode(bytes), inferred)
+        }
+        (None, None) => {
+            return Err("Image attachment needs either 'data' or 'path'".to_string());
+        }
+    };
+
+    Ok(GeminiInlineData {
+        mime_type: image
+            .mime_type
+            .clone()
+            .or_else(|| inferred_mime.map(|m| m.to_string()))
+            .unwrap_or_else(|| "image/png".to_string()),
+        data,
+    })
+}
+
+/// MIME type for a common image file extension
+fn mime_for_extension(ext: &str) -> &'static str {
+    match ext.to_ascii_lowercase().as_str() {
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "bmp" => "image/bmp",
+        _ => "image/png",
+    }
+}
+
+/// Cap on model→tool→model rounds per chat turn, so a confused model can't
+/// loop through the runtime indefinitely
+const MAX_TOOL_ROUNDS: usize = 8;
+
+/// How many of one turn's tool calls may execute concurrently. Bounded so a
+/// model emitting a dozen calls can't stampede Jira or the local scanners.
+const MAX_PARALLEL_TOOL_CALLS: usize = 4;
+
 /// Chat with Gemini AI
-/// 
+///
 /// Sends a message to Google Gemini and returns the AI response.
-/// Supports conversation history for multi-turn conversations.
+/// Supports conversation history for multi-turn conversations. With
+/// `use_tools` set, the runtime's enabled tools are declared to the model
+/// and any function calls it makes are executed through the ToolRuntime
+/// choke-point, feeding results back until it produces a final answer.
 #[utoipa::path(
     post,
     path = "/agent/chat",
@@ -386,6 +1137,7 @@ pub async fn clear_inference_logs_handler(State(state): State<Arc<AppState>>) ->
 )]
 pub async fn chat_handler(
     State(state): State<Arc<AppState>>,
+    Extension(tool_runtime): Extension<Arc<crate::tool_runtime::ToolRuntime>>,
     Json(request): Json<ChatRequest>,
 ) -> Result<Json<ChatResponse>, (StatusCode, Json<ErrorResponse>)> {
     let start_time = Instant::now();
@@ -396,7 +1148,8 @@ pub async fn chat_handler(
         model, &request.message.chars().take(50).collect::<String>());
 
     // Check if Gemini API key is configured
-    if state.gemini_api_key.is_empty() || state.gemini_api_key == "YOUR_GEMINI_API_KEY_HERE" {
+    let gemini_api_key = state.gemini_api_key();
+    if gemini_api_key.is_empty() || gemini_api_key == "YOUR_GEMINI_API_KEY_HERE" {
         // Log failed inference attempt
         state.add_inference_log(
             "gemini".to_string(),
@@ -410,6 +1163,7 @@ pub async fn chat_handler(
             None,
             Some(user_message_preview),
             None,
+            None,
         );
         return Err((
             StatusCode::BAD_REQUEST,
@@ -420,176 +1174,443 @@ pub async fn chat_handler(
         ));
     }
 
-    // Build conversation contents for Gemini API
-    let mut contents: Vec<GeminiContent> = request
-        .history
-        .iter()
-        .map(|msg| GeminiContent {
-            role: msg.role.clone(),
-            parts: vec![GeminiPart { text: msg.content.clone() }],
-        })
-        .collect();
-
-    // Add the current user message
-    contents.push(GeminiContent {
-        role: "user".to_string(),
-        parts: vec![GeminiPart { text: request.message.clone() }],
-    });
-
-    let gemini_request = GeminiRequest { contents };
-
-    // Call Gemini API
-    let client = reqwest::Client::new();
-    let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
-        model, state.gemini_api_key
-    );
-
-    let response = client
-        .post(&url)
-        .header("Content-Type", "application/json")
-        .json(&gemini_request)
-        .send()
-        .await
-        .map_err(|e| {
-            log::error!("REST API: Failed to call Gemini API: {}", e);
+    // Reject unknown model ids up front with suggestions, instead of letting
+    // the upstream call fail with an opaque 404 mid-conversation
+    if let Err(e) = validate_model_id(model) {
+        state.add_inference_log(
+            "gemini".to_string(),
+            model.to_string(),
+            "chat".to_string(),
+            false,
+            Some(400),
+            start_time.elapsed().as_millis() as u64,
+            None, None, None,
+            Some(e.clone()),
+            None,
+            Some(user_message_preview.clone()),
+            None,
+            None,
+        );
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse { error: e, code: 400 }),
+        ));
+    }
+
+    // Compress older turns when the conversation approaches the model's
+    // input window: summarize them with the model itself and carry the
+    // summary forward in the returned history. Clients echo that history
+    // back on the next turn, so the summary persists for the session; the
+    // blunt oldest-first trim further down stays as a safety net.
+    let input_token_limit = cached_input_token_limit(model).unwrap_or(DEFAULT_INPUT_TOKEN_LIMIT);
+    let compression_budget = input_token_limit / 5 * 4;
+    let mut history = request.history.clone();
+    let mut compressed_turns = 0usize;
+    let estimated_prompt_tokens = history
+        .iter()
+        .map(|m| (m.content.len() as u32).div_ceil(4))
+        .sum::<u32>()
+        + (request.message.len() as u32).div_ceil(4)
+        + IMAGE_TOKENS * request.images.len() as u32;
+    if estimated_prompt_tokens > compression_budget && history.len() > KEEP_RECENT_MESSAGES {
+        let split = history.len() - KEEP_RECENT_MESSAGES;
+        match summarize_history(&gemini_api_key, model, &history[..split]).await {
+            Ok(summary) => {
+                log::info!(
+                    "REST API: compressed {} older turns into a {}-char summary",
+                    split,
+                    summary.len()
+                );
+                compressed_turns = split;
+                let mut compact = vec![ChatMessage {
+                    role: "user".to_string(),
+                    content: format!("[Summary of {} earlier turns]\n{}", split, summary),
+                }];
+                compact.extend_from_slice(&history[split..]);
+                history = compact;
+            }
+            Err(e) => {
+                log::warn!("REST API: history summarization failed, relying on trim: {}", e)
+            }
+        }
+    }
+
+    // Build conversation contents for Gemini API
+    let mut contents: Vec<GeminiContent> = history
+        .iter()
+        .map(|msg| GeminiContent {
+            role: msg.role.clone(),
+            parts: vec![GeminiPart::Text { text: msg.content.clone() }],
+        })
+        .collect();
+
+    // Optionally retrieve relevant past-task excerpts and inject them as
+    // a separate context turn before the actual question
+    let mut history_context_excerpts = 0;
+    if request.include_history_context {
+        let retrieved = crate::conversation_history::rag::retrieve_context(
+            &request.message,
+            crate::conversation_history::rag::DEFAULT_MAX_EXCERPTS,
+        )
+        .await;
+        history_context_excerpts = retrieved.len();
+        if !retrieved.is_empty() {
+            contents.push(GeminiContent {
+                role: "user".to_string(),
+                parts: vec![GeminiPart::Text {
+                    text: crate::conversation_history::rag::format_context(&retrieved),
+                }],
+            });
+        }
+    }
+
+    // Add the current user message, with any image attachments as inline data
+    let image_count = request.images.len();
+    let mut parts = vec![GeminiPart::Text { text: request.message.clone() }];
+    for image in &request.images {
+        let inline_data = resolve_image_attachment(image).map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse { error: e, code: 400 }),
+            )
+        })?;
+        parts.push(GeminiPart::InlineData { inline_data });
+    }
+    contents.push(GeminiContent {
+        role: "user".to_string(),
+        parts,
+    });
+
+    // Pre-flight trim: drop the oldest turns while the estimated token
+    // count exceeds the model's input window, so long conversations degrade
+    // by forgetting their start instead of failing upstream
+    let mut trimmed_turns = 0usize;
+    while contents.len() > 1 && estimate_tokens(&contents) > input_token_limit {
+        contents.remove(0);
+        trimmed_turns += 1;
+    }
+    if trimmed_turns > 0 {
+        log::warn!(
+            "REST API: trimmed {} oldest history turns to fit {}'s {}-token input window",
+            trimmed_turns,
+            model,
+            input_token_limit
+        );
+    }
+
+    // Expose the runtime's enabled tools when the client opted in
+    let tools = if request.use_tools {
+        let declarations = tool_runtime.function_declarations();
+        if declarations.is_empty() {
+            None
+        } else {
+            Some(vec![GeminiTool {
+                function_declarations: declarations,
+            }])
+        }
+    } else {
+        None
+    };
+    let tool_config = tools.as_ref().map(|_| GeminiToolConfig {
+        function_calling_config: GeminiFunctionCallingConfig {
+            mode: "AUTO".to_string(),
+        },
+    });
+
+    // Optionally capture the full exchange for GET /inference-logs/{id}
+    let capture_bodies = crate::config::current().logging.capture_inference_bodies;
+
+    // Call Gemini API
+    let client = crate::http::outbound_client();
+    let url = format!(
+        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
+        model, gemini_api_key
+    );
+    let retry_policy = retry::RetryPolicy::from_config();
+
+    let mut tool_calls: Vec<ChatToolCall> = Vec::new();
+    let mut rounds = 0usize;
+    let mut total_retries = 0u32;
+    // Only the last exchange of a multi-round turn is captured
+    let mut request_body = String::new();
+
+    // Function-calling loop: send the conversation, execute any function
+    // calls through the runtime, feed results back, repeat until the model
+    // answers with text (or the round cap is hit).
+    let (ai_response, final_response_text) = loop {
+        let gemini_request = GeminiRequest {
+            contents: contents.clone(),
+            tools: tools.clone(),
+            tool_config: tool_config.clone(),
+        };
+        if capture_bodies {
+            request_body = serde_json::to_string_pretty(&gemini_request).unwrap_or_default();
+        }
+
+        let outcome = retry::send_with_retry(
+            || {
+                client
+                    .post(&url)
+                    .header("Content-Type", "application/json")
+                    .json(&gemini_request)
+            },
+            &retry_policy,
+        )
+        .await;
+        total_retries += outcome.retries;
+        let retry_count = Some(total_retries);
+
+        let response = outcome
+            .result
+            .map_err(|e| {
+                log::error!("REST API: Failed to call Gemini API: {}", e);
+                // Log failed inference
+                state.add_inference_log(
+                    "gemini".to_string(),
+                    model.to_string(),
+                    "chat".to_string(),
+                    false,
+                    None,
+                    start_time.elapsed().as_millis() as u64,
+                    None, None, None,
+                    Some(format!("HTTP error: {}", e)),
+                    None,
+                    Some(user_message_preview.clone()),
+                    None,
+                    retry_count,
+                );
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse {
+                        error: format!("Failed to call Gemini API: {}", e),
+                        code: 500,
+                    }),
+                )
+            })?;
+
+        let status = response.status();
+        let response_text = response.text().await.map_err(|e| {
+            log::error!("REST API: Failed to read Gemini response: {}", e);
             // Log failed inference
             state.add_inference_log(
                 "gemini".to_string(),
                 model.to_string(),
                 "chat".to_string(),
                 false,
-                None,
+                Some(status.as_u16()),
                 start_time.elapsed().as_millis() as u64,
                 None, None, None,
-                Some(format!("HTTP error: {}", e)),
+                Some(format!("Failed to read response: {}", e)),
                 None,
                 Some(user_message_preview.clone()),
                 None,
+                retry_count,
             );
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ErrorResponse {
-                    error: format!("Failed to call Gemini API: {}", e),
+                    error: format!("Failed to read Gemini response: {}", e),
                     code: 500,
                 }),
             )
         })?;
 
-    let status = response.status();
-    let response_text = response.text().await.map_err(|e| {
-        log::error!("REST API: Failed to read Gemini response: {}", e);
-        // Log failed inference
-        state.add_inference_log(
-            "gemini".to_string(),
-            model.to_string(),
-            "chat".to_string(),
-            false,
-            Some(status.as_u16()),
-            start_time.elapsed().as_millis() as u64,
-            None, None, None,
-            Some(format!("Failed to read response: {}", e)),
-            None,
-            Some(user_message_preview.clone()),
-            None,
-        );
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: format!("Failed to read Gemini response: {}", e),
-                code: 500,
-            }),
-        )
-    })?;
+        if !status.is_success() {
+            log::error!("REST API: Gemini API error ({}): {}", status, response_text);
+            // Log failed inference
+            let log_id = state.add_inference_log(
+                "gemini".to_string(),
+                model.to_string(),
+                "chat".to_string(),
+                false,
+                Some(status.as_u16()),
+                start_time.elapsed().as_millis() as u64,
+                None, None, None,
+                Some(format!("API error: {}", response_text)),
+                None,
+                Some(user_message_preview),
+                None,
+                retry_count,
+            );
+            if capture_bodies {
+                state.add_inference_exchange(log_id, &url, request_body, response_text.clone());
+            }
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("Gemini API error: {}", response_text),
+                    code: status.as_u16(),
+                }),
+            ));
+        }
 
-    if !status.is_success() {
-        log::error!("REST API: Gemini API error ({}): {}", status, response_text);
-        // Log failed inference
-        state.add_inference_log(
-            "gemini".to_string(),
-            model.to_string(),
-            "chat".to_string(),
-            false,
-            Some(status.as_u16()),
-            start_time.elapsed().as_millis() as u64,
-            None, None, None,
-            Some(format!("API error: {}", response_text)),
-            None,
-            Some(user_message_preview),
-            None,
-        );
-        return Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: format!("Gemini API error: {}", response_text),
-                code: status.as_u16(),
-            }),
-        ));
-    }
+        let gemini_response: GeminiResponse = serde_json::from_str(&response_text).map_err(|e| {
+            log::error!("REST API: Failed to parse Gemini response: {}", e);
+            // Log failed inference
+            let log_id = state.add_inference_log(
+                "gemini".to_string(),
+                model.to_string(),
+                "chat".to_string(),
+                false,
+                Some(status.as_u16()),
+                start_time.elapsed().as_millis() as u64,
+                None, None, None,
+                Some(format!("Failed to parse response: {}", e)),
+                None,
+                Some(user_message_preview.clone()),
+                None,
+                retry_count,
+            );
+            if capture_bodies {
+                state.add_inference_exchange(log_id, &url, request_body.clone(), response_text.clone());
+            }
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: format!("Failed to parse Gemini response: {}", e),
+                    code: 500,
+                }),
+            )
+        })?;
 
-    let gemini_response: GeminiResponse = serde_json::from_str(&response_text).map_err(|e| {
-        log::error!("REST API: Failed to parse Gemini response: {}", e);
-        // Log failed inference
-        state.add_inference_log(
-            "gemini".to_string(),
-            model.to_string(),
-            "chat".to_string(),
-            false,
-            Some(status.as_u16()),
-            start_time.elapsed().as_millis() as u64,
-            None, None, None,
-            Some(format!("Failed to parse response: {}", e)),
-            None,
-            Some(user_message_preview.clone()),
-            None,
-        );
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: format!("Failed to parse Gemini response: {}", e),
-                code: 500,
-            }),
-        )
-    })?;
+        // Check for API error in response
+        if let Some(error) = gemini_response.error {
+            log::error!("REST API: Gemini API returned error: {}", error.message);
+            // Log failed inference
+            let log_id = state.add_inference_log(
+                "gemini".to_string(),
+                model.to_string(),
+                "chat".to_string(),
+                false,
+                Some(status.as_u16()),
+                start_time.elapsed().as_millis() as u64,
+                None, None, None,
+                Some(error.message.clone()),
+                None,
+                Some(user_message_preview),
+                None,
+                retry_count,
+            );
+            if capture_bodies {
+                state.add_inference_exchange(log_id, &url, request_body, response_text.clone());
+            }
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: error.message,
+                    code: 500,
+                }),
+            ));
+        }
 
-    // Check for API error in response
-    if let Some(error) = gemini_response.error {
-        log::error!("REST API: Gemini API returned error: {}", error.message);
-        // Log failed inference
-        state.add_inference_log(
-            "gemini".to_string(),
-            model.to_string(),
-            "chat".to_string(),
-            false,
-            Some(status.as_u16()),
-            start_time.elapsed().as_millis() as u64,
-            None, None, None,
-            Some(error.message.clone()),
-            None,
-            Some(user_message_preview),
-            None,
-        );
-        return Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: error.message,
-                code: 500,
-            }),
-        ));
-    }
+        let parts = gemini_response
+            .candidates
+            .and_then(|c| c.into_iter().next())
+            .map(|c| c.content.parts)
+            .unwrap_or_default();
+
+        let calls: Vec<GeminiFunctionCall> = parts
+            .iter()
+            .filter_map(|p| p.function_call.clone())
+            .collect();
+
+        if !calls.is_empty() && rounds < MAX_TOOL_ROUNDS {
+            rounds += 1;
+
+            // Echo the model's functionCall turn so the transcript Gemini
+            // sees next round matches what it produced
+            contents.push(GeminiContent {
+                role: "model".to_string(),
+                parts: calls
+                    .iter()
+                    .cloned()
+                    .map(|function_call| GeminiPart::FunctionCall { function_call })
+                    .collect(),
+            });
+
+            // Execute the batch through the runtime choke-point (so
+            // enable/disable, clamps, budgets and breakers all apply) with
+            // bounded parallelism; results are collected back in call order
+            // so each functionResponse lines up with its functionCall
+            let mut results = Vec::with_capacity(calls.len());
+            for chunk in calls.chunks(MAX_PARALLEL_TOOL_CALLS) {
+                let handles: Vec<_> = chunk
+                    .iter()
+                    .cloned()
+                    .map(|call| {
+                        let runtime = tool_runtime.clone();
+                        tokio::spawn(async move {
+                            runtime
+                                .call(
+                                    &call.name,
+                                    call.args.clone(),
+                                    crate::tool_runtime::ToolCallSource::Agent,
+                                )
+                                .await
+                        })
+                    })
+                    .collect();
+                for handle in handles {
+                    results.push(handle.await.unwrap_or_else(|e| {
+                        crate::tool_runtime::ToolCallResult::error(
+                            &format!("Tool task panicked: {}", e),
+                            0,
+                        )
+                    }));
+                }
+            }
+
+            let mut response_parts = Vec::with_capacity(calls.len());
+            for (call, result) in calls.into_iter().zip(results) {
+                log::info!(
+                    "REST API: agent tool call {} ({}) in {}ms",
+                    call.name,
+                    if result.success { "ok" } else { "failed" },
+                    result.duration_ms
+                );
+                let response = if result.success {
+                    serde_json::json!({ "result": result.data })
+                } else {
+                    serde_json::json!({ "error": result.error })
+                };
+                tool_calls.push(ChatToolCall {
+                    operation_id: call.name.clone(),
+                    args: call.args,
+                    success: result.success,
+                    duration_ms: result.duration_ms,
+                });
+                response_parts.push(GeminiPart::FunctionResponse {
+                    function_response: GeminiFunctionResponse {
+                        name: call.name,
+                        response,
+                    },
+                });
+            }
+            contents.push(GeminiContent {
+                role: "user".to_string(),
+                parts: response_parts,
+            });
+            continue;
+        }
 
-    // Extract the response text
-    let ai_response = gemini_response
-        .candidates
-        .and_then(|c| c.into_iter().next())
-        .map(|c| c.content.parts.into_iter().map(|p| p.text).collect::<Vec<_>>().join(""))
-        .unwrap_or_else(|| "No response from Gemini".to_string());
+        // Extract the response text
+        let text = parts
+            .into_iter()
+            .filter_map(|p| p.text)
+            .collect::<Vec<_>>()
+            .join("");
+        if text.is_empty() {
+            break ("No response from Gemini".to_string(), response_text);
+        }
+        break (text, response_text);
+    };
 
+    let retry_count = Some(total_retries);
     let duration_ms = start_time.elapsed().as_millis() as u64;
     log::info!("REST API: Gemini responded with {} chars in {}ms", ai_response.len(), duration_ms);
 
     // Log successful inference with full details
-    state.add_inference_log(
+    let log_id = state.add_inference_log(
         "gemini".to_string(),
         model.to_string(),
         "chat".to_string(),
@@ -605,6 +1626,16 @@ pub async fn chat_handler(
             "response": ai_response.clone(),
             "response_length": ai_response.len(),
             "history_length": request.history.len(),
+            "image_count": image_count,
+            "history_context_excerpts": history_context_excerpts,
+            "compressed_turns": compressed_turns,
+            "trimmed_turns": trimmed_turns,
+            "use_tools": request.use_tools,
+            "tool_calls": tool_calls.iter().map(|c| serde_json::json!({
+                "operation_id": c.operation_id,
+                "success": c.success,
+                "duration_ms": c.duration_ms
+            })).collect::<Vec<_>>(),
             "history": request.history.iter().map(|m| serde_json::json!({
                 "role": m.role,
                 "content": m.content
@@ -615,10 +1646,14 @@ pub async fn chat_handler(
                 "max_output_tokens": null
             }
         })),
+        retry_count,
     );
+    if capture_bodies {
+        state.add_inference_exchange(log_id, &url, request_body, final_response_text);
+    }
 
-    // Build updated history
-    let mut updated_history = request.history.clone();
+    // Build updated history (compressed, when summarization ran)
+    let mut updated_history = history;
     updated_history.push(ChatMessage {
         role: "user".to_string(),
         content: request.message,
@@ -631,13 +1666,433 @@ pub async fn chat_handler(
     Ok(Json(ChatResponse {
         response: ai_response,
         history: updated_history,
+        tool_calls,
     }))
 }
 
+// ============ Token Counting ============
+
+/// Fallback input window when the model list hasn't been fetched yet
+const DEFAULT_INPUT_TOKEN_LIMIT: u32 = 131_072;
+/// Flat per-image token charge used by the estimator (matches Gemini's
+/// fixed cost for an inline image)
+const IMAGE_TOKENS: u32 = 258;
+
+/// Request body for the token counting endpoint
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct CountTokensRequest {
+    /// The prospective message (counted together with the history, exactly
+    /// as POST /agent/chat would send it)
+    #[serde(default)]
+    pub message: String,
+    /// Conversation history to include in the count
+    #[serde(default)]
+    pub history: Vec<ChatMessage>,
+    /// Model whose tokenizer to use (defaults to "gemini-2.0-flash")
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+/// Response from the token counting endpoint
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CountTokensResponse {
+    pub total_tokens: u32,
+    /// "api" when Gemini's countTokens endpoint answered, "estimate" when
+    /// the chars/4 heuristic had to stand in
+    pub method: String,
+    pub model: String,
+    /// The model's input window, when known from the cached model list
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_token_limit: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GeminiCountTokensResponse {
+    #[serde(rename = "totalTokens")]
+    total_tokens: u32,
+}
+
+/// Rough token count for one content: ~4 bytes per token for text and
+/// serialized function parts, plus a flat charge per inline image. Used when
+/// countTokens is unreachable and for the pre-flight history trim in the
+/// chat loop, where an upstream round-trip per turn would be too expensive.
+fn estimate_content_tokens(content: &GeminiContent) -> u32 {
+    content
+        .parts
+        .iter()
+        .map(|part| match part {
+            GeminiPart::Text { text } => (text.len() as u32).div_ceil(4),
+            GeminiPart::InlineData { .. } => IMAGE_TOKENS,
+            GeminiPart::FunctionCall { function_call } => {
+                let bytes = serde_json::to_string(function_call).map(|s| s.len()).unwrap_or(0);
+                (bytes as u32).div_ceil(4)
+            }
+            GeminiPart::FunctionResponse { function_response } => {
+                let bytes = serde_json::to_string(function_response).map(|s| s.len()).unwrap_or(0);
+                (bytes as u32).div_ceil(4)
+            }
+        })
+        .sum()
+}
+
+/// Rough token count for a whole conversation
+fn estimate_tokens(contents: &[GeminiContent]) -> u32 {
+    contents.iter().map(estimate_content_tokens).sum()
+}
+
+/// Input token limit for `model` from the cached model list, if known.
+/// Cache entries are named "models/<id>"; bare ids are matched too.
+fn cached_input_token_limit(model: &str) -> Option<u32> {
+    MODELS_CACHE.read().as_ref().and_then(|cached| {
+        cached
+            .models
+            .iter()
+            .find(|m| m.name == model || m.name.strip_prefix("models/") == Some(model))
+            .and_then(|m| m.input_token_limit)
+    })
+}
+
+/// Edit distance used for "did you mean" model suggestions
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0usize; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            current[j + 1] = (prev[j + 1] + 1)
+                .min(current[j] + 1)
+                .min(prev[j] + cost);
+        }
+        std::mem::swap(&mut prev, &mut current);
+    }
+    prev[b.len()]
+}
+
+/// Validate a requested model id against the cached model list.
+///
+/// Returns a clear error with nearest-match suggestions when the cache is
+/// populated and the id is unknown. An empty cache (first run, upstream
+/// unreachable) skips validation rather than blocking chat on it.
+fn validate_model_id(model: &str) -> Result<(), String> {
+    let cache = MODELS_CACHE.read();
+    let models = match cache.as_ref() {
+        Some(cached) if !cached.models.is_empty() => &cached.models,
+        _ => return Ok(()),
+    };
+    let known = models
+        .iter()
+        .any(|m| m.name == model || m.name.strip_prefix("models/") == Some(model));
+    if known {
+        return Ok(());
+    }
+
+    let mut scored: Vec<(usize, &str)> = models
+        .iter()
+        .map(|m| {
+            let id = m.name.strip_prefix("models/").unwrap_or(&m.name);
+            (levenshtein(model, id), id)
+        })
+        .collect();
+    scored.sort_by_key(|(distance, _)| *distance);
+    let suggestions: Vec<&str> = scored.iter().take(3).map(|(_, id)| *id).collect();
+    Err(format!(
+        "Unknown model '{}'. Closest matches: {}",
+        model,
+        suggestions.join(", ")
+    ))
+}
+
+/// Exact token count via Gemini's countTokens endpoint
+async fn count_tokens_via_api(
+    api_key: &str,
+    model: &str,
+    contents: &[GeminiContent],
+) -> Result<u32, String> {
+    let client = crate::http::outbound_client();
+    let url = format!(
+        "https://generativelanguage.googleapis.com/v1beta/models/{}:countTokens?key={}",
+        model, api_key
+    );
+    let body = serde_json::json!({ "contents": contents });
+
+    let policy = retry::RetryPolicy::from_config();
+    let outcome = retry::send_with_retry(
+        || {
+            client
+                .post(&url)
+                .header("Content-Type", "application/json")
+                .json(&body)
+        },
+        &policy,
+    )
+    .await;
+    let response = outcome
+        .result
+        .map_err(|e| format!("Failed to call Gemini API: {}", e))?;
+
+    let status = response.status();
+    let text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read Gemini response: {}", e))?;
+    if !status.is_success() {
+        return Err(format!("Gemini API error ({}): {}", status, text));
+    }
+
+    let parsed: GeminiCountTokensResponse = serde_json::from_str(&text)
+        .map_err(|e| format!("Failed to parse Gemini response: {}", e))?;
+    Ok(parsed.total_tokens)
+}
+
+/// Messages kept verbatim at the tail when older turns are compressed
+const KEEP_RECENT_MESSAGES: usize = 4;
+
+/// Summarize older conversation turns into one compact context block.
+///
+/// One plain generateContent call (no tools); the caller falls back to the
+/// blunt oldest-first trim when this fails, so errors are just strings.
+async fn summarize_history(
+    api_key: &str,
+    model: &str,
+    older: &[ChatMessage],
+) -> Result<String, String> {
+    let transcript = older
+        .iter()
+        .map(|m| format!("{}: {}", m.role, m.content))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let prompt = format!(
+        "Summarize the following conversation so the summary can replace the \
+         original turns as context for continuing the chat. Preserve decisions, \
+         facts, names, Jira issue keys and open questions; stay under 300 words.\n\n{}",
+        transcript
+    );
+    let body = serde_json::json!({
+        "contents": [{
+            "role": "user",
+            "parts": [{ "text": prompt }]
+        }]
+    });
+
+    let client = crate::http::outbound_client();
+    let url = format!(
+        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
+        model, api_key
+    );
+    let policy = retry::RetryPolicy::from_config();
+    let outcome = retry::send_with_retry(
+        || {
+            client
+                .post(&url)
+                .header("Content-Type", "application/json")
+                .json(&body)
+        },
+        &policy,
+    )
+    .await;
+    let response = outcome
+        .result
+        .map_err(|e| format!("Failed to call Gemini API: {}", e))?;
+    let status = response.status();
+    let text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read Gemini response: {}", e))?;
+    if !status.is_success() {
+        return Err(format!("Gemini API error ({}): {}", status, text));
+    }
+
+    let value: serde_json::Value = serde_json::from_str(&text)
+        .map_err(|e| format!("Failed to parse Gemini response: {}", e))?;
+    let summary = value["candidates"][0]["content"]["parts"]
+        .as_array()
+        .map(|parts| {
+            parts
+                .iter()
+                .filter_map(|p| p["text"].as_str())
+                .collect::<Vec<_>>()
+                .join("")
+        })
+        .unwrap_or_default();
+    if summary.is_empty() {
+        return Err("Model returned an empty summary".to_string());
+    }
+    Ok(summary)
+}
+
+/// Count tokens for a prospective chat turn
+///
+/// Builds the same Gemini `contents` that POST /agent/chat would send
+/// (history plus the message) and counts them via the model's countTokens
+/// endpoint, falling back to a ~4-bytes-per-token estimate when the key is
+/// missing or the upstream call fails. The response includes the model's
+/// input window (from the cached model list) so clients can warn before a
+/// conversation stops fitting.
+#[utoipa::path(
+    post,
+    path = "/agent/count-tokens",
+    request_body = CountTokensRequest,
+    responses(
+        (status = 200, description = "Token count", body = CountTokensResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "agent"
+)]
+pub async fn count_tokens_handler(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<CountTokensRequest>,
+) -> Json<CountTokensResponse> {
+    let model = request
+        .model
+        .as_deref()
+        .unwrap_or("gemini-2.0-flash")
+        .to_string();
+
+    let mut contents: Vec<GeminiContent> = request
+        .history
+        .iter()
+        .map(|msg| GeminiContent {
+            role: msg.role.clone(),
+            parts: vec![GeminiPart::Text {
+                text: msg.content.clone(),
+            }],
+        })
+        .collect();
+    if !request.message.is_empty() {
+        contents.push(GeminiContent {
+            role: "user".to_string(),
+            parts: vec![GeminiPart::Text {
+                text: request.message.clone(),
+            }],
+        });
+    }
+
+    let api_key = state.gemini_api_key();
+    let key_configured = !(api_key.is_empty() || api_key == "YOUR_GEMINI_API_KEY_HERE");
+
+    let (total_tokens, method) = if key_configured {
+        match count_tokens_via_api(&api_key, &model, &contents).await {
+            Ok(count) => (count, "api"),
+            Err(e) => {
+                log::warn!(
+                    "REST API: countTokens failed, falling back to estimate: {}",
+                    e
+                );
+                (estimate_tokens(&contents), "estimate")
+            }
+        }
+    } else {
+        (estimate_tokens(&contents), "estimate")
+    };
+
+    Json(CountTokensResponse {
+        total_tokens,
+        method: method.to_string(),
+        input_token_limit: cached_input_token_limit(&model),
+        model,
+    })
+}
+
+/// Last successful Gemini model list — refreshed by the scheduler's
+/// `model_list_refresh` job and by every successful `/agent/models` call,
+/// and served as a stale fallback when the upstream API is unreachable.
+static MODELS_CACHE: Lazy<parking_lot::RwLock<Option<CachedModels>>> =
+    Lazy::new(|| parking_lot::RwLock::new(None));
+
+/// The cached model list plus when it was fetched, so `/agent/models` can
+/// report staleness and POST /agent/models/refresh can force an update
+#[derive(Debug, Clone)]
+struct CachedModels {
+    models: Vec<GeminiModel>,
+    fetched_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Staleness window when the scheduler's refresh job is disabled
+const DEFAULT_MODELS_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// A cached list is considered expired once it outlives the scheduler's
+/// refresh interval — a healthy `model_list_refresh` job keeps it younger
+fn models_cache_expired(fetched_at: chrono::DateTime<chrono::Utc>) -> bool {
+    let configured = crate::config::current().jobs.model_refresh_secs;
+    let ttl = if configured == 0 {
+        DEFAULT_MODELS_TTL_SECS
+    } else {
+        configured
+    };
+    (chrono::Utc::now() - fetched_at).num_seconds() > ttl as i64
+}
+
+/// Store a freshly fetched model list and build the response for it
+fn cache_and_respond(models: Vec<GeminiModel>) -> GeminiModelsResponse {
+    let fetched_at = chrono::Utc::now();
+    *MODELS_CACHE.write() = Some(CachedModels {
+        models: models.clone(),
+        fetched_at,
+    });
+    GeminiModelsResponse {
+        total: models.len(),
+        models,
+        fetched_at: fetched_at.to_rfc3339(),
+        expired: false,
+    }
+}
+
+/// Fetch the model list from the Gemini API.
+///
+/// Errors carry a suggested HTTP code (the upstream status, or 500 for
+/// transport/parse failures) plus a message.
+async fn fetch_models(api_key: &str) -> Result<Vec<GeminiModel>, (u16, String)> {
+    let client = crate::http::outbound_client();
+    let url = format!(
+        "https://generativelanguage.googleapis.com/v1beta/models?key={}",
+        api_key
+    );
+
+    let policy = retry::RetryPolicy::from_config();
+    let outcome = retry::send_with_retry(|| client.get(&url), &policy).await;
+    let response = outcome
+        .result
+        .map_err(|e| (500, format!("Failed to call Gemini API: {}", e)))?;
+
+    let status = response.status();
+    let response_text = response
+        .text()
+        .await
+        .map_err(|e| (500, format!("Failed to read Gemini response: {}", e)))?;
+
+    if !status.is_success() {
+        return Err((status.as_u16(), format!("Gemini API error: {}", response_text)));
+    }
+
+    let api_response: GeminiModelsApiResponse = serde_json::from_str(&response_text)
+        .map_err(|e| (500, format!("Failed to parse Gemini response: {}", e)))?;
+
+    let mut models = api_response.models.unwrap_or_default();
+    annotate_models(&mut models);
+    Ok(models)
+}
+
+/// Fetch the model list and store it in [`MODELS_CACHE`]. Returns the model
+/// count. Called by the scheduler's `model_list_refresh` job.
+pub(crate) async fn refresh_models_cache(api_key: &str) -> Result<usize, String> {
+    let models = fetch_models(api_key).await.map_err(|(_, e)| e)?;
+    let count = models.len();
+    *MODELS_CACHE.write() = Some(CachedModels {
+        models,
+        fetched_at: chrono::Utc::now(),
+    });
+    Ok(count)
+}
+
 /// List available Gemini models
 /// 
 /// Returns a list of all available Google Gemini models that can be used for inference.
-/// This endpoint queries the Gemini API to get the current list of models.
+/// This endpoint queries the Gemini API to get the current list of models; on
+/// upstream failure the last successfully fetched list is served instead.
 #[utoipa::path(
     get,
     path = "/agent/models",
@@ -655,7 +2110,8 @@ pub async fn list_models_handler(
     log::info!("REST API: agent/models called");
 
     // Check if Gemini API key is configured
-    if state.gemini_api_key.is_empty() || state.gemini_api_key == "YOUR_GEMINI_API_KEY_HERE" {
+    let gemini_api_key = state.gemini_api_key();
+    if gemini_api_key.is_empty() || gemini_api_key == "YOUR_GEMINI_API_KEY_HERE" {
         return Err((
             StatusCode::BAD_REQUEST,
             Json(ErrorResponse {
@@ -665,66 +2121,80 @@ pub async fn list_models_handler(
         ));
     }
 
-    // Call Gemini API to list models
-    let client = reqwest::Client::new();
-    let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/models?key={}",
-        state.gemini_api_key
-    );
-
-    let response = client
-        .get(&url)
-        .send()
-        .await
-        .map_err(|e| {
-            log::error!("REST API: Failed to call Gemini models API: {}", e);
-            (
+    match fetch_models(&gemini_api_key).await {
+        Ok(models) => {
+            log::info!("REST API: Retrieved {} Gemini models", models.len());
+            Ok(Json(cache_and_respond(models)))
+        }
+        Err((code, error)) => {
+            // Serve the last good list rather than failing the UI outright
+            if let Some(cached) = MODELS_CACHE.read().clone() {
+                log::warn!(
+                    "REST API: Gemini models fetch failed ({}), serving cached list: {}",
+                    code,
+                    error
+                );
+                return Ok(Json(GeminiModelsResponse {
+                    total: cached.models.len(),
+                    models: cached.models,
+                    fetched_at: cached.fetched_at.to_rfc3339(),
+                    expired: models_cache_expired(cached.fetched_at),
+                }));
+            }
+            log::error!("REST API: Gemini models fetch failed ({}): {}", code, error);
+            Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ErrorResponse {
-                    error: format!("Failed to call Gemini API: {}", e),
-                    code: 500,
-                }),
-            )
-        })?;
+                Json(ErrorResponse { error, code }),
+            ))
+        }
+    }
+}
 
-    let status = response.status();
-    let response_text = response.text().await.map_err(|e| {
-        log::error!("REST API: Failed to read Gemini models response: {}", e);
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: format!("Failed to read Gemini response: {}", e),
-                code: 500,
-            }),
-        )
-    })?;
+/// Force-refresh the Gemini model list
+///
+/// Re-fetches the catalog from the Gemini API and replaces the cache (the
+/// same refresh the scheduler's `model_list_refresh` job performs). Unlike
+/// GET /agent/models, a failed fetch is reported as an error instead of
+/// being papered over with the cached list, so users know the refresh
+/// didn't happen.
+#[utoipa::path(
+    post,
+    path = "/agent/models/refresh",
+    responses(
+        (status = 200, description = "Refreshed list of Gemini models", body = GeminiModelsResponse),
+        (status = 400, description = "Bad request - API key not configured", body = ErrorResponse),
+        (status = 500, description = "Refresh failed", body = ErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "agent"
+)]
+pub async fn refresh_models_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<Json<GeminiModelsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    log::info!("REST API: agent/models/refresh called");
 
-    if !status.is_success() {
-        log::error!("REST API: Gemini models API error ({}): {}", status, response_text);
+    let gemini_api_key = state.gemini_api_key();
+    if gemini_api_key.is_empty() || gemini_api_key == "YOUR_GEMINI_API_KEY_HERE" {
         return Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
+            StatusCode::BAD_REQUEST,
             Json(ErrorResponse {
-                error: format!("Gemini API error: {}", response_text),
-                code: status.as_u16(),
+                error: "Gemini API key not configured. Please set GEMINI_API_KEY in .env file.".to_string(),
+                code: 400,
             }),
         ));
     }
 
-    let api_response: GeminiModelsApiResponse = serde_json::from_str(&response_text).map_err(|e| {
-        log::error!("REST API: Failed to parse Gemini models response: {}", e);
-        (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ErrorResponse {
-                error: format!("Failed to parse Gemini response: {}", e),
-                code: 500,
-            }),
-        )
-    })?;
-
-    let models = api_response.models.unwrap_or_default();
-    let total = models.len();
-
-    log::info!("REST API: Retrieved {} Gemini models", total);
-
-    Ok(Json(GeminiModelsResponse { models, total }))
+    match fetch_models(&gemini_api_key).await {
+        Ok(models) => {
+            log::info!("REST API: Refreshed {} Gemini models", models.len());
+            Ok(Json(cache_and_respond(models)))
+        }
+        Err((code, error)) => {
+            log::error!("REST API: Gemini models refresh failed ({}): {}", code, error);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse { error, code }),
+            ))
+        }
+    }
 }
@@ -1,44 +1,233 @@
 use axum::{
     body::Body,
     extract::{ConnectInfo, State},
-    http::{header, Request, StatusCode},
+    http::{header, HeaderValue, Method, Request, StatusCode},
     middleware::Next,
-    response::Response,
+    response::{IntoResponse, Response},
 };
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Instant;
 
 use crate::state::AppState;
 
+// ============================================================================
+// Brute-force lockout and auth audit
+// ============================================================================
+
+/// Failed attempts before an IP is temporarily locked out.
+const MAX_FAILED_ATTEMPTS: u32 = 5;
+/// Lockout duration once the attempt limit is hit.
+const LOCKOUT_SECS: u64 = 300;
+/// Audit entries retained in memory.
+const MAX_AUDIT_ENTRIES: usize = 500;
+
+/// One rejected authentication attempt, as surfaced by `GET /auth/audit`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthAuditEntry {
+    pub timestamp: String,
+    pub client_ip: String,
+    pub method: String,
+    pub path: String,
+    /// Why the request was rejected: "missing_header", "invalid_token" or
+    /// "locked_out"
+    pub reason: String,
+    /// Failed attempts from this IP at the time of rejection
+    pub failed_attempts: u32,
+}
+
+/// A client currently locked out, as surfaced by `GET /auth/audit`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LockedClient {
+    pub client_ip: String,
+    pub failed_attempts: u32,
+    /// Seconds until the lockout expires
+    pub remaining_secs: u64,
+}
+
+#[derive(Debug, Clone)]
+struct FailureRecord {
+    count: u32,
+    locked_until: Option<Instant>,
+}
+
+static FAILED_ATTEMPTS: Lazy<RwLock<HashMap<String, FailureRecord>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+static AUTH_AUDIT: Lazy<RwLock<Vec<AuthAuditEntry>>> = Lazy::new(|| RwLock::new(Vec::new()));
+
+/// Whether an IP is currently locked out (expired lockouts are cleared).
+fn is_locked_out(ip: &str) -> bool {
+    let mut attempts = FAILED_ATTEMPTS.write();
+    if let Some(record) = attempts.get_mut(ip) {
+        match record.locked_until {
+            Some(until) if Instant::now() < until => return true,
+            Some(_) => {
+                // Lockout expired — start over
+                attempts.remove(ip);
+            }
+            None => {}
+        }
+    }
+    false
+}
+
+/// Record one failed attempt; returns the new count after locking the IP
+/// out when it crosses the limit.
+fn record_failure(ip: &str) -> u32 {
+    let mut attempts = FAILED_ATTEMPTS.write();
+    let record = attempts.entry(ip.to_string()).or_insert(FailureRecord {
+        count: 0,
+        locked_until: None,
+    });
+    record.count += 1;
+    if record.count >= MAX_FAILED_ATTEMPTS && record.locked_until.is_none() {
+        record.locked_until =
+            Some(Instant::now() + std::time::Duration::from_secs(LOCKOUT_SECS));
+        log::warn!(
+            "REST API: locking out {} for {}s after {} failed auth attempts",
+            ip,
+            LOCKOUT_SECS,
+            record.count
+        );
+    }
+    record.count
+}
+
+fn record_audit(client_ip: &str, method: &str, path: &str, reason: &str, failed_attempts: u32) {
+    let mut audit = AUTH_AUDIT.write();
+    audit.push(AuthAuditEntry {
+        timestamp: chrono::Local::now().to_rfc3339(),
+        client_ip: client_ip.to_string(),
+        method: method.to_string(),
+        path: path.to_string(),
+        reason: reason.to_string(),
+        failed_attempts,
+    });
+    let len = audit.len();
+    if len > MAX_AUDIT_ENTRIES {
+        audit.drain(0..len - MAX_AUDIT_ENTRIES);
+    }
+}
+
+/// Snapshot of the audit log, newest last. Used by `GET /auth/audit`.
+pub(crate) fn audit_entries() -> Vec<AuthAuditEntry> {
+    AUTH_AUDIT.read().clone()
+}
+
+/// Clients currently locked out. Used by `GET /auth/audit`.
+pub(crate) fn locked_clients() -> Vec<LockedClient> {
+    let now = Instant::now();
+    FAILED_ATTEMPTS
+        .read()
+        .iter()
+        .filter_map(|(ip, record)| {
+            let until = record.locked_until?;
+            if until <= now {
+                return None;
+            }
+            Some(LockedClient {
+                client_ip: ip.clone(),
+                failed_attempts: record.count,
+                remaining_secs: (until - now).as_secs(),
+            })
+        })
+        .collect()
+}
+
 /// Auth middleware - validates Bearer token
 pub async fn auth_middleware(
     State(state): State<Arc<AppState>>,
     request: Request<Body>,
     next: Next,
 ) -> Result<Response, StatusCode> {
+    let client_ip = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ci| ci.0.ip().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    let method = request.method().to_string();
+    let path = request.uri().path().to_string();
+
+    if is_locked_out(&client_ip) {
+        log::warn!("REST API: rejected request from locked-out client {}", client_ip);
+        record_audit(&client_ip, &method, &path, "locked_out", MAX_FAILED_ATTEMPTS);
+        return Err(StatusCode::TOO_MANY_REQUESTS);
+    }
+
     let auth_header = request
         .headers()
         .get(header::AUTHORIZATION)
         .and_then(|h| h.to_str().ok());
 
-    let token = auth_header
-        .and_then(|h| h.strip_prefix("Bearer "))
-        .ok_or_else(|| {
+    let token = match auth_header.and_then(|h| h.strip_prefix("Bearer ")) {
+        Some(token) => token,
+        None => {
             log::warn!("REST API: Missing or invalid Authorization header");
-            StatusCode::UNAUTHORIZED
-        })?;
+            let count = record_failure(&client_ip);
+            record_audit(&client_ip, &method, &path, "missing_header", count);
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+    };
 
     if !state.verify_token(token) {
         log::warn!("REST API: Invalid auth token");
+        let count = record_failure(&client_ip);
+        record_audit(&client_ip, &method, &path, "invalid_token", count);
         return Err(StatusCode::UNAUTHORIZED);
     }
 
+    // Success clears the client's failure counter
+    FAILED_ATTEMPTS.write().remove(&client_ip);
+
     log::debug!("REST API: Auth successful");
-    Ok(next.run(request).await)
+    // Scope the actor identity for the audit trail (same task-local
+    // mechanism as the request id)
+    let actor = format!("token:{}", token_fingerprint(token));
+    Ok(crate::audit::ACTOR.scope(actor, next.run(request)).await)
+}
+
+/// Request-id middleware - assigns a correlation id to every request
+///
+/// Honors an incoming `x-request-id` header (so gateway-assigned ids survive),
+/// otherwise generates a fresh UUID. The id is scoped into the
+/// `crate::api::error::REQUEST_ID` task-local — error payloads serialized
+/// anywhere inside the request pick it up — and echoed back in the
+/// `x-request-id` response header.
+pub async fn request_id_middleware(request: Request<Body>, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get("x-request-id")
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    let mut response = crate::api::error::REQUEST_ID
+        .scope(request_id.clone(), next.run(request))
+        .await;
+
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert("x-request-id", value);
+    }
+    response
 }
 
 /// Access logging middleware - logs all HTTP requests
+/// Short stable fingerprint of a bearer token (first 8 hex chars of a
+/// 64-bit hash) — safe to log and expose in analytics.
+pub(crate) fn token_fingerprint(token: &str) -> String {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    token.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())[..8].to_string()
+}
+
 pub async fn access_log_middleware(
     State(state): State<Arc<AppState>>,
     request: Request<Body>,
@@ -55,6 +244,21 @@ pub async fn access_log_middleware(
         .map(|ci| ci.0.to_string())
         .unwrap_or_else(|| "unknown".to_string());
 
+    let user_agent = request
+        .headers()
+        .get(header::USER_AGENT)
+        .and_then(|h| h.to_str().ok())
+        .map(|ua| ua.to_string());
+
+    // Fingerprint of the presented bearer token — identifies the client key
+    // in analytics without ever logging the token itself
+    let client_key_id = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .map(token_fingerprint);
+
     // Process the request
     let response = next.run(request).await;
     
@@ -77,7 +281,69 @@ pub async fn access_log_middleware(
         status_code,
         duration.as_millis() as u64,
         client_ip,
+        user_agent,
+        client_key_id,
     );
 
     response
 }
+
+/// Paths whose responses are large, frequently polled and usually unchanged.
+/// Only these get the ETag treatment — buffering every response body to hash
+/// it would be wasted work on streaming or one-shot endpoints.
+fn is_etag_cached_path(path: &str) -> bool {
+    path == "/history/tasks"
+        || path == "/history/stats"
+        || path.starts_with("/history/stats/")
+        || path == "/changes/workspaces"
+        || path == "/changes/tasks"
+}
+
+/// ETag middleware - computes content hashes for cacheable GET endpoints
+///
+/// Hashes the response body, attaches it as a strong `ETag`, and answers
+/// `If-None-Match` with an empty `304 Not Modified` when the payload has not
+/// changed, so polling UIs and agents stop re-downloading identical payloads.
+pub async fn etag_middleware(request: Request<Body>, next: Next) -> Response {
+    let applicable =
+        request.method() == Method::GET && is_etag_cached_path(request.uri().path());
+    let if_none_match = request
+        .headers()
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
+
+    let response = next.run(request).await;
+    if !applicable || response.status() != StatusCode::OK {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            log::warn!("REST API: Failed to buffer response for ETag: {}", e);
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    let etag = format!("\"{:016x}\"", hasher.finish());
+
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        parts.headers.insert(header::ETAG, value);
+    }
+
+    // If-None-Match may carry a comma-separated list of candidate tags
+    let matched = if_none_match
+        .map(|h| h.split(',').any(|tag| tag.trim() == etag || tag.trim() == "*"))
+        .unwrap_or(false);
+    if matched {
+        parts.status = StatusCode::NOT_MODIFIED;
+        parts.headers.remove(header::CONTENT_LENGTH);
+        return Response::from_parts(parts, Body::empty());
+    }
+
+    Response::from_parts(parts, Body::from(bytes))
+}
@@ -1,2 +1,5 @@
+pub mod error;
 pub mod handlers;
 pub mod middleware;
+pub mod pagination;
+pub mod retry;
@@ -0,0 +1,205 @@
+//! Shared pagination for list endpoints.
+//!
+//! Every list endpoint used to roll its own `?limit=`/`?offset=` handling and
+//! invent its own response shape (`{tasks, ...}`, `{logs, total}`, ...). This
+//! module standardizes both sides:
+//!
+//! - **Query side**: [`PageQuery`] (`limit`, `offset`, `cursor`) — embeddable
+//!   directly for endpoints without other parameters, or mirrored field-by-field
+//!   in endpoint-specific query structs that carry additional filters.
+//! - **Response side**: [`Page<T>`] — the `{items, total, next_cursor}` envelope.
+//!
+//! ## Cursor tokens
+//!
+//! Cursors are opaque to clients: base64 of `"v1:<offset>"`. They exist so the
+//! envelope can hand back a single resumption token (`next_cursor`) instead of
+//! making clients do offset arithmetic, and so the encoding can later change
+//! (e.g. to a keyset cursor) without breaking the API contract. When both
+//! `cursor` and `offset` are supplied, the cursor wins.
+//!
+//! All current list endpoints paginate in-memory snapshots (cached task
+//! indexes, execution logs), so an offset-based cursor is exact — there is no
+//! consistency window to worry about beyond what offset pagination already has.
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use serde::{Deserialize, Serialize};
+
+/// Version prefix inside cursor tokens. Bump if the encoding ever changes so
+/// stale cursors fail with a clear 400 instead of decoding garbage.
+const CURSOR_PREFIX: &str = "v1:";
+
+/// Query parameters shared by all paginated list endpoints.
+///
+/// Endpoints with no other parameters extract this directly via
+/// `Query<PageQuery>`. Endpoints with additional filters mirror these three
+/// fields in their own query struct and call [`resolve_window`].
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+pub struct PageQuery {
+    /// Maximum number of items to return (default: all remaining items)
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// Zero-based offset into the full list (ignored when `cursor` is set)
+    #[serde(default)]
+    pub offset: Option<usize>,
+    /// Opaque continuation token from a previous page's `next_cursor`
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+impl PageQuery {
+    /// Resolve this query into a concrete [`PageWindow`].
+    ///
+    /// Returns `Err` with a human-readable message when the cursor token is
+    /// malformed — handlers map this to a 400 in their own error type.
+    pub fn window(&self) -> Result<PageWindow, String> {
+        resolve_window(self.limit, self.offset, self.cursor.as_deref())
+    }
+}
+
+/// A resolved pagination window: where to start and how many items to take.
+#[derive(Debug, Clone, Copy)]
+pub struct PageWindow {
+    /// Starting offset into the full item list
+    pub offset: usize,
+    /// Page size (`None` = no limit, return everything from `offset`)
+    pub limit: Option<usize>,
+}
+
+impl PageWindow {
+    /// Slice `items` according to this window and wrap the result in the
+    /// standard envelope. `total` always reflects the pre-pagination count;
+    /// `next_cursor` is present only when further items exist.
+    pub fn paginate<T: utoipa::ToSchema>(self, items: Vec<T>) -> Page<T> {
+        let total = items.len();
+        let start = self.offset.min(total);
+        let end = match self.limit {
+            Some(limit) => start.saturating_add(limit).min(total),
+            None => total,
+        };
+        let next_cursor = if end < total {
+            Some(encode_cursor(end))
+        } else {
+            None
+        };
+        let items = items
+            .into_iter()
+            .skip(start)
+            .take(end - start)
+            .collect();
+        Page {
+            items,
+            total,
+            next_cursor,
+        }
+    }
+}
+
+/// Standard list envelope: `{items, total, next_cursor}`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T: utoipa::ToSchema> {
+    /// The items in this page
+    pub items: Vec<T>,
+    /// Total number of items before pagination (after any filters)
+    pub total: usize,
+    /// Opaque cursor for the next page (absent on the last page)
+    pub next_cursor: Option<String>,
+}
+
+/// Resolve raw `limit`/`offset`/`cursor` parameters into a [`PageWindow`].
+///
+/// Shared by endpoint-specific query structs that can't embed [`PageQuery`]
+/// wholesale (utoipa's `IntoParams` does not flatten nested structs).
+pub fn resolve_window(
+    limit: Option<usize>,
+    offset: Option<usize>,
+    cursor: Option<&str>,
+) -> Result<PageWindow, String> {
+    let offset = match cursor {
+        Some(token) => decode_cursor(token)?,
+        None => offset.unwrap_or(0),
+    };
+    Ok(PageWindow { offset, limit })
+}
+
+/// Encode an offset as an opaque cursor token.
+fn encode_cursor(offset: usize) -> String {
+    BASE64.encode(format!("{}{}", CURSOR_PREFIX, offset))
+}
+
+/// Decode a cursor token back to an offset.
+///
+/// Rejects anything that isn't base64 of `"v1:<decimal>"` so clients get a
+/// clear error instead of silently restarting from offset 0.
+fn decode_cursor(token: &str) -> Result<usize, String> {
+    let bytes = BASE64
+        .decode(token)
+        .map_err(|_| format!("Invalid cursor '{}': not valid base64", token))?;
+    let decoded = String::from_utf8(bytes)
+        .map_err(|_| format!("Invalid cursor '{}': not valid UTF-8", token))?;
+    let rest = decoded.strip_prefix(CURSOR_PREFIX).ok_or_else(|| {
+        format!("Invalid cursor '{}': unknown cursor version", token)
+    })?;
+    rest.parse::<usize>()
+        .map_err(|_| format!("Invalid cursor '{}': malformed offset", token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let token = encode_cursor(42);
+        assert_eq!(decode_cursor(&token).unwrap(), 42);
+    }
+
+    #[test]
+    fn test_decode_rejects_garbage() {
+        assert!(decode_cursor("not-base64!!!").is_err());
+        assert!(decode_cursor(&BASE64.encode("v2:10")).is_err());
+        assert!(decode_cursor(&BASE64.encode("v1:abc")).is_err());
+    }
+
+    #[test]
+    fn test_cursor_overrides_offset() {
+        let window =
+            resolve_window(Some(5), Some(99), Some(encode_cursor(10).as_str())).unwrap();
+        assert_eq!(window.offset, 10);
+    }
+
+    #[test]
+    fn test_paginate_emits_next_cursor_until_exhausted() {
+        let items: Vec<usize> = (0..10).collect();
+        let page = PageWindow {
+            offset: 0,
+            limit: Some(4),
+        }
+        .paginate(items.clone());
+        assert_eq!(page.items, vec![0, 1, 2, 3]);
+        assert_eq!(page.total, 10);
+
+        let next = decode_cursor(page.next_cursor.as_deref().unwrap()).unwrap();
+        assert_eq!(next, 4);
+
+        let last = PageWindow {
+            offset: 8,
+            limit: Some(4),
+        }
+        .paginate(items);
+        assert_eq!(last.items, vec![8, 9]);
+        assert!(last.next_cursor.is_none());
+    }
+
+    #[test]
+    fn test_paginate_offset_past_end() {
+        let page = PageWindow {
+            offset: 100,
+            limit: None,
+        }
+        .paginate(vec![1, 2, 3]);
+        assert!(page.items.is_empty());
+        assert_eq!(page.total, 3);
+        assert!(page.next_cursor.is_none());
+    }
+}
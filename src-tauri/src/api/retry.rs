@@ -0,0 +1,169 @@
+//! Shared retry policy for outbound provider HTTP calls.
+//!
+//! Gemini (and any future provider) calls fail transiently — 429 rate
+//! limits, 502/503/504 upstream hiccups, connection resets. Callers wrap
+//! their request in [`send_with_retry`], which retries those failures with
+//! exponential backoff plus jitter, honours a `Retry-After` header when the
+//! upstream sends one, and reports how many retries were spent so the
+//! caller can record it in the inference log. Attempt and delay limits come
+//! from the `[providers]` config section.
+
+use crate::config;
+use rand::Rng;
+use std::time::Duration;
+
+/// Statuses worth retrying: rate limiting and transient upstream failures.
+/// Other 4xx/5xx are returned to the caller unchanged.
+const RETRYABLE_STATUSES: [u16; 4] = [429, 502, 503, 504];
+
+/// Backoff parameters, resolved from the live config at call time.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Total attempts including the first (1 = no retries)
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles on each further retry
+    pub base_delay_ms: u64,
+    /// Cap on any single delay, also applied to Retry-After
+    pub max_delay_ms: u64,
+}
+
+impl RetryPolicy {
+    /// Build a policy from the `[providers]` config section.
+    pub fn from_config() -> Self {
+        let cfg = config::current();
+        Self {
+            max_attempts: cfg.providers.retry_max_attempts.max(1),
+            base_delay_ms: cfg.providers.retry_base_delay_ms.max(1),
+            max_delay_ms: cfg
+                .providers
+                .retry_max_delay_ms
+                .max(cfg.providers.retry_base_delay_ms),
+        }
+    }
+
+    /// Delay before retry number `retry` (1-based): exponential, capped,
+    /// with jitter over the upper half of the window so concurrent callers
+    /// don't hammer the upstream in lockstep.
+    fn delay_for(&self, retry: u32) -> Duration {
+        let exp = self
+            .base_delay_ms
+            .saturating_mul(1u64 << (retry.saturating_sub(1)).min(16));
+        let capped = exp.min(self.max_delay_ms);
+        let jittered = capped / 2 + rand::thread_rng().gen_range(0..=capped / 2);
+        Duration::from_millis(jittered)
+    }
+}
+
+/// Outcome of a retried request: the final response (which may still carry
+/// a retryable status if attempts ran out) or the last transport error,
+/// plus how many retries were performed.
+pub struct RetryOutcome {
+    pub result: Result<reqwest::Response, String>,
+    pub retries: u32,
+}
+
+/// Send a request with retries. `build` is invoked once per attempt so the
+/// request body is rebuilt rather than cloned. Status handling beyond the
+/// retryable set stays with the caller — a 400 comes back on the first
+/// attempt untouched.
+pub async fn send_with_retry<F>(build: F, policy: &RetryPolicy) -> RetryOutcome
+where
+    F: Fn() -> reqwest::RequestBuilder,
+{
+    let mut retries = 0u32;
+    loop {
+        let attempt = retries + 1;
+        let result = build().send().await;
+
+        let (retryable, description, retry_after) = match &result {
+            Ok(response) => {
+                let status = response.status().as_u16();
+                if RETRYABLE_STATUSES.contains(&status) {
+                    (true, format!("HTTP {}", status), parse_retry_after(response))
+                } else {
+                    (false, String::new(), None)
+                }
+            }
+            Err(e) => (e.is_connect() || e.is_timeout(), e.to_string(), None),
+        };
+
+        if !retryable || attempt >= policy.max_attempts {
+            return RetryOutcome {
+                result: result.map_err(|e| e.to_string()),
+                retries,
+            };
+        }
+
+        let delay = retry_after
+            .map(|d| d.min(Duration::from_millis(policy.max_delay_ms)))
+            .unwrap_or_else(|| policy.delay_for(attempt));
+        log::warn!(
+            "Provider call failed ({}), retrying in {:?} (attempt {}/{})",
+            description,
+            delay,
+            attempt,
+            policy.max_attempts
+        );
+        tokio::time::sleep(delay).await;
+        retries += 1;
+    }
+}
+
+/// Parse a delay-seconds `Retry-After` header. HTTP-date form is rare from
+/// LLM providers and falls through to the normal backoff.
+fn parse_retry_after(response: &reqwest::Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay_ms: 500,
+            max_delay_ms: 8000,
+        }
+    }
+
+    #[test]
+    fn delay_doubles_and_stays_within_jitter_window() {
+        let p = policy();
+        for (retry, expected_cap) in [(1u32, 500u64), (2, 1000), (3, 2000)] {
+            let d = p.delay_for(retry).as_millis() as u64;
+            assert!(
+                d >= expected_cap / 2 && d <= expected_cap,
+                "retry {} gave {}ms, expected within [{}, {}]",
+                retry,
+                d,
+                expected_cap / 2,
+                expected_cap
+            );
+        }
+    }
+
+    #[test]
+    fn delay_is_capped_at_max() {
+        let p = policy();
+        // 500ms << 10 would be 512s without the cap
+        let d = p.delay_for(11).as_millis() as u64;
+        assert!(d <= p.max_delay_ms);
+        assert!(d >= p.max_delay_ms / 2);
+    }
+
+    #[test]
+    fn huge_retry_counts_do_not_overflow() {
+        let p = policy();
+        let d = p.delay_for(u32::MAX).as_millis() as u64;
+        assert!(d <= p.max_delay_ms);
+    }
+}
@@ -0,0 +1,145 @@
+//! Append-only audit trail for write-class operations.
+//!
+//! Every mutating action (workspace/task nuke, file restore, patch apply,
+//! ignore-pattern update, settings change, fixture import, backup restore)
+//! calls [`record`] with the action name, its parameters and the outcome.
+//! Entries are appended to `%APPDATA%/jira-dashboard/audit_log.jsonl` —
+//! append-only, never rewritten — with the newest tail also kept in memory
+//! for `GET /system/audit`.
+//!
+//! The **actor** identifies who performed the action: `token:<fingerprint>`
+//! for REST calls (scoped into a task-local by the auth middleware, same
+//! mechanism as the request id) or `ui` for Tauri commands invoked from the
+//! window. Tokens themselves are never written, only their fingerprint.
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::PathBuf;
+
+tokio::task_local! {
+    /// Actor for the current request, e.g. "token:1a2b3c4d". Scoped by the
+    /// auth middleware; absent for Tauri commands (recorded as "ui").
+    pub static ACTOR: String;
+}
+
+const AUDIT_DIR: &str = "jira-dashboard";
+const AUDIT_FILE: &str = "audit_log.jsonl";
+/// Entries kept in memory (the file itself is unbounded).
+const MAX_MEMORY_ENTRIES: usize = 1000;
+
+/// One audited action.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub id: u64,
+    pub timestamp: String,
+    /// Who performed the action: "token:<fingerprint>" or "ui"
+    pub actor: String,
+    /// Action name, e.g. "changes.nuke_task" or "settings.save"
+    pub action: String,
+    /// Action parameters (identifiers, never secrets)
+    pub params: serde_json::Value,
+    pub success: bool,
+    /// Outcome summary or error message
+    pub detail: String,
+}
+
+fn audit_path() -> Option<PathBuf> {
+    let appdata = std::env::var("APPDATA").ok()?;
+    let dir = PathBuf::from(appdata).join(AUDIT_DIR);
+    if !dir.exists() {
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            log::warn!("Failed to create audit dir {:?}: {}", dir, e);
+            return None;
+        }
+    }
+    Some(dir.join(AUDIT_FILE))
+}
+
+/// Load the newest tail of the on-disk log so `/system/audit` covers
+/// actions from before this session.
+fn load_tail() -> Vec<AuditEntry> {
+    let Some(path) = audit_path() else {
+        return Vec::new();
+    };
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return Vec::new(); // first run — no file yet
+    };
+    let mut entries: Vec<AuditEntry> = content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    let len = entries.len();
+    if len > MAX_MEMORY_ENTRIES {
+        entries.drain(0..len - MAX_MEMORY_ENTRIES);
+    }
+    entries
+}
+
+static ENTRIES: Lazy<RwLock<Vec<AuditEntry>>> = Lazy::new(|| RwLock::new(load_tail()));
+static COUNTER: Lazy<RwLock<u64>> =
+    Lazy::new(|| RwLock::new(ENTRIES.read().last().map(|e| e.id).unwrap_or(0)));
+
+/// Record one write-class action. Never fails — a broken audit disk write
+/// is logged but doesn't abort the operation being audited.
+pub fn record(action: &str, params: serde_json::Value, success: bool, detail: &str) {
+    let actor = ACTOR
+        .try_with(|a| a.clone())
+        .unwrap_or_else(|_| "ui".to_string());
+
+    let id = {
+        let mut counter = COUNTER.write();
+        *counter += 1;
+        *counter
+    };
+    let entry = AuditEntry {
+        id,
+        timestamp: chrono::Local::now().to_rfc3339(),
+        actor,
+        action: action.to_string(),
+        params,
+        success,
+        detail: detail.to_string(),
+    };
+
+    // Append-only disk write, best effort
+    if let Some(path) = audit_path() {
+        if let Ok(json) = serde_json::to_string(&entry) {
+            let result = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .and_then(|mut f| writeln!(f, "{}", json));
+            if let Err(e) = result {
+                log::warn!("Audit: failed to append to {:?}: {}", path, e);
+            }
+        }
+    }
+
+    let mut entries = ENTRIES.write();
+    entries.push(entry);
+    let len = entries.len();
+    if len > MAX_MEMORY_ENTRIES {
+        entries.drain(0..len - MAX_MEMORY_ENTRIES);
+    }
+}
+
+/// Snapshot of the in-memory tail, oldest first, optionally filtered by
+/// action prefix. Used by `GET /system/audit`.
+pub fn entries(action_prefix: Option<&str>, limit: Option<usize>) -> Vec<AuditEntry> {
+    let entries = ENTRIES.read();
+    let filtered: Vec<AuditEntry> = entries
+        .iter()
+        .filter(|e| match action_prefix {
+            Some(prefix) => e.action.starts_with(prefix),
+            None => true,
+        })
+        .cloned()
+        .collect();
+    match limit {
+        Some(limit) if filtered.len() > limit => filtered[filtered.len() - limit..].to_vec(),
+        _ => filtered,
+    }
+}
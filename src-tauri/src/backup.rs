@@ -0,0 +1,326 @@
+//! Backup and restore of application data.
+//!
+//! Produces a single zip archive covering everything a user would want to
+//! carry to a new machine:
+//!
+//! - `config/` — the config dir (`config.toml`)
+//! - `data/` — `%APPDATA%/jira-dashboard` (task notes, retention policy,
+//!   tool runtime config, macros, profiles, fixtures, autolink overrides,
+//!   changesignore patterns, and the disk caches)
+//!
+//! A `backup_manifest.json` at the archive root records the manifest format
+//! version and the app version that wrote it; restore refuses archives with
+//! an unknown manifest version and warns (but proceeds) on an app-version
+//! mismatch. Restore never writes outside the two roots — entry paths are
+//! validated component by component before extraction.
+//!
+//! API tokens are not persisted to disk by the app, so they are never in a
+//! backup; users re-enter credentials after a restore.
+
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::config::get_config_dir;
+
+/// Bumped when the archive layout changes incompatibly.
+const MANIFEST_VERSION: u32 = 1;
+const MANIFEST_FILE: &str = "backup_manifest.json";
+/// Subdirectory of the config dir where REST-triggered backups land.
+const BACKUPS_DIR: &str = "backups";
+
+/// Manifest written at the root of every backup archive.
+#[derive(Debug, Serialize, Deserialize)]
+struct BackupManifest {
+    /// Archive layout version — restore rejects versions it doesn't know.
+    version: u32,
+    /// App version that produced the backup (informational; mismatch warns).
+    app_version: String,
+    created_at: String,
+    entries: usize,
+}
+
+/// Response for backup creation (REST and Tauri command).
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupResponse {
+    /// Absolute path of the written archive
+    pub path: String,
+    /// Number of files in the archive (excluding the manifest)
+    pub entries: usize,
+    /// Archive size on disk in bytes
+    pub total_bytes: u64,
+    pub created_at: String,
+}
+
+/// Response for backup restore.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreResponse {
+    /// Number of files written
+    pub entries_restored: usize,
+    /// Manifest version of the archive
+    pub backup_version: u32,
+    /// App version that produced the archive
+    pub app_version: String,
+    /// Non-fatal problems (version mismatch, skipped entries)
+    pub warnings: Vec<String>,
+}
+
+/// The roots included in a backup, as (archive prefix, directory) pairs.
+fn backup_roots() -> Vec<(&'static str, PathBuf)> {
+    let mut roots = vec![("config", get_config_dir())];
+    if let Ok(appdata) = std::env::var("APPDATA") {
+        roots.push(("data", PathBuf::from(appdata).join("jira-dashboard")));
+    }
+    roots
+}
+
+/// Create a backup archive. When `output_path` is `None`, the archive is
+/// written to `<config dir>/backups/xray_backup_<timestamp>.zip`.
+pub fn create_backup(output_path: Option<&str>) -> Result<BackupResponse, String> {
+    let created_at = chrono::Utc::now().to_rfc3339();
+    let path = match output_path {
+        Some(p) => PathBuf::from(p),
+        None => {
+            let dir = get_config_dir().join(BACKUPS_DIR);
+            std::fs::create_dir_all(&dir)
+                .map_err(|e| format!("Failed to create backups dir: {}", e))?;
+            let stamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+            dir.join(format!("xray_backup_{}.zip", stamp))
+        }
+    };
+
+    let file = std::fs::File::create(&path)
+        .map_err(|e| format!("Failed to create {:?}: {}", path, e))?;
+    let mut writer = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let mut entries = 0usize;
+    for (prefix, root) in backup_roots() {
+        if !root.exists() {
+            continue;
+        }
+        let mut files: Vec<PathBuf> = Vec::new();
+        collect_files(&root, &mut files);
+        for file_path in files {
+            // Don't pull previous backups (or live logs) into new backups.
+            let rel = match file_path.strip_prefix(&root) {
+                Ok(r) => r,
+                Err(_) => continue,
+            };
+            if rel.starts_with(BACKUPS_DIR) || rel.starts_with("logs") {
+                continue;
+            }
+            let name = format!("{}/{}", prefix, rel.to_string_lossy().replace('\\', "/"));
+            let content = std::fs::read(&file_path)
+                .map_err(|e| format!("Failed to read {:?}: {}", file_path, e))?;
+            writer
+                .start_file(&name, options)
+                .map_err(|e| format!("Failed to add {} to archive: {}", name, e))?;
+            writer
+                .write_all(&content)
+                .map_err(|e| format!("Failed to write {} to archive: {}", name, e))?;
+            entries += 1;
+        }
+    }
+
+    let manifest = BackupManifest {
+        version: MANIFEST_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: created_at.clone(),
+        entries,
+    };
+    writer
+        .start_file(MANIFEST_FILE, options)
+        .map_err(|e| format!("Failed to add manifest: {}", e))?;
+    writer
+        .write_all(serde_json::to_string_pretty(&manifest).unwrap_or_default().as_bytes())
+        .map_err(|e| format!("Failed to write manifest: {}", e))?;
+    writer
+        .finish()
+        .map_err(|e| format!("Failed to finalize archive: {}", e))?;
+
+    let total_bytes = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+    log::info!(
+        "Backup: wrote {} entries ({} bytes) to {:?}",
+        entries,
+        total_bytes,
+        path
+    );
+
+    Ok(BackupResponse {
+        path: path.to_string_lossy().into_owned(),
+        entries,
+        total_bytes,
+        created_at,
+    })
+}
+
+/// Restore a backup archive created by [`create_backup`].
+///
+/// Validates the manifest version before touching anything; existing files
+/// are overwritten (the archive wins). Caches restored this way may be stale
+/// — they're invalidated the same way as after any other disk change, on the
+/// next read.
+pub fn restore_backup(archive_path: &str) -> Result<RestoreResponse, String> {
+    let file = std::fs::File::open(archive_path)
+        .map_err(|e| format!("Failed to open {:?}: {}", archive_path, e))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| format!("Not a valid zip archive: {}", e))?;
+
+    // Manifest first — refuse anything we don't understand.
+    let manifest: BackupManifest = {
+        let mut entry = archive
+            .by_name(MANIFEST_FILE)
+            .map_err(|_| "Archive has no backup_manifest.json — not an X-Ray backup".to_string())?;
+        let mut content = String::new();
+        entry
+            .read_to_string(&mut content)
+            .map_err(|e| format!("Failed to read manifest: {}", e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Invalid manifest: {}", e))?
+    };
+    if manifest.version > MANIFEST_VERSION {
+        return Err(format!(
+            "Backup manifest version {} is newer than this app supports ({}) — update the app first",
+            manifest.version, MANIFEST_VERSION
+        ));
+    }
+
+    let mut warnings: Vec<String> = Vec::new();
+    if manifest.app_version != env!("CARGO_PKG_VERSION") {
+        warnings.push(format!(
+            "Backup was created by app version {} (this is {})",
+            manifest.app_version,
+            env!("CARGO_PKG_VERSION")
+        ));
+    }
+
+    let roots: Vec<(&str, PathBuf)> = backup_roots();
+    let mut entries_restored = 0usize;
+
+    for i in 0..archive.len() {
+        let mut entry = archive
+            .by_index(i)
+            .map_err(|e| format!("Failed to read archive entry {}: {}", i, e))?;
+        let name = entry.name().to_string();
+        if name == MANIFEST_FILE || name.ends_with('/') {
+            continue;
+        }
+        let (prefix, rel) = match name.split_once('/') {
+            Some(parts) => parts,
+            None => {
+                warnings.push(format!("Skipped unexpected entry: {}", name));
+                continue;
+            }
+        };
+        let root = match roots.iter().find(|(p, _)| *p == prefix) {
+            Some((_, root)) => root,
+            None => {
+                warnings.push(format!("Skipped entry with unknown root: {}", name));
+                continue;
+            }
+        };
+        let rel_path = match safe_relative_path(rel) {
+            Some(p) => p,
+            None => {
+                warnings.push(format!("Skipped unsafe entry path: {}", name));
+                continue;
+            }
+        };
+
+        let target = root.join(rel_path);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+        }
+        let mut content = Vec::new();
+        entry
+            .read_to_end(&mut content)
+            .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+        std::fs::write(&target, &content)
+            .map_err(|e| format!("Failed to write {:?}: {}", target, e))?;
+        entries_restored += 1;
+    }
+
+    log::info!(
+        "Backup: restored {} entries from {:?} ({} warnings)",
+        entries_restored,
+        archive_path,
+        warnings.len()
+    );
+
+    Ok(RestoreResponse {
+        entries_restored,
+        backup_version: manifest.version,
+        app_version: manifest.app_version,
+        warnings,
+    })
+}
+
+/// Validate an archive-relative path: plain components only, no `..`, no
+/// absolute paths, no drive prefixes. Returns the path to join under a root.
+fn safe_relative_path(rel: &str) -> Option<PathBuf> {
+    let path = Path::new(rel);
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::Normal(part) => out.push(part),
+            _ => return None,
+        }
+    }
+    if out.as_os_str().is_empty() {
+        None
+    } else {
+        Some(out)
+    }
+}
+
+fn collect_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                collect_files(&path, out);
+            } else {
+                out.push(path);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn safe_relative_path_accepts_nested() {
+        assert_eq!(
+            safe_relative_path("task_notes/123.json"),
+            Some(PathBuf::from("task_notes/123.json"))
+        );
+    }
+
+    #[test]
+    fn safe_relative_path_rejects_traversal() {
+        assert_eq!(safe_relative_path("../evil.json"), None);
+        assert_eq!(safe_relative_path("a/../../evil.json"), None);
+        assert_eq!(safe_relative_path("/etc/passwd"), None);
+        assert_eq!(safe_relative_path(""), None);
+    }
+
+    #[test]
+    fn manifest_round_trips() {
+        let manifest = BackupManifest {
+            version: MANIFEST_VERSION,
+            app_version: "0.1.0".to_string(),
+            created_at: "2025-01-01T00:00:00Z".to_string(),
+            entries: 3,
+        };
+        let json = serde_json::to_string(&manifest).unwrap();
+        let back: BackupManifest = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.version, MANIFEST_VERSION);
+        assert_eq!(back.entries, 3);
+    }
+}
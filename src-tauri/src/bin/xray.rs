@@ -0,0 +1,243 @@
+//! `xray` — companion CLI for the Cline X-Ray REST API.
+//!
+//! Talks to the locally running server using the connection info the app
+//! writes to the project `.env` (`REST_API_URL` / `REST_API_TOKEN`), so the
+//! workflow is: start the app (windowed or `--headless`), then script away.
+//!
+//! Subcommands:
+//!
+//! ```text
+//! xray latest          # composite view of the most recent task
+//! xray tasks [N]       # recent task summaries (default 20)
+//! xray diff <task-id>  # per-file diff stats for a task
+//! xray jira list       # Jira issues assigned to the configured user
+//! ```
+//!
+//! Pass `--json` to any subcommand to get the raw response body for piping
+//! into `jq`.
+
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use std::env;
+
+const USAGE: &str = "Usage: xray <command> [args] [--json]
+
+Commands:
+  latest          Show the most recent task (checkpoint summary + changed files)
+  tasks [N]       List recent task summaries (default 20)
+  diff <task-id>  Show per-file diff stats for a task
+  jira list       List Jira issues assigned to the configured user
+
+Connection info is read from REST_API_URL / REST_API_TOKEN (.env in the
+project root, written automatically when the app starts).";
+
+#[tokio::main]
+async fn main() {
+    // The app writes connection info to the project-root .env; when invoked
+    // from src-tauri, that file is one level up.
+    for path in [".env", "../.env"] {
+        if std::path::Path::new(path).exists() {
+            dotenvy::from_path(path).ok();
+            break;
+        }
+    }
+
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let json_output = args.iter().any(|a| a == "--json");
+    args.retain(|a| a != "--json");
+
+    let base_url = match env::var("REST_API_URL") {
+        Ok(url) => url.trim_end_matches('/').to_string(),
+        Err(_) => {
+            eprintln!("REST_API_URL not set — is the app running? (it writes .env on startup)");
+            std::process::exit(1);
+        }
+    };
+    let token = env::var("REST_API_TOKEN").unwrap_or_default();
+
+    let mut headers = HeaderMap::new();
+    if let Ok(value) = HeaderValue::from_str(&format!("Bearer {}", token)) {
+        headers.insert(AUTHORIZATION, value);
+    }
+    let client = reqwest::Client::builder()
+        .default_headers(headers)
+        .build()
+        .expect("Failed to build HTTP client");
+
+    let result = match args.iter().map(|s| s.as_str()).collect::<Vec<_>>()[..] {
+        ["latest"] => cmd_latest(&client, &base_url, json_output).await,
+        ["tasks"] => cmd_tasks(&client, &base_url, 20, json_output).await,
+        ["tasks", n] => match n.parse() {
+            Ok(n) => cmd_tasks(&client, &base_url, n, json_output).await,
+            Err(_) => Err(format!("Invalid task count '{}'", n)),
+        },
+        ["diff", task_id] => cmd_diff(&client, &base_url, task_id, json_output).await,
+        ["jira", "list"] => cmd_jira_list(&client, &base_url, json_output).await,
+        _ => {
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// GET a path and return the parsed JSON body, with HTTP errors surfaced
+/// as readable messages (the server's ApiError body includes the details).
+async fn get_json(
+    client: &reqwest::Client,
+    base_url: &str,
+    path: &str,
+) -> Result<serde_json::Value, String> {
+    let url = format!("{}{}", base_url, path);
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("GET {} failed: {}", url, e))?;
+    let status = response.status();
+    let body: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("GET {} returned invalid JSON: {}", url, e))?;
+    if !status.is_success() {
+        let message = body["error"].as_str().unwrap_or("unknown error");
+        return Err(format!("GET {} → {}: {}", path, status.as_u16(), message));
+    }
+    Ok(body)
+}
+
+async fn cmd_latest(
+    client: &reqwest::Client,
+    base_url: &str,
+    json_output: bool,
+) -> Result<(), String> {
+    let body = get_json(client, base_url, "/latest").await?;
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&body).unwrap_or_default());
+        return Ok(());
+    }
+    println!(
+        "Task {} ({})",
+        body["taskId"].as_str().unwrap_or("?"),
+        body["promptTimestamp"].as_str().unwrap_or("?")
+    );
+    let prompt = body["prompt"].as_str().unwrap_or("");
+    let first_line = prompt.lines().next().unwrap_or("");
+    println!("Prompt: {}", first_line);
+    match body["diff"]["files"].as_array() {
+        Some(files) => {
+            println!("Changed files: {}", files.len());
+            for file in files {
+                println!(
+                    "  {:<9} +{:<5} -{:<5} {}",
+                    file["status"].as_str().unwrap_or("?"),
+                    file["linesAdded"].as_u64().unwrap_or(0),
+                    file["linesRemoved"].as_u64().unwrap_or(0),
+                    file["path"].as_str().unwrap_or("?")
+                );
+            }
+        }
+        None => {
+            if let Some(reason) = body["noDiffReason"].as_str() {
+                println!("No diff: {}", reason);
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn cmd_tasks(
+    client: &reqwest::Client,
+    base_url: &str,
+    limit: usize,
+    json_output: bool,
+) -> Result<(), String> {
+    let body = get_json(client, base_url, &format!("/history/tasks?limit={}", limit)).await?;
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&body).unwrap_or_default());
+        return Ok(());
+    }
+    let items = body["items"].as_array().cloned().unwrap_or_default();
+    println!(
+        "Showing {} of {} tasks\n",
+        items.len(),
+        body["total"].as_u64().unwrap_or(items.len() as u64)
+    );
+    for task in &items {
+        println!(
+            "{}  {}  msgs={:<4} tools={:<4} {}",
+            task["taskId"].as_str().unwrap_or("?"),
+            task["startedAt"].as_str().unwrap_or("?"),
+            task["messageCount"].as_u64().unwrap_or(0),
+            task["toolUseCount"].as_u64().unwrap_or(0),
+            task["modelId"].as_str().unwrap_or("-")
+        );
+    }
+    Ok(())
+}
+
+async fn cmd_diff(
+    client: &reqwest::Client,
+    base_url: &str,
+    task_id: &str,
+    json_output: bool,
+) -> Result<(), String> {
+    let body = get_json(
+        client,
+        base_url,
+        &format!("/changes/tasks/{}/diff", task_id),
+    )
+    .await?;
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&body).unwrap_or_default());
+        return Ok(());
+    }
+    let files = body["files"].as_array().cloned().unwrap_or_default();
+    let (mut additions, mut deletions) = (0u64, 0u64);
+    for file in &files {
+        let add = file["linesAdded"].as_u64().unwrap_or(0);
+        let del = file["linesRemoved"].as_u64().unwrap_or(0);
+        additions += add;
+        deletions += del;
+        println!(
+            "{:<9} +{:<5} -{:<5} {}",
+            file["status"].as_str().unwrap_or("?"),
+            add,
+            del,
+            file["path"].as_str().unwrap_or("?")
+        );
+    }
+    println!(
+        "\n{} files changed, {} insertions(+), {} deletions(-)",
+        files.len(),
+        additions,
+        deletions
+    );
+    Ok(())
+}
+
+async fn cmd_jira_list(
+    client: &reqwest::Client,
+    base_url: &str,
+    json_output: bool,
+) -> Result<(), String> {
+    let body = get_json(client, base_url, "/jira/list").await?;
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&body).unwrap_or_default());
+        return Ok(());
+    }
+    let issues = body["issues"].as_array().cloned().unwrap_or_default();
+    println!("Total issues: {}\n", body["total"].as_u64().unwrap_or(issues.len() as u64));
+    for issue in &issues {
+        println!(
+            "[{}] {} ({})",
+            issue["key"].as_str().unwrap_or("?"),
+            issue["summary"].as_str().unwrap_or("No summary"),
+            issue["status"].as_str().unwrap_or("Unknown")
+        );
+    }
+    Ok(())
+}
@@ -1,36 +1,330 @@
-use serde::Deserialize;
+//! Application configuration (`config.toml` under the config dir).
+//!
+//! One TOML file replaces the env vars and hardcoded constants that used to
+//! be scattered across modules:
+//!
+//! - `[server]` — REST listener port (0 = random, the historical behavior)
+//! - `[jira]` — Jira instance URL, account email, default JQL
+//! - `[providers]` — LLM provider settings (API key falls back to `GEMINI_API_KEY`)
+//! - `[cache]` — poller intervals and warmup toggles
+//! - `[retention]` — retention scheduler tick
+//! - `[logging]` — log level and console output
+//! - `[network]` — outbound proxy, corporate CA bundle, insecure flag
+//!
+//! The file is loaded once at startup and **hot-reloaded** by a background
+//! poller (`spawn_config_watcher`) that checks the file mtime; edits take
+//! effect without a restart for every consumer that reads via [`current`]
+//! each time (pollers, schedulers). Startup-only values (server port, Jira
+//! credentials already handed to `AppState`) still need a restart; the
+//! `/system/config` endpoint reports the effective values either way.
+//!
+//! API tokens are deliberately NOT stored here — the Jira token stays in the
+//! settings store and the Gemini key in the environment; the config value for
+//! the key is an optional fallback and is masked in `/system/config` output.
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
 pub struct AppConfig {
+    #[serde(default)]
     pub logging: LoggingConfig,
+    #[serde(default)]
+    pub server: ServerConfig,
+    #[serde(default)]
+    pub jira: JiraConfig,
+    #[serde(default)]
+    pub providers: ProvidersConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    #[serde(default)]
+    pub retention: RetentionConfig,
+    #[serde(default)]
+    pub jobs: JobsConfig,
+    #[serde(default)]
+    pub network: NetworkConfig,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct LoggingConfig {
     pub level: String, // "DEBUG", "INFO", "WARN", "ERROR"
     #[serde(default = "default_log_to_console")]
     pub log_to_console: bool,
+    /// Capture full (size-capped, redacted) request/response bodies for AI
+    /// calls, served by `GET /inference-logs/{id}`. Off by default.
+    #[serde(default)]
+    pub capture_inference_bodies: bool,
+    /// Rotate the log file once it exceeds this size
+    #[serde(default = "default_log_max_file_size_mb")]
+    pub max_file_size_mb: u64,
+    /// Rotated files kept besides the active one (jira_viewer.log.1..N)
+    #[serde(default = "default_log_keep_files")]
+    pub keep_files: usize,
+    /// In-memory ring buffer cap for the REST access log
+    #[serde(default = "default_access_log_entries")]
+    pub access_log_entries: usize,
+    /// In-memory ring buffer cap for AI inference log entries
+    #[serde(default = "default_inference_log_entries")]
+    pub inference_log_entries: usize,
+    /// In-memory ring buffer cap for tool execution log entries
+    #[serde(default = "default_execution_log_entries")]
+    pub execution_log_entries: usize,
+    /// Full inference exchanges kept (much lower than the entry cap —
+    /// bodies are orders of magnitude larger than entries)
+    #[serde(default = "default_inference_exchanges")]
+    pub inference_exchanges: usize,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ServerConfig {
+    /// Port for the REST listener (always bound to 127.0.0.1). 0 = random.
+    #[serde(default)]
+    pub port: u16,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct JiraConfig {
+    /// Jira instance base URL (e.g. "https://example.atlassian.net")
+    #[serde(default)]
+    pub base_url: String,
+    /// Account email for Basic auth
+    #[serde(default)]
+    pub email: String,
+    /// Default JQL for the issue list
+    #[serde(default = "default_jql")]
+    pub default_jql: String,
+    /// TCP connect timeout for Jira calls, in seconds
+    #[serde(default = "default_jira_connect_timeout_secs")]
+    pub connect_timeout_secs: u64,
+    /// Overall per-request timeout for Jira calls, in seconds
+    #[serde(default = "default_jira_request_timeout_secs")]
+    pub request_timeout_secs: u64,
+    /// Proxy URL for Jira calls (e.g. "http://proxy:8080"); empty = direct
+    #[serde(default)]
+    pub proxy: String,
+}
+
+impl Default for JiraConfig {
+    fn default() -> Self {
+        Self {
+            base_url: String::new(),
+            email: String::new(),
+            default_jql: default_jql(),
+            connect_timeout_secs: default_jira_connect_timeout_secs(),
+            request_timeout_secs: default_jira_request_timeout_secs(),
+            proxy: String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ProvidersConfig {
+    /// Gemini API key fallback. The `GEMINI_API_KEY` env var wins when set.
+    /// Masked in `/system/config` output.
+    #[serde(default)]
+    pub gemini_api_key: String,
+    /// Total attempts per provider HTTP call, including the first (1 disables retries)
+    #[serde(default = "default_retry_max_attempts")]
+    pub retry_max_attempts: u32,
+    /// Delay before the first retry, in ms; doubles on each further retry
+    #[serde(default = "default_retry_base_delay_ms")]
+    pub retry_base_delay_ms: u64,
+    /// Cap on any single backoff delay (also applied to Retry-After), in ms
+    #[serde(default = "default_retry_max_delay_ms")]
+    pub retry_max_delay_ms: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CacheConfig {
+    /// Poll interval for the /latest/stream filesystem watcher, in ms.
+    /// (`XRAY_LATEST_STREAM_POLL_MS` still wins for backward compatibility.)
+    #[serde(default = "default_latest_stream_poll_ms")]
+    pub latest_stream_poll_ms: u64,
+    /// Whether to run the startup cache warmer (shadow git discovery + history index)
+    #[serde(default = "default_true")]
+    pub warmup_enabled: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RetentionConfig {
+    /// How often the retention scheduler wakes to check the policy, in seconds
+    #[serde(default = "default_retention_tick_secs")]
+    pub scheduler_tick_secs: u64,
+}
+
+/// Outbound HTTP settings shared by every client (Jira, Gemini, webhooks),
+/// for environments behind corporate proxies with TLS interception.
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct NetworkConfig {
+    /// Proxy URL for all outbound calls (e.g. "http://proxy:8080");
+    /// `[jira] proxy` overrides this for Jira. Empty = direct
+    #[serde(default)]
+    pub proxy: String,
+    /// Path to a PEM bundle of extra root certificates (corporate CA)
+    #[serde(default)]
+    pub ca_bundle_path: String,
+    /// Skip TLS certificate verification. Last resort for broken
+    /// interception setups — a warning is logged when enabled
+    #[serde(default)]
+    pub insecure: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct JobsConfig {
+    /// Interval for rebuilding the conversation history task index, in seconds. 0 disables.
+    #[serde(default = "default_history_index_refresh_secs")]
+    pub history_index_refresh_secs: u64,
+    /// Interval for re-warming the shadow git caches, in seconds. 0 disables.
+    #[serde(default = "default_cache_warm_secs")]
+    pub cache_warm_secs: u64,
+    /// Interval for refreshing the cached Gemini model list, in seconds. 0 disables.
+    #[serde(default = "default_model_refresh_secs")]
+    pub model_refresh_secs: u64,
+    /// Interval for pruning old session log files, in seconds. 0 disables.
+    #[serde(default = "default_log_rotation_secs")]
+    pub log_rotation_secs: u64,
 }
 
 fn default_log_to_console() -> bool {
     true
 }
 
+fn default_log_max_file_size_mb() -> u64 {
+    10
+}
+
+fn default_log_keep_files() -> usize {
+    5
+}
+
+fn default_access_log_entries() -> usize {
+    1000
+}
+
+fn default_inference_log_entries() -> usize {
+    500
+}
+
+fn default_execution_log_entries() -> usize {
+    500
+}
+
+fn default_inference_exchanges() -> usize {
+    100
+}
+
+fn default_jira_connect_timeout_secs() -> u64 {
+    10
+}
+
+fn default_jira_request_timeout_secs() -> u64 {
+    30
+}
+
+fn default_jql() -> String {
+    "assignee = currentUser() ORDER BY updated DESC".to_string()
+}
+
+fn default_latest_stream_poll_ms() -> u64 {
+    2000
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_retention_tick_secs() -> u64 {
+    60 * 60 // hourly
+}
+
+fn default_history_index_refresh_secs() -> u64 {
+    15 * 60
+}
+
+fn default_cache_warm_secs() -> u64 {
+    30 * 60
+}
+
+fn default_model_refresh_secs() -> u64 {
+    60 * 60
+}
+
+fn default_retry_max_attempts() -> u32 {
+    3
+}
+
+fn default_retry_base_delay_ms() -> u64 {
+    500
+}
+
+fn default_retry_max_delay_ms() -> u64 {
+    8000
+}
+
+fn default_log_rotation_secs() -> u64 {
+    24 * 60 * 60
+}
+
 impl Default for LoggingConfig {
     fn default() -> Self {
         Self {
             level: "INFO".to_string(),
             log_to_console: true,
+            capture_inference_bodies: false,
+            max_file_size_mb: default_log_max_file_size_mb(),
+            keep_files: default_log_keep_files(),
+            access_log_entries: default_access_log_entries(),
+            inference_log_entries: default_inference_log_entries(),
+            execution_log_entries: default_execution_log_entries(),
+            inference_exchanges: default_inference_exchanges(),
+        }
+    }
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self { port: 0 }
+    }
+}
+
+impl Default for ProvidersConfig {
+    fn default() -> Self {
+        Self {
+            gemini_api_key: String::new(),
+            retry_max_attempts: default_retry_max_attempts(),
+            retry_base_delay_ms: default_retry_base_delay_ms(),
+            retry_max_delay_ms: default_retry_max_delay_ms(),
+        }
+    }
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            latest_stream_poll_ms: default_latest_stream_poll_ms(),
+            warmup_enabled: true,
+        }
+    }
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            scheduler_tick_secs: default_retention_tick_secs(),
         }
     }
 }
 
-impl Default for AppConfig {
+impl Default for JobsConfig {
     fn default() -> Self {
         Self {
-            logging: LoggingConfig::default(),
+            history_index_refresh_secs: default_history_index_refresh_secs(),
+            cache_warm_secs: default_cache_warm_secs(),
+            model_refresh_secs: default_model_refresh_secs(),
+            log_rotation_secs: default_log_rotation_secs(),
         }
     }
 }
@@ -66,11 +360,82 @@ pub fn get_logs_dir() -> PathBuf {
     }
 }
 
-/// Generate a timestamped log file path for this session
+/// Path of the active log file. Rotated siblings live next to it as
+/// `jira_viewer.log.1` .. `.N` (see `[logging]` max_file_size_mb/keep_files)
 pub fn get_log_file_path() -> PathBuf {
-    let logs_dir = get_logs_dir();
-    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
-    logs_dir.join(format!("jira_viewer_{}.log", timestamp))
+    get_logs_dir().join("jira_viewer.log")
+}
+
+// ============ Live config (startup load + hot reload) ============
+
+/// The currently effective configuration. Swapped atomically by the watcher
+/// on file change; readers always see a complete, parsed config.
+static CURRENT: Lazy<RwLock<AppConfig>> = Lazy::new(|| RwLock::new(load_config()));
+
+/// Get a snapshot of the currently effective configuration.
+///
+/// Consumers that want hot-reload behavior (pollers, schedulers) should call
+/// this each iteration rather than caching the result.
+pub fn current() -> AppConfig {
+    CURRENT.read().clone()
+}
+
+/// Spawn the config hot-reload watcher on the server runtime.
+///
+/// Polls the config file mtime every few seconds (same approach as the
+/// /latest/stream filesystem watcher — no extra native-watcher dependency)
+/// and re-parses on change. A file that fails to parse is logged and ignored;
+/// the previous config stays effective.
+pub fn spawn_config_watcher() {
+    tokio::spawn(async move {
+        let path = get_config_path();
+        let mut last_mtime = file_mtime(&path);
+        log::info!("Config watcher started for {:?}", path);
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            let mtime = file_mtime(&path);
+            if mtime == last_mtime {
+                continue;
+            }
+            last_mtime = mtime;
+            match fs::read_to_string(&path) {
+                Ok(content) => match toml::from_str::<AppConfig>(&content) {
+                    Ok(config) => {
+                        *CURRENT.write() = config;
+                        log::info!("Config hot-reloaded from {:?}", path);
+                    }
+                    Err(e) => {
+                        log::warn!(
+                            "Config watcher: {:?} failed to parse ({}); keeping previous config",
+                            path,
+                            e
+                        );
+                    }
+                },
+                Err(e) => {
+                    log::warn!("Config watcher: failed to read {:?}: {}", path, e);
+                }
+            }
+        }
+    });
+}
+
+fn file_mtime(path: &PathBuf) -> Option<std::time::SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Effective configuration as JSON with secrets masked, for `/system/config`.
+pub fn masked_effective() -> serde_json::Value {
+    let config = current();
+    let mut value = serde_json::to_value(&config).unwrap_or(serde_json::json!({}));
+    if let Some(key) = value["providers"]["gemini_api_key"].as_str() {
+        if !key.is_empty() {
+            value["providers"]["gemini_api_key"] = serde_json::Value::String("***".to_string());
+        }
+    }
+    value["config_path"] =
+        serde_json::Value::String(get_config_path().to_string_lossy().into_owned());
+    value
 }
 
 /// Load configuration from file, or create default if not exists
@@ -88,6 +453,7 @@ pub fn load_config() -> AppConfig {
     // Create default config file if it doesn't exist
     let default_config = AppConfig::default();
     let toml_content = r#"# jira-viewer configuration
+# Edits are hot-reloaded while the app is running (checked every 5s).
 
 [logging]
 # Log level: "DEBUG", "INFO", "WARN", "ERROR"
@@ -97,6 +463,75 @@ level = "INFO"
 
 # Whether to also log to console (useful for development)
 log_to_console = true
+
+# Capture full AI request/response bodies (size-capped, API key redacted)
+# for GET /inference-logs/{id}. Off by default — bodies can be large.
+capture_inference_bodies = false
+
+# Log rotation: rotate jira_viewer.log past this size, keeping this many
+# rotated files (jira_viewer.log.1 .. .N) besides the active one
+max_file_size_mb = 10
+keep_files = 5
+
+# In-memory ring buffer capacities (hot-reloaded; effective values are
+# reported by GET /system/config)
+access_log_entries = 1000
+inference_log_entries = 500
+execution_log_entries = 500
+inference_exchanges = 100
+
+[server]
+# Port for the REST listener (always bound to 127.0.0.1). 0 = random.
+port = 0
+
+[jira]
+# Jira instance and account. The API token is NOT stored here.
+base_url = ""
+email = ""
+default_jql = "assignee = currentUser() ORDER BY updated DESC"
+
+# HTTP settings for the pooled Jira client
+connect_timeout_secs = 10
+request_timeout_secs = 30
+# Proxy URL for Jira calls (e.g. "http://proxy:8080"); empty = direct
+proxy = ""
+
+[providers]
+# Fallback Gemini API key; the GEMINI_API_KEY env var wins when set.
+gemini_api_key = ""
+# Retry policy for provider HTTP calls (429/5xx and transport errors).
+# Total attempts including the first; 1 disables retries.
+retry_max_attempts = 3
+# First-retry delay in ms; doubles per retry up to the cap below.
+retry_base_delay_ms = 500
+retry_max_delay_ms = 8000
+
+[cache]
+# Poll interval for the /latest/stream filesystem watcher, in ms.
+latest_stream_poll_ms = 2000
+# Run the startup cache warmer (shadow git discovery + history index).
+warmup_enabled = true
+
+[retention]
+# How often the retention scheduler job checks the policy, in seconds.
+scheduler_tick_secs = 3600
+
+[jobs]
+# Background job intervals in seconds; 0 disables a job.
+# See GET /system/jobs for last-run status.
+history_index_refresh_secs = 900
+cache_warm_secs = 1800
+model_refresh_secs = 3600
+log_rotation_secs = 86400
+
+[network]
+# Outbound HTTP settings shared by every client (Jira, Gemini, webhooks).
+# Proxy URL (e.g. "http://proxy:8080"); [jira] proxy overrides for Jira.
+proxy = ""
+# Path to a PEM bundle of extra root certificates (corporate CA).
+ca_bundle_path = ""
+# Skip TLS verification entirely. Last resort for broken interception setups.
+insecure = false
 "#;
 
     fs::write(&config_path, toml_content).ok();
@@ -0,0 +1,125 @@
+//! Activity heatmap bucketing across all tasks.
+//!
+//! Contains:
+//! - Full scan of ui_messages.json timestamps across all task directories
+//! - Hour-of-day / day-of-week bucketing for messages and tool calls
+//!
+//! Feeds GET /history/stats/activity for rendering usage heatmaps in the UI.
+
+use chrono::{Datelike, Timelike};
+
+use super::root::tasks_root;
+use super::types::*;
+
+/// Scan all tasks and bucket message / tool-call activity by hour and weekday.
+///
+/// Every `ui_messages.json` entry counts as a message; entries whose `say` or
+/// `ask` is `"tool"` also count as tool calls. Timestamps are converted to
+/// local time before bucketing (heatmaps should reflect the user's clock).
+///
+/// Weekday index 0 = Monday … 6 = Sunday.
+pub fn scan_activity() -> ActivityHeatmapResponse {
+    let mut messages_by_hour = vec![0usize; 24];
+    let mut tool_calls_by_hour = vec![0usize; 24];
+    let mut messages_by_weekday = vec![0usize; 7];
+    let mut tool_calls_by_weekday = vec![0usize; 7];
+    let mut message_grid = vec![vec![0usize; 24]; 7];
+    let mut tool_call_grid = vec![vec![0usize; 24]; 7];
+    let mut total_messages = 0usize;
+    let mut total_tool_calls = 0usize;
+    let mut tasks_scanned = 0usize;
+
+    let root = match tasks_root() {
+        Some(r) => r,
+        None => {
+            return ActivityHeatmapResponse {
+                tasks_scanned: 0,
+                total_messages: 0,
+                total_tool_calls: 0,
+                messages_by_hour,
+                tool_calls_by_hour,
+                messages_by_weekday,
+                tool_calls_by_weekday,
+                message_grid,
+                tool_call_grid,
+            };
+        }
+    };
+
+    let entries = match std::fs::read_dir(&root) {
+        Ok(e) => e,
+        Err(e) => {
+            log::error!("Failed to read tasks directory {:?}: {}", root, e);
+            return ActivityHeatmapResponse {
+                tasks_scanned: 0,
+                total_messages: 0,
+                total_tool_calls: 0,
+                messages_by_hour,
+                tool_calls_by_hour,
+                messages_by_weekday,
+                tool_calls_by_weekday,
+                message_grid,
+                tool_call_grid,
+            };
+        }
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+
+        let ui_messages_path = path.join("ui_messages.json");
+        let content = match std::fs::read_to_string(&ui_messages_path) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+
+        let messages: Vec<RawUiMessage> = match serde_json::from_str(&content) {
+            Ok(m) => m,
+            Err(e) => {
+                log::debug!("Activity scan: skipping {:?}: {}", ui_messages_path, e);
+                continue;
+            }
+        };
+
+        tasks_scanned += 1;
+
+        for msg in &messages {
+            let secs = (msg.ts / 1000) as i64;
+            let dt = match chrono::DateTime::from_timestamp(secs, 0) {
+                Some(dt) => dt.with_timezone(&chrono::Local),
+                None => continue,
+            };
+
+            let hour = dt.hour() as usize;
+            let weekday = dt.weekday().num_days_from_monday() as usize;
+
+            total_messages += 1;
+            messages_by_hour[hour] += 1;
+            messages_by_weekday[weekday] += 1;
+            message_grid[weekday][hour] += 1;
+
+            let is_tool = msg.say.as_deref() == Some("tool") || msg.ask.as_deref() == Some("tool");
+            if is_tool {
+                total_tool_calls += 1;
+                tool_calls_by_hour[hour] += 1;
+                tool_calls_by_weekday[weekday] += 1;
+                tool_call_grid[weekday][hour] += 1;
+            }
+        }
+    }
+
+    ActivityHeatmapResponse {
+        tasks_scanned,
+        total_messages,
+        total_tool_calls,
+        messages_by_hour,
+        tool_calls_by_hour,
+        messages_by_weekday,
+        tool_calls_by_weekday,
+        message_grid,
+        tool_call_grid,
+    }
+}
@@ -0,0 +1,119 @@
+//! Side-by-side task comparison.
+//!
+//! Contains:
+//! - Per-task stat column building (reusing the summary/tools/files parsers)
+//! - Overlapping-file intersection between two tasks
+//!
+//! The main use case is "same prompt, different model": run a task twice and
+//! compare duration, token spend, tool failures and diff churn.
+
+use std::collections::BTreeSet;
+
+use super::files::parse_task_files;
+use super::root::tasks_root;
+use super::summary::parse_task_dir;
+use super::timeline::iso_to_epoch_ms;
+use super::tools::parse_task_tools;
+use super::types::*;
+use crate::shadow_git::{get_task_diff, resolve_workspace_for_task};
+
+/// Compare two tasks side by side.
+///
+/// Returns None if either task directory doesn't exist (the handler turns
+/// that into a 404 naming the missing task).
+pub fn compare_tasks(task_a: &str, task_b: &str) -> Option<TaskCompareResponse> {
+    let (side_a, files_a) = build_compare_side(task_a)?;
+    let (side_b, files_b) = build_compare_side(task_b)?;
+
+    let same_model = match (&side_a.model_id, &side_b.model_id) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    };
+
+    let overlapping_files: Vec<String> =
+        files_a.intersection(&files_b).cloned().collect();
+
+    Some(TaskCompareResponse {
+        task_a: side_a,
+        task_b: side_b,
+        same_model,
+        overlapping_files,
+    })
+}
+
+/// Build one comparison column plus the set of file paths the task touched.
+fn build_compare_side(task_id: &str) -> Option<(TaskCompareSide, BTreeSet<String>)> {
+    let root = tasks_root()?;
+    let dir = root.join(task_id);
+
+    if !dir.is_dir() {
+        log::warn!("Task directory not found: {:?}", dir);
+        return None;
+    }
+
+    let summary = parse_task_dir(task_id, &dir)?;
+
+    // Duration: ended_at (last ui_message) minus started_at (task_id epoch)
+    let duration_seconds = summary.ended_at.as_deref().and_then(|end| {
+        let start_ms = task_id.parse::<i64>().ok()?;
+        let end_ms = iso_to_epoch_ms(end)?;
+        Some((end_ms - start_ms) / 1000)
+    });
+
+    // Tool failure count comes from the tool timeline parser (pairs
+    // tool_use/tool_result by id — more accurate than the summary counters)
+    let tool_failure_count = parse_task_tools(task_id, None, false)
+        .map(|t| t.failure_count)
+        .unwrap_or(0);
+
+    // Files-in-context paths, used both for the count and the overlap set
+    let mut files: BTreeSet<String> = BTreeSet::new();
+    if let Some(files_resp) = parse_task_files(task_id, None, None) {
+        for f in files_resp.files {
+            files.insert(f.path);
+        }
+    }
+
+    // Checkpoint diff churn — best-effort, absent when there's no shadow git
+    // workspace for this task
+    let mut diff_files_changed = None;
+    let mut diff_lines_added = None;
+    let mut diff_lines_removed = None;
+    if let Some((workspace_id, git_dir)) = resolve_workspace_for_task(task_id) {
+        match get_task_diff(task_id, &git_dir, &[]) {
+            Ok(diff) => {
+                diff_files_changed = Some(diff.files.len());
+                diff_lines_added = Some(diff.files.iter().map(|f| f.lines_added).sum());
+                diff_lines_removed = Some(diff.files.iter().map(|f| f.lines_removed).sum());
+            }
+            Err(e) => {
+                log::warn!(
+                    "Compare: no diff for task {} in workspace {}: {}",
+                    task_id, workspace_id, e
+                );
+            }
+        }
+    }
+
+    let side = TaskCompareSide {
+        task_id: task_id.to_string(),
+        started_at: summary.started_at,
+        duration_seconds,
+        model_id: summary.model_id,
+        task_prompt: summary.task_prompt,
+        message_count: summary.message_count,
+        tool_call_count: summary.tool_use_count,
+        tool_failure_count,
+        api_request_count: summary.api_request_count,
+        total_tokens_in: summary.total_tokens_in,
+        total_tokens_out: summary.total_tokens_out,
+        total_cost: summary.total_cost,
+        files_edited: summary.files_edited,
+        files_read: summary.files_read,
+        diff_files_changed,
+        diff_lines_added,
+        diff_lines_removed,
+    };
+
+    Some((side, files))
+}
@@ -10,9 +10,12 @@
 use std::collections::HashMap;
 use std::path::Path;
 
+use super::focus_chain::{completion_percentage, parse_focus_chain_items};
+use super::notes::load_note;
 use super::root::tasks_root;
 use super::summary::parse_ui_messages_end_time;
 use super::types::*;
+use super::usage::parse_usage_totals;
 use super::util::{
     epoch_ms_to_iso, truncate_utf8, PROMPT_TRUNCATE_LEN, TEXT_TRUNCATE_LEN,
     TOOL_INPUT_TRUNCATE_LEN, TOOL_RESULT_TRUNCATE_LEN,
@@ -94,10 +97,18 @@ pub fn parse_task_detail(task_id: &str) -> Option<TaskDetailResponse> {
     } else {
         None
     };
+    let focus_chain_items = focus_chain
+        .as_deref()
+        .map(parse_focus_chain_items)
+        .unwrap_or_default();
+    let focus_chain_completion = completion_percentage(&focus_chain_items);
 
     // Full local path to the task directory
     let task_dir_path = dir.to_string_lossy().to_string();
 
+    // Token usage / cost totals from api_req_started entries
+    let usage = parse_usage_totals(&ui_messages_path);
+
     Some(TaskDetailResponse {
         task_id: task_id.to_string(),
         started_at,
@@ -117,8 +128,17 @@ pub fn parse_task_detail(task_id: &str) -> Option<TaskDetailResponse> {
         environment,
         focus_chain,
         has_focus_chain,
+        focus_chain_items,
+        focus_chain_completion,
         api_history_size_bytes: api_size,
         ui_messages_size_bytes: ui_size,
+        api_request_count: usage.api_request_count,
+        total_tokens_in: usage.tokens_in,
+        total_tokens_out: usage.tokens_out,
+        total_cache_writes: usage.cache_writes,
+        total_cache_reads: usage.cache_reads,
+        total_cost: usage.total_cost,
+        note: load_note(task_id),
         task_dir_path,
     })
 }
@@ -0,0 +1,199 @@
+//! Conversation export in fine-tuning dataset formats.
+//!
+//! Contains:
+//! - Per-task JSONL export (one record per task)
+//! - Bulk export across all tasks
+//! - OpenAI-style and ShareGPT-style record shaping
+//!
+//! Must not contain handler logic — handlers live in `handlers/export.rs`.
+
+use serde_json::json;
+
+use super::redact::redact_text;
+use super::root::tasks_root;
+use super::types::*;
+
+/// Export record style.
+///
+/// - `openai` — `{"messages": [{"role": "...", "content": "..."}]}` (OpenAI fine-tuning)
+/// - `sharegpt` — `{"conversations": [{"from": "human"|"gpt", "value": "..."}]}`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportStyle {
+    OpenAi,
+    ShareGpt,
+}
+
+impl ExportStyle {
+    /// Parse a style string from the `style` query param. Defaults to OpenAI.
+    pub fn parse(s: Option<&str>) -> Option<Self> {
+        match s {
+            None | Some("openai") => Some(ExportStyle::OpenAi),
+            Some("sharegpt") => Some(ExportStyle::ShareGpt),
+            Some(_) => None,
+        }
+    }
+}
+
+/// Export a single task's conversation as one JSONL record line.
+///
+/// Reads `api_conversation_history.json` and flattens each message's text blocks
+/// into a single content string. Tool blocks are rendered as bracketed markers
+/// (`[tool_use: name]`, `[tool_result]`) so turn structure survives without
+/// dumping full tool payloads into the dataset.
+///
+/// When `redact` is true, secret redaction (see `redact.rs`) is applied to each
+/// turn's content before serialization.
+///
+/// Returns None if the task directory doesn't exist or has no api_conversation_history.
+pub fn export_task_jsonl(task_id: &str, style: ExportStyle, redact: bool) -> Option<String> {
+    let root = tasks_root()?;
+    let dir = root.join(task_id);
+
+    if !dir.is_dir() {
+        log::warn!("Task directory not found: {:?}", dir);
+        return None;
+    }
+
+    let api_history_path = dir.join("api_conversation_history.json");
+    if !api_history_path.exists() {
+        log::warn!("No api_conversation_history.json for task {}", task_id);
+        return None;
+    }
+
+    let content = match std::fs::read_to_string(&api_history_path) {
+        Ok(c) => c,
+        Err(e) => {
+            log::warn!("Failed to read {:?}: {}", api_history_path, e);
+            return None;
+        }
+    };
+
+    let raw_messages: Vec<RawApiMessage> = match serde_json::from_str(&content) {
+        Ok(m) => m,
+        Err(e) => {
+            log::warn!("Failed to parse {:?}: {}", api_history_path, e);
+            return None;
+        }
+    };
+
+    Some(build_record_line(task_id, &raw_messages, style, redact))
+}
+
+/// Bulk export: one JSONL record per task, across all task directories.
+///
+/// Tasks that fail to parse are skipped (logged, not fatal) — the export
+/// contains every task that produced a valid record.
+///
+/// Returns (jsonl_content, exported_task_count).
+pub fn export_all_tasks_jsonl(style: ExportStyle, redact: bool) -> (String, usize) {
+    let root = match tasks_root() {
+        Some(r) => r,
+        None => return (String::new(), 0),
+    };
+
+    let entries = match std::fs::read_dir(&root) {
+        Ok(e) => e,
+        Err(e) => {
+            log::error!("Failed to read tasks directory {:?}: {}", root, e);
+            return (String::new(), 0);
+        }
+    };
+
+    // Collect task IDs first so the export is in chronological order (task_id is epoch ms).
+    let mut task_ids: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| e.file_name().to_str().map(|s| s.to_string()))
+        .collect();
+    task_ids.sort();
+
+    let mut lines = String::new();
+    let mut exported = 0usize;
+
+    for task_id in &task_ids {
+        match export_task_jsonl(task_id, style, redact) {
+            Some(line) => {
+                lines.push_str(&line);
+                lines.push('\n');
+                exported += 1;
+            }
+            None => {
+                log::debug!("Bulk export: skipping task {} (no parseable data)", task_id);
+            }
+        }
+    }
+
+    (lines, exported)
+}
+
+/// Build one JSONL record line for a task's messages in the requested style.
+fn build_record_line(
+    task_id: &str,
+    raw_messages: &[RawApiMessage],
+    style: ExportStyle,
+    redact: bool,
+) -> String {
+    let turns: Vec<(String, String)> = raw_messages
+        .iter()
+        .filter_map(|msg| {
+            let content = flatten_message_content(msg);
+            if content.is_empty() {
+                None
+            } else if redact {
+                Some((msg.role.clone(), redact_text(&content)))
+            } else {
+                Some((msg.role.clone(), content))
+            }
+        })
+        .collect();
+
+    let record = match style {
+        ExportStyle::OpenAi => {
+            let messages: Vec<serde_json::Value> = turns
+                .iter()
+                .map(|(role, content)| json!({ "role": role, "content": content }))
+                .collect();
+            json!({ "task_id": task_id, "messages": messages })
+        }
+        ExportStyle::ShareGpt => {
+            let conversations: Vec<serde_json::Value> = turns
+                .iter()
+                .map(|(role, content)| {
+                    let from = if role == "assistant" { "gpt" } else { "human" };
+                    json!({ "from": from, "value": content })
+                })
+                .collect();
+            json!({ "id": task_id, "conversations": conversations })
+        }
+    };
+
+    serde_json::to_string(&record).unwrap_or_default()
+}
+
+/// Flatten a message's content blocks into a single string for export.
+///
+/// Text blocks are included verbatim; thinking blocks are skipped (not part of
+/// the visible conversation); tool blocks become compact bracketed markers.
+fn flatten_message_content(msg: &RawApiMessage) -> String {
+    let mut parts: Vec<String> = Vec::new();
+
+    for block in &msg.content {
+        match block {
+            RawContentBlock::Text { text } => {
+                if !text.trim().is_empty() {
+                    parts.push(text.clone());
+                }
+            }
+            RawContentBlock::Thinking { .. } => {} // internal reasoning — not exported
+            RawContentBlock::ToolUse { name, .. } => {
+                parts.push(format!("[tool_use: {}]", name));
+            }
+            RawContentBlock::ToolResult { .. } => {
+                parts.push("[tool_result]".to_string());
+            }
+            RawContentBlock::Unknown => {}
+        }
+    }
+
+    parts.join("\n\n")
+}
@@ -0,0 +1,65 @@
+//! Focus chain checklist parsing.
+//!
+//! Contains:
+//! - Markdown checklist parsing into structured items
+//! - Completion percentage calculation
+//!
+//! Cline writes a `focus_chain_taskid_<id>.md` file per task with the agent's
+//! running progress checklist — lines like `- [ ] step` / `- [x] step`.
+
+use std::path::Path;
+
+use super::types::FocusChainItem;
+
+/// Parse a focus chain markdown document into structured checklist items.
+///
+/// Recognizes `- [ ]`, `- [x]` and `- [X]` lines (also `*` bullets); all
+/// other lines (headings, prose) are ignored. Item order is the order of
+/// appearance in the document.
+pub(crate) fn parse_focus_chain_items(markdown: &str) -> Vec<FocusChainItem> {
+    let mut items = Vec::new();
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        let rest = match trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* ")) {
+            Some(r) => r.trim_start(),
+            None => continue,
+        };
+
+        let (checked, text) = if let Some(t) = rest.strip_prefix("[ ]") {
+            (false, t)
+        } else if let Some(t) = rest.strip_prefix("[x]").or_else(|| rest.strip_prefix("[X]")) {
+            (true, t)
+        } else {
+            continue;
+        };
+
+        items.push(FocusChainItem {
+            order: items.len(),
+            text: text.trim().to_string(),
+            checked,
+        });
+    }
+
+    items
+}
+
+/// Completion percentage (0–100) for a list of checklist items.
+///
+/// Returns None for an empty list — "no checklist" is distinct from "0% done".
+pub(crate) fn completion_percentage(items: &[FocusChainItem]) -> Option<f64> {
+    if items.is_empty() {
+        return None;
+    }
+    let checked = items.iter().filter(|i| i.checked).count();
+    Some(checked as f64 / items.len() as f64 * 100.0)
+}
+
+/// Read a focus chain file and return its completion percentage.
+///
+/// Lightweight helper for the summary hot path — returns None if the file is
+/// missing, unreadable or contains no checklist items.
+pub(crate) fn focus_chain_completion_from_file(path: &Path) -> Option<f64> {
+    let content = std::fs::read_to_string(path).ok()?;
+    completion_percentage(&parse_focus_chain_items(&content))
+}
@@ -0,0 +1,88 @@
+//! Task comparison handler.
+//!
+//! Responsibility:
+//! - Side-by-side comparison of two tasks
+//!
+//! Owns: GET /history/compare
+
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use std::sync::Arc;
+use std::time::Instant;
+
+use super::common::validate_task_id;
+use crate::conversation_history::compare::compare_tasks;
+use crate::conversation_history::types::{
+    HistoryErrorResponse, TaskCompareQuery, TaskCompareResponse,
+};
+use crate::state::AppState;
+
+/// Compare two tasks side by side
+///
+/// Returns per-task stats (duration, tokens, cost, tool calls and failures,
+/// files touched, checkpoint diff churn) for both tasks plus the files they
+/// have in common. Intended for "same prompt, different model" comparisons.
+#[utoipa::path(
+    get,
+    path = "/history/compare",
+    params(TaskCompareQuery),
+    responses(
+        (status = 200, description = "Side-by-side comparison of the two tasks", body = TaskCompareResponse),
+        (status = 400, description = "Invalid task ID", body = HistoryErrorResponse),
+        (status = 404, description = "One of the tasks was not found", body = HistoryErrorResponse),
+        (status = 500, description = "Internal server error", body = HistoryErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["history"]
+)]
+pub async fn compare_tasks_handler(
+    State(_state): State<Arc<AppState>>,
+    Query(params): Query<TaskCompareQuery>,
+) -> Result<Json<TaskCompareResponse>, (StatusCode, Json<HistoryErrorResponse>)> {
+    validate_task_id(&params.task_a)?;
+    validate_task_id(&params.task_b)?;
+
+    log::info!(
+        "REST API: GET /history/compare?task_a={}&task_b={}",
+        params.task_a, params.task_b
+    );
+    let start = Instant::now();
+
+    let task_a = params.task_a.clone();
+    let task_b = params.task_b.clone();
+    let result = tokio::task::spawn_blocking(move || compare_tasks(&task_a, &task_b)).await;
+
+    match result {
+        Ok(Some(response)) => {
+            log::info!(
+                "REST API: Compared tasks {} and {} in {:?} ({} overlapping files)",
+                params.task_a,
+                params.task_b,
+                start.elapsed(),
+                response.overlapping_files.len()
+            );
+            Ok(Json(response))
+        }
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(HistoryErrorResponse {
+                error: format!(
+                    "Task '{}' or '{}' not found",
+                    params.task_a, params.task_b
+                ),
+                code: 404,
+            }),
+        )),
+        Err(e) => {
+            log::error!("REST API: Task comparison failed: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(HistoryErrorResponse {
+                    error: format!("Failed to compare tasks: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Conversation export handlers.
+//!
+//! Responsibility:
+//! - Per-task JSONL export (fine-tuning dataset format)
+//! - Bulk export across all tasks
+//!
+//! Owns: GET /history/tasks/{task_id}/export, GET /history/export
+
+use axum::extract::{Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use std::sync::Arc;
+
+use super::common::validate_task_id;
+use crate::conversation_history::export::{export_all_tasks_jsonl, export_task_jsonl, ExportStyle};
+use crate::conversation_history::types::{ExportQuery, HistoryErrorResponse};
+use crate::state::AppState;
+
+/// Validate the `format` / `style` query params shared by both export endpoints.
+fn parse_export_params(
+    params: &ExportQuery,
+) -> Result<ExportStyle, (StatusCode, Json<HistoryErrorResponse>)> {
+    // Only jsonl is supported today — reject unknown formats explicitly so
+    // future formats (csv, parquet) can be added without silent behavior changes.
+    if let Some(ref format) = params.format {
+        if format != "jsonl" {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(HistoryErrorResponse {
+                    error: format!("Unsupported format '{}': only 'jsonl' is supported", format),
+                    code: 400,
+                }),
+            ));
+        }
+    }
+
+    ExportStyle::parse(params.style.as_deref()).ok_or_else(|| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(HistoryErrorResponse {
+                error: format!(
+                    "Unsupported style '{}': must be 'openai' or 'sharegpt'",
+                    params.style.as_deref().unwrap_or("")
+                ),
+                code: 400,
+            }),
+        )
+    })
+}
+
+/// Wrap JSONL content in a downloadable response with NDJSON content type.
+fn jsonl_response(content: String, filename: &str) -> Response {
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "application/x-ndjson".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        content,
+    )
+        .into_response()
+}
+
+/// Export a single Cline task conversation as JSONL
+///
+/// Produces one JSONL record for the task in a fine-tuning dataset shape:
+/// - `?style=openai` (default) — `{"messages": [{"role", "content"}]}`
+/// - `?style=sharegpt` — `{"conversations": [{"from", "value"}]}`
+///
+/// Text blocks are exported verbatim; thinking blocks are omitted; tool blocks
+/// become compact `[tool_use: name]` / `[tool_result]` markers.
+///
+/// Only `?format=jsonl` is supported (it is also the default).
+#[utoipa::path(
+    get,
+    path = "/history/tasks/{task_id}/export",
+    params(
+        ("task_id" = String, Path, description = "Task ID (epoch milliseconds directory name)"),
+        ExportQuery
+    ),
+    responses(
+        (status = 200, description = "JSONL export of the task conversation", content_type = "application/x-ndjson", body = String),
+        (status = 404, description = "Task not found", body = HistoryErrorResponse),
+        (status = 400, description = "Invalid parameters", body = HistoryErrorResponse),
+        (status = 500, description = "Internal server error", body = HistoryErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["history", "tool"]
+)]
+pub async fn export_task_handler(
+    State(_state): State<Arc<AppState>>,
+    Path(task_id): Path<String>,
+    Query(params): Query<ExportQuery>,
+) -> Result<Response, (StatusCode, Json<HistoryErrorResponse>)> {
+    validate_task_id(&task_id)?;
+    let style = parse_export_params(&params)?;
+    let redact = params.redact.unwrap_or(false);
+
+    log::info!(
+        "REST API: GET /history/tasks/{}/export — style={:?}, redact={}",
+        task_id, style, redact
+    );
+
+    let tid = task_id.clone();
+    let result = tokio::task::spawn_blocking(move || export_task_jsonl(&tid, style, redact)).await;
+
+    match result {
+        Ok(Some(line)) => {
+            let filename = format!("cline_task_{}.jsonl", task_id);
+            Ok(jsonl_response(format!("{}\n", line), &filename))
+        }
+        Ok(None) => {
+            log::warn!("REST API: Task {} not found for export", task_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(HistoryErrorResponse {
+                    error: format!("Task '{}' not found or has no conversation history", task_id),
+                    code: 404,
+                }),
+            ))
+        }
+        Err(e) => {
+            log::error!("REST API: Failed to export task {}: {}", task_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(HistoryErrorResponse {
+                    error: format!("Failed to export task: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
+
+/// Bulk-export all Cline task conversations as JSONL
+///
+/// Produces one JSONL record per task (chronological order), in the same
+/// record shapes as the per-task export. Tasks with unparseable data are
+/// skipped rather than failing the whole export.
+#[utoipa::path(
+    get,
+    path = "/history/export",
+    params(ExportQuery),
+    responses(
+        (status = 200, description = "JSONL export of all task conversations (one record per task)", content_type = "application/x-ndjson", body = String),
+        (status = 400, description = "Invalid parameters", body = HistoryErrorResponse),
+        (status = 500, description = "Internal server error", body = HistoryErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["history", "tool"]
+)]
+pub async fn export_all_tasks_handler(
+    State(_state): State<Arc<AppState>>,
+    Query(params): Query<ExportQuery>,
+) -> Result<Response, (StatusCode, Json<HistoryErrorResponse>)> {
+    let style = parse_export_params(&params)?;
+    let redact = params.redact.unwrap_or(false);
+
+    log::info!("REST API: GET /history/export — style={:?}, redact={}", style, redact);
+
+    let result = tokio::task::spawn_blocking(move || {
+        let start = std::time::Instant::now();
+        let (content, count) = export_all_tasks_jsonl(style, redact);
+        let elapsed = start.elapsed();
+        log::info!(
+            "Bulk export complete: {} tasks in {:.1}s ({:.1} KB)",
+            count,
+            elapsed.as_secs_f64(),
+            content.len() as f64 / 1024.0
+        );
+        (content, count)
+    })
+    .await;
+
+    match result {
+        Ok((content, count)) => {
+            log::info!("REST API: Bulk export returning {} task records", count);
+            Ok(jsonl_response(content, "cline_tasks_export.jsonl"))
+        }
+        Err(e) => {
+            log::error!("REST API: Bulk export failed: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(HistoryErrorResponse {
+                    error: format!("Failed to export tasks: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
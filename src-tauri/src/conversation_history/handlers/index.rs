@@ -44,10 +44,11 @@ use parking_lot::RwLock;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
+use crate::api::pagination::{resolve_window, Page};
 use crate::conversation_history::cache;
 use crate::conversation_history::summary::scan_all_tasks;
 use crate::conversation_history::types::{
-    HistoryErrorResponse, HistoryTasksQuery, TaskHistoryListResponse,
+    HistoryErrorResponse, HistoryTasksQuery, TaskHistoryListResponse, TaskHistorySummary,
 };
 use crate::state::AppState;
 
@@ -237,6 +238,15 @@ async fn do_refresh(
     }
 }
 
+/// Invalidate the in-memory task index cache.
+///
+/// Called after mutations that change the task set (archive/delete). The next
+/// request triggers a full rescan; the disk cache is overwritten at that point.
+pub(crate) fn invalidate_tasks_index() {
+    *TASKS_INDEX_CACHE.write() = None;
+    log::info!("Task index: cache invalidated (task set changed)");
+}
+
 // ============ Handler ============
 
 /// List all Cline task conversation histories
@@ -249,13 +259,15 @@ async fn do_refresh(
 ///
 /// Results are cached in memory and persisted to disk.
 /// Pass `?refresh=true` to force a full re-scan from disk.
-/// Supports optional `?model=`, `?limit=`, `?offset=` query parameters.
+/// Supports optional `?model=`, `?limit=`, `?offset=`, `?cursor=` query parameters.
+/// Returns the standard `{items, total, next_cursor}` list envelope.
 #[utoipa::path(
     get,
     path = "/history/tasks",
     params(HistoryTasksQuery),
     responses(
-        (status = 200, description = "List of Cline task conversation history summaries", body = TaskHistoryListResponse),
+        (status = 200, description = "Paginated list of Cline task conversation history summaries", body = Page<TaskHistorySummary>),
+        (status = 400, description = "Invalid cursor", body = HistoryErrorResponse),
         (status = 500, description = "Internal server error", body = HistoryErrorResponse)
     ),
     security(("bearerAuth" = [])),
@@ -264,28 +276,36 @@ async fn do_refresh(
 pub async fn list_history_tasks_handler(
     State(_state): State<Arc<AppState>>,
     Query(params): Query<HistoryTasksQuery>,
-) -> Result<Json<TaskHistoryListResponse>, (StatusCode, Json<HistoryErrorResponse>)> {
+) -> Result<Json<Page<TaskHistorySummary>>, (StatusCode, Json<HistoryErrorResponse>)> {
     let force_refresh = params.refresh.unwrap_or(false);
 
+    let window = resolve_window(params.limit, params.offset, params.cursor.as_deref())
+        .map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(HistoryErrorResponse { error: e, code: 400 }),
+            )
+        })?;
+
     let response = get_or_refresh_task_index(force_refresh).await?;
 
     log::info!(
-        "REST API: GET /history/tasks — returning {} tasks (refresh={})",
+        "REST API: GET /history/tasks — {} tasks in index (refresh={})",
         response.total_tasks,
         force_refresh
     );
 
-    Ok(Json(apply_filters(response, &params)))
+    Ok(Json(window.paginate(apply_model_filter(response, &params))))
 }
 
-/// Apply optional query filters (model, limit, offset) to the response.
+/// Apply the optional model filter to the cached index.
 ///
 /// Operates on a clone of the cached data — does not mutate the cache.
-fn apply_filters(
+/// Pagination happens afterwards via the shared envelope helpers.
+fn apply_model_filter(
     mut response: TaskHistoryListResponse,
     params: &HistoryTasksQuery,
-) -> TaskHistoryListResponse {
-    // Filter by model if specified
+) -> Vec<TaskHistorySummary> {
     if let Some(ref model) = params.model {
         response.tasks.retain(|t| {
             t.model_id
@@ -293,21 +313,6 @@ fn apply_filters(
                 .map(|m| m.contains(model.as_str()))
                 .unwrap_or(false)
         });
-        response.total_tasks = response.tasks.len();
     }
-
-    // Apply offset
-    let offset = params.offset.unwrap_or(0);
-    if offset > 0 && offset < response.tasks.len() {
-        response.tasks = response.tasks.split_off(offset);
-    } else if offset >= response.tasks.len() {
-        response.tasks.clear();
-    }
-
-    // Apply limit
-    if let Some(limit) = params.limit {
-        response.tasks.truncate(limit);
-    }
-
-    response
+    response.tasks
 }
@@ -0,0 +1,177 @@
+//! Task lifecycle handlers (archive / delete).
+//!
+//! Responsibility:
+//! - Archiving task directories out of the active task set
+//! - Permanently deleting task directories with safety checks
+//! - Invalidating the task index cache after mutations
+//!
+//! Owns: POST /history/tasks/{task_id}/archive, DELETE /history/tasks/{task_id}
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use std::sync::Arc;
+
+use super::common::validate_task_id;
+use super::index::invalidate_tasks_index;
+use crate::conversation_history::manage::{archive_task, delete_task};
+use crate::conversation_history::types::{
+    ArchiveTaskResponse, DeleteTaskQuery, DeleteTaskResponse, HistoryErrorResponse,
+};
+use crate::state::AppState;
+
+/// Archive a Cline task directory
+///
+/// Moves the task directory from Cline's `tasks/` folder to a sibling
+/// `tasks_archive/` folder on the same volume (an atomic rename — no data is
+/// copied). Archived tasks disappear from the task list and stats on the next
+/// scan; the files themselves are untouched and can be moved back by hand.
+///
+/// The task index cache is invalidated so the next request reflects the change.
+#[utoipa::path(
+    post,
+    path = "/history/tasks/{task_id}/archive",
+    params(
+        ("task_id" = String, Path, description = "Task ID (epoch milliseconds directory name)")
+    ),
+    responses(
+        (status = 200, description = "Task archived — response carries the destination path and size", body = ArchiveTaskResponse),
+        (status = 404, description = "Task not found", body = HistoryErrorResponse),
+        (status = 400, description = "Invalid task ID", body = HistoryErrorResponse),
+        (status = 500, description = "Archive operation failed", body = HistoryErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["history"]
+)]
+pub async fn archive_task_handler(
+    State(_state): State<Arc<AppState>>,
+    Path(task_id): Path<String>,
+) -> Result<Json<ArchiveTaskResponse>, (StatusCode, Json<HistoryErrorResponse>)> {
+    validate_task_id(&task_id)?;
+
+    log::info!("REST API: POST /history/tasks/{}/archive", task_id);
+
+    let tid = task_id.clone();
+    let result = tokio::task::spawn_blocking(move || archive_task(&tid)).await;
+
+    match result {
+        Ok(Some(Ok(response))) => {
+            invalidate_tasks_index();
+            Ok(Json(response))
+        }
+        Ok(Some(Err(e))) => {
+            log::error!("REST API: Failed to archive task {}: {}", task_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(HistoryErrorResponse { error: e, code: 500 }),
+            ))
+        }
+        Ok(None) => Err(task_not_found(&task_id)),
+        Err(e) => {
+            log::error!("REST API: Archive task {} panicked: {}", task_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(HistoryErrorResponse {
+                    error: format!("Failed to archive task: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
+
+/// Delete a Cline task directory permanently
+///
+/// Removes the task directory and everything in it. Two safety checks apply:
+/// - `?dry_run=true` previews the file count and bytes without deleting
+/// - a real delete requires `?confirm=<task_id>` matching the path task ID
+///
+/// The task index cache is invalidated after a real delete so the next
+/// request reflects the change. **This cannot be undone** — use the archive
+/// endpoint if there's any chance the history is still needed.
+#[utoipa::path(
+    delete,
+    path = "/history/tasks/{task_id}",
+    params(
+        ("task_id" = String, Path, description = "Task ID (epoch milliseconds directory name)"),
+        DeleteTaskQuery
+    ),
+    responses(
+        (status = 200, description = "Task deleted (or dry-run preview of what would be deleted)", body = DeleteTaskResponse),
+        (status = 404, description = "Task not found", body = HistoryErrorResponse),
+        (status = 400, description = "Missing or mismatched confirmation token", body = HistoryErrorResponse),
+        (status = 500, description = "Delete operation failed", body = HistoryErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["history"]
+)]
+pub async fn delete_task_handler(
+    State(_state): State<Arc<AppState>>,
+    Path(task_id): Path<String>,
+    Query(params): Query<DeleteTaskQuery>,
+) -> Result<Json<DeleteTaskResponse>, (StatusCode, Json<HistoryErrorResponse>)> {
+    validate_task_id(&task_id)?;
+
+    let dry_run = params.dry_run.unwrap_or(false);
+
+    // A real delete must carry a confirmation token matching the task ID —
+    // prevents accidental deletes from URL typos or over-eager clients.
+    if !dry_run && params.confirm.as_deref() != Some(task_id.as_str()) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(HistoryErrorResponse {
+                error: format!(
+                    "Deleting task '{}' requires ?confirm={} (or use ?dry_run=true to preview)",
+                    task_id, task_id
+                ),
+                code: 400,
+            }),
+        ));
+    }
+
+    log::info!(
+        "REST API: DELETE /history/tasks/{} — dry_run={}",
+        task_id, dry_run
+    );
+
+    let tid = task_id.clone();
+    let result = tokio::task::spawn_blocking(move || delete_task(&tid, dry_run)).await;
+
+    match result {
+        Ok(Some(Ok(response))) => {
+            if response.deleted {
+                invalidate_tasks_index();
+            }
+            Ok(Json(response))
+        }
+        Ok(Some(Err(e))) => {
+            log::error!("REST API: Failed to delete task {}: {}", task_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(HistoryErrorResponse { error: e, code: 500 }),
+            ))
+        }
+        Ok(None) => Err(task_not_found(&task_id)),
+        Err(e) => {
+            log::error!("REST API: Delete task {} panicked: {}", task_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(HistoryErrorResponse {
+                    error: format!("Failed to delete task: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
+
+fn task_not_found(task_id: &str) -> (StatusCode, Json<HistoryErrorResponse>) {
+    log::warn!("REST API: Task {} not found for lifecycle operation", task_id);
+    (
+        StatusCode::NOT_FOUND,
+        Json(HistoryErrorResponse {
+            error: format!("Task '{}' not found", task_id),
+            code: 404,
+        }),
+    )
+}
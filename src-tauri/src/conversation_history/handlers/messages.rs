@@ -16,8 +16,10 @@ use std::sync::Arc;
 
 use super::common::validate_task_id;
 use crate::conversation_history::messages::{parse_task_messages, parse_single_message};
+use crate::conversation_history::redact::{redact_full_message, redact_paginated_messages};
 use crate::conversation_history::types::{
-    FullMessageResponse, HistoryErrorResponse, PaginatedMessagesResponse, TaskMessagesQuery,
+    FullMessageResponse, HistoryErrorResponse, PaginatedMessagesResponse, RedactQuery,
+    TaskMessagesQuery,
 };
 use crate::state::AppState;
 
@@ -34,6 +36,7 @@ use crate::state::AppState;
 ///
 /// Supports pagination via `?offset=` and `?limit=` (default: 20, max: 100).
 /// Supports role filtering via `?role=user` or `?role=assistant`.
+/// Pass `?redact=true` to strip API keys, tokens, and emails from message content.
 ///
 /// This is an on-demand parse — files are read from disk each request.
 /// Lighter than the full task detail endpoint since it skips metadata/files/focus_chain.
@@ -76,10 +79,11 @@ pub async fn get_task_messages_handler(
     let offset = params.offset.unwrap_or(0);
     let limit = params.limit.unwrap_or(20).min(100); // default 20, max 100
     let role_filter = params.role.clone();
+    let redact = params.redact.unwrap_or(false);
 
     log::info!(
-        "REST API: GET /history/tasks/{}/messages — offset={}, limit={}, role={:?}",
-        task_id, offset, limit, role_filter
+        "REST API: GET /history/tasks/{}/messages — offset={}, limit={}, role={:?}, redact={}",
+        task_id, offset, limit, role_filter, redact
     );
 
     let tid = task_id.clone();
@@ -97,7 +101,10 @@ pub async fn get_task_messages_handler(
     .await;
 
     match result {
-        Ok(Some(response)) => {
+        Ok(Some(mut response)) => {
+            if redact {
+                redact_paginated_messages(&mut response);
+            }
             log::info!(
                 "REST API: Task {} messages: returning {} of {} (filtered {}, offset {}, has_more {})",
                 task_id,
@@ -148,12 +155,15 @@ pub async fn get_task_messages_handler(
 ///
 /// Use case: user clicks "expand" on a message in the UI to see full thinking,
 /// full tool input/result, or full text content.
+///
+/// Pass `?redact=true` to strip API keys, tokens, and emails from the content.
 #[utoipa::path(
     get,
     path = "/history/tasks/{task_id}/messages/{index}",
     params(
         ("task_id" = String, Path, description = "Task ID (epoch milliseconds directory name)"),
-        ("index" = usize, Path, description = "Message index in the conversation history array (0-based)")
+        ("index" = usize, Path, description = "Message index in the conversation history array (0-based)"),
+        RedactQuery
     ),
     responses(
         (status = 200, description = "Single message with full untruncated content", body = FullMessageResponse),
@@ -167,12 +177,14 @@ pub async fn get_task_messages_handler(
 pub async fn get_single_message_handler(
     State(_state): State<Arc<AppState>>,
     Path((task_id, msg_index)): Path<(String, usize)>,
+    Query(params): Query<RedactQuery>,
 ) -> Result<Json<FullMessageResponse>, (StatusCode, Json<HistoryErrorResponse>)> {
     validate_task_id(&task_id)?;
+    let redact = params.redact.unwrap_or(false);
 
     log::info!(
-        "REST API: GET /history/tasks/{}/messages/{} — fetching single message with full content",
-        task_id, msg_index
+        "REST API: GET /history/tasks/{}/messages/{} — fetching single message with full content (redact={})",
+        task_id, msg_index, redact
     );
 
     let tid = task_id.clone();
@@ -191,7 +203,10 @@ pub async fn get_single_message_handler(
     .await;
 
     match result {
-        Ok(Some(response)) => {
+        Ok(Some(mut response)) => {
+            if redact {
+                redact_full_message(&mut response);
+            }
             let total_content_chars: usize = response
                 .content
                 .iter()
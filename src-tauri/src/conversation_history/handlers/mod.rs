@@ -13,36 +13,81 @@
 //! - `thinking` — thinking blocks (GET /history/tasks/{task_id}/thinking)
 //! - `files` — files in context (GET /history/tasks/{task_id}/files)
 //! - `stats` — aggregate stats across all tasks (GET /history/stats)
+//! - `export` — JSONL dataset export (GET /history/tasks/{task_id}/export, GET /history/export)
+//! - `usage` — token usage & cost report (GET /history/tasks/{task_id}/usage)
+//! - `timeline` — unified message/tool/checkpoint timeline (GET /history/tasks/{task_id}/timeline)
+//! - `search` — in-task message search (GET /history/tasks/{task_id}/messages/search)
+//! - `prompts` — user prompt chain (GET /history/tasks/{task_id}/prompts)
+//! - `notes` — task annotation notes (GET/PUT /history/tasks/{task_id}/notes)
+//! - `manage` — task lifecycle (POST /history/tasks/{task_id}/archive, DELETE /history/tasks/{task_id})
+//! - `retention` — retention policy config, preview and runs (GET/PUT /history/retention, ...)
+//! - `compare` — side-by-side task comparison (GET /history/compare)
 
 mod common;
 
 // Public submodules - utoipa generates __path_* types that must be accessible
 // from the handlers module for OpenAPI derive macro to find them
+pub mod compare;
+pub mod export;
 pub mod files;
 pub mod index;
+pub mod manage;
 pub mod messages;
+pub mod notes;
+pub mod prompts;
+pub mod retention;
+pub mod search;
 pub mod stats;
 pub mod subtasks;
 pub mod task_detail;
 pub mod thinking;
+pub mod timeline;
 pub mod tools;
+pub mod usage;
 
 // Re-export all handler functions for backward compatibility
+pub use compare::compare_tasks_handler;
+pub use export::{export_all_tasks_handler, export_task_handler};
 pub use files::get_task_files_handler;
 pub use index::list_history_tasks_handler;
+pub use manage::{archive_task_handler, delete_task_handler};
 pub use messages::{get_single_message_handler, get_task_messages_handler};
-pub use stats::get_history_stats_handler;
+pub use notes::{get_task_note_handler, put_task_note_handler};
+pub use prompts::get_task_prompts_handler;
+pub use retention::{
+    get_retention_policy_handler, preview_retention_handler, put_retention_policy_handler,
+    run_retention_handler,
+};
+pub use search::search_task_messages_handler;
+pub use stats::{get_activity_heatmap_handler, get_history_stats_handler, get_model_stats_handler};
 pub use subtasks::get_task_subtasks_handler;
 pub use task_detail::get_task_detail_handler;
 pub use thinking::get_task_thinking_handler;
-pub use tools::get_task_tools_handler;
+pub use timeline::get_task_timeline_handler;
+pub use tools::{get_history_loops_handler, get_task_tools_handler};
+pub use usage::{get_context_growth_handler, get_task_usage_handler};
 
 // Re-export utoipa __path_* types for OpenAPI generation
+pub use compare::__path_compare_tasks_handler;
+pub use export::{__path_export_all_tasks_handler, __path_export_task_handler};
 pub use files::__path_get_task_files_handler;
 pub use index::__path_list_history_tasks_handler;
+pub use manage::{__path_archive_task_handler, __path_delete_task_handler};
 pub use messages::{__path_get_single_message_handler, __path_get_task_messages_handler};
-pub use stats::__path_get_history_stats_handler;
+pub use notes::{__path_get_task_note_handler, __path_put_task_note_handler};
+pub use prompts::__path_get_task_prompts_handler;
+pub use retention::{
+    __path_get_retention_policy_handler, __path_preview_retention_handler,
+    __path_put_retention_policy_handler, __path_run_retention_handler,
+};
+pub use search::__path_search_task_messages_handler;
+pub use stats::{
+    __path_get_activity_heatmap_handler, __path_get_history_stats_handler,
+    __path_get_model_stats_handler,
+};
 pub use subtasks::__path_get_task_subtasks_handler;
 pub use task_detail::__path_get_task_detail_handler;
 pub use thinking::__path_get_task_thinking_handler;
-pub use tools::__path_get_task_tools_handler;
+pub use timeline::__path_get_task_timeline_handler;
+pub use tools::{__path_get_history_loops_handler, __path_get_task_tools_handler};
+pub use usage::{__path_get_context_growth_handler, __path_get_task_usage_handler};
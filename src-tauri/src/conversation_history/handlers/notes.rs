@@ -0,0 +1,122 @@
+//! Task annotation notes handlers.
+//!
+//! Responsibility:
+//! - Reading and editing the reviewer note attached to a task
+//!
+//! Owns: GET /history/tasks/{task_id}/notes, PUT /history/tasks/{task_id}/notes
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use std::sync::Arc;
+
+use super::common::validate_task_id;
+use crate::conversation_history::notes::{load_note, save_note, task_exists};
+use crate::conversation_history::types::{HistoryErrorResponse, TaskNote, UpdateNoteRequest};
+use crate::state::AppState;
+
+/// Get the annotation note for a single Cline task
+///
+/// Returns the reviewer-authored note attached to this task, if any. Notes
+/// are stored in the app's config dir (never inside the Cline task directory)
+/// and survive task re-parsing and cache refreshes.
+///
+/// A task with no note returns an empty note (not a 404) — only a missing
+/// task directory is an error.
+#[utoipa::path(
+    get,
+    path = "/history/tasks/{task_id}/notes",
+    params(
+        ("task_id" = String, Path, description = "Task ID (epoch milliseconds directory name)")
+    ),
+    responses(
+        (status = 200, description = "The task's note (empty if none saved)", body = TaskNote),
+        (status = 404, description = "Task not found", body = HistoryErrorResponse),
+        (status = 400, description = "Invalid task ID", body = HistoryErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["history", "tool"]
+)]
+pub async fn get_task_note_handler(
+    State(_state): State<Arc<AppState>>,
+    Path(task_id): Path<String>,
+) -> Result<Json<TaskNote>, (StatusCode, Json<HistoryErrorResponse>)> {
+    validate_task_id(&task_id)?;
+
+    if !task_exists(&task_id) {
+        return Err(task_not_found(&task_id));
+    }
+
+    log::info!("REST API: GET /history/tasks/{}/notes", task_id);
+
+    let note = load_note(&task_id).unwrap_or_else(|| TaskNote {
+        task_id: task_id.clone(),
+        note: String::new(),
+        updated_at: String::new(),
+    });
+
+    Ok(Json(note))
+}
+
+/// Set or clear the annotation note for a single Cline task
+///
+/// Stores a free-text note attached to this task so reviewers can leave
+/// comments on a Cline session. Sending an empty note clears it.
+///
+/// The note is written to the app's config dir — the Cline task directory
+/// itself is never modified.
+#[utoipa::path(
+    put,
+    path = "/history/tasks/{task_id}/notes",
+    params(
+        ("task_id" = String, Path, description = "Task ID (epoch milliseconds directory name)")
+    ),
+    request_body = UpdateNoteRequest,
+    responses(
+        (status = 200, description = "The stored note with its update timestamp", body = TaskNote),
+        (status = 404, description = "Task not found", body = HistoryErrorResponse),
+        (status = 400, description = "Invalid task ID", body = HistoryErrorResponse),
+        (status = 500, description = "Failed to persist the note", body = HistoryErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["history", "tool"]
+)]
+pub async fn put_task_note_handler(
+    State(_state): State<Arc<AppState>>,
+    Path(task_id): Path<String>,
+    Json(body): Json<UpdateNoteRequest>,
+) -> Result<Json<TaskNote>, (StatusCode, Json<HistoryErrorResponse>)> {
+    validate_task_id(&task_id)?;
+
+    if !task_exists(&task_id) {
+        return Err(task_not_found(&task_id));
+    }
+
+    log::info!(
+        "REST API: PUT /history/tasks/{}/notes — {} chars",
+        task_id,
+        body.note.chars().count()
+    );
+
+    match save_note(&task_id, &body.note) {
+        Ok(note) => Ok(Json(note)),
+        Err(e) => {
+            log::error!("REST API: Failed to save note for task {}: {}", task_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(HistoryErrorResponse { error: e, code: 500 }),
+            ))
+        }
+    }
+}
+
+fn task_not_found(task_id: &str) -> (StatusCode, Json<HistoryErrorResponse>) {
+    log::warn!("REST API: Task {} not found for notes", task_id);
+    (
+        StatusCode::NOT_FOUND,
+        Json(HistoryErrorResponse {
+            error: format!("Task '{}' not found", task_id),
+            code: 404,
+        }),
+    )
+}
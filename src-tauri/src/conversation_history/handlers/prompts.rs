@@ -0,0 +1,96 @@
+//! User prompt chain handler.
+//!
+//! Responsibility:
+//! - Initial task prompt + user feedback prompts, full text
+//!
+//! Owns: GET /history/tasks/{task_id}/prompts
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use std::sync::Arc;
+
+use super::common::validate_task_id;
+use crate::conversation_history::prompts::parse_task_prompts;
+use crate::conversation_history::redact::redact_text;
+use crate::conversation_history::types::{HistoryErrorResponse, RedactQuery, TaskPromptsResponse};
+use crate::state::AppState;
+
+/// Get the user prompt chain for a single Cline task
+///
+/// Returns only the user's own words: the initial task prompt plus every
+/// later user feedback message, full text and untruncated, each with a
+/// timestamp. Tool results and assistant turns are excluded — this is the
+/// view reviewers and /latest consumers usually need.
+///
+/// The first prompt has `kind = "task"`, subsequent ones `kind = "feedback"`.
+///
+/// Supports `?redact=true` to scrub API keys, tokens, and emails from prompt text.
+#[utoipa::path(
+    get,
+    path = "/history/tasks/{task_id}/prompts",
+    params(
+        ("task_id" = String, Path, description = "Task ID (epoch milliseconds directory name)"),
+        RedactQuery
+    ),
+    responses(
+        (status = 200, description = "The task's user prompts in order, full text", body = TaskPromptsResponse),
+        (status = 404, description = "Task not found", body = HistoryErrorResponse),
+        (status = 400, description = "Invalid task ID", body = HistoryErrorResponse),
+        (status = 500, description = "Internal server error", body = HistoryErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["history", "tool"]
+)]
+pub async fn get_task_prompts_handler(
+    State(_state): State<Arc<AppState>>,
+    Path(task_id): Path<String>,
+    Query(params): Query<RedactQuery>,
+) -> Result<Json<TaskPromptsResponse>, (StatusCode, Json<HistoryErrorResponse>)> {
+    validate_task_id(&task_id)?;
+
+    let redact = params.redact.unwrap_or(false);
+
+    log::info!(
+        "REST API: GET /history/tasks/{}/prompts — redact={}",
+        task_id, redact
+    );
+
+    let tid = task_id.clone();
+    let result = tokio::task::spawn_blocking(move || parse_task_prompts(&tid)).await;
+
+    match result {
+        Ok(Some(mut response)) => {
+            if redact {
+                for prompt in &mut response.prompts {
+                    prompt.text = redact_text(&prompt.text);
+                }
+            }
+            log::info!(
+                "REST API: Task {} prompts: {} prompts",
+                task_id, response.prompt_count
+            );
+            Ok(Json(response))
+        }
+        Ok(None) => {
+            log::warn!("REST API: Task {} not found for prompts", task_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(HistoryErrorResponse {
+                    error: format!("Task '{}' not found or has no conversation history", task_id),
+                    code: 404,
+                }),
+            ))
+        }
+        Err(e) => {
+            log::error!("REST API: Failed to parse prompts for task {}: {}", task_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(HistoryErrorResponse {
+                    error: format!("Failed to parse task prompts: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
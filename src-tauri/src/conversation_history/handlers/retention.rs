@@ -0,0 +1,182 @@
+//! Retention policy handlers.
+//!
+//! Responsibility:
+//! - Reading and updating the retention policy
+//! - Dry-run preview and on-demand policy runs
+//!
+//! Owns: GET/PUT /history/retention, GET /history/retention/preview,
+//!       POST /history/retention/run
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::Json;
+use std::sync::Arc;
+
+use super::index::invalidate_tasks_index;
+use crate::conversation_history::retention::{
+    apply_policy, evaluate_policy, load_policy, save_policy,
+};
+use crate::conversation_history::types::{
+    HistoryErrorResponse, RetentionPlan, RetentionPolicy, RetentionRunResponse,
+};
+use crate::state::AppState;
+
+/// Get the current retention policy
+///
+/// Returns the configured policy (max age, size budget, keep-noted flag,
+/// archive vs delete, scheduler interval). A never-configured install returns
+/// the disabled default.
+#[utoipa::path(
+    get,
+    path = "/history/retention",
+    responses(
+        (status = 200, description = "The current retention policy", body = RetentionPolicy)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["history"]
+)]
+pub async fn get_retention_policy_handler(
+    State(_state): State<Arc<AppState>>,
+) -> Json<RetentionPolicy> {
+    log::info!("REST API: GET /history/retention");
+    Json(load_policy())
+}
+
+/// Update the retention policy
+///
+/// Persists the policy to the config dir. The background scheduler picks up
+/// the new settings on its next hourly tick — no restart needed.
+#[utoipa::path(
+    put,
+    path = "/history/retention",
+    request_body = RetentionPolicy,
+    responses(
+        (status = 200, description = "The stored policy", body = RetentionPolicy),
+        (status = 400, description = "Invalid policy values", body = HistoryErrorResponse),
+        (status = 500, description = "Failed to persist the policy", body = HistoryErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["history"]
+)]
+pub async fn put_retention_policy_handler(
+    State(_state): State<Arc<AppState>>,
+    Json(policy): Json<RetentionPolicy>,
+) -> Result<Json<RetentionPolicy>, (StatusCode, Json<HistoryErrorResponse>)> {
+    if policy.interval_hours == 0 {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(HistoryErrorResponse {
+                error: "intervalHours must be at least 1".to_string(),
+                code: 400,
+            }),
+        ));
+    }
+
+    log::info!(
+        "REST API: PUT /history/retention — enabled={}, max_age_days={:?}, max_total_bytes={:?}, delete={}",
+        policy.enabled, policy.max_age_days, policy.max_total_bytes, policy.delete
+    );
+
+    match save_policy(&policy) {
+        Ok(()) => Ok(Json(policy)),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(HistoryErrorResponse { error: e, code: 500 }),
+        )),
+    }
+}
+
+/// Preview what the retention policy would remove (dry run)
+///
+/// Evaluates the stored policy against the current task set and returns the
+/// candidate list with sizes and reasons — nothing is modified. Use this
+/// before enabling the scheduler or calling the run endpoint.
+#[utoipa::path(
+    get,
+    path = "/history/retention/preview",
+    responses(
+        (status = 200, description = "The dry-run plan: candidates, sizes and reasons", body = RetentionPlan),
+        (status = 500, description = "Internal server error", body = HistoryErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["history"]
+)]
+pub async fn preview_retention_handler(
+    State(_state): State<Arc<AppState>>,
+) -> Result<Json<RetentionPlan>, (StatusCode, Json<HistoryErrorResponse>)> {
+    log::info!("REST API: GET /history/retention/preview");
+
+    let result = tokio::task::spawn_blocking(|| {
+        let policy = load_policy();
+        evaluate_policy(&policy)
+    })
+    .await;
+
+    match result {
+        Ok(plan) => {
+            log::info!(
+                "REST API: Retention preview: {} candidates, {:.1} MB reclaimable",
+                plan.candidate_count,
+                plan.bytes_reclaimable as f64 / 1024.0 / 1024.0
+            );
+            Ok(Json(plan))
+        }
+        Err(e) => {
+            log::error!("REST API: Retention preview failed: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(HistoryErrorResponse {
+                    error: format!("Failed to evaluate retention policy: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
+
+/// Apply the retention policy now
+///
+/// Runs the stored policy immediately regardless of the scheduler state —
+/// candidates are archived (or deleted, if the policy says so) and the task
+/// index cache is invalidated. The response carries the plan plus what was
+/// actually processed.
+#[utoipa::path(
+    post,
+    path = "/history/retention/run",
+    responses(
+        (status = 200, description = "The applied run: processed/failed counts and bytes reclaimed", body = RetentionRunResponse),
+        (status = 500, description = "Internal server error", body = HistoryErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["history"]
+)]
+pub async fn run_retention_handler(
+    State(_state): State<Arc<AppState>>,
+) -> Result<Json<RetentionRunResponse>, (StatusCode, Json<HistoryErrorResponse>)> {
+    log::info!("REST API: POST /history/retention/run");
+
+    let result = tokio::task::spawn_blocking(|| {
+        let policy = load_policy();
+        apply_policy(&policy)
+    })
+    .await;
+
+    match result {
+        Ok(run) => {
+            if run.processed > 0 {
+                invalidate_tasks_index();
+            }
+            Ok(Json(run))
+        }
+        Err(e) => {
+            log::error!("REST API: Retention run failed: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(HistoryErrorResponse {
+                    error: format!("Failed to apply retention policy: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! In-task message search handler.
+//!
+//! Responsibility:
+//! - Substring search across one task's content blocks
+//!
+//! Owns: GET /history/tasks/{task_id}/messages/search
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use std::sync::Arc;
+
+use super::common::validate_task_id;
+use crate::conversation_history::search::{
+    search_task_messages, SEARCH_DEFAULT_LIMIT, SEARCH_MAX_LIMIT,
+};
+use crate::conversation_history::types::{
+    HistoryErrorResponse, MessageSearchQuery, MessageSearchResponse,
+};
+use crate::state::AppState;
+
+const VALID_BLOCK_TYPES: [&str; 4] = ["text", "thinking", "tool_use", "tool_result"];
+
+/// Search within a single Cline task's messages
+///
+/// Scans every text-bearing content block (text, thinking, tool_use input,
+/// tool_result output) for a case-insensitive substring and returns matching
+/// message indices with highlighted excerpts — useful for jumping to where a
+/// file, error message or command was mentioned.
+///
+/// Each match includes the block location (message + block index), an excerpt
+/// around the first occurrence with char offsets for highlighting, and the
+/// occurrence count in that block.
+///
+/// Supports filtering via:
+/// - `?q=some+text` — the search term (required, non-empty)
+/// - `?block_type=tool_result` — restrict to one block type
+/// - `?limit=50` — cap the number of matches (default 100, max 500)
+#[utoipa::path(
+    get,
+    path = "/history/tasks/{task_id}/messages/search",
+    params(
+        ("task_id" = String, Path, description = "Task ID (epoch milliseconds directory name)"),
+        MessageSearchQuery
+    ),
+    responses(
+        (status = 200, description = "Matching blocks with excerpts and highlight offsets", body = MessageSearchResponse),
+        (status = 404, description = "Task not found", body = HistoryErrorResponse),
+        (status = 400, description = "Invalid parameters", body = HistoryErrorResponse),
+        (status = 500, description = "Internal server error", body = HistoryErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["history", "tool"]
+)]
+pub async fn search_task_messages_handler(
+    State(_state): State<Arc<AppState>>,
+    Path(task_id): Path<String>,
+    Query(params): Query<MessageSearchQuery>,
+) -> Result<Json<MessageSearchResponse>, (StatusCode, Json<HistoryErrorResponse>)> {
+    validate_task_id(&task_id)?;
+
+    let query = params.q.trim().to_string();
+    if query.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(HistoryErrorResponse {
+                error: "Query parameter 'q' must not be empty".to_string(),
+                code: 400,
+            }),
+        ));
+    }
+
+    if let Some(bt) = params.block_type.as_deref() {
+        if !VALID_BLOCK_TYPES.contains(&bt) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(HistoryErrorResponse {
+                    error: format!(
+                        "Invalid block_type '{}'. Valid values: {}",
+                        bt,
+                        VALID_BLOCK_TYPES.join(", ")
+                    ),
+                    code: 400,
+                }),
+            ));
+        }
+    }
+
+    let limit = params.limit.unwrap_or(SEARCH_DEFAULT_LIMIT).min(SEARCH_MAX_LIMIT);
+
+    log::info!(
+        "REST API: GET /history/tasks/{}/messages/search — q={:?}, block_type={:?}, limit={}",
+        task_id, query, params.block_type, limit
+    );
+
+    let tid = task_id.clone();
+    let block_type = params.block_type.clone();
+
+    let result = tokio::task::spawn_blocking(move || {
+        let start = std::time::Instant::now();
+        let response = search_task_messages(&tid, &query, block_type.as_deref(), limit);
+        let elapsed = start.elapsed();
+        log::info!(
+            "Message search for {} complete in {:.1}ms",
+            tid,
+            elapsed.as_secs_f64() * 1000.0
+        );
+        response
+    })
+    .await;
+
+    match result {
+        Ok(Some(response)) => {
+            log::info!(
+                "REST API: Task {} search: {} matches (truncated={})",
+                task_id, response.total_matches, response.truncated
+            );
+            Ok(Json(response))
+        }
+        Ok(None) => {
+            log::warn!("REST API: Task {} not found for message search", task_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(HistoryErrorResponse {
+                    error: format!("Task '{}' not found or has no conversation history", task_id),
+                    code: 404,
+                }),
+            ))
+        }
+        Err(e) => {
+            log::error!("REST API: Message search failed for task {}: {}", task_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(HistoryErrorResponse {
+                    error: format!("Failed to search task messages: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
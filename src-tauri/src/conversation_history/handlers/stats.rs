@@ -3,8 +3,9 @@
 //! Responsibility:
 //! - Compute aggregate statistics across all tasks
 //! - Reuse cached task index from the index handler
+//! - Activity heatmap buckets (hour-of-day / day-of-week)
 //!
-//! Owns: GET /history/stats
+//! Owns: GET /history/stats, GET /history/stats/activity
 //!
 //! ## Correctness Notes
 //!
@@ -32,8 +33,10 @@ use axum::Json;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use crate::conversation_history::activity::scan_activity;
 use crate::conversation_history::types::{
-    HistoryErrorResponse, HistoryStatsQuery, HistoryStatsResponse, TaskHistoryListResponse,
+    ActivityHeatmapResponse, HistoryErrorResponse, HistoryStatsQuery, HistoryStatsResponse,
+    ModelStats, ModelStatsResponse, TaskHistoryListResponse,
 };
 use crate::state::AppState;
 
@@ -150,11 +153,50 @@ fn compute_stats(task_list: &TaskHistoryListResponse) -> HistoryStatsResponse {
         }
     }
 
+    // ---- Token usage & cost ----
+    // Summed from per-task totals (parsed from api_req_started ui_messages entries).
+    // Breakdowns key on model_id (or "unknown") and the task's start date (local time).
+    let total_api_requests: usize = tasks.iter().map(|t| t.api_request_count).sum();
+    let total_tokens_in: u64 = tasks.iter().map(|t| t.total_tokens_in).sum();
+    let total_tokens_out: u64 = tasks.iter().map(|t| t.total_tokens_out).sum();
+    let total_estimated_cost: f64 = tasks.iter().map(|t| t.total_cost).sum();
+
+    let mut cost_per_model: HashMap<String, f64> = HashMap::new();
+    let mut tokens_per_model: HashMap<String, u64> = HashMap::new();
+    let mut cost_per_day: HashMap<String, f64> = HashMap::new();
+    let mut tokens_per_day: HashMap<String, u64> = HashMap::new();
+    for task in tasks {
+        let model = task.model_id.as_deref().unwrap_or("unknown").to_string();
+        let tokens = task.total_tokens_in + task.total_tokens_out;
+        *cost_per_model.entry(model.clone()).or_insert(0.0) += task.total_cost;
+        *tokens_per_model.entry(model).or_insert(0) += tokens;
+
+        // started_at is ISO 8601 — the first 10 chars are the YYYY-MM-DD date.
+        // Tasks with unparseable IDs have started_at = "unknown" and are skipped.
+        if task.started_at.len() >= 10 && task.started_at != "unknown" {
+            let day = task.started_at[..10].to_string();
+            *cost_per_day.entry(day.clone()).or_insert(0.0) += task.total_cost;
+            *tokens_per_day.entry(day).or_insert(0) += tokens;
+        }
+    }
+
     // ---- File stats ----
     let total_files_edited: usize = tasks.iter().map(|t| t.files_edited).sum();
     let total_files_read: usize = tasks.iter().map(|t| t.files_read).sum();
     let tasks_with_focus_chain = tasks.iter().filter(|t| t.has_focus_chain).count();
 
+    // Average completion across tasks that actually have checklist items —
+    // tasks with a focus chain file but no parseable items don't dilute the rate.
+    let completions: Vec<f64> = tasks
+        .iter()
+        .filter_map(|t| t.focus_chain_completion)
+        .collect();
+    let focus_chain_completion_rate = if completions.is_empty() {
+        None
+    } else {
+        Some(completions.iter().sum::<f64>() / completions.len() as f64)
+    };
+
     // ---- Time range ----
     // Use explicit min/max on started_at (ISO 8601 string — lexicographic order
     // matches chronological order for ISO 8601 with timezone offset).
@@ -189,10 +231,19 @@ fn compute_stats(task_list: &TaskHistoryListResponse) -> HistoryStatsResponse {
         model_usage,
         model_provider_usage,
         cline_version_usage,
+        total_api_requests,
+        total_tokens_in,
+        total_tokens_out,
+        total_estimated_cost,
+        cost_per_model,
+        tokens_per_model,
+        cost_per_day,
+        tokens_per_day,
         total_files_in_context,
         total_files_edited,
         total_files_read,
         tasks_with_focus_chain,
+        focus_chain_completion_rate,
         earliest_task,
         latest_task,
         tasks_root: task_list.tasks_root.clone(),
@@ -237,3 +288,214 @@ pub async fn get_history_stats_handler(
     let stats = compute_stats(&task_list);
     Ok(Json(stats))
 }
+
+/// Get activity heatmap buckets across all Cline tasks
+///
+/// Returns message and tool-call counts bucketed by hour-of-day (0–23) and
+/// day-of-week (0 = Monday … 6 = Sunday), plus a 7×24 grid, computed from
+/// `ui_messages.json` timestamps (converted to local time).
+///
+/// This is a full scan of all task directories on each request — it reads only
+/// `ui_messages.json` files, so it is lighter than the task index scan.
+#[utoipa::path(
+    get,
+    path = "/history/stats/activity",
+    responses(
+        (status = 200, description = "Hour-of-day and day-of-week activity buckets for heatmap rendering", body = ActivityHeatmapResponse),
+        (status = 500, description = "Internal server error", body = HistoryErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["history", "tool"]
+)]
+pub async fn get_activity_heatmap_handler(
+    State(_state): State<Arc<AppState>>,
+) -> Result<Json<ActivityHeatmapResponse>, (StatusCode, Json<HistoryErrorResponse>)> {
+    log::info!("REST API: GET /history/stats/activity — scanning activity buckets");
+
+    let result = tokio::task::spawn_blocking(|| {
+        let start = std::time::Instant::now();
+        let response = scan_activity();
+        let elapsed = start.elapsed();
+        log::info!(
+            "Activity scan complete: {} tasks, {} messages in {:.1}s",
+            response.tasks_scanned,
+            response.total_messages,
+            elapsed.as_secs_f64()
+        );
+        response
+    })
+    .await;
+
+    match result {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            log::error!("REST API: Activity scan failed: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(HistoryErrorResponse {
+                    error: format!("Failed to scan activity: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
+
+// ============ Model A/B comparison ============
+
+/// Group the cached task index by model_id into per-model stats.
+///
+/// Tool failure counts come from a per-task tool timeline parse (the summary
+/// only has call counts, not outcomes), so this does filesystem work per task
+/// and must run inside spawn_blocking. Diff churn uses the files_edited
+/// counter from task_metadata rather than shadow-git diffs — computing real
+/// line churn would cost several git invocations per task.
+fn compute_model_stats(task_list: &TaskHistoryListResponse) -> ModelStatsResponse {
+    use crate::conversation_history::timeline::iso_to_epoch_ms;
+    use crate::conversation_history::tools::parse_task_tools;
+
+    // model_id → (tasks, tool failures) — failures are parsed per task below
+    let mut groups: HashMap<String, Vec<&crate::conversation_history::types::TaskHistorySummary>> =
+        HashMap::new();
+    let mut tasks_without_model = 0usize;
+
+    for task in &task_list.tasks {
+        match &task.model_id {
+            Some(model_id) => groups.entry(model_id.clone()).or_default().push(task),
+            None => tasks_without_model += 1,
+        }
+    }
+
+    let mut models: Vec<ModelStats> = groups
+        .into_iter()
+        .map(|(model_id, tasks)| {
+            let task_count = tasks.len();
+
+            // Duration: ended_at (last ui_message) minus started_at (task_id
+            // epoch). Tasks without a known end time don't dilute the average.
+            let durations: Vec<f64> = tasks
+                .iter()
+                .filter_map(|t| {
+                    let start_ms = t.task_id.parse::<i64>().ok()?;
+                    let end_ms = iso_to_epoch_ms(t.ended_at.as_deref()?)?;
+                    Some((end_ms - start_ms) as f64 / 1000.0)
+                })
+                .collect();
+            let avg_duration_seconds = if durations.is_empty() {
+                None
+            } else {
+                Some(durations.iter().sum::<f64>() / durations.len() as f64)
+            };
+
+            let total_messages: usize = tasks.iter().map(|t| t.message_count).sum();
+            let tool_call_count: usize = tasks.iter().map(|t| t.tool_use_count).sum();
+            let total_files_edited: usize = tasks.iter().map(|t| t.files_edited).sum();
+            let total_tokens_in: u64 = tasks.iter().map(|t| t.total_tokens_in).sum();
+            let total_tokens_out: u64 = tasks.iter().map(|t| t.total_tokens_out).sum();
+            let total_cost: f64 = tasks.iter().map(|t| t.total_cost).sum();
+
+            // Failure counts require pairing tool_use/tool_result blocks —
+            // re-parse each task's tool timeline.
+            let tool_failure_count: usize = tasks
+                .iter()
+                .filter_map(|t| parse_task_tools(&t.task_id, None, false))
+                .map(|timeline| timeline.failure_count)
+                .sum();
+
+            // Division by zero: guarded — None when the model made no tool calls.
+            let tool_failure_rate = if tool_call_count > 0 {
+                let pct = (tool_failure_count as f64 / tool_call_count as f64) * 100.0;
+                Some((pct * 10.0).round() / 10.0)
+            } else {
+                None
+            };
+
+            ModelStats {
+                model_id,
+                task_count,
+                avg_duration_seconds,
+                avg_messages_per_task: total_messages as f64 / task_count as f64,
+                avg_tool_calls_per_task: tool_call_count as f64 / task_count as f64,
+                tool_call_count,
+                tool_failure_count,
+                tool_failure_rate,
+                avg_files_edited: total_files_edited as f64 / task_count as f64,
+                total_files_edited,
+                total_tokens_in,
+                total_tokens_out,
+                total_cost,
+            }
+        })
+        .collect();
+
+    // Most-used models first; tie-break on model_id for a stable order
+    models.sort_by(|a, b| {
+        b.task_count
+            .cmp(&a.task_count)
+            .then_with(|| a.model_id.cmp(&b.model_id))
+    });
+
+    ModelStatsResponse {
+        total_tasks: task_list.total_tasks,
+        tasks_without_model,
+        models,
+    }
+}
+
+/// Get per-model A/B comparison stats across all tasks
+///
+/// Groups tasks by model_id and reports average duration, tool failure rate,
+/// messages per task and edit churn for each — compare how different models
+/// perform on your own workflows.
+///
+/// Pass `?refresh=true` to force a full re-scan from disk first.
+#[utoipa::path(
+    get,
+    path = "/history/stats/models",
+    params(HistoryStatsQuery),
+    responses(
+        (status = 200, description = "Per-model aggregate stats, sorted by task count", body = ModelStatsResponse),
+        (status = 500, description = "Internal server error", body = HistoryErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["history", "tool"]
+)]
+pub async fn get_model_stats_handler(
+    State(_state): State<Arc<AppState>>,
+    Query(params): Query<HistoryStatsQuery>,
+) -> Result<Json<ModelStatsResponse>, (StatusCode, Json<HistoryErrorResponse>)> {
+    let force_refresh = params.refresh.unwrap_or(false);
+
+    let task_list = get_or_refresh_task_index(force_refresh).await?;
+
+    log::info!(
+        "REST API: GET /history/stats/models — grouping {} tasks by model",
+        task_list.total_tasks
+    );
+    let start = std::time::Instant::now();
+
+    // Failure counting re-parses tool timelines from disk — keep it off the
+    // async runtime.
+    let result = tokio::task::spawn_blocking(move || compute_model_stats(&task_list)).await;
+
+    match result {
+        Ok(response) => {
+            log::info!(
+                "REST API: Model stats: {} models in {:?}",
+                response.models.len(),
+                start.elapsed()
+            );
+            Ok(Json(response))
+        }
+        Err(e) => {
+            log::error!("REST API: Model stats failed: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(HistoryErrorResponse {
+                    error: format!("Failed to compute model stats: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
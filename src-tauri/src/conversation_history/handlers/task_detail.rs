@@ -5,14 +5,15 @@
 //!
 //! Owns: GET /history/tasks/{task_id}
 
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::Json;
 use std::sync::Arc;
 
 use super::common::validate_task_id;
 use crate::conversation_history::detail::parse_task_detail;
-use crate::conversation_history::types::{HistoryErrorResponse, TaskDetailResponse};
+use crate::conversation_history::redact::redact_task_detail;
+use crate::conversation_history::types::{HistoryErrorResponse, RedactQuery, TaskDetailResponse};
 use crate::state::AppState;
 
 /// Get full detail for a single Cline task
@@ -28,13 +29,16 @@ use crate::state::AppState;
 /// Timestamps for each message are joined from `ui_messages.json` via `conversationHistoryIndex`.
 /// Content blocks are truncated for manageability (text/thinking: 500 chars, tool input: 300, tool result: 200).
 ///
+/// Pass `?redact=true` to strip API keys, tokens, and emails from all returned content.
+///
 /// This is an on-demand parse — the full task files are read from disk each time.
 /// Typical parse time: 10–200ms depending on task size (up to ~4 MB).
 #[utoipa::path(
     get,
     path = "/history/tasks/{task_id}",
     params(
-        ("task_id" = String, Path, description = "Task ID (epoch milliseconds directory name)")
+        ("task_id" = String, Path, description = "Task ID (epoch milliseconds directory name)"),
+        RedactQuery
     ),
     responses(
         (status = 200, description = "Full task detail with messages, tools, files, model info, environment, and focus chain", body = TaskDetailResponse),
@@ -47,10 +51,15 @@ use crate::state::AppState;
 pub async fn get_task_detail_handler(
     State(_state): State<Arc<AppState>>,
     Path(task_id): Path<String>,
+    Query(params): Query<RedactQuery>,
 ) -> Result<Json<TaskDetailResponse>, (StatusCode, Json<HistoryErrorResponse>)> {
     validate_task_id(&task_id)?;
+    let redact = params.redact.unwrap_or(false);
 
-    log::info!("REST API: GET /history/tasks/{} — parsing task detail", task_id);
+    log::info!(
+        "REST API: GET /history/tasks/{} — parsing task detail (redact={})",
+        task_id, redact
+    );
 
     // Run parse in blocking context (filesystem I/O — may read up to ~4 MB of JSON)
     let tid = task_id.clone();
@@ -68,7 +77,10 @@ pub async fn get_task_detail_handler(
     .await;
 
     match result {
-        Ok(Some(detail)) => {
+        Ok(Some(mut detail)) => {
+            if redact {
+                redact_task_detail(&mut detail);
+            }
             log::info!(
                 "REST API: Task {} detail: {} messages, {} tool calls, {} files, {:.1} KB",
                 task_id,
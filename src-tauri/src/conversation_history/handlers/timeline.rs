@@ -0,0 +1,101 @@
+//! Unified timeline handler.
+//!
+//! Responsibility:
+//! - Merged chronological stream of messages, tool calls and checkpoints
+//!
+//! Owns: GET /history/tasks/{task_id}/timeline
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use std::sync::Arc;
+
+use super::common::validate_task_id;
+use crate::conversation_history::timeline::parse_task_timeline;
+use crate::conversation_history::types::{HistoryErrorResponse, TaskTimelineResponse};
+use crate::state::AppState;
+
+/// Get the unified timeline for a single Cline task
+///
+/// Interleaves three event sources into one chronological stream:
+/// - **message** — each conversation entry (role + truncated text summary)
+/// - **tool_call** — each tool_use block (tool name, input summary, success status)
+/// - **checkpoint** — shadow-git checkpoint steps for this task (hash, files changed)
+///
+/// Message and tool call timestamps are joined from `ui_messages.json`;
+/// checkpoint timestamps come from the shadow git commit log. Events without
+/// timestamps keep their conversation order. This powers the UI's single
+/// scrubber view of a task.
+///
+/// This is an on-demand parse — files (and the shadow git log) are read on each request.
+#[utoipa::path(
+    get,
+    path = "/history/tasks/{task_id}/timeline",
+    params(
+        ("task_id" = String, Path, description = "Task ID (epoch milliseconds directory name)")
+    ),
+    responses(
+        (status = 200, description = "Unified chronological timeline of messages, tool calls and checkpoints", body = TaskTimelineResponse),
+        (status = 404, description = "Task not found", body = HistoryErrorResponse),
+        (status = 400, description = "Invalid task ID", body = HistoryErrorResponse),
+        (status = 500, description = "Internal server error", body = HistoryErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["history", "tool"]
+)]
+pub async fn get_task_timeline_handler(
+    State(_state): State<Arc<AppState>>,
+    Path(task_id): Path<String>,
+) -> Result<Json<TaskTimelineResponse>, (StatusCode, Json<HistoryErrorResponse>)> {
+    validate_task_id(&task_id)?;
+
+    log::info!("REST API: GET /history/tasks/{}/timeline", task_id);
+
+    let tid = task_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let start = std::time::Instant::now();
+        let response = parse_task_timeline(&tid);
+        let elapsed = start.elapsed();
+        log::info!(
+            "Timeline parse for {} complete in {:.1}ms",
+            tid,
+            elapsed.as_secs_f64() * 1000.0
+        );
+        response
+    })
+    .await;
+
+    match result {
+        Ok(Some(response)) => {
+            log::info!(
+                "REST API: Task {} timeline: {} events ({} messages, {} tool calls, {} checkpoints)",
+                task_id,
+                response.total_events,
+                response.message_count,
+                response.tool_call_count,
+                response.checkpoint_count,
+            );
+            Ok(Json(response))
+        }
+        Ok(None) => {
+            log::warn!("REST API: Task {} not found for timeline", task_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(HistoryErrorResponse {
+                    error: format!("Task '{}' not found or has no conversation history", task_id),
+                    code: 404,
+                }),
+            ))
+        }
+        Err(e) => {
+            log::error!("REST API: Failed to build timeline for task {}: {}", task_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(HistoryErrorResponse {
+                    error: format!("Failed to build task timeline: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
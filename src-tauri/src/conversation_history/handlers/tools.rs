@@ -5,7 +5,7 @@
 //! - Success / failure classification
 //! - Tool filtering
 //!
-//! Owns: GET /history/tasks/{task_id}/tools
+//! Owns: GET /history/tasks/{task_id}/tools, GET /history/loops
 
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
@@ -13,8 +13,10 @@ use axum::Json;
 use std::sync::Arc;
 
 use super::common::validate_task_id;
-use crate::conversation_history::tools::parse_task_tools;
-use crate::conversation_history::types::{HistoryErrorResponse, TaskToolsQuery, ToolCallTimelineResponse};
+use crate::conversation_history::tools::{parse_task_tools, scan_all_loops};
+use crate::conversation_history::types::{
+    HistoryErrorResponse, LoopsAggregateResponse, TaskToolsQuery, ToolCallTimelineResponse,
+};
 use crate::state::AppState;
 
 /// Get tool call timeline for a single Cline task
@@ -121,3 +123,59 @@ pub async fn get_task_tools_handler(
         }
     }
 }
+
+/// Get detected retry loops across all Cline tasks
+///
+/// Scans every task's tool call timeline and reports runs where the same tool
+/// was called with near-identical input 3+ times in a row — the signature of a
+/// thrashing agent retrying a failing operation.
+///
+/// Only tasks with at least one detected loop are included, newest first.
+/// Each loop carries the tool name, call index range, repeat/failure counts
+/// and the (truncated) input of the first call in the run.
+///
+/// This is a full scan — every task's conversation history is parsed on each
+/// request, so expect latency proportional to the number of tasks.
+#[utoipa::path(
+    get,
+    path = "/history/loops",
+    responses(
+        (status = 200, description = "Retry loops detected across all tasks", body = LoopsAggregateResponse),
+        (status = 500, description = "Internal server error", body = HistoryErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["history", "tool"]
+)]
+pub async fn get_history_loops_handler(
+    State(_state): State<Arc<AppState>>,
+) -> Result<Json<LoopsAggregateResponse>, (StatusCode, Json<HistoryErrorResponse>)> {
+    log::info!("REST API: GET /history/loops — scanning all tasks for retry loops");
+
+    let result = tokio::task::spawn_blocking(|| {
+        let start = std::time::Instant::now();
+        let response = scan_all_loops();
+        let elapsed = start.elapsed();
+        log::info!(
+            "Loop scan complete in {:.1}ms — {} tasks scanned, {} with loops",
+            elapsed.as_secs_f64() * 1000.0,
+            response.tasks_scanned,
+            response.tasks_with_loops
+        );
+        response
+    })
+    .await;
+
+    match result {
+        Ok(response) => Ok(Json(response)),
+        Err(e) => {
+            log::error!("REST API: Loop scan failed: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(HistoryErrorResponse {
+                    error: format!("Failed to scan for retry loops: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
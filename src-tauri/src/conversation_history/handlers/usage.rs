@@ -0,0 +1,156 @@
+//! Token usage handler.
+//!
+//! Responsibility:
+//! - Per-task token usage and cost report
+//! - Per-task context growth series
+//!
+//! Owns: GET /history/tasks/{task_id}/usage, GET /history/tasks/{task_id}/context-growth
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::Json;
+use std::sync::Arc;
+
+use super::common::validate_task_id;
+use crate::conversation_history::types::{ContextGrowthResponse, HistoryErrorResponse, TaskUsageResponse};
+use crate::conversation_history::usage::{parse_context_growth, parse_task_usage};
+use crate::state::AppState;
+
+/// Get token usage and cost report for a single Cline task
+///
+/// Returns per-request token counts (input, output, cache read/write) and
+/// estimated cost, plus task-level totals. Data comes from the
+/// `api_req_started` entries in `ui_messages.json` — Cline records token and
+/// cost info there after each provider API call.
+///
+/// A task with no recorded API requests returns an all-zero report (not a 404).
+#[utoipa::path(
+    get,
+    path = "/history/tasks/{task_id}/usage",
+    params(
+        ("task_id" = String, Path, description = "Task ID (epoch milliseconds directory name)")
+    ),
+    responses(
+        (status = 200, description = "Per-task token usage and cost report with per-request breakdown", body = TaskUsageResponse),
+        (status = 404, description = "Task not found", body = HistoryErrorResponse),
+        (status = 400, description = "Invalid parameters", body = HistoryErrorResponse),
+        (status = 500, description = "Internal server error", body = HistoryErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["history", "tool"]
+)]
+pub async fn get_task_usage_handler(
+    State(_state): State<Arc<AppState>>,
+    Path(task_id): Path<String>,
+) -> Result<Json<TaskUsageResponse>, (StatusCode, Json<HistoryErrorResponse>)> {
+    validate_task_id(&task_id)?;
+
+    log::info!("REST API: GET /history/tasks/{}/usage", task_id);
+
+    let tid = task_id.clone();
+    let result = tokio::task::spawn_blocking(move || parse_task_usage(&tid)).await;
+
+    match result {
+        Ok(Some(response)) => {
+            log::info!(
+                "REST API: Task {} usage: {} requests, {} in / {} out tokens, ${:.4}",
+                task_id,
+                response.api_request_count,
+                response.total_tokens_in,
+                response.total_tokens_out,
+                response.total_cost,
+            );
+            Ok(Json(response))
+        }
+        Ok(None) => {
+            log::warn!("REST API: Task {} not found for usage report", task_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(HistoryErrorResponse {
+                    error: format!("Task '{}' not found", task_id),
+                    code: 404,
+                }),
+            ))
+        }
+        Err(e) => {
+            log::error!("REST API: Failed to parse usage for task {}: {}", task_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(HistoryErrorResponse {
+                    error: format!("Failed to parse task usage: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
+
+/// Get context window growth series for a single Cline task
+///
+/// Returns one point per API request charting how the input context grew over
+/// the course of the task. Each point's `contextTokens` is
+/// tokensIn + cacheWrites + cacheReads for that request (everything the model
+/// read that turn), with `messageIndex` locating it in the conversation.
+///
+/// Useful for spotting context bloat — a steadily climbing series that never
+/// plateaus means the task kept accumulating context until truncation.
+///
+/// A task with no recorded API requests returns an empty series (not a 404).
+#[utoipa::path(
+    get,
+    path = "/history/tasks/{task_id}/context-growth",
+    params(
+        ("task_id" = String, Path, description = "Task ID (epoch milliseconds directory name)")
+    ),
+    responses(
+        (status = 200, description = "Per-request context size series for charting context growth", body = ContextGrowthResponse),
+        (status = 404, description = "Task not found", body = HistoryErrorResponse),
+        (status = 400, description = "Invalid parameters", body = HistoryErrorResponse),
+        (status = 500, description = "Internal server error", body = HistoryErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["history", "tool"]
+)]
+pub async fn get_context_growth_handler(
+    State(_state): State<Arc<AppState>>,
+    Path(task_id): Path<String>,
+) -> Result<Json<ContextGrowthResponse>, (StatusCode, Json<HistoryErrorResponse>)> {
+    validate_task_id(&task_id)?;
+
+    log::info!("REST API: GET /history/tasks/{}/context-growth", task_id);
+
+    let tid = task_id.clone();
+    let result = tokio::task::spawn_blocking(move || parse_context_growth(&tid)).await;
+
+    match result {
+        Ok(Some(response)) => {
+            log::info!(
+                "REST API: Task {} context growth: {} requests, peak {} tokens",
+                task_id,
+                response.api_request_count,
+                response.peak_context_tokens,
+            );
+            Ok(Json(response))
+        }
+        Ok(None) => {
+            log::warn!("REST API: Task {} not found for context growth", task_id);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(HistoryErrorResponse {
+                    error: format!("Task '{}' not found", task_id),
+                    code: 404,
+                }),
+            ))
+        }
+        Err(e) => {
+            log::error!("REST API: Failed to parse context growth for task {}: {}", task_id, e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(HistoryErrorResponse {
+                    error: format!("Failed to parse context growth: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Task directory lifecycle operations (archive / delete).
+//!
+//! Contains:
+//! - Archiving a task dir to a sibling `tasks_archive` folder
+//! - Deleting a task dir with size accounting and dry-run support
+//!
+//! These are the only operations in this crate that MOVE or REMOVE Cline's
+//! files — everything else is read-only. Keep the safety checks here.
+
+use std::path::{Path, PathBuf};
+
+use super::root::tasks_root;
+use super::types::*;
+
+/// The archive directory — a sibling of the Cline `tasks` directory, so
+/// archiving is a same-volume rename (atomic, no copy of gigabytes).
+fn archive_root() -> Option<PathBuf> {
+    let tasks = tasks_root()?;
+    Some(tasks.parent()?.join("tasks_archive"))
+}
+
+/// Recursively sum file count and total bytes under a directory.
+fn dir_stats(dir: &Path) -> (usize, u64) {
+    let mut file_count = 0usize;
+    let mut total_bytes = 0u64;
+
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                let (c, b) = dir_stats(&path);
+                file_count += c;
+                total_bytes += b;
+            } else if let Ok(meta) = entry.metadata() {
+                file_count += 1;
+                total_bytes += meta.len();
+            }
+        }
+    }
+
+    (file_count, total_bytes)
+}
+
+/// Archive a task directory — move it from `tasks/` to `tasks_archive/`.
+///
+/// Returns None if the task directory doesn't exist; Err for filesystem
+/// failures (archive dir creation, destination collision, rename).
+pub fn archive_task(task_id: &str) -> Option<Result<ArchiveTaskResponse, String>> {
+    let root = tasks_root()?;
+    let src = root.join(task_id);
+
+    if !src.is_dir() {
+        log::warn!("Task directory not found: {:?}", src);
+        return None;
+    }
+
+    Some(do_archive(task_id, &src))
+}
+
+fn do_archive(task_id: &str, src: &Path) -> Result<ArchiveTaskResponse, String> {
+    let archive = archive_root().ok_or_else(|| "Archive directory unavailable".to_string())?;
+    if !archive.exists() {
+        std::fs::create_dir_all(&archive)
+            .map_err(|e| format!("Failed to create archive directory {:?}: {}", archive, e))?;
+    }
+
+    let dest = archive.join(task_id);
+    if dest.exists() {
+        return Err(format!(
+            "Task '{}' is already archived at {:?}",
+            task_id, dest
+        ));
+    }
+
+    let (file_count, total_bytes) = dir_stats(src);
+
+    std::fs::rename(src, &dest)
+        .map_err(|e| format!("Failed to move {:?} to {:?}: {}", src, dest, e))?;
+
+    log::info!(
+        "Archived task {} ({} files, {:.1} MB) to {:?}",
+        task_id,
+        file_count,
+        total_bytes as f64 / 1024.0 / 1024.0,
+        dest
+    );
+
+    Ok(ArchiveTaskResponse {
+        task_id: task_id.to_string(),
+        archived_to: dest.to_string_lossy().to_string(),
+        file_count,
+        total_bytes,
+    })
+}
+
+/// Delete a task directory permanently (or report what would be deleted).
+///
+/// With `dry_run` the directory is left untouched and the response carries
+/// the file count and byte total that a real delete would remove.
+///
+/// Returns None if the task directory doesn't exist; Err for filesystem failures.
+pub fn delete_task(task_id: &str, dry_run: bool) -> Option<Result<DeleteTaskResponse, String>> {
+    let root = tasks_root()?;
+    let dir = root.join(task_id);
+
+    if !dir.is_dir() {
+        log::warn!("Task directory not found: {:?}", dir);
+        return None;
+    }
+
+    let (file_count, total_bytes) = dir_stats(&dir);
+
+    if dry_run {
+        log::info!(
+            "Dry-run delete for task {}: {} files, {:.1} MB",
+            task_id,
+            file_count,
+            total_bytes as f64 / 1024.0 / 1024.0
+        );
+        return Some(Ok(DeleteTaskResponse {
+            task_id: task_id.to_string(),
+            dry_run: true,
+            deleted: false,
+            file_count,
+            total_bytes,
+        }));
+    }
+
+    if let Err(e) = std::fs::remove_dir_all(&dir) {
+        return Some(Err(format!("Failed to delete {:?}: {}", dir, e)));
+    }
+
+    log::info!(
+        "Deleted task {} ({} files, {:.1} MB)",
+        task_id,
+        file_count,
+        total_bytes as f64 / 1024.0 / 1024.0
+    );
+
+    Some(Ok(DeleteTaskResponse {
+        task_id: task_id.to_string(),
+        dry_run: false,
+        deleted: true,
+        file_count,
+        total_bytes,
+    }))
+}
@@ -23,6 +23,10 @@
 //! - `tools` — tool call timeline parsing (GET /history/tasks/:id/tools)
 //! - `thinking` — thinking block parsing (GET /history/tasks/:id/thinking)
 //! - `files` — files-in-context parsing (GET /history/tasks/:id/files)
+//! - `export` — JSONL dataset export (GET /history/tasks/:id/export, GET /history/export)
+//! - `redact` — secret redaction applied via `?redact=true` on content-serving endpoints
+//! - `usage` — token usage & cost parsing (GET /history/tasks/:id/usage)
+//! - `activity` — heatmap bucketing (GET /history/stats/activity)
 
 pub mod types;
 pub mod cache;
@@ -38,6 +42,19 @@ pub(crate) mod tools;
 pub(crate) mod thinking;
 pub(crate) mod files;
 pub(crate) mod subtasks;
+pub(crate) mod export;
+pub(crate) mod redact;
+pub(crate) mod usage;
+pub(crate) mod activity;
+pub(crate) mod timeline;
+pub(crate) mod focus_chain;
+pub(crate) mod search;
+pub(crate) mod rag;
+pub(crate) mod prompts;
+pub(crate) mod notes;
+pub(crate) mod manage;
+pub mod retention;
+pub(crate) mod compare;
 
 pub use types::*;
 pub use handlers::*;
@@ -0,0 +1,101 @@
+//! Per-task annotation notes.
+//!
+//! Contains:
+//! - Note storage under `%APPDATA%/jira-dashboard/task_notes/<task_id>.json`
+//! - Load/save/delete helpers used by the detail parser and notes handlers
+//!
+//! Notes are reviewer-authored free text attached to a Cline session — they
+//! live in our config dir, never inside the Cline task directory itself.
+
+use std::path::PathBuf;
+
+use super::root::tasks_root;
+use super::types::TaskNote;
+
+const NOTES_DIR: &str = "jira-dashboard/task_notes";
+
+/// Return the notes directory, creating it if needed.
+///
+/// Mirrors the disk cache directory resolution: `None` (with a warning log)
+/// if %APPDATA% is unset or the directory can't be created.
+fn notes_dir() -> Option<PathBuf> {
+    let appdata = match std::env::var("APPDATA") {
+        Ok(val) => val,
+        Err(_) => {
+            log::warn!(
+                "Task notes: %APPDATA% not set — cannot resolve notes directory for '{}'",
+                NOTES_DIR
+            );
+            return None;
+        }
+    };
+    let dir = PathBuf::from(appdata).join(NOTES_DIR);
+    if !dir.exists() {
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            log::warn!("Task notes: failed to create directory {:?}: {}", dir, e);
+            return None;
+        }
+    }
+    Some(dir)
+}
+
+fn note_path(task_id: &str) -> Option<PathBuf> {
+    Some(notes_dir()?.join(format!("{}.json", task_id)))
+}
+
+/// Check whether the task directory exists (notes only attach to real tasks).
+pub(crate) fn task_exists(task_id: &str) -> bool {
+    tasks_root().map(|r| r.join(task_id).is_dir()).unwrap_or(false)
+}
+
+/// Load the note for a task, if one has been saved.
+///
+/// Best-effort: unreadable or corrupt note files return None with a log.
+pub(crate) fn load_note(task_id: &str) -> Option<TaskNote> {
+    let path = note_path(task_id)?;
+    if !path.exists() {
+        return None;
+    }
+    let content = std::fs::read_to_string(&path).ok()?;
+    match serde_json::from_str::<TaskNote>(&content) {
+        Ok(note) => Some(note),
+        Err(e) => {
+            log::warn!("Task notes: failed to parse {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// Save (or overwrite) the note for a task. An empty note deletes the file.
+///
+/// Returns the stored note, or an error string suitable for an API response.
+pub(crate) fn save_note(task_id: &str, note_text: &str) -> Result<TaskNote, String> {
+    let path = note_path(task_id)
+        .ok_or_else(|| "Notes directory unavailable (%APPDATA% not set?)".to_string())?;
+
+    let note = TaskNote {
+        task_id: task_id.to_string(),
+        note: note_text.to_string(),
+        updated_at: chrono::Local::now().to_rfc3339(),
+    };
+
+    if note_text.trim().is_empty() {
+        if path.exists() {
+            std::fs::remove_file(&path)
+                .map_err(|e| format!("Failed to delete note file: {}", e))?;
+            log::info!("Task notes: deleted note for task {}", task_id);
+        }
+        return Ok(note);
+    }
+
+    let json = serde_json::to_string_pretty(&note)
+        .map_err(|e| format!("Failed to serialize note: {}", e))?;
+    std::fs::write(&path, &json).map_err(|e| format!("Failed to write note file: {}", e))?;
+
+    log::info!(
+        "Task notes: saved note for task {} ({} chars)",
+        task_id,
+        note_text.chars().count()
+    );
+    Ok(note)
+}
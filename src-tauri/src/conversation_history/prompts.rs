@@ -0,0 +1,92 @@
+//! User prompt chain parsing.
+//!
+//! Contains:
+//! - Extraction of the initial task prompt and all user feedback prompts
+//! - Full (untruncated) text, with timestamps joined from ui_messages.json
+//!
+//! Feeds GET /history/tasks/:taskId/prompts — what reviewers and /latest
+//! consumers usually want: the user's words without the tool noise.
+
+use super::detail::build_timestamp_map;
+use super::root::tasks_root;
+use super::types::*;
+
+/// Parse a task's user prompt chain — the initial task prompt plus every later
+/// user message that carries actual text (tool_result-only turns are skipped).
+///
+/// The first text-bearing user message is classified as "task"; all subsequent
+/// ones as "feedback". Text blocks within a message are joined with blank
+/// lines and returned untruncated.
+///
+/// Returns None if the task directory doesn't exist or has no api_conversation_history.
+pub fn parse_task_prompts(task_id: &str) -> Option<TaskPromptsResponse> {
+    let root = tasks_root()?;
+    let dir = root.join(task_id);
+
+    if !dir.is_dir() {
+        log::warn!("Task directory not found: {:?}", dir);
+        return None;
+    }
+
+    let api_history_path = dir.join("api_conversation_history.json");
+    let ui_messages_path = dir.join("ui_messages.json");
+
+    if !api_history_path.exists() {
+        log::warn!("No api_conversation_history.json for task {}", task_id);
+        return None;
+    }
+
+    let timestamp_map = build_timestamp_map(&ui_messages_path);
+
+    let content = match std::fs::read_to_string(&api_history_path) {
+        Ok(c) => c,
+        Err(e) => {
+            log::warn!("Failed to read {:?}: {}", api_history_path, e);
+            return None;
+        }
+    };
+
+    let raw_messages: Vec<RawApiMessage> = match serde_json::from_str(&content) {
+        Ok(m) => m,
+        Err(e) => {
+            log::warn!("Failed to parse {:?}: {}", api_history_path, e);
+            return None;
+        }
+    };
+
+    let mut prompts: Vec<PromptEntry> = Vec::new();
+
+    for (msg_idx, raw_msg) in raw_messages.iter().enumerate() {
+        if raw_msg.role != "user" {
+            continue;
+        }
+
+        let texts: Vec<&str> = raw_msg
+            .content
+            .iter()
+            .filter_map(|b| match b {
+                RawContentBlock::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+
+        if texts.is_empty() {
+            continue; // tool_result-only turn — not a user prompt
+        }
+
+        let kind = if prompts.is_empty() { "task" } else { "feedback" };
+
+        prompts.push(PromptEntry {
+            message_index: msg_idx,
+            timestamp: timestamp_map.get(&(msg_idx as i64)).cloned(),
+            kind: kind.to_string(),
+            text: texts.join("\n\n"),
+        });
+    }
+
+    Some(TaskPromptsResponse {
+        task_id: task_id.to_string(),
+        prompt_count: prompts.len(),
+        prompts,
+    })
+}
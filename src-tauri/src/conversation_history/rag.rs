@@ -0,0 +1,183 @@
+//! Retrieval of relevant past-task excerpts for chat context.
+//!
+//! Backs the `include_history_context` option on `POST /agent/chat`: the
+//! user's question is tokenized into keywords, candidate tasks are ranked
+//! by keyword overlap against the cached task index, and the best-matching
+//! tasks are scanned with the existing in-task search to pull short
+//! excerpts. No embedding model is involved — keyword overlap over the
+//! index plus substring search is cheap and works offline.
+
+use super::handlers::index::get_or_refresh_task_index;
+use super::search::search_task_messages;
+use std::collections::HashSet;
+
+/// Tasks scanned for excerpts per query (scanning parses the full
+/// conversation JSON, so keep this small).
+const MAX_CANDIDATE_TASKS: usize = 5;
+/// Keywords taken from the query, longest first.
+const MAX_KEYWORDS: usize = 5;
+/// Default number of excerpts injected into the chat context.
+pub const DEFAULT_MAX_EXCERPTS: usize = 3;
+
+/// Common words that carry no retrieval signal.
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "with", "that", "this", "from", "what", "how", "did", "was", "were",
+    "have", "has", "when", "where", "why", "can", "could", "should", "would", "you", "about",
+    "last", "week", "into", "does", "not",
+];
+
+/// One retrieved excerpt from a past task.
+#[derive(Debug, Clone)]
+pub struct RetrievedExcerpt {
+    pub task_id: String,
+    pub started_at: String,
+    /// First user message of the task (truncated), for orientation
+    pub task_prompt: Option<String>,
+    pub excerpt: String,
+}
+
+/// Keywords from a query: lowercase alphanumeric words, stopwords and
+/// short tokens dropped, longest first (longer words are rarer).
+fn keywords_of(query: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut words: Vec<String> = query
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.len() >= 3 && !STOPWORDS.contains(w))
+        .filter(|w| seen.insert(w.to_string()))
+        .map(|w| w.to_string())
+        .collect();
+    words.sort_by_key(|w| std::cmp::Reverse(w.len()));
+    words.truncate(MAX_KEYWORDS);
+    words
+}
+
+/// How many of the keywords appear in the text (case-insensitive).
+fn keyword_hits(text: &str, keywords: &[String]) -> usize {
+    let text = text.to_lowercase();
+    keywords.iter().filter(|k| text.contains(k.as_str())).count()
+}
+
+/// Retrieve the most relevant past-task excerpts for a chat question.
+///
+/// Best effort: an empty result (cold index, no keyword overlap, scan
+/// errors) means the chat simply proceeds without injected context.
+pub async fn retrieve_context(query: &str, max_excerpts: usize) -> Vec<RetrievedExcerpt> {
+    let keywords = keywords_of(query);
+    if keywords.is_empty() {
+        return Vec::new();
+    }
+
+    let index = match get_or_refresh_task_index(false).await {
+        Ok(index) => index,
+        Err(_) => {
+            log::warn!("RAG: task index unavailable, skipping context retrieval");
+            return Vec::new();
+        }
+    };
+
+    // Rank tasks by keyword overlap with their first user message
+    let mut candidates: Vec<(usize, String, String, Option<String>)> = index
+        .tasks
+        .iter()
+        .filter_map(|task| {
+            let prompt = task.task_prompt.as_deref()?;
+            let hits = keyword_hits(prompt, &keywords);
+            if hits == 0 {
+                return None;
+            }
+            Some((
+                hits,
+                task.task_id.clone(),
+                task.started_at.clone(),
+                task.task_prompt.clone(),
+            ))
+        })
+        .collect();
+    candidates.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| b.2.cmp(&a.2)));
+    candidates.truncate(MAX_CANDIDATE_TASKS);
+
+    if candidates.is_empty() {
+        return Vec::new();
+    }
+
+    // Pull one excerpt per candidate task (full JSON parse — off the
+    // async runtime)
+    let keywords_for_scan = keywords.clone();
+    let excerpts = tokio::task::spawn_blocking(move || {
+        let mut excerpts = Vec::new();
+        for (_, task_id, started_at, task_prompt) in candidates {
+            for keyword in &keywords_for_scan {
+                let Some(result) = search_task_messages(&task_id, keyword, None, 1) else {
+                    break; // task unreadable, try the next one
+                };
+                if let Some(best) = result.matches.first() {
+                    excerpts.push(RetrievedExcerpt {
+                        task_id: task_id.clone(),
+                        started_at: started_at.clone(),
+                        task_prompt: task_prompt.clone(),
+                        excerpt: best.excerpt.clone(),
+                    });
+                    break; // one excerpt per task
+                }
+            }
+        }
+        excerpts
+    })
+    .await
+    .unwrap_or_default();
+
+    excerpts.into_iter().take(max_excerpts).collect()
+}
+
+/// Format retrieved excerpts as a context preamble for the model.
+pub fn format_context(excerpts: &[RetrievedExcerpt]) -> String {
+    let mut out = String::from(
+        "Context retrieved from the user's past tasks (most relevant first):\n",
+    );
+    for excerpt in excerpts {
+        out.push_str(&format!(
+            "\n[task {} from {}]\n",
+            excerpt.task_id, excerpt.started_at
+        ));
+        if let Some(prompt) = &excerpt.task_prompt {
+            out.push_str(&format!("Task: {}\n", prompt));
+        }
+        out.push_str(&format!("Excerpt: {}\n", excerpt.excerpt));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keywords_of_drops_stopwords_and_dedupes() {
+        let keywords = keywords_of("How did I fix the timeout error last week? timeout!");
+        assert!(keywords.contains(&"timeout".to_string()));
+        assert!(keywords.contains(&"error".to_string()));
+        assert!(keywords.contains(&"fix".to_string()));
+        assert!(!keywords.contains(&"how".to_string()));
+        assert!(!keywords.contains(&"last".to_string()));
+        assert_eq!(
+            keywords.iter().filter(|k| *k == "timeout").count(),
+            1,
+            "keywords must be deduplicated"
+        );
+    }
+
+    #[test]
+    fn test_keywords_of_longest_first_and_capped() {
+        let keywords = keywords_of("alpha beta gamma delta epsilon zeta eta theta");
+        assert!(keywords.len() <= MAX_KEYWORDS);
+        assert!(keywords[0].len() >= keywords[keywords.len() - 1].len());
+    }
+
+    #[test]
+    fn test_keyword_hits() {
+        let keywords = vec!["timeout".to_string(), "jira".to_string()];
+        assert_eq!(keyword_hits("Fix the Jira fetch TIMEOUT", &keywords), 2);
+        assert_eq!(keyword_hits("unrelated text", &keywords), 0);
+    }
+}
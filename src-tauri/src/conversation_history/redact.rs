@@ -0,0 +1,170 @@
+//! Secret redaction for conversation content.
+//!
+//! Contains:
+//! - Regex-based detection of well-known credential formats (API keys, tokens, emails)
+//! - Entropy heuristic for opaque high-entropy tokens
+//! - Struct-level redaction helpers for API response types
+//!
+//! Applied opportunistically via `?redact=true` on history detail / messages /
+//! export endpoints so conversation logs can be shared safely. Redaction is
+//! best-effort — it targets common credential shapes, not a guarantee.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use super::types::{
+    ContentBlockSummary, ConversationMessage, FullContentBlock, FullMessageResponse,
+    PaginatedMessagesResponse, TaskDetailResponse,
+};
+
+/// Replacement marker inserted in place of detected secrets.
+pub const REDACTED_MARKER: &str = "[REDACTED]";
+
+/// Well-known credential patterns, checked before the entropy heuristic.
+///
+/// Order matters only for overlapping matches — regex `replace_all` is applied
+/// pattern-by-pattern, so broader patterns (e.g. bearer headers) come first.
+static SECRET_PATTERNS: Lazy<Vec<Regex>> = Lazy::new(|| {
+    [
+        // Authorization headers: "Bearer <token>" / "Basic <b64>"
+        r"(?i)\b(?:bearer|basic)\s+[A-Za-z0-9\-_\.=+/]{16,}",
+        // key=value style assignments for obvious secret names
+        r#"(?i)\b(?:api[_-]?key|api[_-]?token|access[_-]?token|auth[_-]?token|secret[_-]?key|client[_-]?secret|password)["']?\s*[:=]\s*["']?[^\s"',;]{8,}"#,
+        // OpenAI / Anthropic style keys
+        r"\bsk-[A-Za-z0-9\-_]{20,}",
+        // AWS access key IDs
+        r"\bAKIA[0-9A-Z]{16}\b",
+        // GitHub tokens (classic + fine-grained)
+        r"\bgh[pousr]_[A-Za-z0-9]{36,}",
+        r"\bgithub_pat_[A-Za-z0-9_]{36,}",
+        // Slack tokens
+        r"\bxox[baprs]-[A-Za-z0-9\-]{10,}",
+        // Atlassian API tokens
+        r"\bATATT[A-Za-z0-9\-_=+/]{20,}",
+        // Google API keys
+        r"\bAIza[0-9A-Za-z\-_]{35}",
+        // JWTs (three base64url segments)
+        r"\beyJ[A-Za-z0-9\-_]+\.[A-Za-z0-9\-_]+\.[A-Za-z0-9\-_]+",
+        // Private key blocks
+        r"-----BEGIN [A-Z ]*PRIVATE KEY-----[\s\S]*?-----END [A-Z ]*PRIVATE KEY-----",
+        // Email addresses
+        r"\b[A-Za-z0-9._%+\-]+@[A-Za-z0-9.\-]+\.[A-Za-z]{2,}\b",
+    ]
+    .iter()
+    .map(|p| Regex::new(p).expect("invalid redaction pattern"))
+    .collect()
+});
+
+/// Candidate tokens for the entropy heuristic: long unbroken base64/hex-ish runs.
+static ENTROPY_CANDIDATE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b[A-Za-z0-9+/\-_=]{32,}\b").expect("invalid candidate pattern"));
+
+/// Minimum Shannon entropy (bits per char) for a candidate to be treated as a secret.
+///
+/// Natural-language words and file paths sit well below this; random 32+ char
+/// keys sit above ~4.5. 4.0 keeps false positives (long identifiers) rare.
+const ENTROPY_THRESHOLD: f64 = 4.0;
+
+/// Shannon entropy of a string in bits per character.
+fn shannon_entropy(s: &str) -> f64 {
+    let len = s.chars().count();
+    if len == 0 {
+        return 0.0;
+    }
+    let mut counts: std::collections::HashMap<char, usize> = std::collections::HashMap::new();
+    for c in s.chars() {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+    counts
+        .values()
+        .map(|&count| {
+            let p = count as f64 / len as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Redact secrets from a text string.
+///
+/// Applies the known-pattern regexes first, then the entropy heuristic for
+/// anything that looks like an opaque random token.
+pub fn redact_text(text: &str) -> String {
+    let mut result = text.to_string();
+
+    for pattern in SECRET_PATTERNS.iter() {
+        result = pattern.replace_all(&result, REDACTED_MARKER).to_string();
+    }
+
+    // Entropy pass: replace high-entropy base64/hex-ish runs that the known
+    // patterns missed (provider-agnostic random tokens).
+    result = ENTROPY_CANDIDATE
+        .replace_all(&result, |caps: &regex::Captures| {
+            let candidate = &caps[0];
+            if shannon_entropy(candidate) >= ENTROPY_THRESHOLD {
+                REDACTED_MARKER.to_string()
+            } else {
+                candidate.to_string()
+            }
+        })
+        .to_string();
+
+    result
+}
+
+/// Redact an optional text field in place.
+fn redact_opt(field: &mut Option<String>) {
+    if let Some(ref text) = field {
+        *field = Some(redact_text(text));
+    }
+}
+
+/// Redact all text-bearing fields of a content block summary.
+pub fn redact_content_block_summary(block: &mut ContentBlockSummary) {
+    redact_opt(&mut block.text);
+    redact_opt(&mut block.tool_input);
+    redact_opt(&mut block.tool_result_text);
+}
+
+/// Redact all content blocks of a conversation message.
+pub fn redact_conversation_message(msg: &mut ConversationMessage) {
+    for block in &mut msg.content {
+        redact_content_block_summary(block);
+    }
+}
+
+/// Redact all text-bearing fields of a full (untruncated) content block.
+pub fn redact_full_content_block(block: &mut FullContentBlock) {
+    redact_opt(&mut block.text);
+    redact_opt(&mut block.tool_input);
+    redact_opt(&mut block.tool_result_text);
+}
+
+/// Redact a full task detail response (messages, tool calls, focus chain, prompt).
+pub fn redact_task_detail(detail: &mut TaskDetailResponse) {
+    redact_opt(&mut detail.task_prompt);
+    redact_opt(&mut detail.focus_chain);
+    for item in &mut detail.focus_chain_items {
+        item.text = redact_text(&item.text);
+    }
+    for msg in &mut detail.messages {
+        redact_conversation_message(msg);
+    }
+    for call in &mut detail.tool_calls {
+        call.input_summary = redact_text(&call.input_summary);
+        redact_opt(&mut call.result_summary);
+    }
+}
+
+/// Redact a paginated messages response.
+pub fn redact_paginated_messages(response: &mut PaginatedMessagesResponse) {
+    for msg in &mut response.messages {
+        redact_conversation_message(msg);
+    }
+}
+
+/// Redact a single full message response.
+pub fn redact_full_message(response: &mut FullMessageResponse) {
+    for block in &mut response.content {
+        redact_full_content_block(block);
+    }
+}
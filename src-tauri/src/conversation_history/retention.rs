@@ -0,0 +1,285 @@
+//! Retention policy engine for task and checkpoint cleanup.
+//!
+//! Contains:
+//! - Policy storage under `%APPDATA%/jira-dashboard` (max age, max total bytes, keep-noted)
+//! - Candidate evaluation (dry-run plan) and policy application
+//! - The scheduler-driven tick that applies the policy on its interval
+//!
+//! The policy never touches tasks with a reviewer note when `keep_noted` is
+//! set, and archives by default — deletion must be opted into explicitly.
+
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::path::PathBuf;
+
+use super::manage::{archive_task, delete_task};
+use super::notes::load_note;
+use super::root::tasks_root;
+use super::types::*;
+use super::util::epoch_ms_to_iso;
+
+const POLICY_DIR: &str = "jira-dashboard";
+const POLICY_FILE: &str = "retention_policy.json";
+
+// ============================================================================
+// Policy storage
+// ============================================================================
+
+fn policy_path() -> Option<PathBuf> {
+    let appdata = std::env::var("APPDATA").ok()?;
+    let dir = PathBuf::from(appdata).join(POLICY_DIR);
+    if !dir.exists() {
+        std::fs::create_dir_all(&dir).ok()?;
+    }
+    Some(dir.join(POLICY_FILE))
+}
+
+/// Load the retention policy, falling back to the (disabled) default.
+pub fn load_policy() -> RetentionPolicy {
+    let path = match policy_path() {
+        Some(p) if p.exists() => p,
+        _ => return RetentionPolicy::default(),
+    };
+    match std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|c| serde_json::from_str(&c).ok())
+    {
+        Some(policy) => policy,
+        None => {
+            log::warn!("Retention: failed to load policy from {:?} — using default", path);
+            RetentionPolicy::default()
+        }
+    }
+}
+
+/// Persist the retention policy. Returns an error string for API responses.
+pub fn save_policy(policy: &RetentionPolicy) -> Result<(), String> {
+    let path = policy_path().ok_or_else(|| "Policy directory unavailable".to_string())?;
+    let json = serde_json::to_string_pretty(policy)
+        .map_err(|e| format!("Failed to serialize policy: {}", e))?;
+    std::fs::write(&path, &json).map_err(|e| format!("Failed to write policy: {}", e))?;
+    log::info!("Retention: saved policy to {:?}", path);
+    Ok(())
+}
+
+// ============================================================================
+// Evaluation
+// ============================================================================
+
+/// Evaluate the policy against the current task set and produce a plan.
+///
+/// Candidates are selected in two passes:
+/// 1. **Age**: tasks whose start time (the task_id epoch) is older than
+///    `max_age_days`.
+/// 2. **Size budget**: if the surviving tasks still exceed `max_total_bytes`,
+///    the oldest are added until the budget is met.
+///
+/// Tasks with a reviewer note are skipped entirely when `keep_noted` is set.
+/// Evaluation never modifies anything — this is the dry-run view.
+pub fn evaluate_policy(policy: &RetentionPolicy) -> RetentionPlan {
+    let mut candidates: Vec<RetentionCandidate> = Vec::new();
+    let mut tasks_scanned = 0usize;
+
+    // (task_id, epoch_ms, bytes, kept_by_note) for all tasks, oldest first
+    let mut tasks: Vec<(String, u64, u64, bool)> = Vec::new();
+
+    if let Some(root) = tasks_root() {
+        if let Ok(entries) = std::fs::read_dir(&root) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if !path.is_dir() {
+                    continue;
+                }
+                let task_id = match path.file_name().and_then(|n| n.to_str()) {
+                    Some(n) => n.to_string(),
+                    None => continue,
+                };
+                let epoch_ms = match task_id.parse::<u64>() {
+                    Ok(ms) => ms,
+                    Err(_) => continue, // not a task dir
+                };
+                tasks_scanned += 1;
+
+                let bytes = dir_size(&path);
+                let noted = policy.keep_noted && load_note(&task_id).is_some();
+                tasks.push((task_id, epoch_ms, bytes, noted));
+            }
+        }
+    }
+
+    tasks.sort_by_key(|(_, ms, _, _)| *ms);
+
+    let now_ms = chrono::Utc::now().timestamp_millis() as u64;
+    let age_cutoff_ms = policy
+        .max_age_days
+        .map(|days| now_ms.saturating_sub(days * 24 * 60 * 60 * 1000));
+
+    let mut selected: Vec<bool> = vec![false; tasks.len()];
+
+    // Pass 1: age
+    if let Some(cutoff) = age_cutoff_ms {
+        for (i, (_, epoch_ms, _, noted)) in tasks.iter().enumerate() {
+            if !noted && *epoch_ms < cutoff {
+                selected[i] = true;
+            }
+        }
+    }
+
+    // Pass 2: size budget — oldest first among survivors
+    if let Some(budget) = policy.max_total_bytes {
+        let mut surviving_bytes: u64 = tasks
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !selected[*i])
+            .map(|(_, (_, _, b, _))| *b)
+            .sum();
+        for (i, (_, _, bytes, noted)) in tasks.iter().enumerate() {
+            if surviving_bytes <= budget {
+                break;
+            }
+            if selected[i] || *noted {
+                continue;
+            }
+            selected[i] = true;
+            surviving_bytes -= bytes;
+        }
+    }
+
+    for (i, (task_id, epoch_ms, bytes, _)) in tasks.iter().enumerate() {
+        if !selected[i] {
+            continue;
+        }
+        let over_age = age_cutoff_ms.map(|c| *epoch_ms < c).unwrap_or(false);
+        candidates.push(RetentionCandidate {
+            task_id: task_id.clone(),
+            started_at: epoch_ms_to_iso(*epoch_ms),
+            total_bytes: *bytes,
+            reason: if over_age { "max_age".to_string() } else { "max_total_bytes".to_string() },
+        });
+    }
+
+    let bytes_reclaimable = candidates.iter().map(|c| c.total_bytes).sum();
+
+    RetentionPlan {
+        policy: policy.clone(),
+        tasks_scanned,
+        candidate_count: candidates.len(),
+        bytes_reclaimable,
+        candidates,
+    }
+}
+
+fn dir_size(dir: &std::path::Path) -> u64 {
+    let mut total = 0u64;
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                total += dir_size(&path);
+            } else if let Ok(meta) = entry.metadata() {
+                total += meta.len();
+            }
+        }
+    }
+    total
+}
+
+// ============================================================================
+// Application
+// ============================================================================
+
+/// Apply the policy: evaluate, then archive or delete each candidate.
+///
+/// Failures on individual tasks are logged and counted but don't abort the
+/// run — a single locked file shouldn't stop gigabytes of cleanup.
+pub fn apply_policy(policy: &RetentionPolicy) -> RetentionRunResponse {
+    let plan = evaluate_policy(policy);
+    let mut processed = 0usize;
+    let mut failed = 0usize;
+    let mut bytes_reclaimed = 0u64;
+
+    for candidate in &plan.candidates {
+        let result = if policy.delete {
+            delete_task(&candidate.task_id, false).map(|r| r.map(|_| ()))
+        } else {
+            archive_task(&candidate.task_id).map(|r| r.map(|_| ()))
+        };
+
+        match result {
+            Some(Ok(())) => {
+                processed += 1;
+                bytes_reclaimed += candidate.total_bytes;
+            }
+            Some(Err(e)) => {
+                log::warn!("Retention: failed to process task {}: {}", candidate.task_id, e);
+                failed += 1;
+            }
+            None => {
+                log::warn!("Retention: task {} vanished before processing", candidate.task_id);
+                failed += 1;
+            }
+        }
+    }
+
+    log::info!(
+        "Retention run complete: {} processed, {} failed, {:.1} MB reclaimed (delete={})",
+        processed,
+        failed,
+        bytes_reclaimed as f64 / 1024.0 / 1024.0,
+        policy.delete
+    );
+
+    RetentionRunResponse {
+        plan,
+        processed,
+        failed,
+        bytes_reclaimed,
+        deleted: policy.delete,
+    }
+}
+
+// ============================================================================
+// Scheduled tick
+// ============================================================================
+
+/// When the policy was last applied by the scheduler (process lifetime).
+static LAST_SCHEDULED_RUN: Lazy<Mutex<Option<std::time::Instant>>> =
+    Lazy::new(|| Mutex::new(None));
+
+/// One tick of the retention schedule, run by the scheduler's
+/// `retention_cleanup` job every `[retention] scheduler_tick_secs`.
+///
+/// Applies the policy when it is enabled and at least `interval_hours` have
+/// passed since the last application, then invalidates the task index cache
+/// if anything was processed. Returns a one-line summary for the
+/// `/system/jobs` status view.
+pub fn run_scheduled_tick() -> Result<String, String> {
+    let policy = load_policy();
+    if !policy.enabled {
+        return Ok("policy disabled".to_string());
+    }
+
+    let interval = std::time::Duration::from_secs(policy.interval_hours.max(1) * 60 * 60);
+    {
+        let mut last_run = LAST_SCHEDULED_RUN.lock();
+        if let Some(at) = *last_run {
+            if at.elapsed() < interval {
+                return Ok(format!(
+                    "not due (next run in ~{} min)",
+                    (interval - at.elapsed()).as_secs() / 60
+                ));
+            }
+        }
+        *last_run = Some(std::time::Instant::now());
+    }
+
+    log::info!("Retention: scheduled run starting");
+    let run = apply_policy(&policy);
+    if run.processed > 0 {
+        super::handlers::index::invalidate_tasks_index();
+    }
+    Ok(format!(
+        "{} processed, {} failed, {} bytes reclaimed",
+        run.processed, run.failed, run.bytes_reclaimed
+    ))
+}
@@ -0,0 +1,172 @@
+//! In-task message search.
+//!
+//! Contains:
+//! - Case-insensitive substring search across a task's content blocks
+//! - Excerpt extraction with match offsets for highlighting
+//! - Block-type filtering
+//!
+//! Feeds GET /history/tasks/:taskId/messages/search.
+
+use super::detail::{build_timestamp_map, extract_tool_result_text};
+use super::root::tasks_root;
+use super::types::*;
+
+/// Characters of context kept on each side of a match in an excerpt.
+const EXCERPT_CONTEXT_CHARS: usize = 80;
+
+/// Default / maximum number of matches returned.
+pub(crate) const SEARCH_DEFAULT_LIMIT: usize = 100;
+pub(crate) const SEARCH_MAX_LIMIT: usize = 500;
+
+/// Search one task's content blocks for a substring (case-insensitive).
+///
+/// Every text-bearing block is scanned: text, thinking, tool_use input
+/// (serialized JSON) and tool_result text. One match entry is produced per
+/// matching block, with an excerpt around the first occurrence and the total
+/// occurrence count in that block.
+///
+/// Returns None if the task directory doesn't exist or has no api_conversation_history.
+pub fn search_task_messages(
+    task_id: &str,
+    query: &str,
+    block_type_filter: Option<&str>,
+    limit: usize,
+) -> Option<MessageSearchResponse> {
+    let root = tasks_root()?;
+    let dir = root.join(task_id);
+
+    if !dir.is_dir() {
+        log::warn!("Task directory not found: {:?}", dir);
+        return None;
+    }
+
+    let api_history_path = dir.join("api_conversation_history.json");
+    let ui_messages_path = dir.join("ui_messages.json");
+
+    if !api_history_path.exists() {
+        log::warn!("No api_conversation_history.json for task {}", task_id);
+        return None;
+    }
+
+    let timestamp_map = build_timestamp_map(&ui_messages_path);
+
+    let content = match std::fs::read_to_string(&api_history_path) {
+        Ok(c) => c,
+        Err(e) => {
+            log::warn!("Failed to read {:?}: {}", api_history_path, e);
+            return None;
+        }
+    };
+
+    let raw_messages: Vec<RawApiMessage> = match serde_json::from_str(&content) {
+        Ok(m) => m,
+        Err(e) => {
+            log::warn!("Failed to parse {:?}: {}", api_history_path, e);
+            return None;
+        }
+    };
+
+    let mut matches: Vec<MessageSearchMatch> = Vec::new();
+    let mut total_matches = 0usize;
+
+    'outer: for (msg_idx, raw_msg) in raw_messages.iter().enumerate() {
+        for (block_idx, block) in raw_msg.content.iter().enumerate() {
+            let (block_type, text): (&str, String) = match block {
+                RawContentBlock::Text { text } => ("text", text.clone()),
+                RawContentBlock::Thinking { thinking, .. } => ("thinking", thinking.clone()),
+                RawContentBlock::ToolUse { input, .. } => {
+                    ("tool_use", serde_json::to_string(input).unwrap_or_default())
+                }
+                RawContentBlock::ToolResult { content, .. } => {
+                    ("tool_result", extract_tool_result_text(content))
+                }
+                RawContentBlock::Unknown => continue,
+            };
+
+            if let Some(filter) = block_type_filter {
+                if block_type != filter {
+                    continue;
+                }
+            }
+
+            let occurrences = find_occurrences(&text, query);
+            if occurrences.is_empty() {
+                continue;
+            }
+
+            total_matches += 1;
+            if matches.len() < limit {
+                let (excerpt, match_offset, match_length) =
+                    build_excerpt(&text, occurrences[0], query.chars().count());
+                matches.push(MessageSearchMatch {
+                    message_index: msg_idx,
+                    role: raw_msg.role.clone(),
+                    block_index: block_idx,
+                    block_type: block_type.to_string(),
+                    timestamp: timestamp_map.get(&(msg_idx as i64)).cloned(),
+                    occurrence_count: occurrences.len(),
+                    excerpt,
+                    match_offset,
+                    match_length,
+                });
+            } else if total_matches > limit {
+                // Count one past the limit to set truncated, then stop scanning
+                break 'outer;
+            }
+        }
+    }
+
+    Some(MessageSearchResponse {
+        task_id: task_id.to_string(),
+        query: query.to_string(),
+        block_type: block_type_filter.map(|s| s.to_string()),
+        total_matches: matches.len(),
+        truncated: total_matches > matches.len(),
+        matches,
+    })
+}
+
+/// Find all char-index occurrences of `needle` in `haystack`, case-insensitive.
+///
+/// Works on char vectors (one lowered char per input char) so indices stay
+/// aligned with the original text for excerpt extraction.
+fn find_occurrences(haystack: &str, needle: &str) -> Vec<usize> {
+    let hay: Vec<char> = haystack.chars().flat_map(|c| c.to_lowercase().next()).collect();
+    let ndl: Vec<char> = needle.chars().flat_map(|c| c.to_lowercase().next()).collect();
+
+    if ndl.is_empty() || hay.len() < ndl.len() {
+        return vec![];
+    }
+
+    let mut found = Vec::new();
+    for i in 0..=(hay.len() - ndl.len()) {
+        if hay[i..i + ndl.len()] == ndl[..] {
+            found.push(i);
+        }
+    }
+    found
+}
+
+/// Build an excerpt around a match: up to `EXCERPT_CONTEXT_CHARS` chars of
+/// context on each side, with ellipses when the text is clipped.
+///
+/// Returns (excerpt, match_offset_in_excerpt, match_length) — offsets are in
+/// chars so the UI can highlight the matched range.
+fn build_excerpt(text: &str, match_start: usize, match_len: usize) -> (String, usize, usize) {
+    let chars: Vec<char> = text.chars().collect();
+    let start = match_start.saturating_sub(EXCERPT_CONTEXT_CHARS);
+    let end = (match_start + match_len + EXCERPT_CONTEXT_CHARS).min(chars.len());
+
+    let mut excerpt = String::new();
+    let mut offset = match_start - start;
+    if start > 0 {
+        excerpt.push('…');
+        offset += 1;
+    }
+    excerpt.extend(&chars[start..end]);
+    if end < chars.len() {
+        excerpt.push('…');
+    }
+
+    (excerpt, offset, match_len)
+}
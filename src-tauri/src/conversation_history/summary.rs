@@ -11,8 +11,10 @@
 use std::collections::HashMap;
 use std::path::Path;
 
+use super::focus_chain::focus_chain_completion_from_file;
 use super::root::tasks_root;
 use super::types::*;
+use super::usage::parse_usage_totals;
 use super::util::{epoch_ms_to_iso, truncate_utf8, PROMPT_TRUNCATE_LEN};
 
 /// Scan all task directories and produce summaries.
@@ -108,7 +110,7 @@ pub fn scan_all_tasks() -> TaskHistoryListResponse {
 }
 
 /// Parse a single task directory into a TaskHistorySummary
-fn parse_task_dir(task_id: &str, dir: &Path) -> Option<TaskHistorySummary> {
+pub(crate) fn parse_task_dir(task_id: &str, dir: &Path) -> Option<TaskHistorySummary> {
     let api_history_path = dir.join("api_conversation_history.json");
     let metadata_path = dir.join("task_metadata.json");
     let ui_messages_path = dir.join("ui_messages.json");
@@ -128,9 +130,15 @@ fn parse_task_dir(task_id: &str, dir: &Path) -> Option<TaskHistorySummary> {
         Err(_) => "unknown".to_string(),
     };
 
-    // Check for focus_chain file
+    // Check for focus_chain file (and its checklist completion, if present)
     let focus_chain_name = format!("focus_chain_taskid_{}.md", task_id);
-    let has_focus_chain = dir.join(&focus_chain_name).exists();
+    let focus_chain_path = dir.join(&focus_chain_name);
+    let has_focus_chain = focus_chain_path.exists();
+    let focus_chain_completion = if has_focus_chain {
+        focus_chain_completion_from_file(&focus_chain_path)
+    } else {
+        None
+    };
 
     // Parse api_conversation_history.json
     let (message_count, tool_use_count, thinking_count, tool_breakdown, task_prompt) =
@@ -143,6 +151,9 @@ fn parse_task_dir(task_id: &str, dir: &Path) -> Option<TaskHistorySummary> {
     // Get end time from ui_messages.json (just the last timestamp)
     let ended_at = parse_ui_messages_end_time(&ui_messages_path);
 
+    // Token usage / cost totals from api_req_started entries
+    let usage = parse_usage_totals(&ui_messages_path);
+
     Some(TaskHistorySummary {
         task_id: task_id.to_string(),
         started_at,
@@ -160,7 +171,12 @@ fn parse_task_dir(task_id: &str, dir: &Path) -> Option<TaskHistorySummary> {
         api_history_size_bytes: api_size,
         ui_messages_size_bytes: ui_size,
         has_focus_chain,
+        focus_chain_completion,
         task_prompt,
+        api_request_count: usage.api_request_count,
+        total_tokens_in: usage.tokens_in,
+        total_tokens_out: usage.tokens_out,
+        total_cost: usage.total_cost,
     })
 }
 
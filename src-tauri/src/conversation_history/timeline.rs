@@ -0,0 +1,208 @@
+//! Unified per-task timeline parsing.
+//!
+//! Contains:
+//! - Message + tool call event extraction from api_conversation_history.json
+//! - Checkpoint step lookup via the shadow git workspace for the task
+//! - Timestamp-based merge into one chronological stream
+//!
+//! Feeds GET /history/tasks/:taskId/timeline for the UI's scrubber view.
+
+use super::detail::build_timestamp_map;
+use super::root::tasks_root;
+use super::types::*;
+use super::util::{truncate_utf8, TEXT_TRUNCATE_LEN, TOOL_INPUT_TRUNCATE_LEN};
+use crate::shadow_git::{list_steps_for_task, resolve_workspace_for_task};
+
+/// Parse a task's unified timeline — conversation messages, tool calls and
+/// shadow-git checkpoint steps interleaved by timestamp.
+///
+/// Message and tool call events come from `api_conversation_history.json`
+/// (timestamps joined from `ui_messages.json`); checkpoint events come from
+/// the shadow git workspace that contains commits for this task, if any.
+///
+/// Events are merged oldest-first. Messages without a ui_messages timestamp
+/// inherit the previous event's position so conversation order is preserved.
+///
+/// Returns None if the task directory doesn't exist or has no api_conversation_history.
+pub fn parse_task_timeline(task_id: &str) -> Option<TaskTimelineResponse> {
+    let root = tasks_root()?;
+    let dir = root.join(task_id);
+
+    if !dir.is_dir() {
+        log::warn!("Task directory not found: {:?}", dir);
+        return None;
+    }
+
+    let api_history_path = dir.join("api_conversation_history.json");
+    let ui_messages_path = dir.join("ui_messages.json");
+
+    if !api_history_path.exists() {
+        log::warn!("No api_conversation_history.json for task {}", task_id);
+        return None;
+    }
+
+    let timestamp_map = build_timestamp_map(&ui_messages_path);
+
+    let content = match std::fs::read_to_string(&api_history_path) {
+        Ok(c) => c,
+        Err(e) => {
+            log::warn!("Failed to read {:?}: {}", api_history_path, e);
+            return None;
+        }
+    };
+
+    let raw_messages: Vec<RawApiMessage> = match serde_json::from_str(&content) {
+        Ok(m) => m,
+        Err(e) => {
+            log::warn!("Failed to parse {:?}: {}", api_history_path, e);
+            return None;
+        }
+    };
+
+    // ---- Message + tool call events (already in conversation order) ----
+    // Each event carries an epoch-ms sort key; messages without a timestamp
+    // inherit the last seen one so the merge can't reorder the conversation.
+    let mut conv_events: Vec<(i64, TimelineEvent)> = Vec::new();
+    let mut message_count = 0usize;
+    let mut tool_call_count = 0usize;
+    let mut last_ts_ms: i64 = 0;
+
+    for (msg_idx, raw_msg) in raw_messages.iter().enumerate() {
+        let timestamp = timestamp_map.get(&(msg_idx as i64)).cloned();
+        if let Some(ts) = timestamp.as_deref() {
+            last_ts_ms = iso_to_epoch_ms(ts).unwrap_or(last_ts_ms);
+        }
+
+        // One "message" event per conversation entry, summarized from its
+        // first text block (tool_result-only user messages get a marker).
+        let summary = raw_msg
+            .content
+            .iter()
+            .find_map(|b| match b {
+                RawContentBlock::Text { text } => Some(truncate_utf8(text, TEXT_TRUNCATE_LEN)),
+                _ => None,
+            })
+            .unwrap_or_else(|| "[no text content]".to_string());
+
+        conv_events.push((
+            last_ts_ms,
+            TimelineEvent {
+                event_type: "message".to_string(),
+                timestamp: timestamp.clone(),
+                message_index: Some(msg_idx),
+                role: Some(raw_msg.role.clone()),
+                summary,
+                tool_name: None,
+                success: None,
+                checkpoint_index: None,
+                checkpoint_hash: None,
+                files_changed: None,
+            },
+        ));
+        message_count += 1;
+
+        // One "tool_call" event per tool_use block, resolved against the
+        // matching tool_result in a later message.
+        for block in &raw_msg.content {
+            if let RawContentBlock::ToolUse { id, name, input } = block {
+                let input_json = serde_json::to_string(input).unwrap_or_default();
+                let success = find_tool_result_status(&raw_messages, msg_idx, id);
+
+                conv_events.push((
+                    last_ts_ms,
+                    TimelineEvent {
+                        event_type: "tool_call".to_string(),
+                        timestamp: timestamp.clone(),
+                        message_index: Some(msg_idx),
+                        role: None,
+                        summary: truncate_utf8(&input_json, TOOL_INPUT_TRUNCATE_LEN),
+                        tool_name: Some(name.clone()),
+                        success,
+                        checkpoint_index: None,
+                        checkpoint_hash: None,
+                        files_changed: None,
+                    },
+                ));
+                tool_call_count += 1;
+            }
+        }
+    }
+
+    // ---- Checkpoint events (from the shadow git workspace, if any) ----
+    let mut workspace_id: Option<String> = None;
+    let mut checkpoint_events: Vec<(i64, TimelineEvent)> = Vec::new();
+
+    if let Some((ws_id, git_dir)) = resolve_workspace_for_task(task_id) {
+        let steps = list_steps_for_task(task_id, &ws_id, &git_dir);
+        for step in steps {
+            let ts_ms = iso_to_epoch_ms(&step.timestamp).unwrap_or(0);
+            checkpoint_events.push((
+                ts_ms,
+                TimelineEvent {
+                    event_type: "checkpoint".to_string(),
+                    timestamp: Some(step.timestamp.clone()),
+                    message_index: None,
+                    role: None,
+                    summary: format!("Checkpoint step {} ({} files changed)", step.index, step.files_changed),
+                    tool_name: None,
+                    success: None,
+                    checkpoint_index: Some(step.index),
+                    checkpoint_hash: Some(step.hash.clone()),
+                    files_changed: Some(step.files_changed),
+                },
+            ));
+        }
+        workspace_id = Some(ws_id);
+    }
+
+    let checkpoint_count = checkpoint_events.len();
+
+    // ---- Merge: stable sort by epoch-ms sort key ----
+    // Conversation events keep their relative order (stable sort); checkpoints
+    // slot in between messages based on commit time.
+    let mut merged: Vec<(i64, TimelineEvent)> = conv_events;
+    merged.extend(checkpoint_events);
+    merged.sort_by_key(|(ts, _)| *ts);
+
+    let events: Vec<TimelineEvent> = merged.into_iter().map(|(_, e)| e).collect();
+
+    Some(TaskTimelineResponse {
+        task_id: task_id.to_string(),
+        total_events: events.len(),
+        message_count,
+        tool_call_count,
+        checkpoint_count,
+        workspace_id,
+        events,
+    })
+}
+
+/// Find the success status of a tool call by scanning forward for the
+/// matching tool_result block (is_error absent/false = success).
+fn find_tool_result_status(
+    raw_messages: &[RawApiMessage],
+    from_msg_idx: usize,
+    tool_use_id: &str,
+) -> Option<bool> {
+    for raw_msg in raw_messages.iter().skip(from_msg_idx + 1) {
+        for block in &raw_msg.content {
+            if let RawContentBlock::ToolResult { tool_use_id: tid, is_error, .. } = block {
+                if tid == tool_use_id {
+                    return Some(!is_error.unwrap_or(false));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Parse an ISO 8601 / RFC 3339 timestamp into epoch milliseconds.
+pub(crate) fn iso_to_epoch_ms(ts: &str) -> Option<i64> {
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(ts) {
+        return Some(dt.timestamp_millis());
+    }
+    if let Ok(dt) = chrono::DateTime::parse_from_str(ts, "%Y-%m-%d %H:%M:%S %z") {
+        return Some(dt.timestamp_millis());
+    }
+    None
+}
@@ -5,6 +5,7 @@
 //! - Tool/result pairing
 //! - Success/failure derivation
 //! - Tool-specific filtering
+//! - Retry-loop detection (same tool, near-identical input)
 //!
 //! Must be isolated from message pagination logic.
 
@@ -67,6 +68,9 @@ pub fn parse_task_tools(
     // ---- Pass 1: Collect all tool_use entries ----
     // We store: (call_index, message_index, tool_name, tool_use_id, input_summary, input_full_length, timestamp)
     let mut tool_calls: Vec<ToolCallTimelineEntry> = Vec::new();
+    // Full (untruncated) input JSON per call — needed for loop detection.
+    // Kept parallel to tool_calls; not part of the response payload.
+    let mut full_inputs: Vec<String> = Vec::new();
     // Map: tool_use_id → index into tool_calls vec (for resolving results)
     let mut pending: HashMap<String, usize> = HashMap::new();
     let mut tool_breakdown: HashMap<String, usize> = HashMap::new();
@@ -101,6 +105,7 @@ pub fn parse_task_tools(
 
                     let idx = tool_calls.len();
                     tool_calls.push(entry);
+                    full_inputs.push(input_json);
                     pending.insert(id.clone(), idx);
                     call_counter += 1;
                 }
@@ -130,6 +135,9 @@ pub fn parse_task_tools(
         }
     }
 
+    // ---- Detect retry loops (before filtering — loops are a whole-task property) ----
+    let loops = detect_tool_loops(&tool_calls, &full_inputs);
+
     // ---- Compute stats before filtering ----
     let total_tool_calls = tool_calls.len();
     let success_count = tool_calls.iter().filter(|c| c.success == Some(true)).count();
@@ -165,6 +173,138 @@ pub fn parse_task_tools(
         failure_count,
         no_result_count,
         tool_breakdown,
+        loops,
         tool_calls: filtered,
     })
 }
+
+// ============================================================================
+// Retry-loop detection
+// ============================================================================
+
+/// Minimum number of near-identical consecutive calls to flag as a loop.
+const LOOP_MIN_REPEATS: usize = 3;
+
+/// Similarity threshold for treating two inputs as "near-identical".
+/// Retry loops usually re-issue the same input verbatim or with tiny edits
+/// (e.g. a changed line number), so a high threshold keeps false positives low.
+const LOOP_SIMILARITY_THRESHOLD: f64 = 0.85;
+
+/// Cheap input similarity: twice the common prefix length over the combined
+/// length (1.0 = identical). Retried inputs diverge at the tail if at all,
+/// so a prefix measure is a good proxy without paying for edit distance.
+fn input_similarity(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    let total = a.chars().count() + b.chars().count();
+    if total == 0 {
+        return 1.0;
+    }
+    let lcp = a
+        .chars()
+        .zip(b.chars())
+        .take_while(|(ca, cb)| ca == cb)
+        .count();
+    (2 * lcp) as f64 / total as f64
+}
+
+/// Detect runs of the same tool called with near-identical input.
+///
+/// Scans the unfiltered timeline in call order and groups consecutive calls
+/// where the tool name matches and the full input is near-identical to the
+/// run's first call. Runs of `LOOP_MIN_REPEATS`+ calls become loop entries.
+pub(crate) fn detect_tool_loops(
+    tool_calls: &[ToolCallTimelineEntry],
+    full_inputs: &[String],
+) -> Vec<ToolLoopEntry> {
+    let mut loops: Vec<ToolLoopEntry> = Vec::new();
+    let mut run_start = 0usize;
+
+    let flush = |start: usize, end: usize, loops: &mut Vec<ToolLoopEntry>| {
+        // [start, end) is a run of near-identical calls
+        let len = end - start;
+        if len >= LOOP_MIN_REPEATS {
+            let failure_count = tool_calls[start..end]
+                .iter()
+                .filter(|c| c.success == Some(false))
+                .count();
+            loops.push(ToolLoopEntry {
+                loop_index: loops.len(),
+                tool_name: tool_calls[start].tool_name.clone(),
+                start_call_index: tool_calls[start].call_index,
+                end_call_index: tool_calls[end - 1].call_index,
+                repeat_count: len,
+                failure_count,
+                input_summary: tool_calls[start].input_summary.clone(),
+            });
+        }
+    };
+
+    for i in 1..tool_calls.len() {
+        let same_tool = tool_calls[i].tool_name == tool_calls[run_start].tool_name;
+        let similar = same_tool
+            && input_similarity(&full_inputs[i], &full_inputs[run_start])
+                >= LOOP_SIMILARITY_THRESHOLD;
+        if !similar {
+            flush(run_start, i, &mut loops);
+            run_start = i;
+        }
+    }
+    flush(run_start, tool_calls.len(), &mut loops);
+
+    loops
+}
+
+/// Scan all task directories and collect detected retry loops.
+///
+/// Runs the full tool-timeline parse (no filters) per task and keeps only
+/// tasks with at least one loop. Tasks are ordered newest first (directory
+/// names are epoch milliseconds).
+pub fn scan_all_loops() -> LoopsAggregateResponse {
+    let mut tasks_scanned = 0usize;
+    let mut tasks: Vec<TaskLoopsSummary> = Vec::new();
+
+    if let Some(root) = tasks_root() {
+        if let Ok(entries) = std::fs::read_dir(&root) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if !path.is_dir() {
+                    continue;
+                }
+                let task_id = match path.file_name().and_then(|n| n.to_str()) {
+                    Some(n) => n.to_string(),
+                    None => continue,
+                };
+
+                let response = match parse_task_tools(&task_id, None, false) {
+                    Some(r) => r,
+                    None => continue,
+                };
+                tasks_scanned += 1;
+
+                if !response.loops.is_empty() {
+                    tasks.push(TaskLoopsSummary {
+                        task_id,
+                        total_tool_calls: response.total_tool_calls,
+                        loops: response.loops,
+                    });
+                }
+            }
+        }
+    }
+
+    // Newest first — task IDs are epoch-ms strings, so reverse lexicographic
+    // sort on equal-length IDs matches chronological order.
+    tasks.sort_by(|a, b| b.task_id.cmp(&a.task_id));
+
+    let tasks_with_loops = tasks.len();
+    let total_loops = tasks.iter().map(|t| t.loops.len()).sum();
+
+    LoopsAggregateResponse {
+        tasks_scanned,
+        tasks_with_loops,
+        total_loops,
+        tasks,
+    }
+}
@@ -45,8 +45,25 @@ pub struct TaskHistorySummary {
     pub ui_messages_size_bytes: u64,
     /// Whether a focus_chain markdown file exists for this task
     pub has_focus_chain: bool,
+    /// Focus chain completion percentage (0–100; None if no checklist items)
+    #[serde(default)]
+    pub focus_chain_completion: Option<f64>,
     /// First user message text (truncated to 200 chars) — task description
     pub task_prompt: Option<String>,
+
+    // ---- Token usage & cost (from api_req_started ui_messages entries) ----
+    /// Number of API requests made during this task
+    #[serde(default)]
+    pub api_request_count: usize,
+    /// Total input tokens across all API requests
+    #[serde(default)]
+    pub total_tokens_in: u64,
+    /// Total output tokens across all API requests
+    #[serde(default)]
+    pub total_tokens_out: u64,
+    /// Total estimated cost in USD across all API requests
+    #[serde(default)]
+    pub total_cost: f64,
 }
 
 /// Response for GET /history/tasks
@@ -127,6 +144,12 @@ pub struct TaskDetailResponse {
     pub focus_chain: Option<String>,
     /// Whether focus_chain file exists
     pub has_focus_chain: bool,
+    /// Structured checklist items parsed from the focus chain markdown
+    #[serde(default)]
+    pub focus_chain_items: Vec<FocusChainItem>,
+    /// Focus chain completion percentage (0–100; None if no checklist items)
+    #[serde(default)]
+    pub focus_chain_completion: Option<f64>,
 
     // ---- File sizes ----
     /// Size of api_conversation_history.json in bytes
@@ -134,11 +157,48 @@ pub struct TaskDetailResponse {
     /// Size of ui_messages.json in bytes
     pub ui_messages_size_bytes: u64,
 
+    // ---- Token usage & cost (from api_req_started ui_messages entries) ----
+    /// Number of API requests made during this task
+    #[serde(default)]
+    pub api_request_count: usize,
+    /// Total input tokens across all API requests
+    #[serde(default)]
+    pub total_tokens_in: u64,
+    /// Total output tokens across all API requests
+    #[serde(default)]
+    pub total_tokens_out: u64,
+    /// Total cache-write tokens across all API requests
+    #[serde(default)]
+    pub total_cache_writes: u64,
+    /// Total cache-read tokens across all API requests
+    #[serde(default)]
+    pub total_cache_reads: u64,
+    /// Total estimated cost in USD across all API requests
+    #[serde(default)]
+    pub total_cost: f64,
+
+    // ---- Annotation note ----
+    /// Reviewer-authored note for this task (from the config dir, if any)
+    #[serde(default)]
+    pub note: Option<TaskNote>,
+
     // ---- Local path ----
     /// Full local filesystem path to the task directory
     pub task_dir_path: String,
 }
 
+/// One parsed focus chain checklist item
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FocusChainItem {
+    /// Item position in the checklist (0-based, document order)
+    pub order: usize,
+    /// Checklist item text (without the `- [ ]` prefix)
+    pub text: String,
+    /// Whether the item is checked off (`[x]`)
+    pub checked: bool,
+}
+
 /// A single conversation message with its content blocks
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -246,12 +306,8 @@ pub struct EnvironmentDetail {
     pub cline_version: Option<String>,
 }
 
-/// Error response for history endpoints
-#[derive(Debug, Serialize, utoipa::ToSchema)]
-pub struct HistoryErrorResponse {
-    pub error: String,
-    pub code: u16,
-}
+/// Error response for history endpoints — alias for the crate-wide structured error.
+pub type HistoryErrorResponse = crate::api::error::ApiError;
 
 // ============================================================================
 // Internal parsing types (Cline's JSON format)
@@ -307,6 +363,9 @@ pub struct RawUiMessage {
     /// The "say" sub-type: "task", "user_feedback", "api_req_started", etc.
     #[serde(default)]
     pub say: Option<String>,
+    /// The "ask" sub-type: "tool", "followup", "command", etc.
+    #[serde(default)]
+    pub ask: Option<String>,
     /// Text content (task prompt for say="task", feedback text for say="user_feedback")
     #[serde(default)]
     pub text: Option<String>,
@@ -371,9 +430,12 @@ pub struct HistoryTasksQuery {
     /// Limit number of results (default: all)
     #[serde(default)]
     pub limit: Option<usize>,
-    /// Offset for pagination (default: 0)
+    /// Offset for pagination (default: 0, ignored when `cursor` is set)
     #[serde(default)]
     pub offset: Option<usize>,
+    /// Opaque continuation token from a previous page's `next_cursor`
+    #[serde(default)]
+    pub cursor: Option<String>,
 }
 
 /// Query parameters for GET /history/tasks/:taskId/messages
@@ -388,6 +450,18 @@ pub struct TaskMessagesQuery {
     /// Filter by role: "user" or "assistant" (default: all)
     #[serde(default)]
     pub role: Option<String>,
+    /// Set to true to redact API keys, tokens, and emails from message content
+    #[serde(default)]
+    pub redact: Option<bool>,
+}
+
+/// Query parameters for endpoints that only support content redaction
+/// (GET /history/tasks/:taskId, GET /history/tasks/:taskId/messages/:index)
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct RedactQuery {
+    /// Set to true to redact API keys, tokens, and emails from returned content
+    #[serde(default)]
+    pub redact: Option<bool>,
 }
 
 /// Query parameters for GET /history/tasks/:taskId/tools
@@ -437,6 +511,27 @@ pub struct ToolCallTimelineEntry {
     pub error_text: Option<String>,
 }
 
+/// A detected retry loop: the same tool called with near-identical input
+/// several times in a row (a thrashing agent).
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolLoopEntry {
+    /// Loop index within the task (0-based)
+    pub loop_index: usize,
+    /// The tool being retried
+    pub tool_name: String,
+    /// Call index of the first call in the loop
+    pub start_call_index: usize,
+    /// Call index of the last call in the loop
+    pub end_call_index: usize,
+    /// Number of near-identical calls in the loop
+    pub repeat_count: usize,
+    /// How many of the calls in the loop failed (is_error=true)
+    pub failure_count: usize,
+    /// Input summary of the first call in the loop (truncated, 300 chars)
+    pub input_summary: String,
+}
+
 /// Response for GET /history/tasks/:taskId/tools — tool call timeline
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -455,10 +550,498 @@ pub struct ToolCallTimelineResponse {
     pub no_result_count: usize,
     /// Tool usage breakdown: tool_name → count (across all calls, before filtering)
     pub tool_breakdown: std::collections::HashMap<String, usize>,
+    /// Detected retry loops (same tool, near-identical input, 3+ consecutive calls)
+    #[serde(default)]
+    pub loops: Vec<ToolLoopEntry>,
     /// The tool call timeline entries (filtered, in chronological order)
     pub tool_calls: Vec<ToolCallTimelineEntry>,
 }
 
+/// Per-task loop summary for the aggregate loops endpoint
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskLoopsSummary {
+    /// Task ID
+    pub task_id: String,
+    /// Total tool calls in this task
+    pub total_tool_calls: usize,
+    /// Detected retry loops in this task
+    pub loops: Vec<ToolLoopEntry>,
+}
+
+/// Response for GET /history/loops — retry loops detected across all tasks
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LoopsAggregateResponse {
+    /// Number of tasks scanned
+    pub tasks_scanned: usize,
+    /// Number of tasks with at least one detected loop
+    pub tasks_with_loops: usize,
+    /// Total loops detected across all tasks
+    pub total_loops: usize,
+    /// Per-task loop summaries (only tasks with loops, newest first)
+    pub tasks: Vec<TaskLoopsSummary>,
+}
+
+// ============================================================================
+// Task comparison (GET /history/compare)
+// ============================================================================
+
+/// Query parameters for GET /history/compare
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct TaskCompareQuery {
+    /// First task ID (epoch milliseconds directory name)
+    pub task_a: String,
+    /// Second task ID (epoch milliseconds directory name)
+    pub task_b: String,
+}
+
+/// Per-task stats column for the side-by-side comparison
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskCompareSide {
+    /// Task ID
+    pub task_id: String,
+    /// ISO 8601 task start time
+    pub started_at: String,
+    /// Wall-clock duration in seconds (None if the end time is unknown)
+    pub duration_seconds: Option<i64>,
+    /// Model ID used for this task
+    pub model_id: Option<String>,
+    /// First user message text (truncated) — useful to confirm "same prompt"
+    pub task_prompt: Option<String>,
+    /// Total number of API messages
+    pub message_count: usize,
+    /// Total tool calls
+    pub tool_call_count: usize,
+    /// Tool calls that returned is_error=true
+    pub tool_failure_count: usize,
+    /// Number of API requests made
+    pub api_request_count: usize,
+    /// Total input tokens
+    pub total_tokens_in: u64,
+    /// Total output tokens
+    pub total_tokens_out: u64,
+    /// Total estimated cost in USD
+    pub total_cost: f64,
+    /// Files edited by Cline (from task_metadata)
+    pub files_edited: usize,
+    /// Files read by Cline (from task_metadata)
+    pub files_read: usize,
+    /// Files changed in the checkpoint diff (None if no checkpoint workspace)
+    pub diff_files_changed: Option<usize>,
+    /// Lines added in the checkpoint diff
+    pub diff_lines_added: Option<usize>,
+    /// Lines removed in the checkpoint diff
+    pub diff_lines_removed: Option<usize>,
+}
+
+/// Response for GET /history/compare — side-by-side task comparison
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskCompareResponse {
+    /// Stats for the first task
+    pub task_a: TaskCompareSide,
+    /// Stats for the second task
+    pub task_b: TaskCompareSide,
+    /// Whether both tasks used the same model
+    pub same_model: bool,
+    /// Files in context in both tasks (sorted)
+    pub overlapping_files: Vec<String>,
+}
+
+// ============================================================================
+// Model A/B comparison (GET /history/stats/models)
+// ============================================================================
+
+/// Aggregate stats for one model across all tasks that used it
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelStats {
+    /// Model ID (e.g. "claude-sonnet-4", "gpt-4o", "gemini-2.0-flash")
+    pub model_id: String,
+    /// Number of tasks that used this model
+    pub task_count: usize,
+    /// Average wall-clock task duration in seconds (tasks with a known end time only)
+    pub avg_duration_seconds: Option<f64>,
+    /// Average API messages per task
+    pub avg_messages_per_task: f64,
+    /// Average tool calls per task
+    pub avg_tool_calls_per_task: f64,
+    /// Total tool calls across all tasks for this model
+    pub tool_call_count: usize,
+    /// Tool calls that returned is_error=true
+    pub tool_failure_count: usize,
+    /// Tool failure rate in percent (None when the model made no tool calls)
+    pub tool_failure_rate: Option<f64>,
+    /// Average files edited per task (from task_metadata — diff churn proxy)
+    pub avg_files_edited: f64,
+    /// Total files edited across all tasks for this model
+    pub total_files_edited: usize,
+    /// Total input tokens
+    pub total_tokens_in: u64,
+    /// Total output tokens
+    pub total_tokens_out: u64,
+    /// Total estimated cost in USD
+    pub total_cost: f64,
+}
+
+/// Response for GET /history/stats/models — per-model A/B comparison
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelStatsResponse {
+    /// Total tasks in the index
+    pub total_tasks: usize,
+    /// Tasks with no model_id in their metadata (excluded from the breakdown)
+    pub tasks_without_model: usize,
+    /// Per-model stats, sorted by task count descending
+    pub models: Vec<ModelStats>,
+}
+
+// ============================================================================
+// Retention policy (GET/PUT /history/retention, preview, run)
+// ============================================================================
+
+/// Configurable retention policy for pruning old Cline task history
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionPolicy {
+    /// Whether the scheduler applies this policy automatically
+    pub enabled: bool,
+    /// Tasks older than this many days become candidates (None = no age limit)
+    #[serde(default)]
+    pub max_age_days: Option<u64>,
+    /// Total size budget in bytes — oldest tasks beyond it become candidates (None = no budget)
+    #[serde(default)]
+    pub max_total_bytes: Option<u64>,
+    /// Never touch tasks that have a reviewer note attached
+    #[serde(default = "default_true")]
+    pub keep_noted: bool,
+    /// Delete candidates permanently instead of archiving them
+    #[serde(default)]
+    pub delete: bool,
+    /// Hours between scheduled runs (minimum 1)
+    #[serde(default = "default_interval_hours")]
+    pub interval_hours: u64,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_interval_hours() -> u64 {
+    24
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_age_days: None,
+            max_total_bytes: None,
+            keep_noted: true,
+            delete: false,
+            interval_hours: 24,
+        }
+    }
+}
+
+/// One task selected for archiving/deletion by the retention policy
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionCandidate {
+    /// Task ID
+    pub task_id: String,
+    /// ISO 8601 task start time (derived from the task ID)
+    pub started_at: String,
+    /// Size of the task directory in bytes
+    pub total_bytes: u64,
+    /// Why the task was selected: "max_age" | "max_total_bytes"
+    pub reason: String,
+}
+
+/// Response for GET /history/retention/preview — the dry-run plan
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionPlan {
+    /// The policy that was evaluated
+    pub policy: RetentionPolicy,
+    /// Number of task directories scanned
+    pub tasks_scanned: usize,
+    /// Number of candidates selected
+    pub candidate_count: usize,
+    /// Bytes that would be reclaimed
+    pub bytes_reclaimable: u64,
+    /// Selected tasks, oldest first
+    pub candidates: Vec<RetentionCandidate>,
+}
+
+/// Response for POST /history/retention/run — the applied run
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionRunResponse {
+    /// The plan that was applied
+    pub plan: RetentionPlan,
+    /// Candidates successfully archived/deleted
+    pub processed: usize,
+    /// Candidates that failed (logged, run continues)
+    pub failed: usize,
+    /// Bytes actually reclaimed
+    pub bytes_reclaimed: u64,
+    /// True if candidates were deleted, false if archived
+    pub deleted: bool,
+}
+
+// ============================================================================
+// Task lifecycle (POST /history/tasks/:taskId/archive, DELETE /history/tasks/:taskId)
+// ============================================================================
+
+/// Response for POST /history/tasks/:taskId/archive
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveTaskResponse {
+    /// Task ID that was archived
+    pub task_id: String,
+    /// Full path the task directory was moved to
+    pub archived_to: String,
+    /// Number of files in the archived directory
+    pub file_count: usize,
+    /// Total bytes in the archived directory
+    pub total_bytes: u64,
+}
+
+/// Query parameters for DELETE /history/tasks/:taskId
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct DeleteTaskQuery {
+    /// Confirmation token — must equal the task ID being deleted
+    #[serde(default)]
+    pub confirm: Option<String>,
+    /// Set to true to preview what would be deleted without removing anything
+    #[serde(default)]
+    pub dry_run: Option<bool>,
+}
+
+/// Response for DELETE /history/tasks/:taskId
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteTaskResponse {
+    /// Task ID
+    pub task_id: String,
+    /// Whether this was a dry run
+    pub dry_run: bool,
+    /// Whether the directory was actually removed
+    pub deleted: bool,
+    /// Number of files (deleted, or that would be deleted)
+    pub file_count: usize,
+    /// Total bytes (deleted, or that would be deleted)
+    pub total_bytes: u64,
+}
+
+// ============================================================================
+// Task annotation notes (GET/PUT /history/tasks/:taskId/notes)
+// ============================================================================
+
+/// A reviewer-authored free-text note attached to a task
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskNote {
+    /// Task ID the note is attached to
+    pub task_id: String,
+    /// Free-text note content (empty = no note)
+    pub note: String,
+    /// ISO 8601 timestamp of the last edit
+    pub updated_at: String,
+}
+
+/// Request body for PUT /history/tasks/:taskId/notes
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateNoteRequest {
+    /// New note content. An empty string clears the note.
+    pub note: String,
+}
+
+// ============================================================================
+// User prompt chain (GET /history/tasks/:taskId/prompts)
+// ============================================================================
+
+/// One user prompt in a task — the initial task or a feedback message
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptEntry {
+    /// Message index in the api_conversation_history array (0-based)
+    pub message_index: usize,
+    /// ISO 8601 timestamp (from ui_messages join, if available)
+    pub timestamp: Option<String>,
+    /// Prompt kind: "task" (first user message) | "feedback" (later user input)
+    pub kind: String,
+    /// Full prompt text, untruncated (text blocks joined; tool results excluded)
+    pub text: String,
+}
+
+/// Response for GET /history/tasks/:taskId/prompts — the user prompt chain
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskPromptsResponse {
+    /// Task ID
+    pub task_id: String,
+    /// Number of prompts returned
+    pub prompt_count: usize,
+    /// Prompts in conversation order (initial task first)
+    pub prompts: Vec<PromptEntry>,
+}
+
+// ============================================================================
+// Message search (GET /history/tasks/:taskId/messages/search)
+// ============================================================================
+
+/// Query parameters for GET /history/tasks/:taskId/messages/search
+#[derive(Debug, Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
+pub struct MessageSearchQuery {
+    /// Search term (case-insensitive substring match)
+    pub q: String,
+    /// Filter by block type: "text" | "thinking" | "tool_use" | "tool_result"
+    #[serde(default)]
+    pub block_type: Option<String>,
+    /// Maximum number of matches to return (default 100, max 500)
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// One matching content block from an in-task message search
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageSearchMatch {
+    /// Message index in the api_conversation_history array (0-based)
+    pub message_index: usize,
+    /// Message role: "user" | "assistant"
+    pub role: String,
+    /// Block index within the message (0-based)
+    pub block_index: usize,
+    /// Block type: "text" | "thinking" | "tool_use" | "tool_result"
+    pub block_type: String,
+    /// ISO 8601 timestamp (from ui_messages join, if available)
+    pub timestamp: Option<String>,
+    /// Number of occurrences of the search term in this block
+    pub occurrence_count: usize,
+    /// Excerpt around the first occurrence (ellipsized when clipped)
+    pub excerpt: String,
+    /// Char offset of the match within the excerpt (for highlighting)
+    pub match_offset: usize,
+    /// Char length of the matched term
+    pub match_length: usize,
+}
+
+/// Response for GET /history/tasks/:taskId/messages/search
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageSearchResponse {
+    /// Task ID
+    pub task_id: String,
+    /// The search term that was used
+    pub query: String,
+    /// The block type filter that was applied, if any
+    pub block_type: Option<String>,
+    /// Number of matches returned
+    pub total_matches: usize,
+    /// True if more matches exist beyond the limit
+    pub truncated: bool,
+    /// Matching blocks in conversation order
+    pub matches: Vec<MessageSearchMatch>,
+}
+
+// ============================================================================
+// Context growth (GET /history/tasks/:taskId/context-growth)
+// ============================================================================
+
+/// One point in a task's context growth series — one API request
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextGrowthPoint {
+    /// API request index (0-based, chronological)
+    pub request_index: usize,
+    /// Conversation message index this request was sent at (from conversationHistoryIndex)
+    pub message_index: Option<i64>,
+    /// ISO 8601 timestamp of the request
+    pub timestamp: String,
+    /// Input context size for this request: tokensIn + cacheWrites + cacheReads
+    pub context_tokens: u64,
+    /// Fresh (non-cached) input tokens for this request
+    pub tokens_in: u64,
+    /// Cache write tokens for this request
+    pub cache_writes: u64,
+    /// Cache read tokens for this request
+    pub cache_reads: u64,
+    /// Running sum of fresh input tokens up to and including this request
+    pub cumulative_tokens_in: u64,
+}
+
+/// Response for GET /history/tasks/:taskId/context-growth — context bloat series
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextGrowthResponse {
+    /// Task ID
+    pub task_id: String,
+    /// Number of API requests (= number of points)
+    pub api_request_count: usize,
+    /// Largest input context observed across all requests
+    pub peak_context_tokens: u64,
+    /// Input context of the last request (the task's final context size)
+    pub final_context_tokens: u64,
+    /// Chronological series of per-request context sizes
+    pub points: Vec<ContextGrowthPoint>,
+}
+
+// ============================================================================
+// Unified timeline (GET /history/tasks/:taskId/timeline)
+// ============================================================================
+
+/// One event in a task's unified timeline — a message, tool call or checkpoint
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineEvent {
+    /// Event type: "message" | "tool_call" | "checkpoint"
+    pub event_type: String,
+    /// ISO 8601 timestamp (None if the message has no ui_messages timestamp)
+    pub timestamp: Option<String>,
+    /// Conversation message index (message and tool_call events)
+    pub message_index: Option<usize>,
+    /// Message role: "user" | "assistant" (message events only)
+    pub role: Option<String>,
+    /// Short text summary of the event (truncated)
+    pub summary: String,
+    /// Tool name (tool_call events only)
+    pub tool_name: Option<String>,
+    /// Tool success status (tool_call events only; None = no result found)
+    pub success: Option<bool>,
+    /// Checkpoint step index, 1-based (checkpoint events only)
+    pub checkpoint_index: Option<usize>,
+    /// Checkpoint commit hash (checkpoint events only)
+    pub checkpoint_hash: Option<String>,
+    /// Files changed in this checkpoint step (checkpoint events only)
+    pub files_changed: Option<usize>,
+}
+
+/// Response for GET /history/tasks/:taskId/timeline — unified chronological stream
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskTimelineResponse {
+    /// Task ID
+    pub task_id: String,
+    /// Total events in the timeline
+    pub total_events: usize,
+    /// Number of message events
+    pub message_count: usize,
+    /// Number of tool call events
+    pub tool_call_count: usize,
+    /// Number of checkpoint events
+    pub checkpoint_count: usize,
+    /// Workspace ID the checkpoints came from (None if no shadow git workspace found)
+    pub workspace_id: Option<String>,
+    /// Chronologically ordered events (oldest first)
+    pub events: Vec<TimelineEvent>,
+}
+
 // ============================================================================
 // Paginated Messages response (P1.5: GET /history/tasks/:taskId/messages)
 // ============================================================================
@@ -677,6 +1260,24 @@ pub struct HistoryStatsResponse {
     /// Cline version breakdown: version → number of tasks
     pub cline_version_usage: std::collections::HashMap<String, usize>,
 
+    // ---- Token usage & cost (from api_req_started ui_messages entries) ----
+    /// Total API requests across all tasks
+    pub total_api_requests: usize,
+    /// Total input tokens across all tasks
+    pub total_tokens_in: u64,
+    /// Total output tokens across all tasks
+    pub total_tokens_out: u64,
+    /// Total estimated cost in USD across all tasks
+    pub total_estimated_cost: f64,
+    /// Estimated cost per model: model_id → total USD (tasks with no model info are grouped under "unknown")
+    pub cost_per_model: std::collections::HashMap<String, f64>,
+    /// Total tokens (in + out) per model: model_id → token count
+    pub tokens_per_model: std::collections::HashMap<String, u64>,
+    /// Estimated cost per day: "YYYY-MM-DD" (task start date, local time) → total USD
+    pub cost_per_day: std::collections::HashMap<String, f64>,
+    /// Total tokens (in + out) per day: "YYYY-MM-DD" → token count
+    pub tokens_per_day: std::collections::HashMap<String, u64>,
+
     // ---- File stats ----
     /// Total files in context across all tasks
     pub total_files_in_context: usize,
@@ -686,6 +1287,8 @@ pub struct HistoryStatsResponse {
     pub total_files_read: usize,
     /// Number of tasks with a focus chain file
     pub tasks_with_focus_chain: usize,
+    /// Average focus chain completion percentage across tasks with checklist items
+    pub focus_chain_completion_rate: Option<f64>,
 
     // ---- Time range ----
     /// ISO 8601 timestamp of the earliest task
@@ -697,6 +1300,33 @@ pub struct HistoryStatsResponse {
     pub tasks_root: String,
 }
 
+/// Response for GET /history/stats/activity — usage heatmap buckets
+///
+/// Message and tool-call counts bucketed by hour-of-day and day-of-week,
+/// computed from `ui_messages.json` timestamps across all tasks (local time).
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityHeatmapResponse {
+    /// Number of tasks with a parseable ui_messages.json
+    pub tasks_scanned: usize,
+    /// Total UI messages bucketed
+    pub total_messages: usize,
+    /// Total tool-call messages bucketed (say/ask = "tool")
+    pub total_tool_calls: usize,
+    /// Message counts by hour of day (index 0–23, local time)
+    pub messages_by_hour: Vec<usize>,
+    /// Tool-call counts by hour of day (index 0–23, local time)
+    pub tool_calls_by_hour: Vec<usize>,
+    /// Message counts by day of week (index 0 = Monday … 6 = Sunday)
+    pub messages_by_weekday: Vec<usize>,
+    /// Tool-call counts by day of week (index 0 = Monday … 6 = Sunday)
+    pub tool_calls_by_weekday: Vec<usize>,
+    /// Message counts as a 7×24 grid: [weekday][hour]
+    pub message_grid: Vec<Vec<usize>>,
+    /// Tool-call counts as a 7×24 grid: [weekday][hour]
+    pub tool_call_grid: Vec<Vec<usize>>,
+}
+
 /// Query parameters for GET /history/stats
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
 pub struct HistoryStatsQuery {
@@ -705,6 +1335,73 @@ pub struct HistoryStatsQuery {
     pub refresh: Option<bool>,
 }
 
+// ============================================================================
+// Token Usage response (GET /history/tasks/:taskId/usage)
+// ============================================================================
+
+/// Token/cost usage for a single API request within a task.
+///
+/// Parsed from `ui_messages.json` entries where `say = "api_req_started"` —
+/// Cline embeds a JSON payload with token counts and estimated cost.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiRequestUsageEntry {
+    /// Sequential index of this API request in the task (0-based)
+    pub request_index: usize,
+    /// ISO 8601 timestamp when the request started
+    pub timestamp: String,
+    /// Input tokens (None if the entry has no token info yet)
+    pub tokens_in: Option<u64>,
+    /// Output tokens
+    pub tokens_out: Option<u64>,
+    /// Prompt-cache write tokens
+    pub cache_writes: Option<u64>,
+    /// Prompt-cache read tokens
+    pub cache_reads: Option<u64>,
+    /// Estimated cost in USD for this request
+    pub cost: Option<f64>,
+}
+
+/// Response for GET /history/tasks/:taskId/usage — per-task token usage and cost
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskUsageResponse {
+    /// Task ID
+    pub task_id: String,
+    /// Number of API requests made during this task
+    pub api_request_count: usize,
+    /// Total input tokens across all API requests
+    pub total_tokens_in: u64,
+    /// Total output tokens across all API requests
+    pub total_tokens_out: u64,
+    /// Total cache-write tokens across all API requests
+    pub total_cache_writes: u64,
+    /// Total cache-read tokens across all API requests
+    pub total_cache_reads: u64,
+    /// Total estimated cost in USD across all API requests
+    pub total_cost: f64,
+    /// Per-request usage entries (chronological order)
+    pub requests: Vec<ApiRequestUsageEntry>,
+}
+
+// ============================================================================
+// Export (GET /history/tasks/:taskId/export, GET /history/export)
+// ============================================================================
+
+/// Query parameters for the JSONL export endpoints
+#[derive(Debug, Deserialize, utoipa::IntoParams, utoipa::ToSchema)]
+pub struct ExportQuery {
+    /// Export format (default: "jsonl"; only "jsonl" is currently supported)
+    #[serde(default)]
+    pub format: Option<String>,
+    /// Record style: "openai" (default) or "sharegpt"
+    #[serde(default)]
+    pub style: Option<String>,
+    /// Set to true to redact API keys, tokens, and emails from exported content
+    #[serde(default)]
+    pub redact: Option<bool>,
+}
+
 // ============================================================================
 // Subtask Detection response (GET /history/tasks/:taskId/subtasks)
 // ============================================================================
@@ -0,0 +1,217 @@
+//! Per-task token usage and cost parsing.
+//!
+//! Contains:
+//! - `api_req_started` ui_messages entry parsing (token + cost info)
+//! - Per-task usage totals (shared with summary/detail parsing)
+//! - Per-request usage timeline for the /usage endpoint
+//! - Context growth series for the /context-growth endpoint
+//!
+//! Cline records token/cost data in `ui_messages.json` entries where
+//! `say = "api_req_started"` — the `text` field holds a JSON object with
+//! `tokensIn`, `tokensOut`, `cacheWrites`, `cacheReads`, and `cost`.
+
+use std::path::Path;
+
+use serde::Deserialize;
+
+use super::root::tasks_root;
+use super::types::*;
+use super::util::epoch_ms_to_iso;
+
+/// The JSON payload embedded in an `api_req_started` ui_message's `text` field.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawApiReqInfo {
+    #[serde(default)]
+    tokens_in: Option<u64>,
+    #[serde(default)]
+    tokens_out: Option<u64>,
+    #[serde(default)]
+    cache_writes: Option<u64>,
+    #[serde(default)]
+    cache_reads: Option<u64>,
+    #[serde(default)]
+    cost: Option<f64>,
+}
+
+/// Aggregated usage totals for one task (all api_req_started entries summed).
+#[derive(Debug, Clone, Default)]
+pub struct UsageTotals {
+    pub api_request_count: usize,
+    pub tokens_in: u64,
+    pub tokens_out: u64,
+    pub cache_writes: u64,
+    pub cache_reads: u64,
+    pub total_cost: f64,
+}
+
+/// Parse usage totals for a task from its ui_messages.json.
+///
+/// Returns default (all zeros) if the file is missing or unparseable —
+/// usage info is an enrichment, not a correctness requirement.
+pub(crate) fn parse_usage_totals(ui_messages_path: &Path) -> UsageTotals {
+    sum_entries(&parse_usage_entries(ui_messages_path))
+}
+
+/// Sum per-request entries into task-level totals.
+fn sum_entries(entries: &[ApiRequestUsageEntry]) -> UsageTotals {
+    let mut totals = UsageTotals::default();
+    for entry in entries {
+        totals.api_request_count += 1;
+        totals.tokens_in += entry.tokens_in.unwrap_or(0);
+        totals.tokens_out += entry.tokens_out.unwrap_or(0);
+        totals.cache_writes += entry.cache_writes.unwrap_or(0);
+        totals.cache_reads += entry.cache_reads.unwrap_or(0);
+        totals.total_cost += entry.cost.unwrap_or(0.0);
+    }
+    totals
+}
+
+/// Parse all per-request usage entries from ui_messages.json (in order).
+pub(crate) fn parse_usage_entries(ui_messages_path: &Path) -> Vec<ApiRequestUsageEntry> {
+    let content = match std::fs::read_to_string(ui_messages_path) {
+        Ok(c) => c,
+        Err(_) => return vec![],
+    };
+
+    let messages: Vec<RawUiMessage> = match serde_json::from_str(&content) {
+        Ok(m) => m,
+        Err(e) => {
+            log::warn!("Failed to parse ui_messages {:?}: {}", ui_messages_path, e);
+            return vec![];
+        }
+    };
+
+    let mut entries = Vec::new();
+
+    for msg in &messages {
+        if msg.say.as_deref() != Some("api_req_started") {
+            continue;
+        }
+        let text = match &msg.text {
+            Some(t) => t,
+            None => continue,
+        };
+        // The text payload is JSON. Entries without token info (request not yet
+        // completed when the log was written) still count as a request.
+        let info: RawApiReqInfo = match serde_json::from_str(text) {
+            Ok(i) => i,
+            Err(e) => {
+                log::debug!("Skipping unparseable api_req_started payload: {}", e);
+                continue;
+            }
+        };
+
+        entries.push(ApiRequestUsageEntry {
+            request_index: entries.len(),
+            timestamp: epoch_ms_to_iso(msg.ts),
+            tokens_in: info.tokens_in,
+            tokens_out: info.tokens_out,
+            cache_writes: info.cache_writes,
+            cache_reads: info.cache_reads,
+            cost: info.cost,
+        });
+    }
+
+    entries
+}
+
+/// Parse a task's context growth series for the `/context-growth` endpoint.
+///
+/// Each `api_req_started` entry becomes one point; its input context size is
+/// tokensIn + cacheWrites + cacheReads (everything the model read that turn).
+/// The `conversationHistoryIndex` on the entry locates the point in the
+/// conversation for charting against the message axis.
+///
+/// Returns None if the task directory doesn't exist. A task with no recorded
+/// API requests yields an empty series rather than a 404.
+pub fn parse_context_growth(task_id: &str) -> Option<ContextGrowthResponse> {
+    let root = tasks_root()?;
+    let dir = root.join(task_id);
+
+    if !dir.is_dir() {
+        log::warn!("Task directory not found: {:?}", dir);
+        return None;
+    }
+
+    let ui_messages_path = dir.join("ui_messages.json");
+    let content = std::fs::read_to_string(&ui_messages_path).unwrap_or_default();
+    let messages: Vec<RawUiMessage> = serde_json::from_str(&content).unwrap_or_default();
+
+    let mut points: Vec<ContextGrowthPoint> = Vec::new();
+    let mut cumulative_tokens_in: u64 = 0;
+
+    for msg in &messages {
+        if msg.say.as_deref() != Some("api_req_started") {
+            continue;
+        }
+        let text = match &msg.text {
+            Some(t) => t,
+            None => continue,
+        };
+        let info: RawApiReqInfo = match serde_json::from_str(text) {
+            Ok(i) => i,
+            Err(e) => {
+                log::debug!("Skipping unparseable api_req_started payload: {}", e);
+                continue;
+            }
+        };
+
+        let tokens_in = info.tokens_in.unwrap_or(0);
+        let cache_writes = info.cache_writes.unwrap_or(0);
+        let cache_reads = info.cache_reads.unwrap_or(0);
+        cumulative_tokens_in += tokens_in;
+
+        points.push(ContextGrowthPoint {
+            request_index: points.len(),
+            message_index: msg.conversation_history_index,
+            timestamp: epoch_ms_to_iso(msg.ts),
+            context_tokens: tokens_in + cache_writes + cache_reads,
+            tokens_in,
+            cache_writes,
+            cache_reads,
+            cumulative_tokens_in,
+        });
+    }
+
+    let peak_context_tokens = points.iter().map(|p| p.context_tokens).max().unwrap_or(0);
+    let final_context_tokens = points.last().map(|p| p.context_tokens).unwrap_or(0);
+
+    Some(ContextGrowthResponse {
+        task_id: task_id.to_string(),
+        api_request_count: points.len(),
+        peak_context_tokens,
+        final_context_tokens,
+        points,
+    })
+}
+
+/// Parse a task's full usage report for the `/usage` endpoint.
+///
+/// Returns None if the task directory doesn't exist. A task with no
+/// ui_messages.json yields an empty (all-zero) report rather than a 404 —
+/// the task exists, it just has no recorded API requests.
+pub fn parse_task_usage(task_id: &str) -> Option<TaskUsageResponse> {
+    let root = tasks_root()?;
+    let dir = root.join(task_id);
+
+    if !dir.is_dir() {
+        log::warn!("Task directory not found: {:?}", dir);
+        return None;
+    }
+
+    let ui_messages_path = dir.join("ui_messages.json");
+    let requests = parse_usage_entries(&ui_messages_path);
+    let totals = sum_entries(&requests);
+
+    Some(TaskUsageResponse {
+        task_id: task_id.to_string(),
+        api_request_count: totals.api_request_count,
+        total_tokens_in: totals.tokens_in,
+        total_tokens_out: totals.tokens_out,
+        total_cache_writes: totals.cache_writes,
+        total_cache_reads: totals.cache_reads,
+        total_cost: totals.total_cost,
+        requests,
+    })
+}
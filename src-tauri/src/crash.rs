@@ -0,0 +1,183 @@
+//! Panic capture and crash reports.
+//!
+//! [`install_panic_hook`] chains a hook onto the default panic handler that
+//! writes a crash report — panic message and location, backtrace, the tail
+//! of the current session log, app version and OS info — to
+//! `<config dir>/crashes/`. The `collect_crash_reports` Tauri command zips
+//! recent reports into one archive for attaching to a bug filing.
+
+use crate::config::get_config_dir;
+use serde::Serialize;
+use std::io::Write;
+use std::path::PathBuf;
+
+const CRASHES_DIR: &str = "crashes";
+/// Log lines included at the end of each report.
+const LOG_TAIL_LINES: usize = 200;
+/// Oldest reports are pruned past this count.
+const MAX_REPORTS: usize = 20;
+
+/// Response for the `collect_crash_reports` Tauri command.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReportArchive {
+    /// Absolute path of the zip archive
+    pub path: String,
+    /// Number of crash reports included
+    pub report_count: usize,
+}
+
+fn crashes_dir() -> PathBuf {
+    get_config_dir().join(CRASHES_DIR)
+}
+
+/// Install a panic hook that writes a crash report before the default
+/// handler runs. Call once, right after logging is initialized.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        // Never let report writing turn one crash into two — ignore errors
+        if let Err(e) = write_report(info) {
+            eprintln!("Failed to write crash report: {}", e);
+        }
+        default_hook(info);
+    }));
+}
+
+/// Compose and persist one crash report, pruning old ones.
+fn write_report(info: &std::panic::PanicHookInfo<'_>) -> Result<(), String> {
+    let dir = crashes_dir();
+    std::fs::create_dir_all(&dir).map_err(|e| format!("create {:?}: {}", dir, e))?;
+
+    let message = if let Some(s) = info.payload().downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = info.payload().downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "<non-string panic payload>".to_string()
+    };
+    let location = info
+        .location()
+        .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+        .unwrap_or_else(|| "<unknown>".to_string());
+    let backtrace = std::backtrace::Backtrace::force_capture();
+
+    let mut report = String::new();
+    report.push_str(&format!(
+        "Cline X-Ray crash report\n\
+         ========================\n\
+         time:     {}\n\
+         version:  {}\n\
+         os:       {} ({})\n\
+         thread:   {}\n\
+         location: {}\n\
+         message:  {}\n\n\
+         Backtrace\n---------\n{}\n",
+        chrono::Local::now().to_rfc3339(),
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        std::thread::current().name().unwrap_or("<unnamed>"),
+        location,
+        message,
+        backtrace,
+    ));
+    report.push_str(&format!("\nLast {} log lines\n-----------------\n", LOG_TAIL_LINES));
+    report.push_str(&session_log_tail());
+
+    let stamp = chrono::Local::now().format("%Y%m%d_%H%M%S%.3f");
+    let path = dir.join(format!("crash_{}.txt", stamp));
+    std::fs::write(&path, report).map_err(|e| format!("write {:?}: {}", path, e))?;
+    eprintln!("Crash report written to {:?}", path);
+
+    prune_old_reports(&dir);
+    Ok(())
+}
+
+/// Tail of the active backend log file.
+fn session_log_tail() -> String {
+    let log_path = crate::config::get_log_file_path();
+    match std::fs::read_to_string(&log_path) {
+        Ok(content) => {
+            let lines: Vec<&str> = content.lines().collect();
+            let start = lines.len().saturating_sub(LOG_TAIL_LINES);
+            let mut tail = lines[start..].join("\n");
+            tail.push('\n');
+            tail
+        }
+        Err(e) => format!("<failed to read {:?}: {}>\n", log_path, e),
+    }
+}
+
+/// Keep only the newest [`MAX_REPORTS`] crash files.
+fn prune_old_reports(dir: &PathBuf) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut reports: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("crash_") && n.ends_with(".txt"))
+        })
+        .collect();
+    reports.sort();
+    while reports.len() > MAX_REPORTS {
+        let oldest = reports.remove(0);
+        let _ = std::fs::remove_file(oldest);
+    }
+}
+
+/// Zip all crash reports into `<config dir>/crashes/crash_reports_<ts>.zip`
+/// and return the archive path, for attaching to a bug report.
+pub fn collect_reports() -> Result<CrashReportArchive, String> {
+    let dir = crashes_dir();
+    let mut reports: Vec<PathBuf> = std::fs::read_dir(&dir)
+        .map_err(|_| "No crash reports found".to_string())?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with("crash_") && n.ends_with(".txt"))
+        })
+        .collect();
+    reports.sort();
+    if reports.is_empty() {
+        return Err("No crash reports found".to_string());
+    }
+
+    let stamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    let archive_path = dir.join(format!("crash_reports_{}.zip", stamp));
+    let file = std::fs::File::create(&archive_path)
+        .map_err(|e| format!("Failed to create {:?}: {}", archive_path, e))?;
+    let mut writer = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+
+    let report_count = reports.len();
+    for report in reports {
+        let name = report
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let content = std::fs::read(&report)
+            .map_err(|e| format!("Failed to read {:?}: {}", report, e))?;
+        writer
+            .start_file(&name, options)
+            .map_err(|e| format!("Failed to add {} to archive: {}", name, e))?;
+        writer
+            .write_all(&content)
+            .map_err(|e| format!("Failed to write {} to archive: {}", name, e))?;
+    }
+    writer
+        .finish()
+        .map_err(|e| format!("Failed to finalize archive: {}", e))?;
+
+    Ok(CrashReportArchive {
+        path: archive_path.to_string_lossy().to_string(),
+        report_count,
+    })
+}
@@ -0,0 +1,199 @@
+//! Startup self-diagnostics.
+//!
+//! `GET /system/diagnostics` (and the `run_diagnostics` Tauri command) runs
+//! a battery of environment checks — git availability, Cline storage
+//! presence, config dir write permissions, REST listener state, API key
+//! configuration — and returns an actionable remediation hint for each
+//! failed check, so "nothing works" reports come with the reason attached.
+
+use crate::state::AppState;
+use axum::{extract::State, Json};
+use serde::Serialize;
+use std::process::Command;
+use std::sync::Arc;
+
+/// Outcome of one diagnostic check.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticCheck {
+    /// Stable check identifier (e.g. "git", "cline_storage")
+    pub id: String,
+    /// Human-readable check name
+    pub name: String,
+    pub ok: bool,
+    /// What was found: version string, path, error detail
+    pub detail: String,
+    /// How to fix it — only present when the check failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+}
+
+/// Response for the diagnostics endpoint.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DiagnosticsResponse {
+    /// True when every check passed
+    pub ok: bool,
+    pub checks: Vec<DiagnosticCheck>,
+    /// ISO 8601 timestamp the diagnostics ran
+    pub generated_at: String,
+}
+
+fn check(id: &str, name: &str, result: Result<String, (String, String)>) -> DiagnosticCheck {
+    match result {
+        Ok(detail) => DiagnosticCheck {
+            id: id.to_string(),
+            name: name.to_string(),
+            ok: true,
+            detail,
+            remediation: None,
+        },
+        Err((detail, remediation)) => DiagnosticCheck {
+            id: id.to_string(),
+            name: name.to_string(),
+            ok: false,
+            detail,
+            remediation: Some(remediation),
+        },
+    }
+}
+
+fn check_git() -> Result<String, (String, String)> {
+    match Command::new("git").arg("--version").output() {
+        Ok(output) if output.status.success() => {
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        }
+        Ok(output) => Err((
+            format!("git --version exited with {}", output.status),
+            "Reinstall git — a broken installation breaks checkpoint diff parsing".to_string(),
+        )),
+        Err(e) => Err((
+            format!("git not found: {}", e),
+            "Install git and make sure it is on PATH; checkpoint diffs and workspace maintenance need the git CLI".to_string(),
+        )),
+    }
+}
+
+fn check_cline_storage() -> Result<String, (String, String)> {
+    match crate::conversation_history::root::tasks_root() {
+        Some(root) if root.is_dir() => {
+            let task_count = std::fs::read_dir(&root).map(|e| e.count()).unwrap_or(0);
+            Ok(format!("{} ({} task dirs)", root.display(), task_count))
+        }
+        Some(root) => Err((
+            format!("{} does not exist", root.display()),
+            "Run at least one Cline task in VS Code so the tasks directory is created".to_string(),
+        )),
+        None => Err((
+            "Cline tasks directory could not be resolved".to_string(),
+            "Install the Cline VS Code extension and run a task; history features need its globalStorage directory".to_string(),
+        )),
+    }
+}
+
+fn check_config_dir_writable() -> Result<String, (String, String)> {
+    let dir = crate::config::get_config_dir();
+    let probe = dir.join(".write_probe");
+    match std::fs::write(&probe, b"probe") {
+        Ok(()) => {
+            let _ = std::fs::remove_file(&probe);
+            Ok(format!("{} is writable", dir.display()))
+        }
+        Err(e) => Err((
+            format!("cannot write to {}: {}", dir.display(), e),
+            "Fix permissions on the config directory — settings, caches and backups are stored there".to_string(),
+        )),
+    }
+}
+
+fn check_rest_listener(state: &AppState) -> Result<String, (String, String)> {
+    if let Some(base_url) = state.api_base_url.read().clone() {
+        return Ok(format!("listening at {}", base_url));
+    }
+    // Server not up — distinguish "failed to start" from "cannot bind at all"
+    match std::net::TcpListener::bind(("127.0.0.1", 0)) {
+        Ok(_) => Err((
+            "REST server is not running (loopback binding works)".to_string(),
+            "Check the logs for the startup error; if [server] port is set in config.toml, another process may be using it".to_string(),
+        )),
+        Err(e) => Err((
+            format!("cannot bind a loopback port: {}", e),
+            "A firewall or security product is blocking local listeners on 127.0.0.1".to_string(),
+        )),
+    }
+}
+
+fn check_gemini_key(state: &AppState) -> Result<String, (String, String)> {
+    let gemini_api_key = state.gemini_api_key();
+    if gemini_api_key.is_empty() || gemini_api_key == "YOUR_GEMINI_API_KEY_HERE" {
+        Err((
+            "no Gemini API key configured".to_string(),
+            "Set GEMINI_API_KEY in the environment, gemini_api_key under [providers] in config.toml, or rotate one in via PUT /system/providers; chat and summarization need it".to_string(),
+        ))
+    } else {
+        Ok("Gemini API key configured".to_string())
+    }
+}
+
+fn check_jira_credentials(state: &AppState) -> Result<String, (String, String)> {
+    if state.jira_base_url.is_empty() || state.jira_email.is_empty() || state.jira_api_token.is_empty() {
+        Err((
+            "Jira credentials incomplete".to_string(),
+            "Configure the Jira base URL, email and API token in Settings; issue endpoints return errors without them".to_string(),
+        ))
+    } else {
+        Ok(format!("Jira configured for {}", state.jira_base_url))
+    }
+}
+
+/// Run every diagnostic check. Blocking (spawns `git`, touches the
+/// filesystem) — call from `spawn_blocking` in async contexts.
+pub fn run_diagnostics(state: &AppState) -> DiagnosticsResponse {
+    let checks = vec![
+        check("git", "Git CLI", check_git()),
+        check("cline_storage", "Cline task storage", check_cline_storage()),
+        check("config_dir", "Config directory writable", check_config_dir_writable()),
+        check("rest_listener", "REST listener", check_rest_listener(state)),
+        check("gemini_key", "Gemini API key", check_gemini_key(state)),
+        check("jira_credentials", "Jira credentials", check_jira_credentials(state)),
+    ];
+    DiagnosticsResponse {
+        ok: checks.iter().all(|c| c.ok),
+        checks,
+        generated_at: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+/// Run startup self-diagnostics
+///
+/// Checks git availability, Cline storage presence, config dir write
+/// permissions, the REST listener and API key configuration. Each failed
+/// check carries a remediation hint.
+#[utoipa::path(
+    get,
+    path = "/system/diagnostics",
+    responses(
+        (status = 200, description = "Diagnostic check results", body = DiagnosticsResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "system"
+)]
+pub async fn system_diagnostics_handler(
+    State(state): State<Arc<AppState>>,
+) -> Json<DiagnosticsResponse> {
+    let result = tokio::task::spawn_blocking(move || run_diagnostics(&state)).await;
+    match result {
+        Ok(response) => Json(response),
+        Err(e) => Json(DiagnosticsResponse {
+            ok: false,
+            checks: vec![DiagnosticCheck {
+                id: "diagnostics".to_string(),
+                name: "Diagnostics runner".to_string(),
+                ok: false,
+                detail: format!("diagnostics task panicked: {}", e),
+                remediation: Some("Check the logs and report this as a bug".to_string()),
+            }],
+            generated_at: chrono::Utc::now().to_rfc3339(),
+        }),
+    }
+}
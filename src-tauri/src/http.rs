@@ -0,0 +1,102 @@
+//! Shared outbound HTTP client with proxy and corporate-CA support.
+//!
+//! Every non-Jira outbound call (Gemini, webhooks, model listing) goes
+//! through [`outbound_client`], which applies the `[network]` config:
+//! proxy URL, an extra PEM CA bundle for TLS-intercepting proxies, and an
+//! explicit insecure escape hatch. The client is pooled and rebuilt when a
+//! hot-reloaded config changes any of those settings. The Jira client
+//! (`jira::pooled_client`) keeps its own pool but shares the TLS setup via
+//! [`apply_network_tls`].
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use reqwest::Client;
+
+/// `[network]` settings the pooled client was built with.
+#[derive(Debug, Clone, PartialEq)]
+struct NetworkSettings {
+    proxy: String,
+    ca_bundle_path: String,
+    insecure: bool,
+}
+
+impl NetworkSettings {
+    fn from_config() -> Self {
+        let network = crate::config::current().network;
+        Self {
+            proxy: network.proxy,
+            ca_bundle_path: network.ca_bundle_path,
+            insecure: network.insecure,
+        }
+    }
+
+    fn build_client(&self) -> Client {
+        let mut builder = Client::builder();
+        if !self.proxy.is_empty() {
+            match reqwest::Proxy::all(&self.proxy) {
+                Ok(proxy) => builder = builder.proxy(proxy),
+                Err(e) => log::error!(
+                    "Invalid [network] proxy '{}': {} — connecting directly",
+                    self.proxy, e
+                ),
+            }
+        }
+        builder = apply_network_tls(builder);
+        builder.build().unwrap_or_else(|e| {
+            log::error!("Failed to build outbound HTTP client ({}), using defaults", e);
+            Client::new()
+        })
+    }
+}
+
+/// Apply the `[network]` TLS settings (CA bundle, insecure flag) to any
+/// reqwest builder. Used by the shared client below and the Jira pool.
+pub fn apply_network_tls(mut builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+    let network = crate::config::current().network;
+    if !network.ca_bundle_path.is_empty() {
+        match std::fs::read(&network.ca_bundle_path) {
+            Ok(pem) => match reqwest::Certificate::from_pem_bundle(&pem) {
+                Ok(certs) => {
+                    for cert in certs {
+                        builder = builder.add_root_certificate(cert);
+                    }
+                }
+                Err(e) => log::error!(
+                    "Failed to parse CA bundle {}: {}",
+                    network.ca_bundle_path, e
+                ),
+            },
+            Err(e) => log::error!(
+                "Failed to read CA bundle {}: {}",
+                network.ca_bundle_path, e
+            ),
+        }
+    }
+    if network.insecure {
+        log::warn!("[network] insecure = true — TLS certificate verification is DISABLED");
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+    builder
+}
+
+static POOLED: Lazy<RwLock<(NetworkSettings, Client)>> = Lazy::new(|| {
+    let settings = NetworkSettings::from_config();
+    let client = settings.build_client();
+    RwLock::new((settings, client))
+});
+
+/// Get the shared outbound client, rebuilding it if the `[network]`
+/// settings changed.
+pub fn outbound_client() -> Client {
+    let settings = NetworkSettings::from_config();
+    {
+        let cached = POOLED.read();
+        if cached.0 == settings {
+            return cached.1.clone();
+        }
+    }
+    log::info!("[network] settings changed — rebuilding outbound HTTP client");
+    let client = settings.build_client();
+    *POOLED.write() = (settings, client.clone());
+    client
+}
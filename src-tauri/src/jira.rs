@@ -1,7 +1,11 @@
 use base64::{engine::general_purpose::STANDARD, Engine};
 use log::{debug, error, info};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 // ============ Public Models ============
 
@@ -141,6 +145,81 @@ struct JiraComponent {
     name: String,
 }
 
+// ============ Pooled HTTP Client ============
+
+/// HTTP settings the pooled client was built with. The client is rebuilt
+/// when a hot-reloaded `[jira]` or `[network]` config changes any of them.
+#[derive(Debug, Clone, PartialEq)]
+struct HttpSettings {
+    connect_timeout_secs: u64,
+    request_timeout_secs: u64,
+    proxy: String,
+    ca_bundle_path: String,
+    insecure: bool,
+}
+
+impl HttpSettings {
+    fn from_config() -> Self {
+        let config = crate::config::current();
+        // [jira] proxy wins when set; otherwise the shared [network] one
+        let proxy = if config.jira.proxy.is_empty() {
+            config.network.proxy.clone()
+        } else {
+            config.jira.proxy.clone()
+        };
+        Self {
+            connect_timeout_secs: config.jira.connect_timeout_secs.max(1),
+            request_timeout_secs: config.jira.request_timeout_secs.max(1),
+            proxy,
+            ca_bundle_path: config.network.ca_bundle_path.clone(),
+            insecure: config.network.insecure,
+        }
+    }
+
+    fn build_client(&self) -> Client {
+        let mut builder = Client::builder()
+            .connect_timeout(Duration::from_secs(self.connect_timeout_secs))
+            .timeout(Duration::from_secs(self.request_timeout_secs));
+        if !self.proxy.is_empty() {
+            match reqwest::Proxy::all(&self.proxy) {
+                Ok(proxy) => builder = builder.proxy(proxy),
+                Err(e) => error!(
+                    "Invalid [jira] proxy '{}': {} — connecting directly",
+                    self.proxy, e
+                ),
+            }
+        }
+        builder = crate::http::apply_network_tls(builder);
+        builder.build().unwrap_or_else(|e| {
+            error!("Failed to build pooled Jira client ({}), using defaults", e);
+            Client::new()
+        })
+    }
+}
+
+/// One shared client so every call path reuses the same connection pool
+/// instead of opening fresh connections per request.
+static POOLED: Lazy<RwLock<(HttpSettings, Client)>> = Lazy::new(|| {
+    let settings = HttpSettings::from_config();
+    let client = settings.build_client();
+    RwLock::new((settings, client))
+});
+
+/// Get the pooled client, rebuilding it if the `[jira]` HTTP settings changed.
+fn pooled_client() -> Client {
+    let settings = HttpSettings::from_config();
+    {
+        let cached = POOLED.read();
+        if cached.0 == settings {
+            return cached.1.clone();
+        }
+    }
+    info!("[jira] HTTP settings changed — rebuilding pooled client");
+    let client = settings.build_client();
+    *POOLED.write() = (settings, client.clone());
+    client
+}
+
 // ============ Jira Client ============
 
 pub struct JiraClient {
@@ -148,6 +227,8 @@ pub struct JiraClient {
     email: String,
     api_token: String,
     client: Client,
+    /// When attached, per-call latency lands in the REST access log
+    access_log: Option<Arc<crate::state::AppState>>,
 }
 
 impl JiraClient {
@@ -157,10 +238,19 @@ impl JiraClient {
             base_url,
             email,
             api_token,
-            client: Client::new(),
+            client: pooled_client(),
+            access_log: None,
         }
     }
 
+    /// Record per-call latency in the given state's access log (entries show
+    /// up with a `jira:` path prefix so they are distinguishable from
+    /// inbound REST traffic)
+    pub fn with_access_log(mut self, state: Arc<crate::state::AppState>) -> Self {
+        self.access_log = Some(state);
+        self
+    }
+
     /// Get the Basic Auth header value
     fn get_auth_header(&self) -> String {
         let credentials = format!("{}:{}", self.email, self.api_token);
@@ -168,26 +258,52 @@ impl JiraClient {
         format!("Basic {}", encoded)
     }
 
+    /// Log one upstream call's latency (status 0 = transport error).
+    fn record_latency(&self, path: &str, status_code: u16, started: Instant) {
+        let duration_ms = started.elapsed().as_millis() as u64;
+        info!("Jira call {} -> {} in {}ms", path, status_code, duration_ms);
+        if let Some(state) = &self.access_log {
+            state.add_access_log(
+                "GET".to_string(),
+                format!("jira:{}", path),
+                status_code,
+                duration_ms,
+                "upstream".to_string(),
+                None,
+                None,
+            );
+        }
+    }
+
     /// Get the current authenticated user
     pub async fn get_current_user(&self) -> Result<JiraCurrentUser, String> {
         info!("=== get_current_user: Fetching current user ===");
         let url = format!("{}/rest/api/3/myself", self.base_url);
         debug!("Request URL: {}", url);
 
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", self.get_auth_header())
-            .header("Accept", "application/json")
-            .send()
-            .await
-            .map_err(|e| {
+        let started = Instant::now();
+        let policy = crate::api::retry::RetryPolicy::from_config();
+        let outcome = crate::api::retry::send_with_retry(
+            || {
+                self.client
+                    .get(&url)
+                    .header("Authorization", self.get_auth_header())
+                    .header("Accept", "application/json")
+            },
+            &policy,
+        )
+        .await;
+        let response = match outcome.result {
+            Ok(response) => response,
+            Err(e) => {
+                self.record_latency("/rest/api/3/myself", 0, started);
                 error!("HTTP request failed: {}", e);
-                debug!("Request error details: {:?}", e);
-                format!("Request failed: {}", e)
-            })?;
+                return Err(format!("Request failed: {}", e));
+            }
+        };
 
         let status = response.status();
+        self.record_latency("/rest/api/3/myself", status.as_u16(), started);
         info!("Response status: {}", status);
 
         if !status.is_success() {
@@ -229,26 +345,36 @@ impl JiraClient {
         debug!("Request URL: {}", url);
         debug!("Request params: maxResults={}, fields=key,summary,status,updated,assignee,priority,issuetype", max_results);
 
-        let response = self
-            .client
-            .get(&url)
-            .query(&[
-                ("jql", jql),
-                ("maxResults", &max_results.to_string()),
-                ("fields", "key,summary,status,updated,assignee,priority,issuetype"),
-            ])
-            .header("Authorization", self.get_auth_header())
-            .header("Accept", "application/json")
-            .header("Content-Type", "application/json")
-            .send()
-            .await
-            .map_err(|e| {
+        let started = Instant::now();
+        let policy = crate::api::retry::RetryPolicy::from_config();
+        let max_results_str = max_results.to_string();
+        let outcome = crate::api::retry::send_with_retry(
+            || {
+                self.client
+                    .get(&url)
+                    .query(&[
+                        ("jql", jql),
+                        ("maxResults", &max_results_str),
+                        ("fields", "key,summary,status,updated,assignee,priority,issuetype"),
+                    ])
+                    .header("Authorization", self.get_auth_header())
+                    .header("Accept", "application/json")
+                    .header("Content-Type", "application/json")
+            },
+            &policy,
+        )
+        .await;
+        let response = match outcome.result {
+            Ok(response) => response,
+            Err(e) => {
+                self.record_latency("/rest/api/3/search/jql", 0, started);
                 error!("HTTP request failed: {}", e);
-                debug!("Request error details: {:?}", e);
-                format!("Request failed: {}", e)
-            })?;
+                return Err(format!("Request failed: {}", e));
+            }
+        };
 
         let status = response.status();
+        self.record_latency("/rest/api/3/search/jql", status.as_u16(), started);
         info!("Response status: {}", status);
         
         // Get the response body as text first for better error reporting
@@ -319,20 +445,29 @@ impl JiraClient {
         let url = format!("{}/rest/api/3/issue/{}", self.base_url, key);
         debug!("Request URL: {}", url);
 
-        let response = self
-            .client
-            .get(&url)
-            .header("Authorization", self.get_auth_header())
-            .header("Accept", "application/json")
-            .send()
-            .await
-            .map_err(|e| {
+        let started = Instant::now();
+        let policy = crate::api::retry::RetryPolicy::from_config();
+        let outcome = crate::api::retry::send_with_retry(
+            || {
+                self.client
+                    .get(&url)
+                    .header("Authorization", self.get_auth_header())
+                    .header("Accept", "application/json")
+            },
+            &policy,
+        )
+        .await;
+        let response = match outcome.result {
+            Ok(response) => response,
+            Err(e) => {
+                self.record_latency("/rest/api/3/issue", 0, started);
                 error!("HTTP request failed for issue {}: {}", key, e);
-                debug!("Request error details: {:?}", e);
-                format!("Request failed: {}", e)
-            })?;
+                return Err(format!("Request failed: {}", e));
+            }
+        };
 
         let status = response.status();
+        self.record_latency("/rest/api/3/issue", status.as_u16(), started);
         info!("Response status: {}", status);
 
         if !status.is_success() {
@@ -9,7 +9,13 @@ use axum::Json;
 use std::sync::Arc;
 
 use crate::state::AppState;
-use super::types::{LatestErrorResponse, LatestQuery, LatestResponse, SubtaskSummaryItem};
+use super::types::{
+    LatestErrorResponse, LatestFileItem, LatestQuery, LatestResponse, RecentItem, RecentQuery,
+    RecentResponse, SubtaskSummaryItem,
+};
+
+/// Default total byte budget for `?include_files=changed` embedded contents.
+const DEFAULT_MAX_FILE_BYTES: usize = 262_144;
 
 /// Get the latest task/subtask prompt merged with its diff and changed files
 ///
@@ -23,12 +29,16 @@ use super::types::{LatestErrorResponse, LatestQuery, LatestResponse, SubtaskSumm
 /// - `scope=subtask` (default): Returns only the latest subtask's diff and prompt
 /// - `scope=task`: Returns the full task diff (all subtasks merged) with the latest prompt
 /// - `exclude`: Pathspec exclusion patterns (e.g. `?exclude=node_modules&exclude=target`)
+/// - `subtasks=N`: Also populate diffs on the last N entries of the `subtasks` array
+/// - `workspace`: Pin a specific checkpoint workspace instead of auto-resolving
+/// - `include_files=changed` + `max_bytes`: Embed full contents of changed files
 #[utoipa::path(
     get,
     path = "/latest",
     params(LatestQuery),
     responses(
         (status = 200, description = "Latest task/subtask prompt + diff + changed files", body = LatestResponse),
+        (status = 400, description = "Invalid query parameter", body = LatestErrorResponse),
         (status = 404, description = "No tasks found or no checkpoint data", body = LatestErrorResponse),
         (status = 500, description = "Internal server error", body = LatestErrorResponse)
     ),
@@ -41,15 +51,44 @@ pub async fn get_latest_handler(
 ) -> Result<Json<LatestResponse>, (StatusCode, Json<LatestErrorResponse>)> {
     let scope = params.scope.clone();
     let excludes = params.exclude.clone();
+    let subtask_depth = params.subtasks.unwrap_or(0);
+    let pinned_workspace = params
+        .workspace
+        .clone()
+        .filter(|w| !w.trim().is_empty());
+    let include_files = params.include_files.clone().filter(|m| !m.is_empty());
+    let max_bytes = params.max_bytes.unwrap_or(DEFAULT_MAX_FILE_BYTES);
+
+    if let Some(ref mode) = include_files {
+        if mode != "changed" {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(LatestErrorResponse {
+                    error: format!(
+                        "Invalid include_files '{}' — expected 'changed'",
+                        mode
+                    ),
+                    code: 400,
+                }),
+            ));
+        }
+    }
 
     log::info!(
-        "REST API: GET /latest — scope={}, excludes={:?}",
-        scope, excludes
+        "REST API: GET /latest — scope={}, excludes={:?}, subtasks={}, workspace={:?}, include_files={:?}",
+        scope, excludes, subtask_depth, pinned_workspace, include_files
     );
 
     // Run the entire orchestration in a blocking context (filesystem + git CLI)
     let result = tokio::task::spawn_blocking(move || {
-        resolve_latest(&scope, &excludes)
+        resolve_latest(
+            &scope,
+            &excludes,
+            subtask_depth,
+            pinned_workspace.as_deref(),
+            include_files.is_some(),
+            max_bytes,
+        )
     })
     .await;
 
@@ -96,6 +135,79 @@ pub async fn get_latest_handler(
     }
 }
 
+/// Get the last N resolved task composites (prompt + diff summary + files)
+///
+/// A catch-up feed across tasks: for each of the most recent N tasks, returns
+/// its latest prompt plus the task diff's file list and line totals (no patch
+/// text). Tasks whose checkpoint data is missing still appear, with
+/// `noDiffReason` set and an empty file list.
+#[utoipa::path(
+    get,
+    path = "/latest/recent",
+    params(RecentQuery),
+    responses(
+        (status = 200, description = "Recent task composites, newest first", body = RecentResponse),
+        (status = 404, description = "No tasks found", body = LatestErrorResponse),
+        (status = 500, description = "Internal server error", body = LatestErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["changes", "history", "tool"]
+)]
+pub async fn latest_recent_handler(
+    State(_state): State<Arc<AppState>>,
+    Query(params): Query<RecentQuery>,
+) -> Result<Json<RecentResponse>, (StatusCode, Json<LatestErrorResponse>)> {
+    let limit = params.limit.unwrap_or(5).clamp(1, 50);
+    let excludes = params.exclude.clone();
+
+    log::info!(
+        "REST API: GET /latest/recent — limit={}, excludes={:?}",
+        limit, excludes
+    );
+
+    let result = tokio::task::spawn_blocking(move || resolve_recent(limit, &excludes)).await;
+
+    match result {
+        Ok(Ok(response)) => {
+            log::info!(
+                "REST API: GET /latest/recent — {} of {} tasks resolved",
+                response.count, response.total_tasks
+            );
+            Ok(Json(response))
+        }
+        Ok(Err(LatestError::NotFound(msg))) => {
+            log::warn!("REST API: GET /latest/recent — 404: {}", msg);
+            Err((
+                StatusCode::NOT_FOUND,
+                Json(LatestErrorResponse {
+                    error: msg,
+                    code: 404,
+                }),
+            ))
+        }
+        Ok(Err(LatestError::Internal(msg))) => {
+            log::error!("REST API: GET /latest/recent — 500: {}", msg);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(LatestErrorResponse {
+                    error: msg,
+                    code: 500,
+                }),
+            ))
+        }
+        Err(e) => {
+            log::error!("REST API: GET /latest/recent — spawn_blocking failed: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(LatestErrorResponse {
+                    error: format!("Internal error: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
+
 // ============ Internal orchestration ============
 
 enum LatestError {
@@ -104,7 +216,14 @@ enum LatestError {
 }
 
 /// Synchronous orchestration: resolve the latest task/subtask + diff.
-fn resolve_latest(scope: &str, excludes: &[String]) -> Result<LatestResponse, LatestError> {
+fn resolve_latest(
+    scope: &str,
+    excludes: &[String],
+    subtask_depth: usize,
+    pinned_workspace: Option<&str>,
+    include_changed_files: bool,
+    max_bytes: usize,
+) -> Result<LatestResponse, LatestError> {
     // 1. Get the most recent task from conversation history
     let task_list = crate::conversation_history::summary::scan_all_tasks();
     let latest_task = task_list
@@ -172,7 +291,24 @@ fn resolve_latest(scope: &str, excludes: &[String]) -> Result<LatestResponse, La
         };
 
     // 4. Resolve workspace for this task (shadow git)
-    let workspace_result = crate::shadow_git::discovery::find_workspace_for_task(task_id);
+    let workspace_result = if let Some(ws_id) = pinned_workspace {
+        // Explicit ?workspace= pin — look it up directly instead of scanning commits
+        let found = crate::shadow_git::discovery::find_workspaces()
+            .into_iter()
+            .find(|ws| ws.id == ws_id)
+            .map(|ws| (ws.id.clone(), std::path::PathBuf::from(&ws.git_dir)));
+        if found.is_none() {
+            return Err(LatestError::NotFound(format!(
+                "Workspace '{}' not found",
+                ws_id
+            )));
+        }
+        found
+    } else {
+        crate::shadow_git::discovery::find_workspace_for_task(task_id)
+    };
+
+    let resolved_git_dir = workspace_result.as_ref().map(|(_, gd)| gd.clone());
 
     let (diff, no_diff_reason, workspace_id) = match workspace_result {
         Some((ws_id, git_dir)) => {
@@ -211,8 +347,9 @@ fn resolve_latest(scope: &str, excludes: &[String]) -> Result<LatestResponse, La
         }
     };
 
-    // 6. Build subtask summaries for UI tab rendering (diffs NOT included)
-    let subtasks_summary: Vec<SubtaskSummaryItem> = if let Some(ref subtasks) = subtasks_opt {
+    // 6. Build subtask summaries for UI tab rendering (diffs only on the last
+    //    N entries when ?subtasks=N was requested)
+    let mut subtasks_summary: Vec<SubtaskSummaryItem> = if let Some(ref subtasks) = subtasks_opt {
         subtasks.subtasks.iter().map(|s| SubtaskSummaryItem {
             subtask_index: s.subtask_index,
             is_initial_task: s.is_initial_task,
@@ -221,11 +358,42 @@ fn resolve_latest(scope: &str, excludes: &[String]) -> Result<LatestResponse, La
             message_count: s.message_count,
             tool_call_count: s.tool_call_count,
             tools_used: s.tools_used.clone(),
+            diff: None,
         }).collect()
     } else {
         vec![]
     };
 
+    // 7. Embed changed-file contents (?include_files=changed), within max_bytes
+    let files = if include_changed_files {
+        match (diff.as_ref(), resolved_git_dir.as_ref()) {
+            (Some(d), Some(git_dir)) => Some(embed_changed_files(d, git_dir, max_bytes)),
+            _ => None,
+        }
+    } else {
+        None
+    };
+
+    // 8. Populate diffs for the last N subtasks (?subtasks=N)
+    if subtask_depth > 0 {
+        if let (Some(ws_id), Some(git_dir)) = (workspace_id.as_ref(), resolved_git_dir.as_ref()) {
+            let start = subtasks_summary.len().saturating_sub(subtask_depth);
+            for item in subtasks_summary.iter_mut().skip(start) {
+                match crate::shadow_git::discovery::get_subtask_diff(
+                    task_id, item.subtask_index, ws_id, git_dir, excludes,
+                ) {
+                    Ok(d) => item.diff = Some(d),
+                    Err(e) => {
+                        log::warn!(
+                            "Subtask #{} diff failed for /latest: {}. Leaving diff null.",
+                            item.subtask_index, e
+                        );
+                    }
+                }
+            }
+        }
+    }
+
     Ok(LatestResponse {
         task_id: task_id.clone(),
         subtask_index,
@@ -244,6 +412,177 @@ fn resolve_latest(scope: &str, excludes: &[String]) -> Result<LatestResponse, La
         task_started_at,
         task_ended_at,
         scope: scope.to_string(),
+        files,
         subtasks: subtasks_summary,
     })
 }
+
+/// Fetch the full contents of a diff's changed files at `toRef`, spending at
+/// most `max_bytes` across all files. Files past the budget are included with
+/// `content: null` and an explanatory error; a file cut mid-way gets
+/// `truncated: true` with its original size preserved in `size`.
+fn embed_changed_files(
+    diff: &crate::shadow_git::types::DiffResult,
+    git_dir: &std::path::Path,
+    max_bytes: usize,
+) -> Vec<LatestFileItem> {
+    let paths: Vec<String> = diff
+        .files
+        .iter()
+        .filter(|f| f.status != "deleted" && !f.binary)
+        .map(|f| f.path.clone())
+        .collect();
+
+    let mut by_path: std::collections::HashMap<String, crate::shadow_git::types::FileContent> =
+        crate::shadow_git::discovery::get_file_contents(&git_dir.to_path_buf(), &diff.to_ref, &paths)
+            .into_iter()
+            .map(|fc| (fc.path.clone(), fc))
+            .collect();
+
+    let mut remaining = max_bytes;
+    let mut items = Vec::with_capacity(diff.files.len());
+
+    for f in &diff.files {
+        if f.status == "deleted" {
+            items.push(LatestFileItem {
+                path: f.path.clone(),
+                content: None,
+                size: None,
+                truncated: false,
+                error: Some("File was deleted — no content at toRef".to_string()),
+            });
+            continue;
+        }
+        if f.binary {
+            items.push(LatestFileItem {
+                path: f.path.clone(),
+                content: None,
+                size: None,
+                truncated: false,
+                error: Some("Binary file — contents omitted".to_string()),
+            });
+            continue;
+        }
+
+        let fetched = by_path.remove(&f.path);
+        let (content, size, error) = match fetched {
+            Some(fc) => (fc.content, fc.size, fc.error),
+            None => (None, None, Some("File content unavailable".to_string())),
+        };
+
+        match content {
+            Some(text) if text.len() <= remaining => {
+                remaining -= text.len();
+                items.push(LatestFileItem {
+                    path: f.path.clone(),
+                    content: Some(text),
+                    size,
+                    truncated: false,
+                    error: None,
+                });
+            }
+            Some(text) if remaining > 0 => {
+                // Cut at a char boundary so we never split a UTF-8 sequence
+                let mut cut = remaining;
+                while cut > 0 && !text.is_char_boundary(cut) {
+                    cut -= 1;
+                }
+                remaining = 0;
+                items.push(LatestFileItem {
+                    path: f.path.clone(),
+                    content: Some(text[..cut].to_string()),
+                    size,
+                    truncated: true,
+                    error: None,
+                });
+            }
+            Some(_) => {
+                items.push(LatestFileItem {
+                    path: f.path.clone(),
+                    content: None,
+                    size,
+                    truncated: false,
+                    error: Some("Omitted: max_bytes budget exhausted".to_string()),
+                });
+            }
+            None => {
+                items.push(LatestFileItem {
+                    path: f.path.clone(),
+                    content: None,
+                    size,
+                    truncated: false,
+                    error,
+                });
+            }
+        }
+    }
+
+    items
+}
+
+/// Resolve the last `limit` tasks into prompt + diff-summary composites.
+fn resolve_recent(limit: usize, excludes: &[String]) -> Result<RecentResponse, LatestError> {
+    let task_list = crate::conversation_history::summary::scan_all_tasks();
+    if task_list.tasks.is_empty() {
+        return Err(LatestError::NotFound("No Cline tasks found".to_string()));
+    }
+    let total_tasks = task_list.tasks.len();
+
+    let mut items = Vec::with_capacity(limit.min(total_tasks));
+    for task in task_list.tasks.iter().take(limit) {
+        let task_id = &task.task_id;
+
+        // Latest prompt: last subtask if ui_messages.json exists, else summary
+        let subtasks_opt = crate::conversation_history::subtasks::parse_task_subtasks(task_id);
+        let (prompt, prompt_timestamp, total_subtasks) = match subtasks_opt {
+            Some(ref st) if !st.subtasks.is_empty() => {
+                let last = st.subtasks.last().unwrap();
+                (last.prompt.clone(), last.timestamp.clone(), st.total_subtasks)
+            }
+            _ => (
+                task.task_prompt.clone().unwrap_or_default(),
+                task.started_at.clone(),
+                0,
+            ),
+        };
+
+        // Diff summary: file list + line totals, patch text dropped
+        let (workspace_id, files, no_diff_reason) =
+            match crate::shadow_git::discovery::find_workspace_for_task(task_id) {
+                Some((ws_id, git_dir)) => {
+                    match crate::shadow_git::discovery::get_task_diff(task_id, &git_dir, excludes) {
+                        Ok(diff) => (Some(ws_id), diff.files, None),
+                        Err(e) => {
+                            log::warn!("Recent: diff failed for task {}: {}", task_id, e);
+                            (Some(ws_id), vec![], Some(e))
+                        }
+                    }
+                }
+                None => (None, vec![], Some("no_checkpoint_workspace".to_string())),
+            };
+
+        let lines_added = files.iter().map(|f| f.lines_added).sum();
+        let lines_removed = files.iter().map(|f| f.lines_removed).sum();
+
+        items.push(RecentItem {
+            task_id: task_id.clone(),
+            prompt,
+            prompt_timestamp,
+            total_subtasks,
+            workspace_id,
+            files,
+            lines_added,
+            lines_removed,
+            no_diff_reason,
+            task_started_at: task.started_at.clone(),
+            task_ended_at: task.ended_at.clone(),
+        });
+    }
+
+    let count = items.len();
+    Ok(RecentResponse {
+        items,
+        count,
+        total_tasks,
+    })
+}
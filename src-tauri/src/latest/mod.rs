@@ -8,6 +8,8 @@
 
 pub mod types;
 pub mod handler;
+pub mod stream;
 
 pub use types::*;
-pub use handler::get_latest_handler;
+pub use handler::{get_latest_handler, latest_recent_handler};
+pub use stream::latest_stream_handler;
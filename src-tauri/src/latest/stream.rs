@@ -0,0 +1,361 @@
+//! SSE stream for /latest updates.
+//!
+//! `GET /latest/stream` pushes an event whenever a new subtask prompt lands
+//! in the Cline tasks directory or a new checkpoint commit appears in a
+//! shadow-git workspace, so external dashboards and bots get "Cline just
+//! finished something" notifications without polling the composite endpoint.
+//!
+//! There is no OS-level file watcher dependency — a background task polls
+//! cheap filesystem metadata (ui_messages.json mtime, git refs mtimes) and
+//! fans detected changes out over a broadcast channel to all subscribers.
+
+use axum::response::sse::{Event, KeepAlive, Sse};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll};
+use std::time::SystemTime;
+use tokio::sync::{broadcast, mpsc};
+
+/// Default poll interval for the filesystem watcher, in milliseconds.
+const DEFAULT_POLL_INTERVAL_MS: u64 = 2_000;
+
+/// Broadcast channel shared by the watcher (sender) and SSE handlers (receivers).
+static EVENTS: Lazy<broadcast::Sender<LatestStreamEvent>> =
+    Lazy::new(|| broadcast::channel(64).0);
+
+/// Pause flag for the watcher (system tray quick action). While paused the
+/// poll loop skips scanning; changes that happen meanwhile are detected on
+/// resume since the fingerprint baseline is kept.
+static WATCHER_PAUSED: AtomicBool = AtomicBool::new(false);
+
+/// Pause or resume the background filesystem watcher.
+pub fn set_watcher_paused(paused: bool) {
+    WATCHER_PAUSED.store(paused, Ordering::Relaxed);
+    log::info!(
+        "Latest stream watcher {}",
+        if paused { "paused" } else { "resumed" }
+    );
+}
+
+/// Whether the background filesystem watcher is currently paused.
+pub fn watcher_paused() -> bool {
+    WATCHER_PAUSED.load(Ordering::Relaxed)
+}
+
+/// A single /latest/stream event.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LatestStreamEvent {
+    /// Event kind: "subtask" (new prompt activity), "checkpoint" (new
+    /// commit) or "breaker" (circuit breaker transition)
+    pub kind: String,
+    /// Task ID the event relates to (subtask events)
+    pub task_id: Option<String>,
+    /// Workspace ID the event relates to (checkpoint events)
+    pub workspace_id: Option<String>,
+    /// Extra detail (breaker events: "<operation_id>: <old> -> <new>")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// ISO 8601 timestamp when the change was detected
+    pub timestamp: String,
+}
+
+/// Minimum failures of one tool within a task before a notification fires.
+const TOOL_FAILURE_NOTIFY_THRESHOLD: usize = 3;
+
+/// Failure counts already notified, keyed `task_id/tool_name` — repeated
+/// task activity only notifies when a tool accumulates *new* failures at
+/// or past the threshold.
+static NOTIFIED_FAILURES: Lazy<parking_lot::RwLock<HashMap<String, usize>>> =
+    Lazy::new(|| parking_lot::RwLock::new(HashMap::new()));
+
+/// Notification line for task activity: the task prompt plus edited file
+/// count when the task metadata is readable, else the generic fallback.
+fn task_activity_summary(task_id: &str) -> String {
+    let summary = crate::conversation_history::root::tasks_root().and_then(|root| {
+        crate::conversation_history::summary::parse_task_dir(task_id, &root.join(task_id))
+    });
+    match summary {
+        Some(task) => {
+            let mut prompt = task
+                .task_prompt
+                .unwrap_or_else(|| format!("task {}", task_id));
+            if prompt.chars().count() > 80 {
+                prompt = format!("{}…", prompt.chars().take(80).collect::<String>());
+            }
+            if task.files_edited > 0 {
+                format!(
+                    "Task finished: {} — {} file{} changed",
+                    prompt,
+                    task.files_edited,
+                    if task.files_edited == 1 { "" } else { "s" }
+                )
+            } else {
+                format!("Task finished: {}", prompt)
+            }
+        }
+        None => format!("Cline task {} has new activity", task_id),
+    }
+}
+
+/// Check a changed task for tools that keep failing and emit one
+/// `tool.failing` notification per new failure count past the threshold.
+fn notify_tool_failures(task_id: &str) {
+    let Some(timeline) = crate::conversation_history::tools::parse_task_tools(task_id, None, true)
+    else {
+        return;
+    };
+    let mut per_tool: HashMap<&str, usize> = HashMap::new();
+    for call in &timeline.tool_calls {
+        *per_tool.entry(call.tool_name.as_str()).or_default() += 1;
+    }
+    for (tool, count) in per_tool {
+        if count < TOOL_FAILURE_NOTIFY_THRESHOLD {
+            continue;
+        }
+        let key = format!("{}/{}", task_id, tool);
+        {
+            let mut notified = NOTIFIED_FAILURES.write();
+            if notified.get(&key).copied().unwrap_or(0) >= count {
+                continue;
+            }
+            notified.insert(key, count);
+        }
+        crate::notify::emit(
+            "tool.failing",
+            serde_json::json!({
+                "taskId": task_id,
+                "tool": tool,
+                "failureCount": count,
+                "summary": format!("{} failed {}× in task {}", tool, count, task_id),
+                "link": format!("xray://task/{}", task_id),
+            }),
+        );
+    }
+}
+
+/// Publish an event to all connected stream clients (no-op without
+/// listeners), forwarding task/checkpoint activity to the notify subsystem.
+pub fn publish(event: LatestStreamEvent) {
+    match event.kind.as_str() {
+        "subtask" => {
+            let task_id = event.task_id.as_deref().unwrap_or("?");
+            crate::notify::emit(
+                "task.finished",
+                serde_json::json!({
+                    "taskId": event.task_id,
+                    "summary": task_activity_summary(task_id),
+                    "link": format!("xray://task/{}", task_id),
+                }),
+            );
+            notify_tool_failures(task_id);
+        }
+        "checkpoint" => {
+            let workspace_id = event.workspace_id.as_deref().unwrap_or("?");
+            crate::notify::emit(
+                "checkpoint.created",
+                serde_json::json!({
+                    "workspaceId": event.workspace_id,
+                    "summary": format!("New checkpoint in workspace {}", workspace_id),
+                }),
+            );
+        }
+        // Breaker transitions notify from the circuit breaker itself, which
+        // knows the open/closed direction.
+        _ => {}
+    }
+    let _ = EVENTS.send(event);
+}
+
+/// Poll interval: `XRAY_LATEST_STREAM_POLL_MS` wins (backward compatibility),
+/// then `[cache] latest_stream_poll_ms` from config.toml.
+fn poll_interval_ms() -> u64 {
+    std::env::var("XRAY_LATEST_STREAM_POLL_MS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|ms| *ms > 0)
+        .unwrap_or_else(|| {
+            let configured = crate::config::current().cache.latest_stream_poll_ms;
+            if configured > 0 {
+                configured
+            } else {
+                DEFAULT_POLL_INTERVAL_MS
+            }
+        })
+}
+
+/// Spawn the background poller that feeds /latest/stream subscribers.
+///
+/// Called once from `start_rest_server`. The first scan only primes the
+/// fingerprint baseline — subscribers receive events for changes that happen
+/// after the server started, not a replay of historic activity.
+pub fn spawn_latest_watcher() {
+    let interval = poll_interval_ms();
+    tokio::spawn(async move {
+        log::info!(
+            "Latest stream watcher started (poll interval {}ms)",
+            interval
+        );
+        let mut task_baseline: HashMap<String, SystemTime> = HashMap::new();
+        let mut ws_baseline: HashMap<String, SystemTime> = HashMap::new();
+        let mut primed = false;
+
+        loop {
+            if !watcher_paused() {
+                let scan = tokio::task::spawn_blocking(scan_fingerprints).await;
+                if let Ok((tasks, workspaces)) = scan {
+                    if primed {
+                        emit_changes(&task_baseline, &tasks, "subtask");
+                        emit_changes(&ws_baseline, &workspaces, "checkpoint");
+                    } else {
+                        primed = true;
+                    }
+                    task_baseline = tasks;
+                    ws_baseline = workspaces;
+                }
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(interval)).await;
+        }
+    });
+}
+
+/// Compare a fingerprint scan against the baseline and broadcast one event
+/// per new or updated entry. Send errors (no subscribers) are ignored.
+fn emit_changes(
+    baseline: &HashMap<String, SystemTime>,
+    current: &HashMap<String, SystemTime>,
+    kind: &str,
+) {
+    for (id, mtime) in current {
+        let changed = match baseline.get(id) {
+            Some(prev) => mtime > prev,
+            None => true,
+        };
+        if changed {
+            let event = LatestStreamEvent {
+                kind: kind.to_string(),
+                task_id: (kind == "subtask").then(|| id.clone()),
+                workspace_id: (kind == "checkpoint").then(|| id.clone()),
+                detail: None,
+                timestamp: chrono::Utc::now().to_rfc3339(),
+            };
+            log::info!("Latest stream: {} event for {}", kind, id);
+            publish(event);
+        }
+    }
+}
+
+/// Scan task and workspace fingerprints: (task_id → ui_messages.json mtime,
+/// workspace_id → newest ref mtime).
+fn scan_fingerprints() -> (HashMap<String, SystemTime>, HashMap<String, SystemTime>) {
+    let mut tasks = HashMap::new();
+    if let Some(root) = crate::conversation_history::root::tasks_root() {
+        if let Ok(entries) = std::fs::read_dir(&root) {
+            for entry in entries.flatten() {
+                let task_id = entry.file_name().to_string_lossy().to_string();
+                let ui_messages = entry.path().join("ui_messages.json");
+                if let Ok(meta) = std::fs::metadata(&ui_messages) {
+                    if let Ok(mtime) = meta.modified() {
+                        tasks.insert(task_id, mtime);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut workspaces = HashMap::new();
+    for ws in crate::shadow_git::discovery::find_workspaces() {
+        if let Some(mtime) = refs_mtime(Path::new(&ws.git_dir)) {
+            workspaces.insert(ws.id.clone(), mtime);
+        }
+    }
+
+    (tasks, workspaces)
+}
+
+/// Newest mtime across a git dir's HEAD, packed-refs, and loose refs.
+/// A new commit always touches at least one of these.
+fn refs_mtime(git_dir: &Path) -> Option<SystemTime> {
+    let mut newest: Option<SystemTime> = None;
+    let mut consider = |path: &Path| {
+        if let Ok(meta) = std::fs::metadata(path) {
+            if let Ok(mtime) = meta.modified() {
+                if newest.is_none_or(|n| mtime > n) {
+                    newest = Some(mtime);
+                }
+            }
+        }
+    };
+
+    consider(&git_dir.join("HEAD"));
+    consider(&git_dir.join("packed-refs"));
+
+    let refs_dir = git_dir.join("refs").join("heads");
+    if let Ok(entries) = std::fs::read_dir(&refs_dir) {
+        for entry in entries.flatten() {
+            consider(&entry.path());
+        }
+    }
+
+    newest
+}
+
+/// Per-connection stream adapter: forwards broadcast events through an
+/// unbounded mpsc channel, which implements the polling Axum's SSE needs.
+struct SseEventStream(mpsc::UnboundedReceiver<LatestStreamEvent>);
+
+impl futures_core::Stream for SseEventStream {
+    type Item = Result<Event, Infallible>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.0.poll_recv(cx).map(|opt| {
+            opt.map(|ev| {
+                let data = serde_json::to_string(&ev).unwrap_or_else(|_| "{}".to_string());
+                Ok(Event::default().event(ev.kind.clone()).data(data))
+            })
+        })
+    }
+}
+
+/// Stream /latest update events over Server-Sent Events
+///
+/// Each event has `event: subtask` or `event: checkpoint` and a JSON payload
+/// identifying the task or workspace that changed. Events are emitted as the
+/// filesystem watcher detects new subtask prompts and checkpoint commits —
+/// connect once and react instead of polling GET /latest.
+#[utoipa::path(
+    get,
+    path = "/latest/stream",
+    responses(
+        (status = 200, description = "SSE stream of subtask/checkpoint events (text/event-stream)", body = LatestStreamEvent)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["changes", "history", "tool"]
+)]
+pub async fn latest_stream_handler() -> Sse<impl futures_core::Stream<Item = Result<Event, Infallible>>> {
+    log::info!("REST API: GET /latest/stream — subscriber connected");
+
+    let mut brx = EVENTS.subscribe();
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        loop {
+            match brx.recv().await {
+                Ok(ev) => {
+                    if tx.send(ev).is_err() {
+                        break; // subscriber disconnected
+                    }
+                }
+                // Slow subscriber skipped some events — keep streaming
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+
+    Sse::new(SseEventStream(rx)).keep_alive(KeepAlive::default())
+}
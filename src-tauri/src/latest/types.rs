@@ -28,6 +28,27 @@ pub struct SubtaskSummaryItem {
     pub tool_call_count: usize,
     /// Tool names used (deduplicated, sorted)
     pub tools_used: Vec<String>,
+    /// Diff for this subtask. Only populated for the last N subtasks when
+    /// `?subtasks=N` was requested; otherwise null (UI fetches on demand).
+    #[serde(default)]
+    pub diff: Option<DiffResult>,
+}
+
+/// A changed file's full contents, embedded in the /latest response when
+/// `?include_files=changed` is requested.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LatestFileItem {
+    /// File path relative to repo root
+    pub path: String,
+    /// File content (possibly truncated; None if unavailable or omitted)
+    pub content: Option<String>,
+    /// Original size in bytes at the diff's `toRef` (if known)
+    pub size: Option<usize>,
+    /// Whether the content was cut short to fit the `max_bytes` budget
+    pub truncated: bool,
+    /// Why content is missing (binary file, budget exhausted, retrieval error)
+    pub error: Option<String>,
 }
 
 /// Composite response for GET /latest
@@ -82,6 +103,12 @@ pub struct LatestResponse {
     /// Scope used for this response ("subtask" or "task")
     pub scope: String,
 
+    // ---- Embedded file contents (?include_files=changed) ----
+    /// Full contents of the changed files at the diff's `toRef`, subject to
+    /// the `max_bytes` budget. Null unless `?include_files=changed` was given.
+    #[serde(default)]
+    pub files: Option<Vec<LatestFileItem>>,
+
     // ---- Subtask summaries (for UI tab rendering) ----
     /// All subtasks in this task with metadata (prompt, tool counts, etc.)
     /// Diffs are NOT included — the UI fetches them on-demand per subtask tab click.
@@ -97,15 +124,78 @@ pub struct LatestQuery {
     /// Pathspec exclusion patterns (repeated), e.g. ?exclude=node_modules&exclude=target
     #[serde(default)]
     pub exclude: Vec<String>,
+    /// Include prompts + diffs for the last N subtasks (default: only the latest prompt/diff)
+    #[serde(default)]
+    pub subtasks: Option<usize>,
+    /// Pin a specific checkpoint workspace ID instead of auto-resolving from the task
+    #[serde(default)]
+    pub workspace: Option<String>,
+    /// File inclusion mode: "changed" embeds full contents of changed files
+    #[serde(default)]
+    pub include_files: Option<String>,
+    /// Total byte budget for embedded file contents (default 262144)
+    #[serde(default)]
+    pub max_bytes: Option<usize>,
 }
 
 fn default_scope() -> String {
     "task".to_string()
 }
 
-/// Error response for /latest
-#[derive(Debug, Serialize, utoipa::ToSchema)]
-pub struct LatestErrorResponse {
-    pub error: String,
-    pub code: u16,
+/// Query parameters for GET /latest/recent
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct RecentQuery {
+    /// Number of recent tasks to resolve (default 5, max 50)
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// Pathspec exclusion patterns (repeated), e.g. ?exclude=node_modules&exclude=target
+    #[serde(default)]
+    pub exclude: Vec<String>,
 }
+
+/// One resolved task composite in the GET /latest/recent response.
+///
+/// A lighter cut of [`LatestResponse`]: the prompt plus the diff's file list
+/// and line totals, without the unified patch text (fetch the full diff via
+/// /changes/tasks/{taskId}/diff when needed).
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentItem {
+    /// Task ID (directory name, epoch milliseconds)
+    pub task_id: String,
+    /// The latest prompt of the task (last subtask, or initial prompt)
+    pub prompt: String,
+    /// ISO 8601 timestamp when this prompt was issued
+    pub prompt_timestamp: String,
+    /// Total number of subtasks in this task (including initial)
+    pub total_subtasks: usize,
+    /// Workspace ID (checkpoint workspace that contains this task)
+    pub workspace_id: Option<String>,
+    /// Changed files with per-file line counts (no patch text)
+    pub files: Vec<crate::shadow_git::types::DiffFile>,
+    /// Total lines added across all files
+    pub lines_added: usize,
+    /// Total lines removed across all files
+    pub lines_removed: usize,
+    /// Reason why the file list is empty (if diff resolution failed)
+    pub no_diff_reason: Option<String>,
+    /// ISO 8601 timestamp when the task started
+    pub task_started_at: String,
+    /// ISO 8601 timestamp when the task ended (last UI message)
+    pub task_ended_at: Option<String>,
+}
+
+/// Response for GET /latest/recent
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentResponse {
+    /// Resolved composites, newest task first
+    pub items: Vec<RecentItem>,
+    /// Number of items returned
+    pub count: usize,
+    /// Total number of tasks available
+    pub total_tasks: usize,
+}
+
+/// Error response for /latest — alias for the crate-wide structured error.
+pub type LatestErrorResponse = crate::api::error::ApiError;
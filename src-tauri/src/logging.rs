@@ -1,10 +1,81 @@
 use crate::config::{get_config_path, get_log_file_path, get_logs_dir, load_config};
+use axum::extract::Query;
+use axum::http::StatusCode;
+use axum::Json;
 use log::LevelFilter;
+use serde::{Deserialize, Serialize};
 use simplelog::*;
-use std::fs::File;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Hard cap on `?lines=` for the log tail endpoint.
+const MAX_TAIL_LINES: usize = 2000;
+const DEFAULT_TAIL_LINES: usize = 200;
+
+/// File writer that rotates `jira_viewer.log` → `.log.1` .. `.log.N` once the
+/// active file exceeds the configured size, deleting the oldest. Appends
+/// across sessions so restarts don't discard the previous run's tail.
+struct RotatingFileWriter {
+    path: PathBuf,
+    file: File,
+    written: u64,
+    max_bytes: u64,
+    keep_files: usize,
+}
+
+impl RotatingFileWriter {
+    fn open(path: PathBuf, max_bytes: u64, keep_files: usize) -> std::io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let written = file.metadata().map(|m| m.len()).unwrap_or(0);
+        Ok(Self {
+            path,
+            file,
+            written,
+            max_bytes,
+            keep_files,
+        })
+    }
+
+    /// Shift rotated files up by one, drop the oldest, start a fresh active
+    /// file. Failures leave the current file in place (we keep writing).
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.file.flush()?;
+        let rotated = |n: usize| PathBuf::from(format!("{}.{}", self.path.display(), n));
+        let _ = std::fs::remove_file(rotated(self.keep_files));
+        for n in (1..self.keep_files).rev() {
+            let _ = std::fs::rename(rotated(n), rotated(n + 1));
+        }
+        if self.keep_files > 0 {
+            std::fs::rename(&self.path, rotated(1))?;
+        } else {
+            std::fs::remove_file(&self.path)?;
+        }
+        self.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        self.written = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.written + buf.len() as u64 > self.max_bytes && self.written > 0 {
+            if let Err(e) = self.rotate() {
+                eprintln!("Log rotation failed: {}", e);
+            }
+        }
+        let n = self.file.write(buf)?;
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()
+    }
+}
 
 /// Initialize the logging system based on config file settings
-/// Creates a new timestamped log file for each app session
+/// Writes to a size-rotated file set under the logs directory
 pub fn init_logging() {
     let config = load_config();
     let log_path = get_log_file_path();
@@ -39,9 +110,11 @@ pub fn init_logging() {
         ));
     }
 
-    // File logger - always create a new timestamped file
-    if let Ok(file) = File::create(&log_path) {
-        loggers.push(WriteLogger::new(level, log_config.clone(), file));
+    // File logger — rotated at the configured size, retention per config
+    let max_bytes = config.logging.max_file_size_mb.max(1) * 1024 * 1024;
+    match RotatingFileWriter::open(log_path.clone(), max_bytes, config.logging.keep_files) {
+        Ok(writer) => loggers.push(WriteLogger::new(level, log_config.clone(), writer)),
+        Err(e) => eprintln!("Failed to open log file {:?}: {}", log_path, e),
     }
 
     // Initialize combined logger
@@ -59,3 +132,72 @@ pub fn init_logging() {
     log::info!("Logs directory: {:?}", get_logs_dir());
     log::debug!("Debug logging is ENABLED - raw API data will be logged");
 }
+
+// ============ Log tail endpoint ============
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct LogTailQuery {
+    /// Number of lines from the end of the log (default 200, max 2000)
+    pub lines: Option<usize>,
+}
+
+/// Response for `GET /system/logs/tail`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LogTailResponse {
+    /// Path of the active log file
+    pub file: String,
+    /// Most recent log lines, oldest first
+    pub lines: Vec<String>,
+}
+
+/// Tail the backend log
+///
+/// Returns the most recent lines of the active backend log file, for
+/// in-app troubleshooting without hunting down the logs directory.
+#[utoipa::path(
+    get,
+    path = "/system/logs/tail",
+    params(LogTailQuery),
+    responses(
+        (status = 200, description = "Most recent log lines", body = LogTailResponse),
+        (status = 404, description = "Log file not found", body = crate::api::error::ApiError)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "system"
+)]
+pub async fn system_logs_tail_handler(
+    Query(query): Query<LogTailQuery>,
+) -> Result<Json<LogTailResponse>, (StatusCode, Json<crate::api::error::ApiError>)> {
+    let requested = query
+        .lines
+        .unwrap_or(DEFAULT_TAIL_LINES)
+        .clamp(1, MAX_TAIL_LINES);
+    let log_path = get_log_file_path();
+
+    let result = tokio::task::spawn_blocking(move || std::fs::read_to_string(&log_path)).await;
+    match result {
+        Ok(Ok(content)) => {
+            let all: Vec<&str> = content.lines().collect();
+            let start = all.len().saturating_sub(requested);
+            Ok(Json(LogTailResponse {
+                file: get_log_file_path().to_string_lossy().to_string(),
+                lines: all[start..].iter().map(|l| l.to_string()).collect(),
+            }))
+        }
+        Ok(Err(e)) => Err((
+            StatusCode::NOT_FOUND,
+            Json(crate::api::error::ApiError::not_found(format!(
+                "Failed to read log file: {}",
+                e
+            ))),
+        )),
+        Err(e) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(crate::api::error::ApiError::internal(format!(
+                "Log read task failed: {}",
+                e
+            ))),
+        )),
+    }
+}
@@ -2,16 +2,27 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod api;
+mod audit;
+mod backup;
 mod config;
 mod conversation_history;
+mod crash;
+mod diagnostics;
+mod http;
 mod jira;
 mod latest;
 mod logging;
+mod notify;
+mod offline;
 mod openapi;
+mod prefs;
+mod scheduler;
+mod secrets;
 mod server;
 mod shadow_git;
 mod state;
 mod tool_runtime;
+mod tsgen;
 
 use config::get_config_dir;
 use jira::{IssueDetails, IssueSummary, JiraClient, JiraSettings, SearchResult};
@@ -26,12 +37,23 @@ use std::sync::{Arc, Mutex};
 // ============ App State ============
 
 static SETTINGS: Lazy<Mutex<Option<JiraSettings>>> = Lazy::new(|| {
-    // Pre-configured settings from jtest.py
-    Mutex::new(Some(JiraSettings {
-        base_url: "https://sonymusicpub.atlassian.net".to_string(),
-        email: "olek.buzunov@sonymusicpub.com".to_string(),
-        default_jql: "assignee = currentUser() ORDER BY updated DESC".to_string(),
-    }))
+    // Jira connection comes from config.toml ([jira]); the pre-configured
+    // dev values from jtest.py remain the fallback when the file is empty.
+    let jira = config::current().jira;
+    let settings = if jira.base_url.is_empty() {
+        JiraSettings {
+            base_url: "https://sonymusicpub.atlassian.net".to_string(),
+            email: "olek.buzunov@sonymusicpub.com".to_string(),
+            default_jql: jira.default_jql,
+        }
+    } else {
+        JiraSettings {
+            base_url: jira.base_url,
+            email: jira.email,
+            default_jql: jira.default_jql,
+        }
+    };
+    Mutex::new(Some(settings))
 });
 
 static API_TOKEN: Lazy<Mutex<Option<String>>> = Lazy::new(|| {
@@ -176,7 +198,13 @@ fn create_jira_client() -> Result<JiraClient, String> {
         .clone()
         .ok_or("API token not configured")?;
 
-    Ok(JiraClient::new(settings.base_url, settings.email, token))
+    let client = JiraClient::new(settings.base_url, settings.email, token);
+    // Record per-call latency in the access log once state exists
+    let state = APP_STATE.lock().ok().and_then(|s| s.clone());
+    Ok(match state {
+        Some(state) => client.with_access_log(state),
+        None => client,
+    })
 }
 
 // ============ Tauri Commands ============
@@ -209,6 +237,16 @@ fn save_settings(settings: JiraSettings, api_token: String) -> Result<(), String
         .lock()
         .map_err(|e| format!("Failed to lock token: {}", e))?;
 
+    audit::record(
+        "settings.save",
+        serde_json::json!({
+            "baseUrl": settings.base_url,
+            "email": settings.email,
+        }),
+        true,
+        "Jira settings updated",
+    );
+
     *stored_settings = Some(settings);
     *stored_token = Some(api_token);
 
@@ -230,9 +268,43 @@ async fn get_current_user() -> Result<serde_json::Value, String> {
 
 #[tauri::command]
 async fn list_issues(jql: String) -> Result<SearchResult, String> {
+    // Offline: degrade to the last cached result instead of timing out
+    if offline::is_offline() {
+        if let Ok(Some(cache)) = load_issues_cache() {
+            if cache.jql == jql {
+                info!("Offline mode: serving {} cached issues", cache.issues.len());
+                return Ok(SearchResult {
+                    total: cache.issues.len() as i32,
+                    issues: cache.issues,
+                });
+            }
+        }
+        return Err("offline: no cached issues for this query".to_string());
+    }
+
     let client = create_jira_client()?;
     let result = client.search_issues(&jql, 100).await?;
-    
+
+    // Fire notifications for issues that weren't in the previous cache
+    // (same query only — a JQL change isn't a new assignment)
+    if let Ok(Some(cache)) = load_issues_cache() {
+        if cache.jql == jql {
+            let known: std::collections::HashSet<&str> =
+                cache.issues.iter().map(|i| i.key.as_str()).collect();
+            for issue in result.issues.iter().filter(|i| !known.contains(i.key.as_str())) {
+                notify::emit(
+                    "jira.issue_assigned",
+                    serde_json::json!({
+                        "key": issue.key,
+                        "status": issue.status,
+                        "summary": format!("[{}] {}", issue.key, issue.summary),
+                        "link": format!("xray://jira/{}", issue.key),
+                    }),
+                );
+            }
+        }
+    }
+
     // Save to cache after successful fetch
     if let Err(e) = save_issues_cache(&result.issues, &jql) {
         error!("Failed to save issues to cache: {}", e);
@@ -339,6 +411,19 @@ static REST_API_INFO: Lazy<Mutex<Option<ApiInfo>>> = Lazy::new(|| Mutex::new(Non
 /// Global AppState storage (for access logs)
 static APP_STATE: Lazy<Mutex<Option<Arc<AppState>>>> = Lazy::new(|| Mutex::new(None));
 
+/// Global ToolRuntime storage (for runtime Tauri commands)
+static TOOL_RUNTIME: Lazy<Mutex<Option<Arc<tool_runtime::ToolRuntime>>>> =
+    Lazy::new(|| Mutex::new(None));
+
+/// Fetch the shared ToolRuntime, or fail if the REST server is not up yet
+fn get_tool_runtime() -> Result<Arc<tool_runtime::ToolRuntime>, String> {
+    TOOL_RUNTIME
+        .lock()
+        .map_err(|e| format!("Failed to lock tool runtime: {}", e))?
+        .clone()
+        .ok_or_else(|| "Tool runtime not initialized".to_string())
+}
+
 /// Tauri command: Get REST API connection info
 #[tauri::command]
 fn get_api_info() -> Result<ApiInfo, String> {
@@ -396,6 +481,95 @@ fn clear_inference_logs() -> Result<(), String> {
     Ok(())
 }
 
+/// Tauri command: Export a task's full checkpoint diff to a .patch file
+///
+/// The frontend picks `dest_path` via a save dialog; the patch is computed
+/// the same way as GET /changes/tasks/:taskId/diff and written verbatim so
+/// it can be applied elsewhere with `git apply`. Returns the number of
+/// bytes written. `workspace` is optional — when omitted, the task is
+/// auto-linked to its checkpoint workspace.
+#[tauri::command]
+fn export_task_diff(
+    task_id: String,
+    workspace: Option<String>,
+    dest_path: String,
+) -> Result<usize, String> {
+    let git_dir = match workspace.filter(|w| !w.is_empty()) {
+        Some(ws_id) => shadow_git::discovery::find_workspaces()
+            .into_iter()
+            .find(|w| w.id == ws_id)
+            .map(|w| std::path::PathBuf::from(w.git_dir))
+            .ok_or_else(|| format!("Workspace '{}' not found", ws_id))?,
+        None => {
+            shadow_git::resolve_workspace_for_task(&task_id)
+                .ok_or_else(|| {
+                    format!(
+                        "Could not auto-link task '{}' to a checkpoint workspace",
+                        task_id
+                    )
+                })?
+                .1
+        }
+    };
+
+    let diff = shadow_git::discovery::get_task_diff(&task_id, &git_dir, &[])?;
+
+    fs::write(&dest_path, &diff.patch)
+        .map_err(|e| format!("Failed to write patch to {}: {}", dest_path, e))?;
+
+    info!(
+        "Exported task {} diff to {} ({} bytes)",
+        task_id,
+        dest_path,
+        diff.patch.len()
+    );
+    Ok(diff.patch.len())
+}
+
+/// Tauri command: Generate the typed TypeScript client from the live OpenAPI spec
+///
+/// Writes `client.generated.ts` into the frontend source tree (or to
+/// `output_path` when given) so UI bindings stay in sync with handler changes.
+#[tauri::command]
+fn generate_ts_client(output_path: Option<String>) -> Result<String, String> {
+    use utoipa::OpenApi;
+    let spec = openapi::stamped_spec_value(openapi::PublicApiDoc::openapi());
+    tsgen::write_typescript_client(&spec, output_path.as_deref())
+}
+
+/// Tauri command: Create a backup archive of all application data
+///
+/// Zips the config dir and `%APPDATA%/jira-dashboard` into `output_path`
+/// (or `<config dir>/backups/` when omitted) and returns the archive info.
+#[tauri::command]
+async fn create_backup(output_path: Option<String>) -> Result<backup::BackupResponse, String> {
+    tokio::task::spawn_blocking(move || backup::create_backup(output_path.as_deref()))
+        .await
+        .map_err(|e| format!("Backup task panicked: {}", e))?
+}
+
+/// Tauri command: Restore a backup archive created by `create_backup`
+///
+/// Validates the manifest version before extracting; archives from a newer
+/// app version are rejected.
+#[tauri::command]
+async fn restore_backup(path: String) -> Result<backup::RestoreResponse, String> {
+    let params = serde_json::json!({ "path": path.clone() });
+    let result = tokio::task::spawn_blocking(move || backup::restore_backup(&path))
+        .await
+        .map_err(|e| format!("Restore task panicked: {}", e))?;
+    match &result {
+        Ok(response) => audit::record(
+            "system.restore",
+            params,
+            true,
+            &format!("{} entries restored", response.entries_restored),
+        ),
+        Err(e) => audit::record("system.restore", params, false, e),
+    }
+    result
+}
+
 /// Generate a secure random auth token
 fn generate_auth_token() -> String {
     use rand::Rng;
@@ -403,20 +577,53 @@ fn generate_auth_token() -> String {
     hex::encode(bytes)
 }
 
-/// Save API info to the .env file for CLI scripts to read
+/// Save API info for CLI scripts to read: `api_info.json` plus a
+/// shell-sourceable `api_info.env` in the config directory. Legacy project
+/// `.env` mutation only happens with `--write-env` (it relied on fragile
+/// CWD heuristics and trashed unrelated files when they leaked in).
 fn save_api_info_to_file(api_info: &ApiInfo) -> Result<(), String> {
-    // Get the project root directory (where .env file is located)
-    // The Tauri dev server runs from src-tauri, so we need to go up one level
-    // Priority: project root .env > current dir .env
+    let config_dir = config::get_config_dir();
+
+    let json_path = config_dir.join("api_info.json");
+    let json = serde_json::to_string_pretty(api_info)
+        .map_err(|e| format!("Failed to serialize API info: {}", e))?;
+    fs::write(&json_path, json)
+        .map_err(|e| format!("Failed to write {:?}: {}", json_path, e))?;
+
+    let env_path = config_dir.join("api_info.env");
+    let env_content = format!(
+        "# REST API Info (auto-generated on app start)\n\
+         # Source this file: . {}\n\
+         REST_API_URL={}\n\
+         REST_API_TOKEN={}\n",
+        env_path.display(),
+        api_info.base_url,
+        api_info.token
+    );
+    fs::write(&env_path, env_content)
+        .map_err(|e| format!("Failed to write {:?}: {}", env_path, e))?;
+
+    info!("Saved REST API info to {:?}", json_path);
+    info!("  REST_API_URL={}", api_info.base_url);
+    info!("  REST_API_TOKEN={}...", &api_info.token[..8]);
+
+    if std::env::args().any(|a| a == "--write-env") {
+        save_api_info_to_dotenv(api_info)?;
+    }
+    Ok(())
+}
+
+/// Legacy behavior behind `--write-env`: patch REST_API_* lines into the
+/// project `.env` (the Tauri dev server runs from src-tauri, so prefer the
+/// parent directory's file).
+fn save_api_info_to_dotenv(api_info: &ApiInfo) -> Result<(), String> {
     let project_root_env = std::path::PathBuf::from("../.env");
     let current_env = std::path::PathBuf::from(".env");
-    
-    // Prefer project root .env if it exists, otherwise use current dir
+
     let env_path = if project_root_env.exists() {
         project_root_env
     } else if current_env.exists() {
-        // Check if this is src-tauri/.env (we don't want that)
-        // If we're in src-tauri, use project root anyway
+        // Avoid creating src-tauri/.env when running from the dev server
         let cwd = std::env::current_dir().unwrap_or_default();
         if cwd.ends_with("src-tauri") || cwd.to_string_lossy().contains("src-tauri") {
             project_root_env
@@ -424,47 +631,156 @@ fn save_api_info_to_file(api_info: &ApiInfo) -> Result<(), String> {
             current_env
         }
     } else {
-        // Create in project root
         project_root_env
     };
-    
+
     info!("Using .env file at: {:?}", env_path);
-    
-    // Read existing .env content
+
     let existing_content = if env_path.exists() {
         fs::read_to_string(&env_path)
             .map_err(|e| format!("Failed to read .env file: {}", e))?
     } else {
         String::new()
     };
-    
+
     // Remove existing REST_API_* lines
     let filtered_lines: Vec<&str> = existing_content
         .lines()
         .filter(|line| !line.starts_with("REST_API_URL=") && !line.starts_with("REST_API_TOKEN="))
         .collect();
-    
-    // Build new content
+
     let mut new_content = filtered_lines.join("\n");
     if !new_content.is_empty() && !new_content.ends_with('\n') {
         new_content.push('\n');
     }
-    
-    // Add REST API info
-    new_content.push_str(&format!("\n# REST API Info (auto-generated on app start)\n"));
+    new_content.push_str("\n# REST API Info (auto-generated on app start)\n");
     new_content.push_str(&format!("REST_API_URL={}\n", api_info.base_url));
     new_content.push_str(&format!("REST_API_TOKEN={}\n", api_info.token));
-    
-    // Write back to .env file
+
     fs::write(&env_path, &new_content)
         .map_err(|e| format!("Failed to write .env file: {}", e))?;
-    
+
     info!("Saved REST API info to .env file");
-    info!("  REST_API_URL={}", api_info.base_url);
-    info!("  REST_API_TOKEN={}...", &api_info.token[..8]);
     Ok(())
 }
 
+// ============ Tool Runtime Commands ============
+
+/// Tauri command: List all tools known to the runtime
+#[tauri::command]
+fn runtime_list_tools() -> Result<Vec<tool_runtime::ToolInfo>, String> {
+    Ok(get_tool_runtime()?.list_tools())
+}
+
+/// Tauri command: Get the full runtime configuration (global + per-tool)
+#[tauri::command]
+fn runtime_get_config() -> Result<serde_json::Value, String> {
+    let runtime = get_tool_runtime()?;
+    Ok(serde_json::json!({
+        "config": runtime.get_global_config(),
+        "toolConfigs": runtime.get_all_tool_configs(),
+    }))
+}
+
+/// Tauri command: Update the global runtime configuration
+#[tauri::command]
+fn runtime_set_global_config(config: tool_runtime::GlobalRuntimeConfig) -> Result<(), String> {
+    get_tool_runtime()?.set_global_config(config);
+    Ok(())
+}
+
+/// Tauri command: Configure a specific tool
+#[tauri::command]
+fn runtime_configure_tool(
+    operation_id: String,
+    config: tool_runtime::ToolConfig,
+) -> Result<(), String> {
+    get_tool_runtime()?.configure_tool(&operation_id, config);
+    Ok(())
+}
+
+/// Tauri command: Invoke a tool through the runtime choke-point
+#[tauri::command]
+async fn runtime_invoke_tool(
+    operation_id: String,
+    args: serde_json::Value,
+) -> Result<tool_runtime::ToolCallResult, String> {
+    let runtime = get_tool_runtime()?;
+    Ok(runtime
+        .call(&operation_id, args, tool_runtime::ToolCallSource::UiConsole)
+        .await)
+}
+
+/// Tauri command: Get tool execution logs
+#[tauri::command]
+fn runtime_get_logs() -> Result<Vec<tool_runtime::ToolExecutionLog>, String> {
+    Ok(get_tool_runtime()?.get_execution_logs())
+}
+
+/// Tauri command: Clear tool execution logs
+#[tauri::command]
+fn runtime_clear_logs() -> Result<(), String> {
+    get_tool_runtime()?.clear_execution_logs();
+    Ok(())
+}
+
+/// Tauri command: Reset all circuit breakers
+#[tauri::command]
+fn runtime_reset_circuit_breakers() -> Result<(), String> {
+    get_tool_runtime()?.reset_circuit_breakers();
+    Ok(())
+}
+
+// ============ Conversation History Commands ============
+// Direct parser access for the desktop UI — same data as the /history REST
+// endpoints, but these keep working when the embedded server fails to start.
+
+/// Tauri command: List all parsed task summaries
+#[tauri::command]
+fn list_history_tasks() -> Result<conversation_history::TaskHistoryListResponse, String> {
+    Ok(conversation_history::summary::scan_all_tasks())
+}
+
+/// Tauri command: Get full detail for one task
+#[tauri::command]
+fn get_task_detail(task_id: String) -> Result<conversation_history::TaskDetailResponse, String> {
+    conversation_history::detail::parse_task_detail(&task_id)
+        .ok_or_else(|| format!("Task '{}' not found or unreadable", task_id))
+}
+
+/// Tauri command: Get the tool call timeline for one task
+#[tauri::command]
+fn get_task_tools(
+    task_id: String,
+    tool_name: Option<String>,
+    failed_only: Option<bool>,
+) -> Result<conversation_history::ToolCallTimelineResponse, String> {
+    conversation_history::tools::parse_task_tools(
+        &task_id,
+        tool_name.as_deref(),
+        failed_only.unwrap_or(false),
+    )
+    .ok_or_else(|| format!("Task '{}' not found or unreadable", task_id))
+}
+
+/// Tauri command: Get a page of task messages (default 20, max 100 — same
+/// as the REST endpoint)
+#[tauri::command]
+fn get_task_messages(
+    task_id: String,
+    offset: Option<usize>,
+    limit: Option<usize>,
+    role: Option<String>,
+) -> Result<conversation_history::PaginatedMessagesResponse, String> {
+    conversation_history::messages::parse_task_messages(
+        &task_id,
+        offset.unwrap_or(0),
+        limit.unwrap_or(20).min(100),
+        role.as_deref(),
+    )
+    .ok_or_else(|| format!("Task '{}' not found or unreadable", task_id))
+}
+
 /// Start the Axum REST server
 /// SECURITY: Always binds to 127.0.0.1, never 0.0.0.0
 fn start_rest_server(app_state: Arc<AppState>) -> Result<String, String> {
@@ -475,14 +791,20 @@ fn start_rest_server(app_state: Arc<AppState>) -> Result<String, String> {
         .map_err(|e| format!("Failed to create Tokio runtime: {}", e))?;
 
     let (actual_addr, server_future) = runtime.block_on(async {
-        // SECURITY: Bind to loopback only!
-        let listener = TcpListener::bind("127.0.0.1:0").await
-            .map_err(|e| format!("Failed to bind TCP listener: {}", e))?;
+        // SECURITY: Bind to loopback only! Port comes from config.toml
+        // ([server] port); 0 keeps the historical random-port behavior.
+        let port = config::current().server.port;
+        let listener = TcpListener::bind(("127.0.0.1", port)).await
+            .map_err(|e| format!("Failed to bind TCP listener on port {}: {}", port, e))?;
         let actual_addr = listener.local_addr()
             .map_err(|e| format!("Failed to get local address: {}", e))?;
 
         // Create ToolRuntime and initialize with OpenAPI spec
         let tool_runtime = tool_runtime::ToolRuntime::new(app_state.clone());
+        tool_runtime.load_persisted_config();
+        tool_runtime.load_persisted_fixtures();
+        tool_runtime.load_persisted_macros();
+        *TOOL_RUNTIME.lock().unwrap() = Some(tool_runtime.clone());
         
         // Load OpenAPI spec for validation
         let openapi_spec = serde_json::to_value(openapi::PublicApiDoc::openapi())
@@ -494,6 +816,22 @@ fn start_rest_server(app_state: Arc<AppState>) -> Result<String, String> {
 
         let app = server::create_router(app_state, tool_runtime);
 
+        // Periodic background jobs (cache refreshes, model list, log
+        // rotation, retention) — see GET /system/jobs
+        scheduler::spawn_scheduler();
+
+        // One-shot cache warmer so first UI load and /latest skip cold scans
+        shadow_git::warmup::spawn_cache_warmer();
+
+        // Filesystem poller feeding the /latest/stream SSE endpoint
+        latest::stream::spawn_latest_watcher();
+
+        // Hot-reload watcher for config.toml
+        config::spawn_config_watcher();
+
+        // Connectivity probe driving auto offline mode
+        offline::spawn_probe();
+
         let server = axum::serve(listener, app);
 
         Ok::<_, String>((actual_addr, server))
@@ -512,12 +850,321 @@ fn start_rest_server(app_state: Arc<AppState>) -> Result<String, String> {
     Ok(base_url)
 }
 
+/// Tauri command: Run startup self-diagnostics (same checks as
+/// GET /system/diagnostics)
+#[tauri::command]
+fn run_diagnostics() -> Result<diagnostics::DiagnosticsResponse, String> {
+    let state = APP_STATE
+        .lock()
+        .map_err(|e| format!("Failed to lock app state: {}", e))?
+        .clone()
+        .ok_or_else(|| "App state not initialized".to_string())?;
+    Ok(diagnostics::run_diagnostics(&state))
+}
+
+/// Tauri command: Zip recent crash reports into one archive for bug filing
+#[tauri::command]
+fn collect_crash_reports() -> Result<crash::CrashReportArchive, String> {
+    crash::collect_reports()
+}
+
+/// Tauri command: Get the current offline mode state
+#[tauri::command]
+fn get_offline_status() -> offline::OfflineStatus {
+    offline::status()
+}
+
+/// Tauri command: Force offline mode on/off, or return to auto-detection
+/// with `None` (same behavior as PUT /system/offline)
+#[tauri::command]
+fn set_offline_override(offline_mode: Option<bool>) -> offline::OfflineStatus {
+    offline::set_override(offline_mode);
+    audit::record(
+        "system.offline",
+        serde_json::json!({ "override": offline_mode }),
+        true,
+        &format!(
+            "offline mode now {}",
+            if offline::is_offline() { "active" } else { "inactive" }
+        ),
+    );
+    offline::status()
+}
+
+/// Tauri command: Set or rotate a provider API key at runtime (same
+/// behavior as PUT /system/providers)
+#[tauri::command]
+fn set_provider_key(provider: String, key: String) -> Result<(), String> {
+    if provider != "gemini" {
+        return Err(format!("Unknown provider '{}'", provider));
+    }
+    let key = key.trim();
+    if key.is_empty() {
+        return Err("API key must not be empty".to_string());
+    }
+    secrets::set("gemini_api_key", key)?;
+    let state = APP_STATE
+        .lock()
+        .map_err(|e| format!("Failed to lock app state: {}", e))?
+        .clone()
+        .ok_or_else(|| "App state not initialized".to_string())?;
+    state.set_gemini_api_key(key.to_string());
+    audit::record(
+        "providers.update",
+        serde_json::json!({ "provider": "gemini" }),
+        true,
+        "Gemini API key rotated",
+    );
+    Ok(())
+}
+
+// ============ UI Preference Commands ============
+
+/// Tauri command: Get all persisted UI preferences
+#[tauri::command]
+fn get_ui_prefs() -> HashMap<String, serde_json::Value> {
+    prefs::get_all()
+}
+
+/// Tauri command: Set one UI preference (a null value deletes the key)
+#[tauri::command]
+fn set_ui_pref(key: String, value: serde_json::Value) {
+    prefs::set(key, value);
+}
+
+/// Tauri command: Merge several UI preferences in one debounced write
+#[tauri::command]
+fn set_ui_prefs(values: HashMap<String, serde_json::Value>) {
+    prefs::set_many(values);
+}
+
+// ============ Open-in-Editor Commands ============
+// Bridge the viewer back to the user's real tooling: the system file
+// explorer for task directories, the OS-associated editor for blobs
+// materialized from checkpoint refs.
+
+/// Tauri command: Open a task's directory in the system file explorer
+#[tauri::command]
+fn open_task_dir(app: tauri::AppHandle, task_id: String) -> Result<(), String> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let root = conversation_history::root::tasks_root()
+        .ok_or_else(|| "Cline tasks directory not found".to_string())?;
+    let dir = root.join(&task_id);
+    if !dir.is_dir() {
+        return Err(format!("Task '{}' not found", task_id));
+    }
+    app.opener()
+        .open_path(dir.to_string_lossy(), None::<&str>)
+        .map_err(|e| format!("Failed to open task directory: {}", e))
+}
+
+/// Tauri command: Materialize a file as it was at a checkpoint ref and open
+/// it in the OS-associated editor. Returns the temp file path.
+#[tauri::command]
+fn open_file_at_ref(
+    app: tauri::AppHandle,
+    workspace: String,
+    git_ref: String,
+    path: String,
+) -> Result<String, String> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let git_dir = shadow_git::discovery::find_workspaces()
+        .into_iter()
+        .find(|w| w.id == workspace)
+        .map(|w| std::path::PathBuf::from(w.git_dir))
+        .ok_or_else(|| format!("Workspace '{}' not found", workspace))?;
+
+    let content = shadow_git::git_backend::show_file(&git_dir, &git_ref, &path)?
+        .ok_or_else(|| format!("'{}' does not exist at {}", path, git_ref))?;
+
+    // <tmp>/cline-xray/<ref>-<filename> — the ref prefix keeps versions of
+    // the same file apart and shows the provenance in the editor tab
+    let file_name = std::path::Path::new(&path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| format!("Invalid file path '{}'", path))?;
+    let safe_ref: String = git_ref
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .take(12)
+        .collect();
+    let tmp_dir = std::env::temp_dir().join("cline-xray");
+    fs::create_dir_all(&tmp_dir).map_err(|e| format!("Failed to create temp dir: {}", e))?;
+    let tmp_path = tmp_dir.join(format!("{}-{}", safe_ref, file_name));
+    fs::write(&tmp_path, content).map_err(|e| format!("Failed to write temp file: {}", e))?;
+
+    app.opener()
+        .open_path(tmp_path.to_string_lossy(), None::<&str>)
+        .map_err(|e| format!("Failed to open '{}': {}", file_name, e))?;
+    Ok(tmp_path.to_string_lossy().to_string())
+}
+
+// ============ Deep Links ============
+
+/// Route one `xray://` URL: focus the main window and tell the UI what to
+/// select via a `deeplink://navigate` event with `{kind, id}`.
+///
+/// Supported forms: `xray://task/<task-id>` and `xray://jira/<issue-key>`.
+/// These links are included in notification payloads so webhook templates
+/// (Slack posts, reports) can link straight back into the app.
+fn handle_deep_link(app: &tauri::AppHandle, url: &str) {
+    use tauri::{Emitter, Manager};
+
+    let Some(rest) = url.strip_prefix("xray://") else {
+        return;
+    };
+    let mut parts = rest.trim_end_matches('/').splitn(2, '/');
+    let kind = parts.next().unwrap_or("");
+    let id = parts.next().unwrap_or("");
+    if id.is_empty() || !matches!(kind, "task" | "jira") {
+        error!("Ignoring malformed deep link: {}", url);
+        return;
+    }
+
+    info!("Deep link: {} {}", kind, id);
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+    if let Err(e) = app.emit(
+        "deeplink://navigate",
+        serde_json::json!({ "kind": kind, "id": id }),
+    ) {
+        error!("Failed to emit deep link navigation event: {}", e);
+    }
+}
+
+// ============ System Tray ============
+
+/// Tray tooltip: server address plus current request rate.
+fn tray_tooltip() -> String {
+    let rate = APP_STATE
+        .lock()
+        .ok()
+        .and_then(|s| s.as_ref().map(|s| s.requests_last_minute()))
+        .unwrap_or(0);
+    match REST_API_INFO.lock().ok().and_then(|i| i.clone()) {
+        Some(info) => format!("Cline X-Ray — {} ({} req/min)", info.base_url, rate),
+        None => "Cline X-Ray — REST server not running".to_string(),
+    }
+}
+
+/// Build the system tray: a status line, quick actions (copy API URL/token,
+/// open the log folder, pause/resume the filesystem watcher) and quit — so
+/// the app can run minimized as a background service.
+fn setup_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
+    use tauri::menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem};
+    use tauri::tray::TrayIconBuilder;
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+    use tauri_plugin_opener::OpenerExt;
+
+    let status_text = match REST_API_INFO.lock().unwrap().as_ref() {
+        Some(info) => format!("REST server: {}", info.base_url),
+        None => "REST server: not running".to_string(),
+    };
+
+    let status = MenuItem::with_id(app, "tray_status", &status_text, false, None::<&str>)?;
+    let copy_url = MenuItem::with_id(app, "tray_copy_url", "Copy API URL", true, None::<&str>)?;
+    let copy_token = MenuItem::with_id(app, "tray_copy_token", "Copy API token", true, None::<&str>)?;
+    let open_logs = MenuItem::with_id(app, "tray_open_logs", "Open log folder", true, None::<&str>)?;
+    let pause_watcher = CheckMenuItem::with_id(
+        app,
+        "tray_pause_watcher",
+        "Pause file watcher",
+        true,
+        false,
+        None::<&str>,
+    )?;
+    let quit = MenuItem::with_id(app, "tray_quit", "Quit", true, None::<&str>)?;
+
+    let menu = Menu::with_items(
+        app,
+        &[
+            &status,
+            &PredefinedMenuItem::separator(app)?,
+            &copy_url,
+            &copy_token,
+            &open_logs,
+            &pause_watcher,
+            &PredefinedMenuItem::separator(app)?,
+            &quit,
+        ],
+    )?;
+
+    let pause_item = pause_watcher.clone();
+    let mut builder = TrayIconBuilder::with_id("main")
+        .menu(&menu)
+        .tooltip(tray_tooltip())
+        .on_menu_event(move |app, event| match event.id.as_ref() {
+            "tray_copy_url" => {
+                if let Some(info) = REST_API_INFO.lock().unwrap().as_ref() {
+                    if let Err(e) = app.clipboard().write_text(info.base_url.clone()) {
+                        error!("Tray: failed to copy API URL: {}", e);
+                    }
+                }
+            }
+            "tray_copy_token" => {
+                if let Some(info) = REST_API_INFO.lock().unwrap().as_ref() {
+                    if let Err(e) = app.clipboard().write_text(info.token.clone()) {
+                        error!("Tray: failed to copy API token: {}", e);
+                    }
+                }
+            }
+            "tray_open_logs" => {
+                let logs_dir = config::get_logs_dir();
+                if let Err(e) = app
+                    .opener()
+                    .open_path(logs_dir.to_string_lossy(), None::<&str>)
+                {
+                    error!("Tray: failed to open log folder: {}", e);
+                }
+            }
+            "tray_pause_watcher" => {
+                let paused = pause_item.is_checked().unwrap_or(false);
+                latest::stream::set_watcher_paused(paused);
+            }
+            "tray_quit" => {
+                info!("Tray: quit requested");
+                app.exit(0);
+            }
+            _ => {}
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    let tray = builder.build(app)?;
+
+    // Keep the request rate in the tooltip fresh while minimized
+    std::thread::spawn(move || loop {
+        std::thread::sleep(std::time::Duration::from_secs(30));
+        if tray.set_tooltip(Some(tray_tooltip())).is_err() {
+            break; // tray gone, app shutting down
+        }
+    });
+
+    Ok(())
+}
+
 // ============ Main Entry Point ============
 
 fn main() {
     // Initialize logging FIRST before anything else
     logging::init_logging();
-    info!("Tauri application starting...");
+
+    // Capture panics as crash reports now that the log file exists
+    crash::install_panic_hook();
+
+    // `--headless` runs the REST API + caches without the Tauri window, for
+    // machines with no display (CI, remote servers). Everything up to the
+    // Tauri builder is shared between the two modes.
+    let headless = std::env::args().any(|a| a == "--headless");
+    if headless {
+        info!("Application starting in headless mode (REST API only)...");
+    } else {
+        info!("Tauri application starting...");
+    }
 
     // Load .env file from project root
     // Try multiple paths since we might be running from different directories
@@ -544,11 +1191,26 @@ fn main() {
     let jira_settings = SETTINGS.lock().unwrap().clone().unwrap();
     let jira_token = API_TOKEN.lock().unwrap().clone().unwrap();
 
-    // Get Gemini API key from environment (now loaded from .env)
-    let gemini_api_key = std::env::var("GEMINI_API_KEY").unwrap_or_else(|_| {
-        info!("GEMINI_API_KEY not set in environment");
-        "YOUR_GEMINI_API_KEY_HERE".to_string()
-    });
+    // Get Gemini API key: env var first, then the secrets store (keys
+    // rotated via PUT /system/providers), then config.toml ([providers])
+    let gemini_api_key = std::env::var("GEMINI_API_KEY")
+        .ok()
+        .filter(|k| !k.is_empty())
+        .or_else(|| {
+            secrets::get("gemini_api_key").inspect(|_| {
+                info!("Using Gemini API key from the secrets store");
+            })
+        })
+        .unwrap_or_else(|| {
+            let configured = config::current().providers.gemini_api_key;
+            if configured.is_empty() {
+                info!("GEMINI_API_KEY not set in environment, secrets store or config.toml");
+                "YOUR_GEMINI_API_KEY_HERE".to_string()
+            } else {
+                info!("Using Gemini API key from config.toml");
+                configured
+            }
+        });
     if gemini_api_key != "YOUR_GEMINI_API_KEY_HERE" {
         info!("Gemini API key configured ({}...)", &gemini_api_key[..8.min(gemini_api_key.len())]);
     }
@@ -585,7 +1247,16 @@ fn main() {
             if let Err(e) = save_api_info_to_file(&api_info) {
                 error!("Failed to save API info to file: {}", e);
             }
-            
+
+            // `--print-api-info` emits the connection info as JSON on stdout
+            // so scripts can capture it without parsing logs or files
+            if std::env::args().any(|a| a == "--print-api-info") {
+                if let Ok(json) = serde_json::to_string(&api_info) {
+                    println!("{}", json);
+                }
+            }
+
+
             info!("REST API endpoints:");
             info!("  GET {}/health - Health check (no auth)", base_url);
             info!("  GET {}/openapi.json - OpenAPI spec (no auth)", base_url);
@@ -596,8 +1267,48 @@ fn main() {
         }
     }
 
+    if headless {
+        // The REST server runs on its own runtime thread (spawned inside
+        // start_rest_server); background workers (retention scheduler, cache
+        // warmer, /latest watcher) live on that runtime too. Nothing left to
+        // do on the main thread but wait for Ctrl+C.
+        info!("Headless mode: skipping Tauri window — press Ctrl+C to stop");
+        loop {
+            std::thread::park();
+        }
+    }
+
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_deep_link::init())
+        .setup(|app| {
+            // Let the notify subsystem raise native notifications
+            notify::set_app_handle(app.handle().clone());
+            // System tray: server status + quick actions
+            if let Err(e) = setup_tray(app) {
+                error!("Failed to set up system tray: {}", e);
+            }
+            // Deep links: xray://task/<id> and xray://jira/<KEY>. Bundled
+            // installs register the scheme via tauri.conf.json; runtime
+            // registration covers dev builds on Windows/Linux.
+            {
+                use tauri_plugin_deep_link::DeepLinkExt;
+                #[cfg(any(windows, target_os = "linux"))]
+                if let Err(e) = app.deep_link().register("xray") {
+                    error!("Failed to register xray:// scheme: {}", e);
+                }
+                let handle = app.handle().clone();
+                app.deep_link().on_open_url(move |event| {
+                    for url in event.urls() {
+                        handle_deep_link(&handle, url.as_str());
+                    }
+                });
+            }
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             is_configured,
             get_settings,
@@ -612,6 +1323,32 @@ fn main() {
             clear_access_logs,
             get_inference_logs,
             clear_inference_logs,
+            export_task_diff,
+            generate_ts_client,
+            create_backup,
+            restore_backup,
+            runtime_list_tools,
+            runtime_get_config,
+            runtime_set_global_config,
+            runtime_configure_tool,
+            runtime_invoke_tool,
+            runtime_get_logs,
+            runtime_clear_logs,
+            runtime_reset_circuit_breakers,
+            list_history_tasks,
+            get_task_detail,
+            get_task_tools,
+            get_task_messages,
+            open_task_dir,
+            open_file_at_ref,
+            get_ui_prefs,
+            set_ui_pref,
+            set_ui_prefs,
+            run_diagnostics,
+            collect_crash_reports,
+            set_provider_key,
+            get_offline_status,
+            set_offline_override,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
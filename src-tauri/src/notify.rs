@@ -0,0 +1,548 @@
+//! Outbound notifications for significant events.
+//!
+//! Producers call [`emit`] with an event name and a JSON payload; the
+//! subsystem fans the event out to:
+//!
+//! - **Webhooks** — user-configured HTTP endpoints persisted to
+//!   `%APPDATA%/jira-dashboard/webhooks.json`, each with an event filter,
+//!   an optional payload template and delivery retry (3 attempts with
+//!   backoff)
+//! - **Native notifications** — a Tauri desktop notification when enabled
+//!   (off by default; webhook delivery never depends on a window existing)
+//!
+//! Wired producers:
+//!
+//! - `task.finished` — new subtask prompt activity (from the /latest watcher)
+//! - `tool.failing` — a tool failed repeatedly within one task
+//! - `checkpoint.created` — new shadow git checkpoint commit
+//! - `circuit_breaker.opened` — a tool circuit breaker tripped
+//! - `jira.issue_assigned` — an issue appeared in the assigned-issues cache
+//! - `test.ping` — fired by `POST /system/webhooks/test`
+//!
+//! Event filters match exactly, or by prefix with a trailing `*`
+//! (`"task.*"`); an empty filter matches everything.
+
+use axum::{http::StatusCode, Json};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Error response — alias for the crate-wide structured error.
+pub type NotifyErrorResponse = crate::api::error::ApiError;
+
+const WEBHOOKS_DIR: &str = "jira-dashboard";
+const WEBHOOKS_FILE: &str = "webhooks.json";
+/// Seconds to wait before each retry after a failed delivery attempt.
+const RETRY_DELAYS_SECS: [u64; 2] = [5, 25];
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// One outbound webhook.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookConfig {
+    /// Stable ID (assigned on save when empty)
+    #[serde(default)]
+    pub id: String,
+    /// Endpoint URL; events are POSTed as JSON
+    pub url: String,
+    /// Event filter: exact names or `prefix.*` patterns; empty = all events
+    #[serde(default)]
+    pub events: Vec<String>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Optional payload template. Placeholders: `{{event}}`, `{{occurredAt}}`,
+    /// `{{payload}}` (the payload as JSON) and `{{payload.<key>}}` for
+    /// top-level payload fields. Must render to valid JSON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+}
+
+/// Notification settings persisted to `webhooks.json`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NotifySettings {
+    /// Show a Tauri desktop notification for each event
+    #[serde(default)]
+    pub native_notifications: bool,
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Return the webhooks file path, creating the directory if needed.
+fn webhooks_path() -> Option<PathBuf> {
+    let appdata = std::env::var("APPDATA").ok()?;
+    let dir = PathBuf::from(appdata).join(WEBHOOKS_DIR);
+    if !dir.exists() {
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            log::warn!("Failed to create webhooks dir {:?}: {}", dir, e);
+            return None;
+        }
+    }
+    Some(dir.join(WEBHOOKS_FILE))
+}
+
+fn load_settings() -> NotifySettings {
+    let Some(path) = webhooks_path() else {
+        return NotifySettings::default();
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+            log::warn!("Failed to parse webhooks file {:?}: {}", path, e);
+            NotifySettings::default()
+        }),
+        Err(_) => NotifySettings::default(), // first run — no file yet
+    }
+}
+
+fn save_settings(settings: &NotifySettings) -> Result<(), String> {
+    let path = webhooks_path().ok_or_else(|| "Webhooks directory unavailable".to_string())?;
+    let json = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize webhooks: {}", e))?;
+    std::fs::write(&path, &json).map_err(|e| format!("Failed to write webhooks: {}", e))
+}
+
+/// The currently effective notification settings.
+static SETTINGS: Lazy<RwLock<NotifySettings>> = Lazy::new(|| RwLock::new(load_settings()));
+
+/// App handle for native notifications — set once in the Tauri setup hook;
+/// stays `None` in headless mode.
+static APP_HANDLE: Lazy<RwLock<Option<tauri::AppHandle>>> = Lazy::new(|| RwLock::new(None));
+
+/// Store the app handle so [`emit`] can raise native notifications.
+pub fn set_app_handle(handle: tauri::AppHandle) {
+    *APP_HANDLE.write() = Some(handle);
+}
+
+// ============================================================================
+// Delivery tracking
+// ============================================================================
+
+/// Outcome of the most recent delivery to one webhook.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryStatus {
+    pub webhook_id: String,
+    /// Event that triggered the delivery
+    pub event: String,
+    /// When the delivery finished (ISO 8601)
+    pub at: String,
+    pub ok: bool,
+    /// Attempts made (1 = first try succeeded)
+    pub attempts: u32,
+    /// HTTP status or error detail of the final attempt
+    pub detail: String,
+}
+
+static LAST_DELIVERY: Lazy<RwLock<HashMap<String, DeliveryStatus>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+// ============================================================================
+// Emission
+// ============================================================================
+
+/// Fan an event out to all matching webhooks and (when enabled) a native
+/// notification. Non-blocking: deliveries run on spawned tasks. Safe to
+/// call with no webhooks configured — it's a no-op then.
+pub fn emit(event: &str, payload: serde_json::Value) {
+    let settings = SETTINGS.read().clone();
+
+    if settings.native_notifications {
+        native_notify(event, &payload);
+    }
+
+    let hooks: Vec<WebhookConfig> = settings
+        .webhooks
+        .iter()
+        .filter(|w| w.enabled && event_matches(&w.events, event))
+        .cloned()
+        .collect();
+    if hooks.is_empty() {
+        return;
+    }
+
+    let Ok(handle) = tokio::runtime::Handle::try_current() else {
+        log::warn!("Notify: no async runtime — dropping event '{}'", event);
+        return;
+    };
+
+    let occurred_at = chrono::Utc::now().to_rfc3339();
+    for hook in hooks {
+        let body = build_body(&hook, event, &occurred_at, &payload);
+        let event = event.to_string();
+        handle.spawn(deliver(hook, event, body));
+    }
+}
+
+/// Check an event name against a webhook's filter.
+fn event_matches(patterns: &[String], event: &str) -> bool {
+    if patterns.is_empty() {
+        return true;
+    }
+    patterns.iter().any(|p| {
+        if let Some(prefix) = p.strip_suffix('*') {
+            event.starts_with(prefix)
+        } else {
+            p == event
+        }
+    })
+}
+
+/// Build the JSON body for one webhook: the rendered template when one is
+/// set (falling back to the default envelope if it doesn't render to valid
+/// JSON), otherwise `{event, occurredAt, payload}`.
+fn build_body(
+    hook: &WebhookConfig,
+    event: &str,
+    occurred_at: &str,
+    payload: &serde_json::Value,
+) -> serde_json::Value {
+    let default_body = serde_json::json!({
+        "event": event,
+        "occurredAt": occurred_at,
+        "payload": payload,
+    });
+    let Some(template) = &hook.template else {
+        return default_body;
+    };
+    let rendered = render_template(template, event, occurred_at, payload);
+    match serde_json::from_str(&rendered) {
+        Ok(body) => body,
+        Err(e) => {
+            log::warn!(
+                "Notify: webhook {} template rendered invalid JSON ({}); using default body",
+                hook.id,
+                e
+            );
+            default_body
+        }
+    }
+}
+
+/// Substitute template placeholders. String payload values are inserted
+/// JSON-escaped (without surrounding quotes) so templates can place them
+/// inside their own quoted strings.
+fn render_template(
+    template: &str,
+    event: &str,
+    occurred_at: &str,
+    payload: &serde_json::Value,
+) -> String {
+    let mut out = template
+        .replace("{{event}}", event)
+        .replace("{{occurredAt}}", occurred_at)
+        .replace("{{payload}}", &payload.to_string());
+    if let Some(map) = payload.as_object() {
+        for (key, value) in map {
+            let placeholder = format!("{{{{payload.{}}}}}", key);
+            if !out.contains(&placeholder) {
+                continue;
+            }
+            let replacement = match value {
+                serde_json::Value::String(s) => {
+                    let quoted = serde_json::Value::String(s.clone()).to_string();
+                    quoted[1..quoted.len() - 1].to_string()
+                }
+                other => other.to_string(),
+            };
+            out = out.replace(&placeholder, &replacement);
+        }
+    }
+    out
+}
+
+/// POST the body to the webhook, retrying with backoff. Records the final
+/// outcome in the delivery log shown by `GET /system/webhooks`.
+async fn deliver(hook: WebhookConfig, event: String, body: serde_json::Value) {
+    let client = crate::http::outbound_client();
+    let max_attempts = RETRY_DELAYS_SECS.len() as u32 + 1;
+    let mut attempts = 0u32;
+    let mut detail = String::new();
+    let mut ok = false;
+
+    while attempts < max_attempts {
+        if attempts > 0 {
+            let delay = RETRY_DELAYS_SECS[(attempts - 1) as usize];
+            tokio::time::sleep(std::time::Duration::from_secs(delay)).await;
+        }
+        attempts += 1;
+
+        match client.post(&hook.url).json(&body).send().await {
+            Ok(response) if response.status().is_success() => {
+                ok = true;
+                detail = format!("HTTP {}", response.status().as_u16());
+                break;
+            }
+            Ok(response) => {
+                detail = format!("HTTP {}", response.status().as_u16());
+            }
+            Err(e) => {
+                detail = format!("{}", e);
+            }
+        }
+        log::warn!(
+            "Notify: webhook {} delivery attempt {}/{} for '{}' failed: {}",
+            hook.id,
+            attempts,
+            max_attempts,
+            event,
+            detail
+        );
+    }
+
+    if ok {
+        log::info!(
+            "Notify: delivered '{}' to webhook {} (attempt {})",
+            event,
+            hook.id,
+            attempts
+        );
+    }
+
+    LAST_DELIVERY.write().insert(
+        hook.id.clone(),
+        DeliveryStatus {
+            webhook_id: hook.id,
+            event,
+            at: chrono::Utc::now().to_rfc3339(),
+            ok,
+            attempts,
+            detail,
+        },
+    );
+}
+
+/// Raise a Tauri desktop notification (windowed mode only).
+fn native_notify(event: &str, payload: &serde_json::Value) {
+    let Some(app) = APP_HANDLE.read().clone() else {
+        return;
+    };
+    use tauri_plugin_notification::NotificationExt;
+    let body = payload
+        .get("summary")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| payload.to_string());
+    if let Err(e) = app
+        .notification()
+        .builder()
+        .title(format!("Cline X-Ray — {}", event))
+        .body(body)
+        .show()
+    {
+        log::warn!("Notify: native notification failed: {}", e);
+    }
+    // The notification plugin doesn't expose desktop click events, so the
+    // focus hint is sent to the webview alongside the toast — the UI
+    // listens for `notify://focus-task` and selects the task when the
+    // window regains focus.
+    if let Some(task_id) = payload.get("taskId").and_then(|v| v.as_str()) {
+        use tauri::Emitter;
+        if let Err(e) = app.emit("notify://focus-task", task_id.to_string()) {
+            log::warn!("Notify: failed to emit focus-task event: {}", e);
+        }
+    }
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Response for the webhooks status endpoint.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhooksResponse {
+    pub settings: NotifySettings,
+    /// Most recent delivery outcome per webhook
+    pub deliveries: Vec<DeliveryStatus>,
+}
+
+/// Response for the webhook test endpoint.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TestWebhooksResponse {
+    /// Webhooks the test event was dispatched to
+    pub dispatched: usize,
+}
+
+/// Get notification settings
+///
+/// Returns the configured webhooks plus the most recent delivery outcome
+/// for each.
+#[utoipa::path(
+    get,
+    path = "/system/webhooks",
+    responses(
+        (status = 200, description = "Notification settings and delivery status", body = WebhooksResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "system"
+)]
+pub async fn get_webhooks_handler() -> Json<WebhooksResponse> {
+    let settings = SETTINGS.read().clone();
+    let mut deliveries: Vec<DeliveryStatus> = LAST_DELIVERY.read().values().cloned().collect();
+    deliveries.sort_by(|a, b| a.webhook_id.cmp(&b.webhook_id));
+    Json(WebhooksResponse {
+        settings,
+        deliveries,
+    })
+}
+
+/// Replace notification settings
+///
+/// Persists the full settings document; webhooks without an ID are assigned
+/// one. Takes effect immediately for subsequent events.
+#[utoipa::path(
+    put,
+    path = "/system/webhooks",
+    request_body = NotifySettings,
+    responses(
+        (status = 200, description = "Saved settings", body = NotifySettings),
+        (status = 400, description = "Invalid settings", body = NotifyErrorResponse),
+        (status = 500, description = "Failed to persist settings", body = NotifyErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "system"
+)]
+pub async fn put_webhooks_handler(
+    Json(mut settings): Json<NotifySettings>,
+) -> Result<Json<NotifySettings>, (StatusCode, Json<NotifyErrorResponse>)> {
+    for hook in &mut settings.webhooks {
+        if hook.url.trim().is_empty() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(NotifyErrorResponse {
+                    error: "Webhook URL must not be empty".to_string(),
+                    code: 400,
+                }),
+            ));
+        }
+        if hook.id.is_empty() {
+            hook.id = uuid::Uuid::new_v4().to_string();
+        }
+    }
+    save_settings(&settings).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(NotifyErrorResponse { error: e, code: 500 }),
+        )
+    })?;
+    *SETTINGS.write() = settings.clone();
+    crate::audit::record(
+        "notify.settings_save",
+        serde_json::json!({
+            "webhookCount": settings.webhooks.len(),
+            "nativeNotifications": settings.native_notifications,
+        }),
+        true,
+        "Notification settings saved",
+    );
+    log::info!(
+        "Notify: saved settings ({} webhooks, native={})",
+        settings.webhooks.len(),
+        settings.native_notifications
+    );
+    Ok(Json(settings))
+}
+
+/// Fire a test event
+///
+/// Emits a `test.ping` event through the normal delivery path so webhook
+/// configuration can be verified end to end.
+#[utoipa::path(
+    post,
+    path = "/system/webhooks/test",
+    responses(
+        (status = 200, description = "Test event dispatched", body = TestWebhooksResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "system"
+)]
+pub async fn test_webhooks_handler() -> Json<TestWebhooksResponse> {
+    let dispatched = {
+        let settings = SETTINGS.read();
+        settings
+            .webhooks
+            .iter()
+            .filter(|w| w.enabled && event_matches(&w.events, "test.ping"))
+            .count()
+    };
+    emit(
+        "test.ping",
+        serde_json::json!({
+            "summary": "Test notification from Cline X-Ray",
+        }),
+    );
+    Json(TestWebhooksResponse { dispatched })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_filter_matches_everything() {
+        assert!(event_matches(&[], "task.finished"));
+    }
+
+    #[test]
+    fn exact_and_prefix_matching() {
+        let patterns = vec!["task.*".to_string(), "test.ping".to_string()];
+        assert!(event_matches(&patterns, "task.finished"));
+        assert!(event_matches(&patterns, "test.ping"));
+        assert!(!event_matches(&patterns, "circuit_breaker.opened"));
+    }
+
+    #[test]
+    fn template_substitutes_placeholders() {
+        let payload = serde_json::json!({"taskId": "123", "count": 2});
+        let rendered = render_template(
+            r#"{"text": "{{event}} for {{payload.taskId}}", "n": {{payload.count}}}"#,
+            "task.finished",
+            "2025-01-01T00:00:00Z",
+            &payload,
+        );
+        let value: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(value["text"], "task.finished for 123");
+        assert_eq!(value["n"], 2);
+    }
+
+    #[test]
+    fn template_escapes_string_values() {
+        let payload = serde_json::json!({"summary": "say \"hi\""});
+        let rendered = render_template(
+            r#"{"text": "{{payload.summary}}"}"#,
+            "test.ping",
+            "2025-01-01T00:00:00Z",
+            &payload,
+        );
+        let value: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(value["text"], "say \"hi\"");
+    }
+
+    #[test]
+    fn invalid_template_falls_back_to_envelope() {
+        let hook = WebhookConfig {
+            id: "h1".to_string(),
+            url: "http://localhost/hook".to_string(),
+            events: vec![],
+            enabled: true,
+            template: Some("not json {{event}}".to_string()),
+        };
+        let body = build_body(
+            &hook,
+            "test.ping",
+            "2025-01-01T00:00:00Z",
+            &serde_json::json!({}),
+        );
+        assert_eq!(body["event"], "test.ping");
+    }
+}
@@ -0,0 +1,176 @@
+//! Global offline mode with explicit degradation.
+//!
+//! When offline — auto-detected by a background connectivity probe or
+//! forced via `PUT /system/offline` — endpoints that need the network
+//! (Jira, LLM calls) return `503` with a machine-readable `"offline"`
+//! category instead of burning retries against an unreachable upstream.
+//! Everything that serves purely from disk (history, changes, latest)
+//! keeps working untouched.
+
+use axum::{
+    extract::Request,
+    http::StatusCode,
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// How often the background probe re-checks connectivity.
+const PROBE_INTERVAL_SECS: u64 = 30;
+const PROBE_TIMEOUT_SECS: u64 = 5;
+/// Host probed for reachability — the Gemini endpoint every LLM call needs.
+const PROBE_HOST: (&str, u16) = ("generativelanguage.googleapis.com", 443);
+
+/// User override: `Some(true/false)` forces the mode, `None` = auto.
+static OVERRIDE: Lazy<RwLock<Option<bool>>> = Lazy::new(|| RwLock::new(None));
+/// Latest auto-probe verdict.
+static AUTO_OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Whether offline mode is currently active (override wins over auto).
+pub fn is_offline() -> bool {
+    (*OVERRIDE.read()).unwrap_or_else(|| AUTO_OFFLINE.load(Ordering::Relaxed))
+}
+
+/// Force offline (`Some(true)`), force online (`Some(false)`) or return to
+/// auto-detection (`None`).
+pub fn set_override(value: Option<bool>) {
+    *OVERRIDE.write() = value;
+}
+
+/// Current offline state for the UI and `GET /system/offline`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct OfflineStatus {
+    /// Whether offline degradation is active right now
+    pub offline: bool,
+    /// Where the verdict came from: "override" or "auto"
+    pub source: String,
+    /// What the background connectivity probe last concluded
+    pub auto_detected_offline: bool,
+}
+
+/// Snapshot the current state.
+pub fn status() -> OfflineStatus {
+    let override_value = *OVERRIDE.read();
+    let auto = AUTO_OFFLINE.load(Ordering::Relaxed);
+    OfflineStatus {
+        offline: override_value.unwrap_or(auto),
+        source: if override_value.is_some() {
+            "override".to_string()
+        } else {
+            "auto".to_string()
+        },
+        auto_detected_offline: auto,
+    }
+}
+
+/// One blocking connectivity check: can any resolved address be reached?
+fn probe_once() -> bool {
+    use std::net::ToSocketAddrs;
+    let Ok(addrs) = PROBE_HOST.to_socket_addrs() else {
+        return false;
+    };
+    addrs.take(3).any(|addr| {
+        std::net::TcpStream::connect_timeout(&addr, Duration::from_secs(PROBE_TIMEOUT_SECS))
+            .is_ok()
+    })
+}
+
+/// Start the background connectivity probe. Logs transitions so the log
+/// explains why upstream endpoints suddenly return 503.
+pub fn spawn_probe() {
+    std::thread::spawn(|| loop {
+        let offline = !probe_once();
+        let previous = AUTO_OFFLINE.swap(offline, Ordering::Relaxed);
+        if offline != previous {
+            if offline {
+                log::warn!("Connectivity probe failed — auto offline mode is active");
+            } else {
+                log::info!("Connectivity restored — auto offline mode cleared");
+            }
+        }
+        std::thread::sleep(Duration::from_secs(PROBE_INTERVAL_SECS));
+    });
+}
+
+/// The 503 payload gated routes return while offline. `category` is the
+/// machine-readable marker clients should branch on.
+fn offline_response() -> Response {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(serde_json::json!({
+            "error": "Offline mode active — upstream Jira/LLM calls are disabled",
+            "code": 503,
+            "category": "offline",
+        })),
+    )
+        .into_response()
+}
+
+/// Route layer for endpoints that need the network: short-circuits with the
+/// offline 503 instead of letting the request reach an unreachable upstream.
+pub async fn offline_guard(request: Request, next: Next) -> Response {
+    if is_offline() {
+        return offline_response();
+    }
+    next.run(request).await
+}
+
+// ============ Handlers ============
+
+/// Request body for the offline override endpoint
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct OfflineOverrideRequest {
+    /// `true`/`false` forces the mode; `null` returns to auto-detection
+    pub offline: Option<bool>,
+}
+
+/// Get offline mode state
+///
+/// Reports whether offline degradation is active, whether it was forced or
+/// auto-detected, and the background probe's latest verdict.
+#[utoipa::path(
+    get,
+    path = "/system/offline",
+    responses(
+        (status = 200, description = "Current offline state", body = OfflineStatus)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "system"
+)]
+pub async fn get_offline_handler() -> Json<OfflineStatus> {
+    Json(status())
+}
+
+/// Set or clear the offline override
+///
+/// Forces offline mode on or off, or (with `offline: null`) hands control
+/// back to the connectivity probe. While offline, Jira/LLM endpoints return
+/// 503 with category `offline`; disk-backed endpoints keep working.
+#[utoipa::path(
+    put,
+    path = "/system/offline",
+    request_body = OfflineOverrideRequest,
+    responses(
+        (status = 200, description = "New offline state", body = OfflineStatus)
+    ),
+    security(("bearerAuth" = [])),
+    tag = "system"
+)]
+pub async fn put_offline_handler(
+    Json(request): Json<OfflineOverrideRequest>,
+) -> Json<OfflineStatus> {
+    set_override(request.offline);
+    crate::audit::record(
+        "system.offline",
+        serde_json::json!({ "override": request.offline }),
+        true,
+        &format!("offline mode now {}", if is_offline() { "active" } else { "inactive" }),
+    );
+    Json(status())
+}
@@ -20,7 +20,9 @@ use utoipa::{Modify, OpenApi};
         crate::api::handlers::health_handler,
         crate::api::handlers::jira_list_handler,
         crate::api::handlers::chat_handler,
+        crate::api::handlers::count_tokens_handler,
         crate::api::handlers::list_models_handler,
+        crate::api::handlers::refresh_models_handler,
         // Tool runtime - Agent-facing endpoints only
         crate::tool_runtime::handlers::list_tools_handler,      // GET /tools - Discovery
         crate::tool_runtime::handlers::invoke_tool_handler,     // POST /tools/invoke - Execution
@@ -28,70 +30,166 @@ use utoipa::{Modify, OpenApi};
         crate::shadow_git::handlers::list_workspaces_handler,   // GET /changes/workspaces
         crate::shadow_git::handlers::list_tasks_handler,        // GET /changes/tasks
         crate::shadow_git::handlers::task_diff_handler,         // GET /changes/tasks/:taskId/diff
+        crate::shadow_git::handlers::file_diff_handler,         // GET /changes/tasks/:taskId/diff/file
+        crate::shadow_git::handlers::task_diff_patch_handler,   // GET /changes/tasks/:taskId/diff.patch
+        crate::shadow_git::handlers::range_diff_handler,        // GET /changes/tasks/:taskId/range-diff
+        crate::shadow_git::handlers::file_history_handler,      // GET /changes/tasks/:taskId/files/:path/history
+        crate::shadow_git::handlers::blame_handler,             // GET /changes/tasks/:taskId/blame
+        crate::shadow_git::handlers::tree_handler,              // GET /changes/tree
+        crate::shadow_git::handlers::search_handler,            // GET /changes/search
         crate::shadow_git::handlers::list_steps_handler,        // GET /changes/tasks/:taskId/steps
         crate::shadow_git::handlers::step_diff_handler,         // GET /changes/tasks/:taskId/steps/:index/diff
         crate::shadow_git::handlers::subtask_diff_handler,      // GET /changes/tasks/:taskId/subtasks/:subtaskIndex/diff
+        crate::shadow_git::handlers::task_bundle_handler,       // GET /changes/tasks/:taskId/bundle
+        crate::shadow_git::handlers::apply_task_handler,        // POST /changes/tasks/:taskId/apply
+        crate::shadow_git::handlers::summarize_task_handler,    // POST /changes/tasks/:taskId/summarize
+        crate::shadow_git::changesignore::get_ignore_handler,   // GET /changes/ignore
+        crate::shadow_git::changesignore::put_ignore_handler,   // PUT /changes/ignore
+        crate::shadow_git::changesignore::preview_ignore_handler, // POST /changes/ignore/preview
+        crate::shadow_git::handlers::restore_files_handler,     // POST /changes/restore
+        crate::shadow_git::handlers::nuke_task_handler,         // POST /changes/tasks/:taskId/nuke
         crate::shadow_git::handlers::nuke_workspace_handler,    // POST /changes/workspaces/:id/nuke
+        crate::shadow_git::handlers::gc_workspace_handler,      // POST /changes/workspaces/:id/gc
+        crate::shadow_git::handlers::workspace_activity_handler, // GET /changes/workspaces/:id/activity
         // Conversation History
         crate::conversation_history::handlers::list_history_tasks_handler, // GET /history/tasks
         crate::conversation_history::handlers::get_task_detail_handler,    // GET /history/tasks/:taskId
         crate::conversation_history::handlers::get_task_messages_handler,  // GET /history/tasks/:taskId/messages
         crate::conversation_history::handlers::get_single_message_handler, // GET /history/tasks/:taskId/messages/:index
+        crate::conversation_history::handlers::search_task_messages_handler, // GET /history/tasks/:taskId/messages/search
         crate::conversation_history::handlers::get_task_tools_handler,     // GET /history/tasks/:taskId/tools
         crate::conversation_history::handlers::get_task_thinking_handler,  // GET /history/tasks/:taskId/thinking
         crate::conversation_history::handlers::get_task_files_handler,     // GET /history/tasks/:taskId/files
         crate::conversation_history::handlers::get_task_subtasks_handler,  // GET /history/tasks/:taskId/subtasks
         crate::conversation_history::handlers::get_history_stats_handler,  // GET /history/stats
+        crate::conversation_history::handlers::get_activity_heatmap_handler, // GET /history/stats/activity
+        crate::conversation_history::handlers::get_model_stats_handler,    // GET /history/stats/models
+        crate::conversation_history::handlers::export_task_handler,        // GET /history/tasks/:taskId/export
+        crate::conversation_history::handlers::export_all_tasks_handler,   // GET /history/export
+        crate::conversation_history::handlers::get_task_usage_handler,     // GET /history/tasks/:taskId/usage
+        crate::conversation_history::handlers::get_history_loops_handler,  // GET /history/loops
+        crate::conversation_history::handlers::get_task_timeline_handler,  // GET /history/tasks/:taskId/timeline
+        crate::conversation_history::handlers::get_context_growth_handler, // GET /history/tasks/:taskId/context-growth
+        crate::conversation_history::handlers::get_task_prompts_handler,   // GET /history/tasks/:taskId/prompts
+        crate::conversation_history::handlers::get_task_note_handler,      // GET /history/tasks/:taskId/notes
+        crate::conversation_history::handlers::put_task_note_handler,      // PUT /history/tasks/:taskId/notes
+        crate::conversation_history::handlers::archive_task_handler,       // POST /history/tasks/:taskId/archive
+        crate::conversation_history::handlers::delete_task_handler,        // DELETE /history/tasks/:taskId
+        crate::conversation_history::handlers::get_retention_policy_handler, // GET /history/retention
+        crate::conversation_history::handlers::put_retention_policy_handler, // PUT /history/retention
+        crate::conversation_history::handlers::preview_retention_handler,  // GET /history/retention/preview
+        crate::conversation_history::handlers::run_retention_handler,      // POST /history/retention/run
+        crate::conversation_history::handlers::compare_tasks_handler,      // GET /history/compare
         // Latest composite endpoint
         crate::latest::handler::get_latest_handler,                        // GET /latest
+        crate::latest::stream::latest_stream_handler,                      // GET /latest/stream
+        crate::latest::handler::latest_recent_handler,                     // GET /latest/recent
     ),
     components(
         schemas(
             crate::api::handlers::HealthResponse,
             crate::api::handlers::JiraIssueSummary,
             crate::api::handlers::JiraListResponse,
-            crate::api::handlers::ErrorResponse,
+            crate::api::error::ApiError,
             crate::api::handlers::ChatRequest,
             crate::api::handlers::ChatMessage,
+            crate::api::handlers::ImageAttachment,
             crate::api::handlers::ChatResponse,
+            crate::api::handlers::ChatToolCall,
+            crate::api::handlers::CountTokensRequest,
+            crate::api::handlers::CountTokensResponse,
             crate::api::handlers::GeminiModel,
+            crate::api::handlers::ModelPricing,
             crate::api::handlers::GeminiModelsResponse,
             // Tool runtime - Agent-facing schemas only
             crate::tool_runtime::ToolInvokeRequest,
             crate::tool_runtime::ToolCallSource,
             crate::tool_runtime::ValidationResult,
             crate::tool_runtime::ToolConfig,
+            crate::tool_runtime::FixtureStrategy,
+            crate::tool_runtime::TruncationStrategy,
             crate::tool_runtime::ArgClamp,
             crate::tool_runtime::ToolInfo,
             crate::tool_runtime::handlers::ToolInvokeResponse,
-            crate::tool_runtime::handlers::ToolsListResponse,
-            crate::tool_runtime::handlers::ToolErrorResponse,
+            crate::api::pagination::Page<crate::tool_runtime::ToolInfo>,
             // Shadow Git / Changes schemas
             crate::shadow_git::WorkspaceInfo,
             crate::shadow_git::WorkspacesResponse,
             crate::shadow_git::ClineTaskSummary,
-            crate::shadow_git::TasksResponse,
+            crate::api::pagination::Page<crate::shadow_git::ClineTaskSummary>,
             crate::shadow_git::CheckpointStep,
             crate::shadow_git::StepsResponse,
             crate::shadow_git::DiffFile,
             crate::shadow_git::DiffResult,
-            crate::shadow_git::handlers::ChangesErrorResponse,
+            crate::shadow_git::IntralineRange,
+            crate::shadow_git::IntralineLine,
+            crate::shadow_git::FileHistoryEntry,
+            crate::shadow_git::FileHistoryResponse,
+            crate::shadow_git::BlameLine,
+            crate::shadow_git::BlameResponse,
+            crate::shadow_git::TreeEntry,
+            crate::shadow_git::TreeResponse,
+            crate::shadow_git::SearchMatch,
+            crate::shadow_git::SearchResponse,
+            crate::shadow_git::apply::ApplyRequest,
+            crate::shadow_git::apply::ApplyResponse,
+            crate::shadow_git::FileSummary,
+            crate::shadow_git::TaskDiffSummary,
+            crate::shadow_git::SummarizeRequest,
+            crate::shadow_git::SummarizeResponse,
+            crate::shadow_git::changesignore::IgnoreUpdateRequest,
+            crate::shadow_git::changesignore::IgnoreResponse,
+            crate::shadow_git::changesignore::IgnorePreviewRequest,
+            crate::shadow_git::changesignore::PatternPreview,
+            crate::shadow_git::changesignore::IgnorePreviewResponse,
+            crate::shadow_git::restore::RestoreRequest,
+            crate::shadow_git::restore::RestoredFile,
+            crate::shadow_git::restore::RestoreResponse,
             crate::shadow_git::cleanup::NukeWorkspaceResponse,
+            crate::shadow_git::cleanup::NukeTaskResponse,
+            crate::shadow_git::cleanup::GcWorkspaceResponse,
+            crate::shadow_git::ActivityDay,
+            crate::shadow_git::ActivityResponse,
             // Conversation History schemas
             crate::conversation_history::TaskHistorySummary,
-            crate::conversation_history::TaskHistoryListResponse,
+            crate::api::pagination::Page<crate::conversation_history::TaskHistorySummary>,
             crate::conversation_history::TaskDetailResponse,
             crate::conversation_history::ConversationMessage,
             crate::conversation_history::ContentBlockSummary,
             crate::conversation_history::ToolCallDetail,
             crate::conversation_history::FileInContextDetail,
             crate::conversation_history::ModelUsageDetail,
+            crate::conversation_history::FocusChainItem,
             crate::conversation_history::EnvironmentDetail,
             crate::conversation_history::PaginatedMessagesResponse,
             crate::conversation_history::FullMessageResponse,
             crate::conversation_history::FullContentBlock,
             crate::conversation_history::ToolCallTimelineEntry,
             crate::conversation_history::ToolCallTimelineResponse,
+            crate::conversation_history::ToolLoopEntry,
+            crate::conversation_history::TaskLoopsSummary,
+            crate::conversation_history::LoopsAggregateResponse,
+            crate::conversation_history::TimelineEvent,
+            crate::conversation_history::TaskTimelineResponse,
+            crate::conversation_history::RetentionPolicy,
+            crate::conversation_history::RetentionCandidate,
+            crate::conversation_history::RetentionPlan,
+            crate::conversation_history::RetentionRunResponse,
+            crate::conversation_history::ModelStats,
+            crate::conversation_history::ModelStatsResponse,
+            crate::conversation_history::TaskCompareSide,
+            crate::conversation_history::TaskCompareResponse,
+            crate::conversation_history::ArchiveTaskResponse,
+            crate::conversation_history::DeleteTaskResponse,
+            crate::conversation_history::TaskNote,
+            crate::conversation_history::UpdateNoteRequest,
+            crate::conversation_history::PromptEntry,
+            crate::conversation_history::TaskPromptsResponse,
+            crate::conversation_history::MessageSearchQuery,
+            crate::conversation_history::MessageSearchMatch,
+            crate::conversation_history::MessageSearchResponse,
+            crate::conversation_history::ContextGrowthPoint,
+            crate::conversation_history::ContextGrowthResponse,
             crate::conversation_history::TaskToolsQuery,
             crate::conversation_history::ThinkingBlockEntry,
             crate::conversation_history::ThinkingBlocksResponse,
@@ -99,12 +197,18 @@ use utoipa::{Modify, OpenApi};
             crate::conversation_history::TaskFilesResponse,
             crate::conversation_history::TaskFilesQuery,
             crate::conversation_history::HistoryStatsResponse,
+            crate::conversation_history::ActivityHeatmapResponse,
+            crate::conversation_history::ExportQuery,
+            crate::conversation_history::ApiRequestUsageEntry,
+            crate::conversation_history::TaskUsageResponse,
             crate::conversation_history::SubtaskEntry,
             crate::conversation_history::SubtasksResponse,
-            crate::conversation_history::HistoryErrorResponse,
             // Latest composite schemas
+            crate::latest::LatestFileItem,
             crate::latest::LatestResponse,
-            crate::latest::LatestErrorResponse,
+            crate::latest::RecentItem,
+            crate::latest::RecentResponse,
+            crate::latest::stream::LatestStreamEvent,
         )
     ),
     modifiers(&SecurityAddon),
@@ -140,40 +244,132 @@ pub struct PublicApiDoc;
     ),
     paths(
         // Logging endpoints
+        crate::api::handlers::system_config_handler,
+        crate::api::handlers::system_backup_handler,
+        crate::api::handlers::system_restore_handler,
+        crate::api::handlers::system_jobs_handler,
+        crate::diagnostics::system_diagnostics_handler,
+        crate::logging::system_logs_tail_handler,
+        crate::api::handlers::update_providers_handler,
+        crate::offline::get_offline_handler,
+        crate::offline::put_offline_handler,
+        crate::notify::get_webhooks_handler,
+        crate::notify::put_webhooks_handler,
+        crate::notify::test_webhooks_handler,
         crate::api::handlers::access_logs_handler,
+        crate::api::handlers::access_logs_summary_handler,
+        crate::api::handlers::auth_audit_handler,
+        crate::api::handlers::system_audit_handler,
         crate::api::handlers::clear_access_logs_handler,
         crate::api::handlers::inference_logs_handler,
         crate::api::handlers::clear_inference_logs_handler,
+        crate::api::handlers::inference_log_detail_handler,
         // Tool runtime admin endpoints
         crate::tool_runtime::handlers::get_tool_logs_handler,
         crate::tool_runtime::handlers::clear_tool_logs_handler,
         crate::tool_runtime::handlers::get_config_handler,
         crate::tool_runtime::handlers::update_config_handler,
+        crate::tool_runtime::handlers::get_runtime_config_handler,
+        crate::tool_runtime::handlers::put_runtime_config_handler,
+        crate::tool_runtime::handlers::get_tool_config_handler,
         crate::tool_runtime::handlers::configure_tool_handler,
+        crate::tool_runtime::handlers::get_budgets_handler,
+        crate::tool_runtime::handlers::reset_budgets_handler,
+        crate::tool_runtime::handlers::reset_tool_budget_handler,
         crate::tool_runtime::handlers::get_circuit_breakers_handler,
         crate::tool_runtime::handlers::reset_circuit_breakers_handler,
         crate::tool_runtime::handlers::reset_tool_circuit_breaker_handler,
+        crate::tool_runtime::handlers::get_runtime_metrics_handler,
+        crate::tool_runtime::handlers::export_logs_handler,
+        crate::tool_runtime::handlers::list_macros_handler,
+        crate::tool_runtime::handlers::register_macro_handler,
+        crate::tool_runtime::handlers::delete_macro_handler,
+        crate::tool_runtime::handlers::reset_breaker_handler,
+        crate::tool_runtime::handlers::list_profiles_handler,
+        crate::tool_runtime::handlers::save_profile_handler,
+        crate::tool_runtime::handlers::activate_profile_handler,
+        crate::tool_runtime::handlers::delete_profile_handler,
+        crate::tool_runtime::handlers::replay_log_handler,
+        crate::tool_runtime::handlers::list_fixture_sessions_handler,
+        crate::tool_runtime::handlers::start_fixture_session_handler,
+        crate::tool_runtime::handlers::stop_fixture_session_handler,
+        crate::tool_runtime::handlers::activate_fixture_set_handler,
         crate::tool_runtime::handlers::get_fixtures_handler,
         crate::tool_runtime::handlers::import_fixtures_handler,
         crate::tool_runtime::handlers::clear_fixtures_handler,
         crate::tool_runtime::handlers::enable_all_tools_handler,
         crate::tool_runtime::handlers::disable_all_tools_handler,
+        crate::tool_runtime::run_eval_handler,
     ),
     components(
         schemas(
+            crate::api::error::ApiError,
+            crate::api::handlers::RestoreRequest,
+            crate::backup::BackupResponse,
+            crate::backup::RestoreResponse,
+            crate::scheduler::JobsResponse,
+            crate::diagnostics::DiagnosticsResponse,
+            crate::diagnostics::DiagnosticCheck,
+            crate::logging::LogTailResponse,
+            crate::api::handlers::UpdateProvidersRequest,
+            crate::api::handlers::UpdateProvidersResponse,
+            crate::offline::OfflineStatus,
+            crate::offline::OfflineOverrideRequest,
+            crate::scheduler::JobStatus,
+            crate::notify::NotifySettings,
+            crate::notify::WebhookConfig,
+            crate::notify::DeliveryStatus,
+            crate::notify::WebhooksResponse,
+            crate::notify::TestWebhooksResponse,
             crate::api::handlers::AccessLogsResponse,
+            crate::api::handlers::AccessLogSummaryResponse,
+            crate::api::handlers::RouteStats,
+            crate::api::handlers::ClientStats,
+            crate::api::handlers::AuthAuditResponse,
+            crate::api::middleware::AuthAuditEntry,
+            crate::api::middleware::LockedClient,
+            crate::api::handlers::SystemAuditResponse,
+            crate::audit::AuditEntry,
             crate::api::handlers::InferenceLogsResponse,
+            crate::api::handlers::InferenceLogDetailResponse,
+            crate::state::InferenceExchange,
             // Tool runtime admin schemas
             crate::tool_runtime::ToolCallResult,
             crate::tool_runtime::GlobalRuntimeConfig,
             crate::tool_runtime::ToolExecutionLog,
+            crate::tool_runtime::ToolBudget,
+            crate::tool_runtime::BudgetState,
             crate::tool_runtime::CircuitBreakerState,
             crate::tool_runtime::CircuitState,
-            crate::tool_runtime::handlers::ToolExecutionLogsResponse,
+            crate::api::pagination::Page<crate::tool_runtime::ToolExecutionLog>,
             crate::tool_runtime::handlers::RuntimeConfigResponse,
+            crate::tool_runtime::handlers::BudgetStatusResponse,
             crate::tool_runtime::handlers::CircuitBreakerStatusResponse,
             crate::tool_runtime::handlers::FixturesResponse,
+            crate::tool_runtime::handlers::RuntimeMetricsResponse,
+            crate::tool_runtime::handlers::MacrosResponse,
+            crate::tool_runtime::MacroTool,
+            crate::tool_runtime::MacroStep,
+            crate::tool_runtime::EvalRequest,
+            crate::tool_runtime::EvalCase,
+            crate::tool_runtime::ExpectedToolCall,
+            crate::tool_runtime::PredictedToolCall,
+            crate::tool_runtime::EvalCaseResult,
+            crate::tool_runtime::ModelEvalReport,
+            crate::tool_runtime::EvalResponse,
+            crate::tool_runtime::handlers::ProfilesResponse,
+            crate::tool_runtime::handlers::SaveProfileRequest,
+            crate::tool_runtime::ProfileInfo,
+            crate::tool_runtime::RuntimeProfile,
+            crate::tool_runtime::handlers::ReplayLogRequest,
+            crate::tool_runtime::handlers::ReplayLogResponse,
+            crate::tool_runtime::ToolMetrics,
+            crate::tool_runtime::handlers::FixtureSessionsResponse,
+            crate::tool_runtime::handlers::FixtureSetResponse,
+            crate::tool_runtime::handlers::StartSessionRequest,
+            crate::tool_runtime::FixtureSetInfo,
             crate::tool_runtime::handlers::UpdateGlobalConfigRequest,
+            crate::tool_runtime::handlers::UpdateRuntimeConfigRequest,
             crate::tool_runtime::handlers::ConfigureToolRequest,
         )
     ),
@@ -203,3 +399,223 @@ impl Modify for SecurityAddon {
         }
     }
 }
+
+// ============================================================================
+// SPEC VERSIONING & CHANGELOG
+// ============================================================================
+
+/// File (under the app config dir) holding the last spec snapshot seen by
+/// `GET /openapi/changelog`. Diffed against the live spec on each call.
+const SPEC_SNAPSHOT_FILE: &str = "openapi_spec.json";
+
+/// Serialize a spec and stamp it with version metadata:
+///
+/// - `info.x-build-version` — crate version the server was built from
+/// - `info.x-spec-hash` — content hash of the unstamped spec, so consumers
+///   can detect any change with a single string comparison
+///
+/// The hash is computed before stamping (otherwise the hash would hash itself).
+pub fn stamped_spec_value(openapi: utoipa::openapi::OpenApi) -> serde_json::Value {
+    let mut value = serde_json::to_value(&openapi).unwrap_or(serde_json::json!({}));
+    let hash = spec_hash(&value);
+    if let Some(info) = value.get_mut("info").and_then(|i| i.as_object_mut()) {
+        info.insert(
+            "x-build-version".to_string(),
+            serde_json::Value::String(env!("CARGO_PKG_VERSION").to_string()),
+        );
+        info.insert("x-spec-hash".to_string(), serde_json::Value::String(hash));
+    }
+    value
+}
+
+/// Content hash of a serialized spec (same 16-hex-digit format as the ETag
+/// middleware uses for response bodies).
+pub fn spec_hash(spec: &serde_json::Value) -> String {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    spec.to_string().hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Response for GET /openapi/changelog — spec drift since the last snapshot.
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecChangelogResponse {
+    /// Hash of the currently served spec
+    pub current_hash: String,
+    /// Hash of the previously persisted spec (absent on first run)
+    pub previous_hash: Option<String>,
+    /// Whether anything changed since the snapshot
+    pub changed: bool,
+    /// Whether any change is breaking (removed paths, operations or schemas)
+    pub breaking: bool,
+    /// Paths present now but not in the snapshot
+    pub added_paths: Vec<String>,
+    /// Paths present in the snapshot but gone now (breaking)
+    pub removed_paths: Vec<String>,
+    /// Operations ("GET /x") added on paths that existed in both specs
+    pub added_operations: Vec<String>,
+    /// Operations removed from paths that existed in both specs (breaking)
+    pub removed_operations: Vec<String>,
+    /// Component schemas present now but not in the snapshot
+    pub added_schemas: Vec<String>,
+    /// Component schemas in the snapshot but gone now (breaking)
+    pub removed_schemas: Vec<String>,
+    /// Schemas present in both but with different definitions
+    pub changed_schemas: Vec<String>,
+}
+
+/// Diff the current spec against the previously persisted snapshot, then
+/// persist the current spec as the new snapshot.
+///
+/// On first run (no snapshot on disk) everything is reported unchanged — the
+/// baseline is simply recorded. The diff is structural and coarse: path-level,
+/// operation-level and schema-name-level, with schema bodies compared by
+/// equality. That is enough for tool consumers to detect breaking surface
+/// changes without a full JSON-schema diff engine.
+pub fn diff_against_snapshot(current: &serde_json::Value) -> SpecChangelogResponse {
+    let snapshot_path = crate::config::get_config_dir().join(SPEC_SNAPSHOT_FILE);
+    let previous: Option<serde_json::Value> = std::fs::read_to_string(&snapshot_path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok());
+
+    let current_hash = spec_hash(current);
+    let mut response = SpecChangelogResponse {
+        current_hash: current_hash.clone(),
+        previous_hash: previous.as_ref().map(spec_hash),
+        changed: false,
+        breaking: false,
+        added_paths: vec![],
+        removed_paths: vec![],
+        added_operations: vec![],
+        removed_operations: vec![],
+        added_schemas: vec![],
+        removed_schemas: vec![],
+        changed_schemas: vec![],
+    };
+
+    if let Some(ref prev) = previous {
+        diff_specs(prev, current, &mut response);
+        response.changed = response.previous_hash.as_deref() != Some(current_hash.as_str());
+        response.breaking = !response.removed_paths.is_empty()
+            || !response.removed_operations.is_empty()
+            || !response.removed_schemas.is_empty();
+    }
+
+    // Persist the current spec as the new baseline (best-effort, like the
+    // other disk caches — a failed write just means the next diff uses the
+    // older snapshot).
+    if let Err(e) = std::fs::write(&snapshot_path, current.to_string()) {
+        log::warn!(
+            "OpenAPI changelog: failed to persist spec snapshot to {:?}: {}",
+            snapshot_path,
+            e
+        );
+    }
+
+    response
+}
+
+/// HTTP methods that count as operations when diffing a path item.
+const METHODS: [&str; 7] = ["get", "put", "post", "delete", "patch", "head", "options"];
+
+fn diff_specs(
+    prev: &serde_json::Value,
+    current: &serde_json::Value,
+    out: &mut SpecChangelogResponse,
+) {
+    let empty = serde_json::Map::new();
+    let prev_paths = prev["paths"].as_object().unwrap_or(&empty);
+    let cur_paths = current["paths"].as_object().unwrap_or(&empty);
+
+    for path in cur_paths.keys() {
+        if !prev_paths.contains_key(path) {
+            out.added_paths.push(path.clone());
+        }
+    }
+    for path in prev_paths.keys() {
+        if !cur_paths.contains_key(path) {
+            out.removed_paths.push(path.clone());
+        }
+    }
+
+    // Operation-level diff for paths present in both
+    for (path, cur_item) in cur_paths {
+        let Some(prev_item) = prev_paths.get(path) else {
+            continue;
+        };
+        for method in METHODS {
+            let in_cur = cur_item.get(method).is_some();
+            let in_prev = prev_item.get(method).is_some();
+            let op = format!("{} {}", method.to_uppercase(), path);
+            if in_cur && !in_prev {
+                out.added_operations.push(op);
+            } else if !in_cur && in_prev {
+                out.removed_operations.push(op);
+            }
+        }
+    }
+
+    let prev_schemas = prev["components"]["schemas"].as_object().unwrap_or(&empty);
+    let cur_schemas = current["components"]["schemas"]
+        .as_object()
+        .unwrap_or(&empty);
+
+    for (name, cur_schema) in cur_schemas {
+        match prev_schemas.get(name) {
+            None => out.added_schemas.push(name.clone()),
+            Some(prev_schema) if prev_schema != cur_schema => {
+                out.changed_schemas.push(name.clone())
+            }
+            Some(_) => {}
+        }
+    }
+    for name in prev_schemas.keys() {
+        if !cur_schemas.contains_key(name) {
+            out.removed_schemas.push(name.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_detects_added_and_removed() {
+        let prev = serde_json::json!({
+            "paths": {"/a": {"get": {}}, "/b": {"get": {}, "post": {}}},
+            "components": {"schemas": {"Foo": {"type": "object"}}}
+        });
+        let cur = serde_json::json!({
+            "paths": {"/a": {"get": {}}, "/b": {"get": {}}, "/c": {"get": {}}},
+            "components": {"schemas": {"Bar": {"type": "object"}}}
+        });
+        let mut out = SpecChangelogResponse {
+            current_hash: String::new(),
+            previous_hash: None,
+            changed: false,
+            breaking: false,
+            added_paths: vec![],
+            removed_paths: vec![],
+            added_operations: vec![],
+            removed_operations: vec![],
+            added_schemas: vec![],
+            removed_schemas: vec![],
+            changed_schemas: vec![],
+        };
+        diff_specs(&prev, &cur, &mut out);
+        assert_eq!(out.added_paths, vec!["/c"]);
+        assert!(out.removed_paths.is_empty());
+        assert_eq!(out.removed_operations, vec!["POST /b"]);
+        assert_eq!(out.added_schemas, vec!["Bar"]);
+        assert_eq!(out.removed_schemas, vec!["Foo"]);
+    }
+
+    #[test]
+    fn test_stamp_adds_hash_and_build_version() {
+        let value = stamped_spec_value(PublicApiDoc::openapi());
+        assert!(value["info"]["x-spec-hash"].is_string());
+        assert_eq!(value["info"]["x-build-version"], env!("CARGO_PKG_VERSION"));
+    }
+}
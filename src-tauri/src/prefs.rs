@@ -0,0 +1,94 @@
+//! Persistent UI preferences (window geometry, last-viewed task, column
+//! widths, theme).
+//!
+//! A schema-less key → JSON value store so the frontend can add preferences
+//! without backend changes. Values live in memory behind a lock and are
+//! flushed to `ui_prefs.json` under the config dir by a debounced writer —
+//! rapid updates (window drags, column resizes) coalesce into one write.
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const PREFS_FILE: &str = "ui_prefs.json";
+/// Quiet period before dirty preferences are flushed to disk.
+const WRITE_DEBOUNCE_MS: u64 = 750;
+
+static PREFS: Lazy<RwLock<HashMap<String, serde_json::Value>>> =
+    Lazy::new(|| RwLock::new(load()));
+static WRITE_SCHEDULED: AtomicBool = AtomicBool::new(false);
+
+fn prefs_path() -> std::path::PathBuf {
+    crate::config::get_config_dir().join(PREFS_FILE)
+}
+
+fn load() -> HashMap<String, serde_json::Value> {
+    let path = prefs_path();
+    match std::fs::read_to_string(&path) {
+        Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+            log::warn!("Failed to parse UI prefs {:?}: {}", path, e);
+            HashMap::new()
+        }),
+        Err(_) => HashMap::new(), // first run — no file yet
+    }
+}
+
+fn flush() {
+    let snapshot = PREFS.read().clone();
+    let path = prefs_path();
+    match serde_json::to_string_pretty(&snapshot) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                log::warn!("Failed to write UI prefs to {:?}: {}", path, e);
+            }
+        }
+        Err(e) => log::warn!("Failed to serialize UI prefs: {}", e),
+    }
+}
+
+/// Schedule a debounced flush; further updates within the window coalesce
+/// into the already-waiting writer.
+fn schedule_flush() {
+    if WRITE_SCHEDULED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    std::thread::spawn(|| {
+        std::thread::sleep(std::time::Duration::from_millis(WRITE_DEBOUNCE_MS));
+        WRITE_SCHEDULED.store(false, Ordering::SeqCst);
+        flush();
+    });
+}
+
+/// All stored preferences.
+pub fn get_all() -> HashMap<String, serde_json::Value> {
+    PREFS.read().clone()
+}
+
+/// Set one preference. `null` removes the key.
+pub fn set(key: String, value: serde_json::Value) {
+    {
+        let mut prefs = PREFS.write();
+        if value.is_null() {
+            prefs.remove(&key);
+        } else {
+            prefs.insert(key, value);
+        }
+    }
+    schedule_flush();
+}
+
+/// Merge several preferences at once (a single debounced write).
+pub fn set_many(values: HashMap<String, serde_json::Value>) {
+    {
+        let mut prefs = PREFS.write();
+        for (key, value) in values {
+            if value.is_null() {
+                prefs.remove(&key);
+            } else {
+                prefs.insert(key, value);
+            }
+        }
+    }
+    schedule_flush();
+}
@@ -0,0 +1,295 @@
+//! In-process scheduler for periodic background jobs.
+//!
+//! One loop owns every recurring maintenance task instead of each module
+//! spawning its own timer:
+//!
+//! - `history_index_refresh` — rebuild the conversation history task index
+//! - `cache_warm` — re-warm the shadow git discovery/task/step caches
+//! - `model_list_refresh` — refresh the cached Gemini model list
+//! - `log_rotation` — prune session log files older than a week
+//! - `retention_cleanup` — tick the retention policy engine
+//!
+//! Intervals come from `config.toml` (`[jobs]`, and `[retention]
+//! scheduler_tick_secs` for the retention job) and are re-read every loop so
+//! hot-reloaded values take effect; an interval of `0` disables a job. Jobs
+//! run sequentially — none of them are latency-sensitive and sequencing
+//! avoids two filesystem scans racing each other.
+//!
+//! `GET /system/jobs` reports per-job status: last run time, duration,
+//! outcome and run/failure counts.
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+
+use crate::config::AppConfig;
+
+/// How often the scheduler loop wakes to check for due jobs.
+const TICK_SECS: u64 = 5;
+/// Log files older than this are pruned by the `log_rotation` job.
+const LOG_MAX_AGE_DAYS: u64 = 7;
+
+/// Status of one scheduled job, as reported by `GET /system/jobs`.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct JobStatus {
+    /// Job identifier (stable, used as the config key prefix)
+    pub name: String,
+    /// Currently effective interval in seconds (0 = disabled)
+    pub interval_secs: u64,
+    pub enabled: bool,
+    /// When the job last ran (ISO 8601), if it has run
+    pub last_run_at: Option<String>,
+    pub last_duration_ms: Option<u64>,
+    /// Whether the last run succeeded
+    pub last_ok: Option<bool>,
+    /// Summary or error message from the last run
+    pub last_result: Option<String>,
+    pub runs: u64,
+    pub failures: u64,
+}
+
+/// Response for the `/system/jobs` endpoint.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct JobsResponse {
+    pub jobs: Vec<JobStatus>,
+    pub total: usize,
+}
+
+type JobFuture = Pin<Box<dyn std::future::Future<Output = Result<String, String>> + Send>>;
+
+/// A scheduled job: a name, an interval lookup into the live config, and
+/// the work itself (which returns a one-line summary for the status view).
+struct JobSpec {
+    name: &'static str,
+    interval: fn(&AppConfig) -> u64,
+    run: fn() -> JobFuture,
+}
+
+fn job_specs() -> Vec<JobSpec> {
+    vec![
+        JobSpec {
+            name: "history_index_refresh",
+            interval: |c| c.jobs.history_index_refresh_secs,
+            run: || Box::pin(run_history_index_refresh()),
+        },
+        JobSpec {
+            name: "cache_warm",
+            interval: |c| c.jobs.cache_warm_secs,
+            run: || Box::pin(run_cache_warm()),
+        },
+        JobSpec {
+            name: "model_list_refresh",
+            interval: |c| c.jobs.model_refresh_secs,
+            run: || Box::pin(run_model_list_refresh()),
+        },
+        JobSpec {
+            name: "log_rotation",
+            interval: |c| c.jobs.log_rotation_secs,
+            run: || Box::pin(run_log_rotation()),
+        },
+        JobSpec {
+            name: "retention_cleanup",
+            interval: |c| c.retention.scheduler_tick_secs.max(60),
+            run: || Box::pin(run_retention_cleanup()),
+        },
+    ]
+}
+
+/// Recorded outcome of past runs, keyed by job name.
+static STATUS: Lazy<RwLock<HashMap<&'static str, JobStatus>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Spawn the scheduler loop on the server runtime. Called once at REST
+/// server startup.
+///
+/// Jobs do NOT fire immediately — the one-shot startup cache warmer already
+/// covers the cold start, so each job first runs one interval after launch.
+pub fn spawn_scheduler() {
+    let specs = job_specs();
+    {
+        let mut status = STATUS.write();
+        for spec in &specs {
+            status.insert(
+                spec.name,
+                JobStatus {
+                    name: spec.name.to_string(),
+                    interval_secs: 0,
+                    enabled: true,
+                    last_run_at: None,
+                    last_duration_ms: None,
+                    last_ok: None,
+                    last_result: None,
+                    runs: 0,
+                    failures: 0,
+                },
+            );
+        }
+    }
+    let job_count = specs.len();
+
+    tokio::spawn(async move {
+        let config = crate::config::current();
+        let mut next_due: HashMap<&'static str, Instant> = specs
+            .iter()
+            .map(|spec| {
+                let interval = (spec.interval)(&config).max(TICK_SECS);
+                (spec.name, Instant::now() + Duration::from_secs(interval))
+            })
+            .collect();
+
+        loop {
+            tokio::time::sleep(Duration::from_secs(TICK_SECS)).await;
+            let config = crate::config::current();
+
+            for spec in &specs {
+                let interval = (spec.interval)(&config);
+                if interval == 0 {
+                    continue; // disabled via config
+                }
+                let due = next_due
+                    .get(spec.name)
+                    .copied()
+                    .unwrap_or_else(Instant::now);
+                if Instant::now() < due {
+                    continue;
+                }
+
+                let started = Instant::now();
+                let result = (spec.run)().await;
+                record_run(spec.name, started.elapsed(), &result);
+                next_due.insert(spec.name, Instant::now() + Duration::from_secs(interval));
+
+                match &result {
+                    Ok(summary) => log::info!("Scheduler: {} — {}", spec.name, summary),
+                    Err(e) => log::warn!("Scheduler: {} failed — {}", spec.name, e),
+                }
+            }
+        }
+    });
+    log::info!("Scheduler: spawned with {} jobs", job_count);
+}
+
+fn record_run(name: &'static str, duration: Duration, result: &Result<String, String>) {
+    let mut status = STATUS.write();
+    if let Some(entry) = status.get_mut(name) {
+        entry.last_run_at = Some(chrono::Utc::now().to_rfc3339());
+        entry.last_duration_ms = Some(duration.as_millis() as u64);
+        entry.last_ok = Some(result.is_ok());
+        entry.last_result = Some(match result {
+            Ok(summary) => summary.clone(),
+            Err(e) => e.clone(),
+        });
+        entry.runs += 1;
+        if result.is_err() {
+            entry.failures += 1;
+        }
+    }
+}
+
+/// Current status of every job, with intervals resolved against the live
+/// config (so a hot-reloaded interval shows up immediately).
+pub fn jobs_response() -> JobsResponse {
+    let config = crate::config::current();
+    let status = STATUS.read();
+    let mut jobs: Vec<JobStatus> = job_specs()
+        .iter()
+        .map(|spec| {
+            let interval = (spec.interval)(&config);
+            let mut entry = status.get(spec.name).cloned().unwrap_or(JobStatus {
+                name: spec.name.to_string(),
+                interval_secs: 0,
+                enabled: true,
+                last_run_at: None,
+                last_duration_ms: None,
+                last_ok: None,
+                last_result: None,
+                runs: 0,
+                failures: 0,
+            });
+            entry.interval_secs = interval;
+            entry.enabled = interval > 0;
+            entry
+        })
+        .collect();
+    jobs.sort_by(|a, b| a.name.cmp(&b.name));
+    let total = jobs.len();
+    JobsResponse { jobs, total }
+}
+
+// ============================================================================
+// Job bodies
+// ============================================================================
+
+async fn run_history_index_refresh() -> Result<String, String> {
+    match crate::conversation_history::handlers::index::get_or_refresh_task_index(true).await {
+        Ok(index) => Ok(format!("{} tasks indexed", index.total_tasks)),
+        Err((_, e)) => Err(e.error.clone()),
+    }
+}
+
+async fn run_cache_warm() -> Result<String, String> {
+    let (workspaces, tasks, steps) =
+        tokio::task::spawn_blocking(crate::shadow_git::warmup::warm_shadow_git)
+            .await
+            .map_err(|e| format!("cache warm panicked: {}", e))?;
+    Ok(format!(
+        "{} workspaces, {} task lists, steps for {} tasks",
+        workspaces, tasks, steps
+    ))
+}
+
+async fn run_model_list_refresh() -> Result<String, String> {
+    let key = std::env::var("GEMINI_API_KEY")
+        .ok()
+        .filter(|k| !k.is_empty())
+        .or_else(|| crate::secrets::get("gemini_api_key"))
+        .unwrap_or_else(|| crate::config::current().providers.gemini_api_key.clone());
+    if key.is_empty() || key == "YOUR_GEMINI_API_KEY_HERE" {
+        return Ok("skipped — no API key configured".to_string());
+    }
+    let count = crate::api::handlers::refresh_models_cache(&key).await?;
+    Ok(format!("{} models cached", count))
+}
+
+async fn run_log_rotation() -> Result<String, String> {
+    tokio::task::spawn_blocking(prune_old_logs)
+        .await
+        .map_err(|e| format!("log rotation panicked: {}", e))?
+}
+
+/// Delete session log files older than [`LOG_MAX_AGE_DAYS`]. The active
+/// log is timestamped at startup and written continuously, so its mtime
+/// keeps it safely inside the window.
+fn prune_old_logs() -> Result<String, String> {
+    let logs_dir = crate::config::get_logs_dir();
+    let cutoff = std::time::SystemTime::now()
+        - Duration::from_secs(LOG_MAX_AGE_DAYS * 24 * 60 * 60);
+    let mut pruned = 0usize;
+    let entries = std::fs::read_dir(&logs_dir)
+        .map_err(|e| format!("Failed to read logs dir {:?}: {}", logs_dir, e))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let modified = match entry.metadata().and_then(|m| m.modified()) {
+            Ok(m) => m,
+            Err(_) => continue,
+        };
+        if modified < cutoff && std::fs::remove_file(&path).is_ok() {
+            pruned += 1;
+        }
+    }
+    Ok(format!("{} old log files pruned", pruned))
+}
+
+async fn run_retention_cleanup() -> Result<String, String> {
+    tokio::task::spawn_blocking(crate::conversation_history::retention::run_scheduled_tick)
+        .await
+        .map_err(|e| format!("retention tick panicked: {}", e))?
+}
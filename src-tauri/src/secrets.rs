@@ -0,0 +1,67 @@
+//! Persistent secret storage for provider API keys.
+//!
+//! Keys rotated at runtime through `PUT /system/providers` (or the
+//! `set_provider_key` Tauri command) land here so they survive restarts.
+//! They are kept OUT of `config.toml` on purpose — that file is surfaced
+//! by `GET /system/config` and meant to be hand-edited and shared;
+//! `secrets.json` is never served and is written with owner-only
+//! permissions on Unix.
+//!
+//! Resolution order at startup stays: environment variable, then this
+//! store, then the `[providers]` config fallback.
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+const SECRETS_FILE: &str = "secrets.json";
+
+static SECRETS: Lazy<RwLock<HashMap<String, String>>> = Lazy::new(|| RwLock::new(load()));
+
+fn secrets_path() -> PathBuf {
+    crate::config::get_config_dir().join(SECRETS_FILE)
+}
+
+fn load() -> HashMap<String, String> {
+    let path = secrets_path();
+    match std::fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::warn!("Failed to parse {:?}: {} — starting empty", path, e);
+            HashMap::new()
+        }),
+        Err(_) => HashMap::new(),
+    }
+}
+
+fn persist(secrets: &HashMap<String, String>) -> Result<(), String> {
+    let path = secrets_path();
+    let json = serde_json::to_string_pretty(secrets)
+        .map_err(|e| format!("Failed to serialize secrets: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Err(e) = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)) {
+            log::warn!("Failed to restrict permissions on {:?}: {}", path, e);
+        }
+    }
+    Ok(())
+}
+
+/// Get a stored secret by name (e.g. "gemini_api_key").
+pub fn get(name: &str) -> Option<String> {
+    SECRETS.read().get(name).cloned()
+}
+
+/// Store (or overwrite) a secret and persist the store. An empty value
+/// removes the entry.
+pub fn set(name: &str, value: &str) -> Result<(), String> {
+    let mut secrets = SECRETS.write();
+    if value.is_empty() {
+        secrets.remove(name);
+    } else {
+        secrets.insert(name.to_string(), value.to_string());
+    }
+    persist(&secrets)
+}
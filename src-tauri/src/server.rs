@@ -1,12 +1,15 @@
-use crate::api::{handlers, middleware::{auth_middleware, access_log_middleware}};
+use crate::api::{handlers, middleware::{auth_middleware, access_log_middleware, etag_middleware, request_id_middleware}};
 use crate::conversation_history;
+use crate::diagnostics;
 use crate::latest;
+use crate::notify;
 use crate::openapi::{PublicApiDoc, AdminApiDoc};
 use crate::shadow_git;
 use crate::state::AppState;
 use crate::tool_runtime::{self, ToolRuntime};
-use axum::{middleware, response::Json, routing::{get, delete, post, put}, Router};
+use axum::{middleware, response::Json, routing::{get, delete, post, put}, Extension, Router};
 use std::sync::Arc;
+use tower_http::compression::{predicate::SizeAbove, CompressionLayer};
 use tower_http::cors::{Any, CorsLayer};
 use utoipa::OpenApi;
 
@@ -21,18 +24,51 @@ pub fn create_router(state: Arc<AppState>, tool_runtime: Arc<ToolRuntime>) -> Ro
     // Public routes (no auth required)
     let public_routes = Router::new()
         .route("/health", get(handlers::health_handler))
+        .route("/docs", get(docs_handler))
         .route("/openapi.json", get(openapi_public_handler))
+        .route("/openapi.yaml", get(openapi_yaml_handler))
+        .route("/openapi/changelog", get(openapi_changelog_handler))
         .route("/openapi_admin.json", get(openapi_admin_handler))
         .route("/access-logs", get(handlers::access_logs_handler))
+        .route("/access-logs/summary", get(handlers::access_logs_summary_handler))
         .route("/access-logs", delete(handlers::clear_access_logs_handler))
         .route("/inference-logs", get(handlers::inference_logs_handler))
-        .route("/inference-logs", delete(handlers::clear_inference_logs_handler));
+        .route("/inference-logs", delete(handlers::clear_inference_logs_handler))
+        .route("/inference-logs/:id", get(handlers::inference_log_detail_handler));
 
-    // Protected routes (require Bearer token auth)
-    let protected_routes = Router::new()
+    // Upstream-dependent routes (Jira/LLM): short-circuit with a 503
+    // `offline` error while offline mode is active
+    let online_routes = Router::new()
         .route("/jira/list", get(handlers::jira_list_handler))
         .route("/agent/chat", post(handlers::chat_handler))
+        .route("/agent/count-tokens", post(handlers::count_tokens_handler))
         .route("/agent/models", get(handlers::list_models_handler))
+        .route("/agent/models/refresh", post(handlers::refresh_models_handler))
+        .layer(middleware::from_fn(crate::offline::offline_guard))
+        .layer(middleware::from_fn_with_state(state.clone(), auth_middleware))
+        // The chat handler drives function calls through the ToolRuntime
+        .layer(Extension(tool_runtime.clone()));
+
+    // Protected routes (require Bearer token auth)
+    let protected_routes = Router::new()
+        .route("/system/config", get(handlers::system_config_handler))
+        .route("/system/providers", put(handlers::update_providers_handler))
+        .route("/system/backup", post(handlers::system_backup_handler))
+        .route("/system/restore", post(handlers::system_restore_handler))
+        .route("/system/jobs", get(handlers::system_jobs_handler))
+        .route("/system/diagnostics", get(diagnostics::system_diagnostics_handler))
+        .route("/system/logs/tail", get(crate::logging::system_logs_tail_handler))
+        .route("/auth/audit", get(handlers::auth_audit_handler))
+        .route("/system/audit", get(handlers::system_audit_handler))
+        .route(
+            "/system/webhooks",
+            get(notify::get_webhooks_handler).put(notify::put_webhooks_handler),
+        )
+        .route("/system/webhooks/test", post(notify::test_webhooks_handler))
+        .route(
+            "/system/offline",
+            get(crate::offline::get_offline_handler).put(crate::offline::put_offline_handler),
+        )
         .layer(middleware::from_fn_with_state(state.clone(), auth_middleware));
 
     // Tool Runtime routes (Tools Console)
@@ -43,7 +79,7 @@ pub fn create_router(state: Arc<AppState>, tool_runtime: Arc<ToolRuntime>) -> Ro
         .route("/tools/logs", delete(tool_runtime::clear_tool_logs_handler))
         .route("/tools/config", get(tool_runtime::get_config_handler))
         .route("/tools/config", put(tool_runtime::update_config_handler))
-        .route("/tools/:operation_id/config", put(tool_runtime::configure_tool_handler))
+        .route("/tools/:operation_id/config", get(tool_runtime::get_tool_config_handler).put(tool_runtime::configure_tool_handler))
         .route("/tools/circuit-breakers", get(tool_runtime::get_circuit_breakers_handler))
         .route("/tools/circuit-breakers", delete(tool_runtime::reset_circuit_breakers_handler))
         .route("/tools/:operation_id/circuit-breaker", delete(tool_runtime::reset_tool_circuit_breaker_handler))
@@ -52,6 +88,40 @@ pub fn create_router(state: Arc<AppState>, tool_runtime: Arc<ToolRuntime>) -> Ro
         .route("/tools/fixtures", delete(tool_runtime::clear_fixtures_handler))
         .route("/tools/enable-all", post(tool_runtime::enable_all_tools_handler))
         .route("/tools/disable-all", post(tool_runtime::disable_all_tools_handler))
+        .route("/runtime/config", get(tool_runtime::get_runtime_config_handler).put(tool_runtime::put_runtime_config_handler))
+        // /runtime/* aliases so Dev Console clients can drive the runtime
+        // without mixing in the agent-facing /tools discovery surface
+        .route("/runtime/tools", get(tool_runtime::list_tools_handler))
+        .route("/runtime/tools/:operation_id/config", get(tool_runtime::get_tool_config_handler).put(tool_runtime::configure_tool_handler))
+        .route("/runtime/invoke", post(tool_runtime::invoke_tool_handler))
+        .route("/runtime/logs", get(tool_runtime::get_tool_logs_handler))
+        .route("/runtime/logs", delete(tool_runtime::clear_tool_logs_handler))
+        .route("/runtime/circuit-breakers", get(tool_runtime::get_circuit_breakers_handler))
+        .route("/runtime/circuit-breakers", delete(tool_runtime::reset_circuit_breakers_handler))
+        .route("/runtime/tools/:operation_id/circuit-breaker", delete(tool_runtime::reset_tool_circuit_breaker_handler))
+        .route("/runtime/breakers/:operation_id/reset", post(tool_runtime::reset_breaker_handler))
+        .route("/runtime/budgets", get(tool_runtime::get_budgets_handler))
+        .route("/runtime/budgets", delete(tool_runtime::reset_budgets_handler))
+        .route("/runtime/tools/:operation_id/budget", delete(tool_runtime::reset_tool_budget_handler))
+        .route("/runtime/metrics", get(tool_runtime::get_runtime_metrics_handler))
+        .route("/runtime/logs/export", get(tool_runtime::export_logs_handler))
+        .route("/runtime/macros", get(tool_runtime::list_macros_handler))
+        .route("/runtime/macros", post(tool_runtime::register_macro_handler))
+        .route("/runtime/macros/:name", delete(tool_runtime::delete_macro_handler))
+        .route("/runtime/profiles", get(tool_runtime::list_profiles_handler))
+        .route("/runtime/profiles", post(tool_runtime::save_profile_handler))
+        .route("/runtime/profiles/:name", delete(tool_runtime::delete_profile_handler))
+        .route("/runtime/profiles/:name/activate", post(tool_runtime::activate_profile_handler))
+        .route("/runtime/logs/:id/replay", post(tool_runtime::replay_log_handler))
+        .route("/runtime/fixtures/sessions", get(tool_runtime::list_fixture_sessions_handler))
+        .route("/runtime/fixtures/sessions", post(tool_runtime::start_fixture_session_handler))
+        .route("/runtime/fixtures/sessions", delete(tool_runtime::stop_fixture_session_handler))
+        .route("/runtime/fixtures/sessions/:name/activate", post(tool_runtime::activate_fixture_set_handler))
+        .route(
+            "/agent/eval",
+            post(tool_runtime::run_eval_handler)
+                .layer(middleware::from_fn(crate::offline::offline_guard)),
+        )
         .with_state(tool_runtime);
 
     // Shadow Git / Changes routes (protected)
@@ -59,33 +129,68 @@ pub fn create_router(state: Arc<AppState>, tool_runtime: Arc<ToolRuntime>) -> Ro
         .route("/changes/workspaces", get(shadow_git::list_workspaces_handler))
         .route("/changes/tasks", get(shadow_git::list_tasks_handler))
         .route("/changes/tasks/:task_id/diff", get(shadow_git::task_diff_handler))
+        .route("/changes/tasks/:task_id/diff.patch", get(shadow_git::task_diff_patch_handler))
+        .route("/changes/tasks/:task_id/diff/file", get(shadow_git::file_diff_handler))
+        .route("/changes/tasks/:task_id/range-diff", get(shadow_git::range_diff_handler))
+        .route("/changes/tasks/:task_id/files/:path/history", get(shadow_git::file_history_handler))
+        .route("/changes/tasks/:task_id/blame", get(shadow_git::blame_handler))
         .route("/changes/tasks/:task_id/steps", get(shadow_git::list_steps_handler))
         .route("/changes/tasks/:task_id/steps/:index/diff", get(shadow_git::step_diff_handler))
         .route("/changes/tasks/:task_id/subtasks/:subtask_index/diff", get(shadow_git::subtask_diff_handler))
+        .route("/changes/tree", get(shadow_git::tree_handler))
+        .route("/changes/search", get(shadow_git::search_handler))
+        .route("/changes/tasks/:task_id/bundle", get(shadow_git::task_bundle_handler))
+        .route("/changes/tasks/:task_id/apply", post(shadow_git::apply_task_handler))
+        .route("/changes/tasks/:task_id/summarize", post(shadow_git::summarize_task_handler))
+        .route("/changes/tasks/:task_id/nuke", post(shadow_git::nuke_task_handler))
         .route("/changes/workspaces/:id/nuke", post(shadow_git::nuke_workspace_handler))
+        .route("/changes/workspaces/:id/gc", post(shadow_git::gc_workspace_handler))
+        .route("/changes/workspaces/:id/activity", get(shadow_git::workspace_activity_handler))
+        .route("/changes/ignore", get(shadow_git::changesignore::get_ignore_handler).put(shadow_git::changesignore::put_ignore_handler))
+        .route("/changes/ignore/preview", post(shadow_git::changesignore::preview_ignore_handler))
         .route("/changes/file-contents", post(shadow_git::file_contents_handler))
+        .route("/changes/restore", post(shadow_git::restore_files_handler))
         .layer(middleware::from_fn_with_state(state.clone(), auth_middleware));
 
     // Latest composite route (protected)
     let latest_routes = Router::new()
         .route("/latest", get(latest::get_latest_handler))
+        .route("/latest/stream", get(latest::latest_stream_handler))
+        .route("/latest/recent", get(latest::latest_recent_handler))
         .layer(middleware::from_fn_with_state(state.clone(), auth_middleware));
 
     // Conversation History routes (protected)
     let history_routes = Router::new()
         .route("/history/tasks", get(conversation_history::list_history_tasks_handler))
         .route("/history/stats", get(conversation_history::get_history_stats_handler))
-        .route("/history/tasks/:task_id", get(conversation_history::get_task_detail_handler))
+        .route("/history/stats/activity", get(conversation_history::get_activity_heatmap_handler))
+        .route("/history/stats/models", get(conversation_history::get_model_stats_handler))
+        .route("/history/tasks/:task_id", get(conversation_history::get_task_detail_handler).delete(conversation_history::delete_task_handler))
+        .route("/history/tasks/:task_id/archive", post(conversation_history::archive_task_handler))
         .route("/history/tasks/:task_id/messages", get(conversation_history::get_task_messages_handler))
+        .route("/history/tasks/:task_id/messages/search", get(conversation_history::search_task_messages_handler))
         .route("/history/tasks/:task_id/messages/:index", get(conversation_history::get_single_message_handler))
         .route("/history/tasks/:task_id/tools", get(conversation_history::get_task_tools_handler))
         .route("/history/tasks/:task_id/thinking", get(conversation_history::get_task_thinking_handler))
         .route("/history/tasks/:task_id/files", get(conversation_history::get_task_files_handler))
         .route("/history/tasks/:task_id/subtasks", get(conversation_history::get_task_subtasks_handler))
+        .route("/history/tasks/:task_id/export", get(conversation_history::export_task_handler))
+        .route("/history/tasks/:task_id/usage", get(conversation_history::get_task_usage_handler))
+        .route("/history/tasks/:task_id/timeline", get(conversation_history::get_task_timeline_handler))
+        .route("/history/tasks/:task_id/context-growth", get(conversation_history::get_context_growth_handler))
+        .route("/history/tasks/:task_id/prompts", get(conversation_history::get_task_prompts_handler))
+        .route("/history/tasks/:task_id/notes", get(conversation_history::get_task_note_handler).put(conversation_history::put_task_note_handler))
+        .route("/history/export", get(conversation_history::export_all_tasks_handler))
+        .route("/history/loops", get(conversation_history::get_history_loops_handler))
+        .route("/history/compare", get(conversation_history::compare_tasks_handler))
+        .route("/history/retention", get(conversation_history::get_retention_policy_handler).put(conversation_history::put_retention_policy_handler))
+        .route("/history/retention/preview", get(conversation_history::preview_retention_handler))
+        .route("/history/retention/run", post(conversation_history::run_retention_handler))
         .layer(middleware::from_fn_with_state(state.clone(), auth_middleware));
 
     Router::new()
         .merge(public_routes)
+        .merge(online_routes)
         .merge(protected_routes)
         .merge(tool_routes)
         .merge(changes_routes)
@@ -93,16 +198,82 @@ pub fn create_router(state: Arc<AppState>, tool_runtime: Arc<ToolRuntime>) -> Ro
         .merge(history_routes)
         // Add access logging middleware to all routes
         .layer(middleware::from_fn_with_state(state.clone(), access_log_middleware))
+        // Correlation ids for error payloads and the x-request-id header
+        .layer(middleware::from_fn(request_id_middleware))
+        // ETag/If-None-Match for large, frequently polled endpoints
+        .layer(middleware::from_fn(etag_middleware))
+        // Compress multi-megabyte diff/conversation payloads; tiny responses
+        // are not worth the CPU round-trip
+        .layer(CompressionLayer::new().compress_when(SizeAbove::new(1024)))
         .layer(cors)
         .with_state(state)
 }
 
+/// Serve the interactive API explorer at `/docs`
+///
+/// A single self-contained HTML page embedding RapiDoc (loaded from CDN —
+/// the server itself stays asset-free) pointed at `/openapi.json`. The
+/// session bearer token is deliberately NOT embedded: this page is served
+/// unauthenticated and the CORS layer is wide open, so anything baked into
+/// it is readable by any local process or browser page. Users paste the
+/// token (from the tray's "Copy API token") into RapiDoc's auth dialog
+/// instead.
+async fn docs_handler() -> axum::response::Html<&'static str> {
+    axum::response::Html(
+        r#"<!doctype html>
+<html>
+<head>
+  <meta charset="utf-8">
+  <title>Jira Dashboard API Explorer</title>
+  <script type="module" src="https://unpkg.com/rapidoc/dist/rapidoc-min.js"></script>
+</head>
+<body style="margin:0">
+  <rapi-doc
+    spec-url="/openapi.json"
+    theme="dark"
+    render-style="focused"
+    show-header="false"
+    allow-server-selection="false"
+    allow-authentication="true"
+    api-key-name="Authorization"
+    api-key-location="header"
+  ></rapi-doc>
+</body>
+</html>
+"#,
+    )
+}
+
 /// Serve public OpenAPI spec as JSON
 /// 
 /// This is the standard `/openapi.json` endpoint that external API tools
-/// will auto-discover. It contains only public API endpoints.
-async fn openapi_public_handler() -> Json<utoipa::openapi::OpenApi> {
-    Json(PublicApiDoc::openapi())
+/// will auto-discover. It contains only public API endpoints, stamped with
+/// `info.x-spec-hash` / `info.x-build-version` so consumers can detect drift.
+async fn openapi_public_handler() -> Json<serde_json::Value> {
+    Json(crate::openapi::stamped_spec_value(PublicApiDoc::openapi()))
+}
+
+/// Serve public OpenAPI spec as YAML at `/openapi.yaml`
+///
+/// Same stamped document as `/openapi.json`, for tools that prefer YAML specs.
+async fn openapi_yaml_handler() -> impl axum::response::IntoResponse {
+    let value = crate::openapi::stamped_spec_value(PublicApiDoc::openapi());
+    let yaml = serde_yaml::to_string(&value).unwrap_or_default();
+    (
+        [(axum::http::header::CONTENT_TYPE, "application/yaml")],
+        yaml,
+    )
+}
+
+/// Serve spec changelog at `/openapi/changelog`
+///
+/// Diffs the currently served public spec against the snapshot persisted on
+/// the previous call, reporting added/removed paths, operations and schemas
+/// plus a breaking-change flag, then records the current spec as the new
+/// baseline.
+async fn openapi_changelog_handler() -> Json<crate::openapi::SpecChangelogResponse> {
+    let value = crate::openapi::stamped_spec_value(PublicApiDoc::openapi());
+    Json(crate::openapi::diff_against_snapshot(&value))
 }
 
 /// Serve admin OpenAPI spec as JSON
@@ -110,6 +281,6 @@ async fn openapi_public_handler() -> Json<utoipa::openapi::OpenApi> {
 /// This endpoint serves the internal/admin API specification at `/openapi_admin.json`.
 /// It is intentionally NOT auto-discoverable - only developers who know this
 /// path can access the admin API documentation.
-async fn openapi_admin_handler() -> Json<utoipa::openapi::OpenApi> {
-    Json(AdminApiDoc::openapi())
+async fn openapi_admin_handler() -> Json<serde_json::Value> {
+    Json(crate::openapi::stamped_spec_value(AdminApiDoc::openapi()))
 }
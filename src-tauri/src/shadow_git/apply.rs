@@ -0,0 +1,163 @@
+//! Apply a checkpoint diff onto a real working tree.
+//!
+//! Powers "replay this Cline change onto a fresh clone": the task's full
+//! patch is written to a temp file and applied to a target repository with
+//! `git apply`. This stays on the git CLI deliberately — libgit2's apply
+//! support has no three-way merge, and `--3way` conflict markers are the
+//! main reason to use this endpoint over copying files around.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+
+/// Request body for POST /changes/tasks/:taskId/apply
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyRequest {
+    /// Absolute path to the target repository's working tree
+    pub target_path: String,
+    /// Workspace ID (optional — auto-linked from the task when omitted)
+    #[serde(default)]
+    pub workspace: Option<String>,
+    /// Use three-way merge (leaves conflict markers instead of failing)
+    #[serde(default)]
+    pub three_way: bool,
+    /// Only check whether the patch would apply — no files are modified
+    #[serde(default)]
+    pub check_only: bool,
+    /// Pathspec exclusion patterns for the source diff (repeated)
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+/// Response for POST /changes/tasks/:taskId/apply
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApplyResponse {
+    /// Task ID whose diff was applied
+    pub task_id: String,
+    /// Workspace ID the diff came from
+    pub workspace_id: String,
+    /// Target working tree the patch was applied to
+    pub target_path: String,
+    /// Whether three-way merge was used
+    pub three_way: bool,
+    /// Whether this was a dry run (`--check`)
+    pub check_only: bool,
+    /// True when the patch applied cleanly (or would, for check-only)
+    pub applied: bool,
+    /// Files git reported conflicts or errors for
+    pub conflicts: Vec<String>,
+    /// Raw `git apply` output (stdout + stderr)
+    pub output: String,
+    /// Size of the applied patch in bytes
+    pub patch_bytes: usize,
+}
+
+/// Apply a task's full checkpoint diff to a target working tree.
+///
+/// Returns Err for setup problems (bad target, empty patch); a patch that
+/// fails to apply is a normal response with `applied: false` and the
+/// conflicting paths listed.
+pub fn apply_task_diff(
+    task_id: &str,
+    workspace_id: &str,
+    git_dir: std::path::PathBuf,
+    req: &ApplyRequest,
+) -> Result<ApplyResponse, String> {
+    let target = Path::new(&req.target_path);
+    if !target.is_dir() {
+        return Err(format!(
+            "Target path '{}' does not exist or is not a directory",
+            req.target_path
+        ));
+    }
+
+    // The target must be a git working tree — git apply --3way needs the
+    // object database, and --check output is confusing outside a repo
+    let probe = Command::new("git")
+        .args(["-C", &req.target_path, "rev-parse", "--is-inside-work-tree"])
+        .output()
+        .map_err(|e| format!("Failed to run git rev-parse: {}", e))?;
+    if !probe.status.success() {
+        return Err(format!(
+            "Target path '{}' is not a git working tree",
+            req.target_path
+        ));
+    }
+
+    let diff = super::discovery::get_task_diff(task_id, &git_dir, &req.exclude)?;
+    if diff.patch.trim().is_empty() {
+        return Err(format!("Task '{}' produced an empty patch", task_id));
+    }
+
+    // Write the patch to a temp file — git apply reads better from a file
+    // than stdin when we also want --verbose diagnostics
+    let patch_path = std::env::temp_dir().join(format!("cline-xray-{}.patch", task_id));
+    std::fs::write(&patch_path, &diff.patch)
+        .map_err(|e| format!("Failed to write temp patch file: {}", e))?;
+
+    let mut args = vec![
+        "-C".to_string(),
+        req.target_path.clone(),
+        "apply".to_string(),
+        "--verbose".to_string(),
+    ];
+    if req.three_way {
+        args.push("--3way".to_string());
+    }
+    if req.check_only {
+        args.push("--check".to_string());
+    }
+    args.push(patch_path.to_string_lossy().to_string());
+
+    log::info!("Applying task {} diff: git {}", task_id, args.join(" "));
+
+    let output = Command::new("git").args(&args).output();
+
+    // Best-effort temp cleanup before error handling
+    let _ = std::fs::remove_file(&patch_path);
+
+    let output = output.map_err(|e| format!("Failed to run git apply: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let combined = format!("{}{}", stdout, stderr);
+    let applied = output.status.success();
+
+    // git apply reports problems as "error: <path>: ..." / "error: patch
+    // failed: <path>:<line>" / "U <path>" (3way conflicts on stdout)
+    let mut conflicts: Vec<String> = Vec::new();
+    for line in combined.lines() {
+        let path = if let Some(rest) = line.strip_prefix("error: patch failed: ") {
+            rest.split(':').next().map(|p| p.to_string())
+        } else if let Some(rest) = line.strip_prefix("error: ") {
+            rest.split(':').next().map(|p| p.to_string())
+        } else {
+            line.strip_prefix("U ").map(|rest| rest.trim().to_string())
+        };
+        if let Some(p) = path {
+            let p = p.trim().to_string();
+            if !p.is_empty() && !conflicts.contains(&p) {
+                conflicts.push(p);
+            }
+        }
+    }
+
+    log::info!(
+        "Apply task {} → {}: applied={}, {} conflicts",
+        task_id, req.target_path, applied, conflicts.len()
+    );
+
+    Ok(ApplyResponse {
+        task_id: task_id.to_string(),
+        workspace_id: workspace_id.to_string(),
+        target_path: req.target_path.clone(),
+        three_way: req.three_way,
+        check_only: req.check_only,
+        applied,
+        conflicts,
+        output: combined,
+        patch_bytes: diff.patch.len(),
+    })
+}
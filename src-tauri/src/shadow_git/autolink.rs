@@ -0,0 +1,189 @@
+//! Task → workspace auto-linking.
+//!
+//! Cline records the project's working directory inside `environment_details`
+//! blocks in `api_conversation_history.json` (e.g.
+//! `# Current Working Directory (c:/dev/my-project)`), but the checkpoint
+//! workspace directory name is an opaque hash. This module bridges the two:
+//! it parses the cwd from the conversation, resolves the workspace by
+//! scanning checkpoint commit subjects, and persists the resulting
+//! task → workspace mapping so later requests skip the git scan entirely.
+//!
+//! The /changes endpoints use this to make the `workspace` query parameter
+//! optional — when omitted, the task is auto-linked.
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use super::find_workspace_for_task;
+
+const LINKS_DIR: &str = "jira-dashboard";
+const LINKS_FILE: &str = "workspace_links.json";
+
+/// A resolved task → workspace link, persisted to the config dir.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskWorkspaceLink {
+    /// Task ID (epoch milliseconds directory name)
+    pub task_id: String,
+    /// Workspace ID (checkpoint directory name)
+    pub workspace_id: String,
+    /// Absolute path to the .git / .git_disabled directory
+    pub git_dir: String,
+    /// Project path parsed from environment_details (None if not found)
+    pub project_path: Option<String>,
+}
+
+/// In-memory link store, loaded from disk on first access.
+static LINKS: once_cell::sync::Lazy<RwLock<HashMap<String, TaskWorkspaceLink>>> =
+    once_cell::sync::Lazy::new(|| RwLock::new(load_links()));
+
+fn links_path() -> Option<PathBuf> {
+    let appdata = match std::env::var("APPDATA") {
+        Ok(val) => val,
+        Err(_) => {
+            log::warn!("Workspace links: %APPDATA% not set — cannot persist links");
+            return None;
+        }
+    };
+    let dir = PathBuf::from(appdata).join(LINKS_DIR);
+    if !dir.exists() {
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            log::warn!("Workspace links: failed to create directory {:?}: {}", dir, e);
+            return None;
+        }
+    }
+    Some(dir.join(LINKS_FILE))
+}
+
+/// Load the persisted link map. Best-effort: missing or corrupt files
+/// yield an empty map with a log.
+fn load_links() -> HashMap<String, TaskWorkspaceLink> {
+    let path = match links_path() {
+        Some(p) if p.exists() => p,
+        _ => return HashMap::new(),
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(content) => match serde_json::from_str::<Vec<TaskWorkspaceLink>>(&content) {
+            Ok(links) => {
+                log::info!("Workspace links: loaded {} cached links", links.len());
+                links.into_iter().map(|l| (l.task_id.clone(), l)).collect()
+            }
+            Err(e) => {
+                log::warn!("Workspace links: corrupt {:?} ({}) — starting empty", path, e);
+                HashMap::new()
+            }
+        },
+        Err(e) => {
+            log::warn!("Workspace links: failed to read {:?}: {}", path, e);
+            HashMap::new()
+        }
+    }
+}
+
+/// Persist the current link map. Best-effort with warn logs.
+fn save_links(links: &HashMap<String, TaskWorkspaceLink>) {
+    let path = match links_path() {
+        Some(p) => p,
+        None => return,
+    };
+    let mut list: Vec<&TaskWorkspaceLink> = links.values().collect();
+    list.sort_by(|a, b| a.task_id.cmp(&b.task_id));
+    match serde_json::to_string_pretty(&list) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                log::warn!("Workspace links: failed to write {:?}: {}", path, e);
+            }
+        }
+        Err(e) => log::warn!("Workspace links: serialization failed: {}", e),
+    }
+}
+
+/// Parse the project working directory from a task's conversation.
+///
+/// Scans `api_conversation_history.json` for the environment_details markers
+/// Cline embeds in user messages:
+/// - `# Current Working Directory (<path>)`
+/// - `# Current Workspace Directory (<path>)` (newer Cline versions)
+///
+/// Operates on the raw file text — the markers live inside JSON string
+/// values, so a substring search is both simpler and cheaper than a full
+/// parse. Returns None if the task or marker doesn't exist.
+pub fn parse_task_cwd(task_id: &str) -> Option<String> {
+    let root = crate::conversation_history::root::tasks_root()?;
+    let path = root.join(task_id).join("api_conversation_history.json");
+    let content = std::fs::read_to_string(&path).ok()?;
+
+    for marker in &[
+        "# Current Working Directory (",
+        "# Current Workspace Directory (",
+    ] {
+        if let Some(start) = content.find(marker) {
+            let rest = &content[start + marker.len()..];
+            if let Some(end) = rest.find(')') {
+                let cwd = rest[..end].trim();
+                if !cwd.is_empty() {
+                    return Some(cwd.to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Look up the original project path for a workspace from the link store.
+///
+/// Any cached task link for this workspace that carried a parsed
+/// `project_path` will do — all tasks in a workspace share the project
+/// directory. Returns None when no link for the workspace has one.
+pub fn project_path_for_workspace(workspace_id: &str) -> Option<String> {
+    let links = LINKS.read();
+    links
+        .values()
+        .find(|l| l.workspace_id == workspace_id && l.project_path.is_some())
+        .and_then(|l| l.project_path.clone())
+}
+
+/// Resolve the checkpoint workspace for a task, using the persisted link
+/// store when possible.
+///
+/// Resolution order:
+/// 1. Cached link (memory/disk) — verified that the git_dir still exists
+/// 2. Commit-subject scan via `find_workspace_for_task` (slow path), after
+///    which the link is cached along with the parsed project path
+///
+/// Returns None if no workspace contains checkpoints for this task.
+pub fn resolve_workspace_for_task(task_id: &str) -> Option<(String, PathBuf)> {
+    // Fast path: cached link
+    {
+        let links = LINKS.read();
+        if let Some(link) = links.get(task_id) {
+            let git_dir = PathBuf::from(&link.git_dir);
+            if git_dir.exists() {
+                return Some((link.workspace_id.clone(), git_dir));
+            }
+            // Stale link (workspace nuked or renamed) — fall through to re-scan
+            log::info!(
+                "Workspace links: cached git_dir for task {} is gone — re-resolving",
+                task_id
+            );
+        }
+    }
+
+    // Slow path: scan commit subjects across all workspaces
+    let (workspace_id, git_dir) = find_workspace_for_task(task_id)?;
+
+    let link = TaskWorkspaceLink {
+        task_id: task_id.to_string(),
+        workspace_id: workspace_id.clone(),
+        git_dir: git_dir.to_string_lossy().to_string(),
+        project_path: parse_task_cwd(task_id),
+    };
+
+    let mut links = LINKS.write();
+    links.insert(task_id.to_string(), link);
+    save_links(&links);
+
+    Some((workspace_id, git_dir))
+}
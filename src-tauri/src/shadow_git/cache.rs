@@ -173,3 +173,55 @@ pub fn save_steps(workspace_id: &str, task_id: &str, data: &StepsResponse) {
         }
     }
 }
+
+// ============ Task diff summaries ============
+
+/// Summary cache file name: summary_<workspace_id>_<task_id>.json
+fn summary_file(workspace_id: &str, task_id: &str) -> String {
+    format!("summary_{}_{}.json", workspace_id, task_id)
+}
+
+/// Load a cached LLM task-diff summary from disk
+pub fn load_task_summary(workspace_id: &str, task_id: &str) -> Option<super::types::TaskDiffSummary> {
+    let path = cache_dir()?.join(summary_file(workspace_id, task_id));
+    match std::fs::read_to_string(&path) {
+        Ok(json) => {
+            match serde_json::from_str::<super::types::TaskDiffSummary>(&json) {
+                Ok(data) => {
+                    log::info!(
+                        "Loaded diff summary for task {} (workspace {}) from disk cache",
+                        task_id,
+                        workspace_id
+                    );
+                    Some(data)
+                }
+                Err(e) => {
+                    log::warn!("Failed to parse summary cache for {}:{}: {}", workspace_id, task_id, e);
+                    None
+                }
+            }
+        }
+        Err(_) => None,
+    }
+}
+
+/// Save an LLM task-diff summary to disk cache
+pub fn save_task_summary(workspace_id: &str, task_id: &str, data: &super::types::TaskDiffSummary) {
+    if let Some(dir) = cache_dir() {
+        let path = dir.join(summary_file(workspace_id, task_id));
+        match serde_json::to_string_pretty(data) {
+            Ok(json) => {
+                if let Err(e) = std::fs::write(&path, json) {
+                    log::warn!("Failed to write summary cache for {}:{}: {}", workspace_id, task_id, e);
+                } else {
+                    log::info!(
+                        "Saved diff summary for task {} (workspace {}) to disk cache",
+                        task_id,
+                        workspace_id
+                    );
+                }
+            }
+            Err(e) => log::warn!("Failed to serialize summary cache for {}:{}: {}", workspace_id, task_id, e),
+        }
+    }
+}
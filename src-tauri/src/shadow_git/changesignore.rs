@@ -0,0 +1,424 @@
+//! Persisted ignore patterns for the diff endpoints (".changesignore").
+//!
+//! Patterns are pathspec fragments (e.g. "node_modules", "src-tauri/target")
+//! that get appended to the `exclude` list of every task diff computation,
+//! on top of whatever the request passed explicitly. A global pattern file
+//! applies to all workspaces; per-workspace files merge on top of it.
+//! Files live under `%APPDATA%/jira-dashboard/changesignore/` — one pattern
+//! per line, blank lines and `#` comments ignored.
+
+use std::path::PathBuf;
+
+use axum::http::StatusCode;
+use axum::response::Json;
+use serde::{Deserialize, Serialize};
+
+const IGNORE_DIR: &str = "jira-dashboard/changesignore";
+const GLOBAL_FILE: &str = "global";
+
+/// Query parameters for GET/PUT /changes/ignore
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct IgnoreQuery {
+    /// Workspace ID — GET merges its patterns into `effective`, PUT writes
+    /// the workspace file instead of the global one
+    #[serde(default)]
+    pub workspace: Option<String>,
+}
+
+/// Request body for PUT /changes/ignore
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IgnoreUpdateRequest {
+    /// The full new pattern list (replaces the previous contents)
+    pub patterns: Vec<String>,
+}
+
+/// Response for GET and PUT /changes/ignore
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IgnoreResponse {
+    /// Workspace the response is scoped to (None for global-only)
+    pub workspace: Option<String>,
+    /// Patterns from the global file
+    pub global_patterns: Vec<String>,
+    /// Patterns from the workspace file (empty without a workspace param)
+    pub workspace_patterns: Vec<String>,
+    /// Merged, deduplicated list as applied to diffs (global first)
+    pub effective: Vec<String>,
+}
+
+/// Return the ignore directory, creating it if needed.
+fn ignore_dir() -> Option<PathBuf> {
+    let appdata = std::env::var("APPDATA").ok()?;
+    let dir = PathBuf::from(appdata).join(IGNORE_DIR);
+    if !dir.exists() {
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            log::warn!("Failed to create changesignore dir {:?}: {}", dir, e);
+            return None;
+        }
+    }
+    Some(dir)
+}
+
+/// Workspace file name: ws_<workspace_id> — the id is validated so it
+/// can't escape the ignore directory.
+fn workspace_file(workspace_id: &str) -> Result<String, String> {
+    if workspace_id.is_empty()
+        || !workspace_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(format!("Invalid workspace id '{}'", workspace_id));
+    }
+    Ok(format!("ws_{}", workspace_id))
+}
+
+/// Read one pattern file — blank lines and `#` comments are skipped.
+fn read_patterns(path: &PathBuf) -> Vec<String> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => text
+            .lines()
+            .map(|l| l.trim())
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .map(|l| l.to_string())
+            .collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Patterns from the global file.
+pub fn global_patterns() -> Vec<String> {
+    ignore_dir()
+        .map(|d| read_patterns(&d.join(GLOBAL_FILE)))
+        .unwrap_or_default()
+}
+
+/// Patterns from one workspace's file (empty for unknown/invalid ids).
+pub fn workspace_patterns(workspace_id: &str) -> Vec<String> {
+    let Ok(file) = workspace_file(workspace_id) else {
+        return Vec::new();
+    };
+    ignore_dir()
+        .map(|d| read_patterns(&d.join(file)))
+        .unwrap_or_default()
+}
+
+/// The merged pattern list applied to diffs: global first, then the
+/// workspace's own patterns, deduplicated.
+pub fn effective_patterns(workspace_id: Option<&str>) -> Vec<String> {
+    let mut merged = global_patterns();
+    if let Some(ws) = workspace_id {
+        for p in workspace_patterns(ws) {
+            if !merged.contains(&p) {
+                merged.push(p);
+            }
+        }
+    }
+    merged
+}
+
+/// Append the effective ignore patterns to a request's exclude list,
+/// skipping duplicates — the shared entry point for the diff handlers.
+pub fn merge_into_excludes(excludes: &mut Vec<String>, workspace_id: &str) {
+    for p in effective_patterns(Some(workspace_id)) {
+        if !excludes.contains(&p) {
+            excludes.push(p);
+        }
+    }
+}
+
+/// Write a pattern list to the global or a workspace file.
+fn save_patterns(workspace_id: Option<&str>, patterns: &[String]) -> Result<(), String> {
+    let dir = ignore_dir().ok_or_else(|| "Could not resolve config directory (APPDATA not set?)".to_string())?;
+    let file = match workspace_id {
+        Some(ws) => workspace_file(ws)?,
+        None => GLOBAL_FILE.to_string(),
+    };
+    let mut body = patterns
+        .iter()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    if !body.is_empty() {
+        body.push('\n');
+    }
+    std::fs::write(dir.join(&file), body)
+        .map_err(|e| format!("Failed to write ignore file '{}': {}", file, e))
+}
+
+/// Build the standard response for the current state of the pattern files.
+fn current_state(workspace_id: Option<String>) -> IgnoreResponse {
+    let ws_patterns = workspace_id
+        .as_deref()
+        .map(workspace_patterns)
+        .unwrap_or_default();
+    IgnoreResponse {
+        effective: effective_patterns(workspace_id.as_deref()),
+        global_patterns: global_patterns(),
+        workspace_patterns: ws_patterns,
+        workspace: workspace_id,
+    }
+}
+
+/// Get the persisted diff ignore patterns
+///
+/// Returns the global pattern list and, when `workspace` is given, that
+/// workspace's own patterns plus the merged `effective` list that the diff
+/// endpoints apply on top of explicit `exclude` parameters.
+#[utoipa::path(
+    get,
+    path = "/changes/ignore",
+    params(IgnoreQuery),
+    responses(
+        (status = 200, description = "Current ignore patterns", body = IgnoreResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["changes", "tool"]
+)]
+pub async fn get_ignore_handler(
+    axum::extract::Query(params): axum::extract::Query<IgnoreQuery>,
+) -> Json<IgnoreResponse> {
+    log::info!(
+        "REST API: GET /changes/ignore — workspace={:?}",
+        params.workspace
+    );
+    Json(current_state(params.workspace.filter(|w| !w.is_empty())))
+}
+
+/// Replace the persisted diff ignore patterns
+///
+/// Without `workspace`, rewrites the global pattern file; with it, rewrites
+/// that workspace's override file (global patterns are left untouched and
+/// still merge in). Responds with the resulting state.
+#[utoipa::path(
+    put,
+    path = "/changes/ignore",
+    params(IgnoreQuery),
+    request_body = IgnoreUpdateRequest,
+    responses(
+        (status = 200, description = "Patterns updated", body = IgnoreResponse),
+        (status = 400, description = "Invalid workspace id or unwritable config dir", body = super::handlers::ChangesErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["changes"]
+)]
+pub async fn put_ignore_handler(
+    axum::extract::Query(params): axum::extract::Query<IgnoreQuery>,
+    Json(body): Json<IgnoreUpdateRequest>,
+) -> Result<Json<IgnoreResponse>, (StatusCode, Json<super::handlers::ChangesErrorResponse>)> {
+    let workspace = params.workspace.filter(|w| !w.is_empty());
+    log::info!(
+        "REST API: PUT /changes/ignore — workspace={:?}, {} patterns",
+        workspace,
+        body.patterns.len()
+    );
+
+    save_patterns(workspace.as_deref(), &body.patterns).map_err(|e| {
+        log::warn!("REST API: Failed to save ignore patterns: {}", e);
+        (
+            StatusCode::BAD_REQUEST,
+            Json(super::handlers::ChangesErrorResponse { error: e, code: 400 }),
+        )
+    })?;
+
+    crate::audit::record(
+        "changes.ignore_update",
+        serde_json::json!({
+            "workspace": workspace,
+            "patternCount": body.patterns.len(),
+        }),
+        true,
+        "Ignore patterns saved",
+    );
+
+    Ok(Json(current_state(workspace)))
+}
+
+// ============ Pattern preview ============
+
+/// Request body for POST /changes/ignore/preview
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IgnorePreviewRequest {
+    /// Task whose diff the patterns are tested against
+    pub task_id: String,
+    /// Workspace ID (optional — auto-linked from the task when omitted)
+    #[serde(default)]
+    pub workspace: Option<String>,
+    /// Candidate patterns to validate and preview
+    pub patterns: Vec<String>,
+}
+
+/// Validation and match outcome for one candidate pattern
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PatternPreview {
+    /// The candidate pattern
+    pub pattern: String,
+    /// False when the pattern is malformed (see `error`)
+    pub valid: bool,
+    /// Why the pattern was rejected (None for valid patterns)
+    pub error: Option<String>,
+    /// Files from the task diff this pattern would exclude
+    pub matched_files: Vec<String>,
+}
+
+/// Response for POST /changes/ignore/preview
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IgnorePreviewResponse {
+    /// Task ID the preview ran against
+    pub task_id: String,
+    /// Workspace ID
+    pub workspace_id: String,
+    /// Number of files in the task diff (before exclusion)
+    pub total_files: usize,
+    /// Union of files excluded by any valid pattern
+    pub excluded_files: Vec<String>,
+    /// Per-pattern validation and matches
+    pub patterns: Vec<PatternPreview>,
+}
+
+/// Validate one candidate pattern — returns an error message for
+/// malformed input, None when it's usable.
+fn validate_pattern(pattern: &str) -> Option<String> {
+    if pattern.trim().is_empty() {
+        return Some("Pattern is empty".to_string());
+    }
+    if pattern.starts_with(':') {
+        return Some("Pathspec magic (leading ':') is not allowed — use a plain path or glob".to_string());
+    }
+    if pattern.starts_with('/') {
+        return Some("Patterns are relative to the repo root — drop the leading '/'".to_string());
+    }
+    if pattern.contains('\\') {
+        return Some("Use forward slashes, not backslashes".to_string());
+    }
+    None
+}
+
+/// Does a pattern exclude a path? Mirrors git's default pathspec rules:
+/// a pattern without glob characters matches the path itself or anything
+/// under it as a directory; `*` and `?` glob across the whole path.
+pub fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim_end_matches('/');
+    if !pattern.contains(['*', '?']) {
+        return path == pattern || path.starts_with(&format!("{}/", pattern));
+    }
+    glob_match(pattern.as_bytes(), path.as_bytes())
+}
+
+/// Minimal glob matcher: `*` matches any run of characters (including
+/// `/`, like git's default non-:(glob) pathspec wildcards), `?` exactly one.
+fn glob_match(pattern: &[u8], path: &[u8]) -> bool {
+    match (pattern.first(), path.first()) {
+        (None, None) => true,
+        (Some(b'*'), _) => {
+            glob_match(&pattern[1..], path)
+                || (!path.is_empty() && glob_match(pattern, &path[1..]))
+        }
+        (Some(b'?'), Some(_)) => glob_match(&pattern[1..], &path[1..]),
+        (Some(pc), Some(c)) if pc == c => glob_match(&pattern[1..], &path[1..]),
+        _ => false,
+    }
+}
+
+/// Preview which diff files candidate ignore patterns would exclude
+///
+/// Validates each pattern and tests it against the file list of the task's
+/// full diff (computed without any exclusions), so users can check what a
+/// pattern hits before saving it via PUT /changes/ignore. Invalid patterns
+/// are reported per-entry instead of failing the whole request.
+#[utoipa::path(
+    post,
+    path = "/changes/ignore/preview",
+    request_body = IgnorePreviewRequest,
+    responses(
+        (status = 200, description = "Per-pattern validation and matched files", body = IgnorePreviewResponse),
+        (status = 400, description = "Invalid task or workspace", body = super::handlers::ChangesErrorResponse),
+        (status = 500, description = "Internal server error", body = super::handlers::ChangesErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["changes", "tool"]
+)]
+pub async fn preview_ignore_handler(
+    Json(body): Json<IgnorePreviewRequest>,
+) -> Result<Json<IgnorePreviewResponse>, (StatusCode, Json<super::handlers::ChangesErrorResponse>)> {
+    let task_id = body.task_id.clone();
+    if task_id.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(super::handlers::ChangesErrorResponse {
+                error: "Missing required 'taskId' field".to_string(),
+                code: 400,
+            }),
+        ));
+    }
+
+    let (workspace_id, git_dir) =
+        super::handlers::resolve_workspace_for_request(&task_id, body.workspace.clone()).await?;
+
+    log::info!(
+        "REST API: POST /changes/ignore/preview — task={}, workspace={}, {} patterns",
+        task_id, workspace_id, body.patterns.len()
+    );
+
+    let tid = task_id.clone();
+    let diff = tokio::task::spawn_blocking(move || {
+        let git_path = PathBuf::from(&git_dir);
+        super::discovery::get_task_diff(&tid, &git_path, &[])
+    })
+    .await
+    .map_err(|e| (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(super::handlers::ChangesErrorResponse {
+            error: format!("Failed to compute task diff: {}", e),
+            code: 500,
+        }),
+    ))?
+    .map_err(|e| (
+        StatusCode::BAD_REQUEST,
+        Json(super::handlers::ChangesErrorResponse { error: e, code: 400 }),
+    ))?;
+
+    let paths: Vec<&str> = diff.files.iter().map(|f| f.path.as_str()).collect();
+    let mut excluded: Vec<String> = Vec::new();
+    let mut previews: Vec<PatternPreview> = Vec::new();
+
+    for pattern in &body.patterns {
+        if let Some(error) = validate_pattern(pattern) {
+            previews.push(PatternPreview {
+                pattern: pattern.clone(),
+                valid: false,
+                error: Some(error),
+                matched_files: Vec::new(),
+            });
+            continue;
+        }
+        let matched: Vec<String> = paths
+            .iter()
+            .filter(|p| pattern_matches(pattern, p))
+            .map(|p| p.to_string())
+            .collect();
+        for m in &matched {
+            if !excluded.contains(m) {
+                excluded.push(m.clone());
+            }
+        }
+        previews.push(PatternPreview {
+            pattern: pattern.clone(),
+            valid: true,
+            error: None,
+            matched_files: matched,
+        });
+    }
+
+    Ok(Json(IgnorePreviewResponse {
+        task_id,
+        workspace_id,
+        total_files: paths.len(),
+        excluded_files: excluded,
+        patterns: previews,
+    }))
+}
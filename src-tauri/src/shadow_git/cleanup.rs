@@ -1,4 +1,5 @@
-//! Workspace cleanup — nuke all checkpoint history by re-initializing the bare git repo.
+//! Workspace cleanup — nuke all checkpoint history by re-initializing the
+//! bare git repo, or drop a single task's checkpoints via ref surgery.
 
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -19,6 +20,332 @@ pub struct NukeWorkspaceResponse {
     pub success: bool,
 }
 
+/// Result of nuking a single task's checkpoints
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct NukeTaskResponse {
+    /// Task ID whose checkpoints were deleted
+    pub task_id: String,
+    /// Workspace ID the task belonged to
+    pub workspace_id: String,
+    /// Number of checkpoint commits dropped
+    pub deleted_commits: usize,
+    /// Refs that were rewound or deleted
+    pub updated_refs: Vec<String>,
+    /// Loose + packed objects reclaimed by the follow-up gc
+    pub reclaimed_objects: usize,
+    /// Whether the operation was successful
+    pub success: bool,
+}
+
+/// Total object count (loose + in-pack) from `git count-objects -v`.
+fn count_objects(git_dir: &str) -> usize {
+    let output = std::process::Command::new("git")
+        .args(["--git-dir", git_dir, "count-objects", "-v"])
+        .output();
+
+    match output {
+        Ok(out) if out.status.success() => {
+            let stdout = String::from_utf8_lossy(&out.stdout);
+            let mut total = 0usize;
+            for line in stdout.lines() {
+                if let Some(rest) = line.strip_prefix("count: ") {
+                    total += rest.trim().parse::<usize>().unwrap_or(0);
+                } else if let Some(rest) = line.strip_prefix("in-pack: ") {
+                    total += rest.trim().parse::<usize>().unwrap_or(0);
+                }
+            }
+            total
+        }
+        _ => 0,
+    }
+}
+
+/// First-parent (hash, subject) log of a single ref, newest first.
+fn ref_log(git_dir: &str, ref_name: &str) -> Vec<(String, String)> {
+    let output = std::process::Command::new("git")
+        .args([
+            "--git-dir", git_dir,
+            "log", "--first-parent", "--pretty=format:%H|%s",
+            ref_name,
+        ])
+        .output();
+
+    match output {
+        Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout)
+            .lines()
+            .filter_map(|line| {
+                line.split_once('|')
+                    .map(|(h, s)| (h.to_string(), s.to_string()))
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Nuke a single task's checkpoints, keeping other tasks' history intact.
+///
+/// Checkpoint commits for a task sit at the tip of whichever ref Cline was
+/// committing to, so they can be dropped by rewinding that ref past them
+/// (or deleting the ref when the task owns its whole history). Task commits
+/// buried under OTHER tasks' commits can't be removed without rewriting
+/// that later history — the operation refuses and nothing is modified.
+///
+/// After the ref surgery, reflogs are expired and `git gc --prune=now`
+/// reclaims the now-unreachable objects; the response reports how many.
+pub fn nuke_task(
+    workspace_id: &str,
+    task_id: &str,
+    git_dir: &str,
+) -> Result<NukeTaskResponse, String> {
+    let git_path = Path::new(git_dir);
+
+    // Same safety rules as the workspace nuke
+    let dir_name = git_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("");
+
+    if dir_name == ".git_disabled" {
+        return Err(format!(
+            "Cannot nuke task '{}': git dir is '.git_disabled' — Cline is actively running a task. \
+             Wait for the task to finish before nuking.",
+            task_id
+        ));
+    }
+
+    if dir_name != ".git" {
+        return Err(format!(
+            "Cannot nuke task '{}': unexpected git dir name '{}' (expected '.git')",
+            task_id, dir_name
+        ));
+    }
+
+    if !git_path.exists() {
+        return Err(format!(
+            "Cannot nuke task '{}': git dir does not exist at '{}'",
+            task_id, git_dir
+        ));
+    }
+
+    let task_marker = format!("-{}", task_id);
+    let is_task_commit =
+        |subject: &str| subject.starts_with("checkpoint-") && subject.ends_with(&task_marker);
+
+    // All refs in the repo
+    let refs_output = std::process::Command::new("git")
+        .args(["--git-dir", git_dir, "for-each-ref", "--format=%(refname)"])
+        .output()
+        .map_err(|e| format!("Failed to run git for-each-ref: {}", e))?;
+    if !refs_output.status.success() {
+        let stderr = String::from_utf8_lossy(&refs_output.stderr);
+        return Err(format!("git for-each-ref failed: {}", stderr.trim()));
+    }
+    let refs: Vec<String> = String::from_utf8_lossy(&refs_output.stdout)
+        .lines()
+        .map(|l| l.to_string())
+        .collect();
+
+    // Plan the surgery before touching anything: per ref, the task's commits
+    // must form a prefix of the first-parent log (i.e. sit at the tip)
+    let mut planned: Vec<(String, Option<String>, usize)> = Vec::new(); // (ref, new_tip, dropped)
+    let mut total_task_commits = 0usize;
+    let mut droppable = 0usize;
+
+    for ref_name in &refs {
+        let log = ref_log(git_dir, ref_name);
+        let in_ref = log.iter().filter(|(_, s)| is_task_commit(s)).count();
+        total_task_commits = total_task_commits.max(in_ref);
+        if in_ref == 0 {
+            continue;
+        }
+
+        let prefix = log.iter().take_while(|(_, s)| is_task_commit(s)).count();
+        if prefix < in_ref {
+            return Err(format!(
+                "Task '{}' has checkpoint commits buried under later work on '{}' — \
+                 removing them would rewrite other tasks' history. Nothing was modified.",
+                task_id, ref_name
+            ));
+        }
+
+        // New tip = first commit past the task's prefix, or delete the ref
+        let new_tip = log.get(prefix).map(|(h, _)| h.clone());
+        droppable = droppable.max(prefix);
+        planned.push((ref_name.clone(), new_tip, prefix));
+    }
+
+    if planned.is_empty() {
+        return Err(format!(
+            "No checkpoint commits found for task '{}' in workspace '{}'",
+            task_id, workspace_id
+        ));
+    }
+
+    let objects_before = count_objects(git_dir);
+
+    // Apply the planned ref updates
+    let mut updated_refs = Vec::new();
+    for (ref_name, new_tip, dropped) in &planned {
+        let result = match new_tip {
+            Some(tip) => std::process::Command::new("git")
+                .args(["--git-dir", git_dir, "update-ref", ref_name, tip])
+                .output(),
+            None => std::process::Command::new("git")
+                .args(["--git-dir", git_dir, "update-ref", "-d", ref_name])
+                .output(),
+        };
+        match result {
+            Ok(out) if out.status.success() => {
+                log::info!(
+                    "Nuke task '{}': {} {} (dropped {} commits)",
+                    task_id,
+                    if new_tip.is_some() { "rewound" } else { "deleted" },
+                    ref_name,
+                    dropped
+                );
+                updated_refs.push(ref_name.clone());
+            }
+            Ok(out) => {
+                let stderr = String::from_utf8_lossy(&out.stderr);
+                return Err(format!(
+                    "git update-ref failed for '{}': {}",
+                    ref_name,
+                    stderr.trim()
+                ));
+            }
+            Err(e) => return Err(format!("Failed to run git update-ref: {}", e)),
+        }
+    }
+
+    // Expire reflogs and prune — this is what actually reclaims the objects
+    let _ = std::process::Command::new("git")
+        .args(["--git-dir", git_dir, "reflog", "expire", "--expire=now", "--all"])
+        .output();
+    let _ = std::process::Command::new("git")
+        .args(["--git-dir", git_dir, "gc", "--prune=now", "--quiet"])
+        .output();
+
+    let objects_after = count_objects(git_dir);
+    let reclaimed_objects = objects_before.saturating_sub(objects_after);
+
+    log::info!(
+        "Nuke task '{}' in workspace '{}': {} commits dropped, {} refs updated, {} objects reclaimed",
+        task_id, workspace_id, droppable, updated_refs.len(), reclaimed_objects
+    );
+
+    Ok(NukeTaskResponse {
+        task_id: task_id.to_string(),
+        workspace_id: workspace_id.to_string(),
+        deleted_commits: droppable,
+        updated_refs,
+        reclaimed_objects,
+        success: true,
+    })
+}
+
+/// Result of a gc/repack operation
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GcWorkspaceResponse {
+    /// Workspace ID that was repacked
+    pub workspace_id: String,
+    /// On-disk size before gc in bytes
+    pub size_before_bytes: u64,
+    /// On-disk size after gc in bytes
+    pub size_after_bytes: u64,
+    /// Bytes reclaimed (before - after; 0 when gc grew the repo)
+    pub reclaimed_bytes: u64,
+    /// Loose + packed object count before gc
+    pub objects_before: usize,
+    /// Loose + packed object count after gc
+    pub objects_after: usize,
+    /// The git command that was executed
+    pub git_command: String,
+    /// Whether the operation was successful
+    pub success: bool,
+}
+
+/// Run aggressive gc/repack on a workspace's shadow repo.
+///
+/// Expires reflogs first so unreachable commits (e.g. from task nukes) are
+/// actually prunable, then runs `git gc --aggressive --prune=now`. Reports
+/// on-disk size and object counts before/after so users can see what was
+/// reclaimed. Requires an active `.git` dir — same safety rule as nuking.
+pub fn gc_workspace(workspace_id: &str, git_dir: &str) -> Result<GcWorkspaceResponse, String> {
+    let git_path = Path::new(git_dir);
+
+    let dir_name = git_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("");
+
+    if dir_name == ".git_disabled" {
+        return Err(format!(
+            "Cannot gc workspace '{}': git dir is '.git_disabled' — Cline is actively running a task. \
+             Wait for the task to finish before repacking.",
+            workspace_id
+        ));
+    }
+
+    if dir_name != ".git" {
+        return Err(format!(
+            "Cannot gc workspace '{}': unexpected git dir name '{}' (expected '.git')",
+            workspace_id, dir_name
+        ));
+    }
+
+    if !git_path.exists() {
+        return Err(format!(
+            "Cannot gc workspace '{}': git dir does not exist at '{}'",
+            workspace_id, git_dir
+        ));
+    }
+
+    let size_before_bytes = super::discovery::dir_size(git_path);
+    let objects_before = count_objects(git_dir);
+
+    log::info!(
+        "GC workspace '{}': {} bytes, {} objects before",
+        workspace_id, size_before_bytes, objects_before
+    );
+
+    let _ = std::process::Command::new("git")
+        .args(["--git-dir", git_dir, "reflog", "expire", "--expire=now", "--all"])
+        .output();
+
+    let git_command = format!("git --git-dir \"{}\" gc --aggressive --prune=now", git_dir);
+    let gc_result = std::process::Command::new("git")
+        .args(["--git-dir", git_dir, "gc", "--aggressive", "--prune=now", "--quiet"])
+        .output();
+
+    match gc_result {
+        Ok(out) if out.status.success() => {
+            let size_after_bytes = super::discovery::dir_size(git_path);
+            let objects_after = count_objects(git_dir);
+            log::info!(
+                "GC workspace '{}': {} → {} bytes, {} → {} objects",
+                workspace_id, size_before_bytes, size_after_bytes, objects_before, objects_after
+            );
+            Ok(GcWorkspaceResponse {
+                workspace_id: workspace_id.to_string(),
+                size_before_bytes,
+                size_after_bytes,
+                reclaimed_bytes: size_before_bytes.saturating_sub(size_after_bytes),
+                objects_before,
+                objects_after,
+                git_command,
+                success: true,
+            })
+        }
+        Ok(out) => {
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            Err(format!("git gc failed for '{}': {}", git_dir, stderr.trim()))
+        }
+        Err(e) => Err(format!("Failed to run git gc for '{}': {}", git_dir, e)),
+    }
+}
+
 /// Count the commits in a bare git repo before nuking it.
 /// Returns (commit_count, task_count).
 fn count_commits_and_tasks(git_dir: &str) -> (usize, usize) {
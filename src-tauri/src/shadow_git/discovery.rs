@@ -58,12 +58,14 @@ pub fn find_workspaces() -> Vec<WorkspaceInfo> {
             let git_dir = ws_path.join(git_name);
             if git_dir.exists() {
                 let (task_count, last_modified) = count_tasks_and_latest(&git_dir);
+                let size_bytes = dir_size(&git_dir);
                 workspaces.push(WorkspaceInfo {
                     id: ws_id.clone(),
                     git_dir: git_dir.to_string_lossy().to_string(),
                     active: *active,
                     task_count,
                     last_modified,
+                    size_bytes,
                 });
                 // Only count the first one found (.git takes precedence)
                 break;
@@ -78,6 +80,24 @@ pub fn find_workspaces() -> Vec<WorkspaceInfo> {
     workspaces
 }
 
+/// Recursive on-disk size of a directory in bytes (best-effort — unreadable
+/// entries are skipped).
+pub(crate) fn dir_size(path: &std::path::Path) -> u64 {
+    let mut total = 0u64;
+    if let Ok(entries) = std::fs::read_dir(path) {
+        for entry in entries.flatten() {
+            if let Ok(meta) = entry.metadata() {
+                if meta.is_dir() {
+                    total += dir_size(&entry.path());
+                } else {
+                    total += meta.len();
+                }
+            }
+        }
+    }
+    total
+}
+
 /// Count distinct task-ids and find the most recent commit timestamp.
 /// Returns (task_count, last_modified_iso).
 fn count_tasks_and_latest(git_dir: &PathBuf) -> (usize, String) {
@@ -102,6 +122,38 @@ type CheckpointCommit = (String, String, String);
 /// Parse all checkpoint commits from a git repo.
 /// Returns Vec of (commit_hash, task_id, iso_timestamp).
 fn parse_checkpoint_commits(git_dir: &PathBuf) -> Vec<CheckpointCommit> {
+    // Primary: libgit2 (in-process, works without git on PATH)
+    match super::git_backend::log_all(git_dir) {
+        Ok(log) => log
+            .into_iter()
+            .filter_map(|(hash, subject, timestamp)| {
+                parse_checkpoint_subject(&subject).map(|task_id| (hash, task_id, timestamp))
+            })
+            .collect(),
+        Err(e) => {
+            log::warn!(
+                "libgit2 log failed for {:?} ({}) — falling back to git CLI",
+                git_dir, e
+            );
+            parse_checkpoint_commits_cli(git_dir)
+        }
+    }
+}
+
+/// Extract the task_id from a checkpoint commit subject: `checkpoint-<wsId>-<taskId>`
+fn parse_checkpoint_subject(subject: &str) -> Option<String> {
+    let rest = subject.strip_prefix("checkpoint-")?;
+    let dash_pos = rest.rfind('-')?;
+    let task_id = &rest[dash_pos + 1..];
+    if task_id.is_empty() {
+        None
+    } else {
+        Some(task_id.to_string())
+    }
+}
+
+/// CLI fallback for commit enumeration.
+fn parse_checkpoint_commits_cli(git_dir: &PathBuf) -> Vec<CheckpointCommit> {
     let git_dir_str = git_dir.to_string_lossy().to_string();
 
     // git --git-dir <path> log --all --pretty=format:%H|%s|%aI
@@ -135,14 +187,8 @@ fn parse_checkpoint_commits(git_dir: &PathBuf) -> Vec<CheckpointCommit> {
                 let subject = parts[1];
                 let timestamp = parts[2].to_string();
 
-                // Parse: checkpoint-<wsId>-<taskId>
-                if let Some(rest) = subject.strip_prefix("checkpoint-") {
-                    if let Some(dash_pos) = rest.rfind('-') {
-                        let task_id = &rest[dash_pos + 1..];
-                        if !task_id.is_empty() {
-                            commits.push((hash, task_id.to_string(), timestamp));
-                        }
-                    }
+                if let Some(task_id) = parse_checkpoint_subject(subject) {
+                    commits.push((hash, task_id, timestamp));
                 }
             }
 
@@ -155,64 +201,115 @@ fn parse_checkpoint_commits(git_dir: &PathBuf) -> Vec<CheckpointCommit> {
     }
 }
 
-/// Count files changed in a single commit using git diff --name-only
-fn count_files_in_commit(git_dir: &PathBuf, hash: &str) -> usize {
-    let git_dir_str = git_dir.to_string_lossy().to_string();
-    // diff this commit vs its parent: git --git-dir <path> diff --name-only <hash>^..<hash>
-    let output = Command::new("git")
-        .args([
-            "--git-dir",
-            &git_dir_str,
-            "diff",
-            "--name-only",
-            &format!("{}^..{}", hash, hash),
-        ])
-        .output();
+/// Parsed checkpoint commit plus the paths it touched:
+/// (hash, task_id, iso_timestamp, changed_paths)
+type CheckpointCommitFiles = (String, String, String, Vec<String>);
 
-    match output {
-        Ok(out) => {
-            if !out.status.success() {
-                // Might fail for root commit (no parent). Try diff-tree for root.
-                return count_files_root_commit(git_dir, hash);
-            }
-            let stdout = String::from_utf8_lossy(&out.stdout);
-            stdout.lines().filter(|l| !l.is_empty()).count()
+/// Parse all checkpoint commits with their changed paths in ONE pass.
+///
+/// This is the single-invocation replacement for the old
+/// one-`git diff`-per-commit enumeration: libgit2 walks the repo once and
+/// diffs trees in memory; the CLI fallback is a single
+/// `git log --all --numstat` call instead of O(commits) subprocesses.
+fn parse_checkpoint_commits_with_files(git_dir: &PathBuf) -> Vec<CheckpointCommitFiles> {
+    match super::git_backend::log_all_with_paths(git_dir) {
+        Ok(log) => log
+            .into_iter()
+            .filter_map(|(hash, subject, timestamp, paths)| {
+                parse_checkpoint_subject(&subject).map(|task_id| (hash, task_id, timestamp, paths))
+            })
+            .collect(),
+        Err(e) => {
+            log::warn!(
+                "libgit2 log-with-paths failed for {:?} ({}) — falling back to git CLI",
+                git_dir, e
+            );
+            parse_checkpoint_commits_with_files_cli(git_dir)
         }
-        Err(_) => 0,
     }
 }
 
-/// Count files in a root commit (no parent) using diff-tree
-fn count_files_root_commit(git_dir: &PathBuf, hash: &str) -> usize {
+/// CLI fallback: one `git log --all --numstat --pretty` invocation.
+///
+/// Output interleaves header lines (`<hash>|<subject>|<iso>`) with numstat
+/// lines (`<added>\t<removed>\t<path>`) and blanks — commits with no file
+/// changes simply have no numstat lines.
+fn parse_checkpoint_commits_with_files_cli(git_dir: &PathBuf) -> Vec<CheckpointCommitFiles> {
     let git_dir_str = git_dir.to_string_lossy().to_string();
+
     let output = Command::new("git")
         .args([
             "--git-dir",
             &git_dir_str,
-            "diff-tree",
-            "--no-commit-id",
-            "--name-only",
-            "-r",
-            hash,
+            "log",
+            "--all",
+            "--numstat",
+            "--pretty=format:%H|%s|%aI",
         ])
         .output();
 
-    match output {
+    let out = match output {
+        Ok(out) if out.status.success() => out,
         Ok(out) => {
-            let stdout = String::from_utf8_lossy(&out.stdout);
-            stdout.lines().filter(|l| !l.is_empty()).count()
+            let stderr = String::from_utf8_lossy(&out.stderr);
+            log::warn!("git log --numstat failed for {:?}: {}", git_dir, stderr.trim());
+            return Vec::new();
+        }
+        Err(e) => {
+            log::error!("Failed to execute git for {:?}: {}", git_dir, e);
+            return Vec::new();
+        }
+    };
+
+    let stdout = String::from_utf8_lossy(&out.stdout);
+    let mut commits: Vec<CheckpointCommitFiles> = Vec::new();
+    // Tracks whether the commit currently being filled is a checkpoint
+    // commit — numstat lines for non-checkpoint commits are skipped.
+    let mut current_is_checkpoint = false;
+
+    for line in stdout.lines() {
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.contains('|') && !line.contains('\t') {
+            // Header line: <hash>|<subject>|<iso>
+            let parts: Vec<&str> = line.splitn(3, '|').collect();
+            if parts.len() < 3 {
+                current_is_checkpoint = false;
+                continue;
+            }
+            match parse_checkpoint_subject(parts[1]) {
+                Some(task_id) => {
+                    commits.push((parts[0].to_string(), task_id, parts[2].to_string(), Vec::new()));
+                    current_is_checkpoint = true;
+                }
+                None => current_is_checkpoint = false,
+            }
+        } else if current_is_checkpoint {
+            // Numstat line: <added>\t<removed>\t<path>
+            let parts: Vec<&str> = line.split('\t').collect();
+            if parts.len() >= 3 {
+                if let Some(last) = commits.last_mut() {
+                    last.3.push(parts[2].to_string());
+                }
+            }
         }
-        Err(_) => 0,
     }
+
+    commits
 }
 
 /// List all tasks for a specific workspace, grouped from checkpoint commits.
 /// The `git_dir` should be the .git or .git_disabled path for the workspace.
+///
+/// Uses the single-pass commit+numstat enumeration — no per-commit git
+/// invocations regardless of workspace size.
 pub fn list_tasks_for_workspace(workspace_id: &str, git_dir: &PathBuf) -> Vec<ClineTaskSummary> {
-    let commits = parse_checkpoint_commits(git_dir);
+    let commits = parse_checkpoint_commits_with_files(git_dir);
 
     // Group commits by task_id
-    let mut task_map: HashMap<String, Vec<CheckpointCommit>> = HashMap::new();
+    let mut task_map: HashMap<String, Vec<CheckpointCommitFiles>> = HashMap::new();
     for commit in commits {
         task_map.entry(commit.1.clone()).or_default().push(commit);
     }
@@ -224,29 +321,14 @@ pub fn list_tasks_for_workspace(workspace_id: &str, git_dir: &PathBuf) -> Vec<Cl
 
             // Count total distinct files changed across all steps
             let mut all_files = std::collections::HashSet::new();
-            for (hash, _, _) in &task_commits {
-                let git_dir_str = git_dir.to_string_lossy().to_string();
-                let output = Command::new("git")
-                    .args([
-                        "--git-dir",
-                        &git_dir_str,
-                        "diff",
-                        "--name-only",
-                        &format!("{}^..{}", hash, hash),
-                    ])
-                    .output();
-                if let Ok(out) = output {
-                    let stdout = String::from_utf8_lossy(&out.stdout);
-                    for f in stdout.lines().filter(|l| !l.is_empty()) {
-                        all_files.insert(f.to_string());
-                    }
-                }
+            for (_, _, _, paths) in &task_commits {
+                all_files.extend(paths.iter().cloned());
             }
 
             // Most recent timestamp (commits are in reverse chronological order from git log)
             let last_modified = task_commits
                 .first()
-                .map(|(_, _, ts)| ts.clone())
+                .map(|(_, _, ts, _)| ts.clone())
                 .unwrap_or_default();
 
             ClineTaskSummary {
@@ -272,17 +354,20 @@ pub fn list_tasks_for_workspace(workspace_id: &str, git_dir: &PathBuf) -> Vec<Cl
 
 /// List individual checkpoint steps for a specific task.
 /// Returns steps in chronological order (oldest first), each with a 1-based index.
+///
+/// Uses the single-pass commit+numstat enumeration — no per-commit git
+/// invocations regardless of step count.
 pub fn list_steps_for_task(
     task_id: &str,
     workspace_id: &str,
     git_dir: &PathBuf,
 ) -> Vec<super::types::CheckpointStep> {
-    let commits = parse_checkpoint_commits(git_dir);
+    let commits = parse_checkpoint_commits_with_files(git_dir);
 
     // Filter to only commits for this task, they come in reverse chronological order
-    let mut task_commits: Vec<CheckpointCommit> = commits
+    let mut task_commits: Vec<CheckpointCommitFiles> = commits
         .into_iter()
-        .filter(|(_, tid, _)| tid == task_id)
+        .filter(|(_, tid, _, _)| tid == task_id)
         .collect();
 
     // Reverse to chronological order (oldest first)
@@ -291,13 +376,12 @@ pub fn list_steps_for_task(
     let steps: Vec<super::types::CheckpointStep> = task_commits
         .iter()
         .enumerate()
-        .map(|(i, (hash, _, timestamp))| {
-            let files_changed = count_files_in_commit(git_dir, hash);
+        .map(|(i, (hash, _, timestamp, paths))| {
             super::types::CheckpointStep {
                 hash: hash.clone(),
                 subject: format!("checkpoint-{}-{}", workspace_id, task_id),
                 timestamp: timestamp.clone(),
-                files_changed,
+                files_changed: paths.len(),
                 index: i + 1,
             }
         })
@@ -347,13 +431,34 @@ pub fn get_step_diff(
         format!("{}^", to_ref)
     };
 
+    // Primary: libgit2 — one in-process diff, no subprocesses. Root-commit
+    // from_refs ("<hash>^") are handled as empty-tree diffs internally.
+    match super::git_backend::diff_refs(git_dir, &from_ref, &to_ref) {
+        Ok((files, patch)) => {
+            log::info!(
+                "Step diff for task {} step {} via libgit2: {} files, {} bytes patch",
+                task_id, step_index, files.len(), patch.len()
+            );
+            let git_commands = vec![format!("libgit2: diff {}..{}", from_ref, to_ref)];
+            return Ok(super::types::DiffResult {
+                files,
+                patch,
+                from_ref,
+                to_ref,
+                git_commands,
+                intraline: None,
+            });
+        }
+        Err(e) => log::warn!("libgit2 step diff failed ({}) — falling back to git CLI", e),
+    }
+
     let git_dir_str = git_dir.to_string_lossy().to_string();
     let mut git_commands: Vec<String> = Vec::new();
 
     // Get --numstat for file-level stats
     let numstat_args = [
         "--git-dir", &git_dir_str,
-        "diff", "--numstat",
+        "diff", "--numstat", "-M", "-C",
         &from_ref, &to_ref,
     ];
     git_commands.push(format!("git {}", numstat_args.join(" ")));
@@ -382,7 +487,7 @@ pub fn get_step_diff(
     // Get unified diff patch text
     let patch_args = [
         "--git-dir", &git_dir_str,
-        "diff", &from_ref, &to_ref,
+        "diff", "-M", "-C", &from_ref, &to_ref,
     ];
     git_commands.push(format!("git {}", patch_args.join(" ")));
 
@@ -418,6 +523,7 @@ pub fn get_step_diff(
         from_ref,
         to_ref,
         git_commands,
+        intraline: None,
     })
 }
 
@@ -457,6 +563,29 @@ pub fn get_task_diff(
     let from_ref = format!("{}^", first_hash);
     let to_ref = last_hash.clone();
 
+    // Primary: libgit2 — but only without excludes, since libgit2 has no
+    // support for `:(exclude)` pathspec magic
+    if excludes.is_empty() {
+        match super::git_backend::diff_refs(git_dir, &from_ref, &to_ref) {
+            Ok((files, patch)) => {
+                log::info!(
+                    "Task diff for task {} via libgit2: {} → {} ({} files, {} bytes patch)",
+                    task_id, from_ref, to_ref, files.len(), patch.len()
+                );
+                let git_commands = vec![format!("libgit2: diff {}..{}", from_ref, to_ref)];
+                return Ok(super::types::DiffResult {
+                    files,
+                    patch,
+                    from_ref,
+                    to_ref,
+                    git_commands,
+                    intraline: None,
+                });
+            }
+            Err(e) => log::warn!("libgit2 task diff failed ({}) — falling back to git CLI", e),
+        }
+    }
+
     let git_dir_str = git_dir.to_string_lossy().to_string();
     let mut git_commands: Vec<String> = Vec::new();
 
@@ -470,6 +599,7 @@ pub fn get_task_diff(
     let mut numstat_args = vec![
         "--git-dir".to_string(), git_dir_str.clone(),
         "diff".to_string(), "--numstat".to_string(),
+        "-M".to_string(), "-C".to_string(),
         from_ref.clone(), to_ref.clone(),
     ];
     if !excludes.is_empty() {
@@ -533,7 +663,7 @@ pub fn get_task_diff(
     // Build patch args with exclude patterns
     let mut patch_args = vec![
         "--git-dir".to_string(), git_dir_str.clone(),
-        "diff".to_string(),
+        "diff".to_string(), "-M".to_string(), "-C".to_string(),
         from_ref.clone(), to_ref.clone(),
     ];
     if !excludes.is_empty() {
@@ -582,95 +712,143 @@ pub fn get_task_diff(
         from_ref,
         to_ref,
         git_commands,
+        intraline: None,
     })
 }
 
-/// Parse an ISO 8601 / RFC 3339 timestamp into epoch milliseconds for comparison.
-/// Handles both chrono rfc3339 (with fractional seconds) and git %aI (without).
-/// Falls back to string comparison if parsing fails.
-fn parse_timestamp_ms(ts: &str) -> i64 {
-    // Try chrono parsing (handles both formats)
-    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(ts) {
-        return dt.timestamp_millis();
-    }
-    // Fallback: try without fractional seconds
-    if let Ok(dt) = chrono::DateTime::parse_from_str(ts, "%Y-%m-%dT%H:%M:%S%:z") {
-        return dt.timestamp_millis();
+/// Compute the cumulative diff between two checkpoint steps of a task.
+///
+/// `from_step` and `to_step` are 1-based chronological indices. The diff
+/// covers everything that changed AFTER `from_step` up to and including
+/// `to_step` — i.e. "what changed since the state at from_step" — so
+/// `from_step` must be strictly less than `to_step`.
+pub fn get_range_diff(
+    task_id: &str,
+    from_step: usize,
+    to_step: usize,
+    git_dir: &PathBuf,
+) -> Result<super::types::DiffResult, String> {
+    // Verify git_dir exists on disk (Cline may rename .git ↔ .git_disabled during tasks)
+    if !git_dir.exists() {
+        return Err(format!(
+            "Git directory does not exist (Cline may have disabled it): {}",
+            git_dir.display()
+        ));
     }
-    log::warn!("Failed to parse timestamp for comparison: {}", ts);
-    0
-}
 
-/// Map subtask time boundaries to checkpoint step ranges.
-///
-/// Given subtask timestamps (from conversation_history) and step timestamps (from git),
-/// returns Vec of (subtask_index, first_step_array_idx, last_step_array_idx).
-/// Steps array must be in chronological order (oldest first).
-///
-/// Uses proper datetime parsing instead of lexicographic string comparison
-/// to handle format differences (chrono rfc3339 with fractional seconds vs
-/// git %aI without fractional seconds).
-pub fn map_subtasks_to_steps(
-    subtasks: &crate::conversation_history::types::SubtasksResponse,
-    steps: &[super::types::CheckpointStep],
-) -> Vec<(usize, usize, usize)> {
-    let mut mappings = Vec::new();
+    let commits = parse_checkpoint_commits(git_dir);
 
-    // Pre-parse step timestamps to epoch_ms for efficient comparison
-    let step_times: Vec<i64> = steps.iter()
-        .map(|s| parse_timestamp_ms(&s.timestamp))
+    // Filter to this task, reverse to chronological order (oldest first)
+    let mut task_commits: Vec<CheckpointCommit> = commits
+        .into_iter()
+        .filter(|(_, tid, _)| tid == task_id)
         .collect();
+    task_commits.reverse();
 
-    for (si, subtask) in subtasks.subtasks.iter().enumerate() {
-        let subtask_start_ms = parse_timestamp_ms(&subtask.timestamp);
-        let subtask_end_ms = subtasks.subtasks
-            .get(si + 1)
-            .map(|next| parse_timestamp_ms(&next.timestamp));
+    if task_commits.is_empty() {
+        return Err(format!("No checkpoint commits found for task '{}'", task_id));
+    }
 
-        let mut first_step: Option<usize> = None;
-        let mut last_step: Option<usize> = None;
+    if from_step == 0 || to_step > task_commits.len() || from_step >= to_step {
+        return Err(format!(
+            "Invalid step range {}..{} (task has {} steps; from_step must be >= 1 and < to_step)",
+            from_step,
+            to_step,
+            task_commits.len()
+        ));
+    }
 
-        for (i, step_ms) in step_times.iter().enumerate() {
-            let in_range = *step_ms >= subtask_start_ms
-                && subtask_end_ms.map_or(true, |end| *step_ms < end);
-            if in_range {
-                if first_step.is_none() {
-                    first_step = Some(i);
-                }
-                last_step = Some(i);
-            }
-        }
+    // Both endpoints are real commits — no root-commit `^` handling needed
+    let from_ref = task_commits[from_step - 1].0.clone();
+    let to_ref = task_commits[to_step - 1].0.clone();
 
-        if let (Some(first), Some(last)) = (first_step, last_step) {
-            log::debug!(
-                "Subtask #{} mapped to steps {}..{} (step hashes: {}..{})",
-                si, first, last,
-                &steps[first].hash[..8], &steps[last].hash[..8]
-            );
-            mappings.push((si, first, last));
-        } else {
-            log::warn!(
-                "Subtask #{} (start={}, end={:?}) has no matching checkpoint steps",
-                si, subtask.timestamp,
-                subtasks.subtasks.get(si + 1).map(|n| &n.timestamp)
+    // Primary: libgit2 — one in-process diff, no subprocesses
+    match super::git_backend::diff_refs(git_dir, &from_ref, &to_ref) {
+        Ok((files, patch)) => {
+            log::info!(
+                "Range diff for task {} steps {}..{} via libgit2: {} files, {} bytes patch",
+                task_id, from_step, to_step, files.len(), patch.len()
             );
+            let git_commands = vec![format!("libgit2: diff {}..{}", from_ref, to_ref)];
+            return Ok(super::types::DiffResult {
+                files,
+                patch,
+                from_ref,
+                to_ref,
+                git_commands,
+                intraline: None,
+            });
         }
+        Err(e) => log::warn!("libgit2 range diff failed ({}) — falling back to git CLI", e),
     }
 
-    mappings
+    let git_dir_str = git_dir.to_string_lossy().to_string();
+    let mut git_commands: Vec<String> = Vec::new();
+
+    // Get --numstat for file-level stats
+    let numstat_args = [
+        "--git-dir", &git_dir_str,
+        "diff", "--numstat", "-M", "-C",
+        &from_ref, &to_ref,
+    ];
+    git_commands.push(format!("git {}", numstat_args.join(" ")));
+
+    let numstat_output = Command::new("git")
+        .args(numstat_args)
+        .output()
+        .map_err(|e| format!("Failed to run git diff --numstat: {}", e))?;
+
+    if !numstat_output.status.success() {
+        let stderr = String::from_utf8_lossy(&numstat_output.stderr);
+        return Err(format!("git diff --numstat failed: {}", stderr.trim()));
+    }
+    let files = parse_numstat(&String::from_utf8_lossy(&numstat_output.stdout));
+
+    // Get unified diff patch text
+    let patch_args = [
+        "--git-dir", &git_dir_str,
+        "diff", "-M", "-C", &from_ref, &to_ref,
+    ];
+    git_commands.push(format!("git {}", patch_args.join(" ")));
+
+    let patch_output = Command::new("git")
+        .args(patch_args)
+        .output()
+        .map_err(|e| format!("Failed to run git diff: {}", e))?;
+
+    if !patch_output.status.success() {
+        let stderr = String::from_utf8_lossy(&patch_output.stderr);
+        return Err(format!("git diff failed: {}", stderr.trim()));
+    }
+    let patch = String::from_utf8_lossy(&patch_output.stdout).to_string();
+
+    log::info!(
+        "Range diff for task {} steps {}..{}: {} files, {} bytes patch",
+        task_id, from_step, to_step, files.len(), patch.len()
+    );
+
+    Ok(super::types::DiffResult {
+        files,
+        patch,
+        from_ref,
+        to_ref,
+        git_commands,
+        intraline: None,
+    })
 }
 
-/// Compute the diff for a single subtask phase.
+/// Compute the diff for a single file within a task.
 ///
-/// Maps conversation history subtask boundaries to checkpoint step ranges,
-/// then computes git diff between the boundary steps.
-/// `subtask_index` is 0-based (0 = initial task, 1+ = feedback subtasks).
-pub fn get_subtask_diff(
+/// Uses the same commit range as [`get_task_diff`] (first checkpoint's parent
+/// → last checkpoint), or — when `step` is given — the same range as
+/// [`get_step_diff`] for that 1-based step, but restricted to one path.
+/// A path the range never touched yields an empty diff (no files, empty
+/// patch), matching what `git diff -- <path>` reports.
+pub fn get_file_diff(
     task_id: &str,
-    subtask_index: usize,
-    workspace_id: &str,
+    file_path: &str,
+    step: Option<usize>,
     git_dir: &PathBuf,
-    excludes: &[String],
 ) -> Result<super::types::DiffResult, String> {
     // Verify git_dir exists on disk (Cline may rename .git ↔ .git_disabled during tasks)
     if !git_dir.exists() {
@@ -680,37 +858,655 @@ pub fn get_subtask_diff(
         ));
     }
 
-    // 1. Get subtask boundaries from conversation_history
-    let subtasks = crate::conversation_history::subtasks::parse_task_subtasks(task_id)
-        .ok_or_else(|| format!("No subtask data for task '{}' (ui_messages.json not found or no task entry)", task_id))?;
+    let commits = parse_checkpoint_commits(git_dir);
 
-    if subtask_index >= subtasks.total_subtasks {
-        return Err(format!(
-            "Subtask index {} out of range (task has {} subtasks)",
-            subtask_index, subtasks.total_subtasks
-        ));
-    }
+    // Filter to this task, reverse to chronological order (oldest first)
+    let mut task_commits: Vec<CheckpointCommit> = commits
+        .into_iter()
+        .filter(|(_, tid, _)| tid == task_id)
+        .collect();
+    task_commits.reverse();
 
-    // 2. Get checkpoint steps (chronological order)
-    let steps = list_steps_for_task(task_id, workspace_id, git_dir);
-    if steps.is_empty() {
-        return Err(format!("No checkpoint steps for task '{}'", task_id));
+    if task_commits.is_empty() {
+        return Err(format!("No checkpoint commits found for task '{}'", task_id));
     }
 
-    // 3. Map subtasks to step ranges
-    let mappings = map_subtasks_to_steps(&subtasks, &steps);
-    let mapping = mappings.iter()
-        .find(|(si, _, _)| *si == subtask_index)
-        .ok_or_else(|| format!(
-            "No checkpoint steps found within subtask #{}'s time window",
-            subtask_index
-        ))?;
+    let (from_ref, to_ref) = match step {
+        Some(step_index) => {
+            if step_index == 0 || step_index > task_commits.len() {
+                return Err(format!(
+                    "Step index {} out of range (task has {} steps)",
+                    step_index,
+                    task_commits.len()
+                ));
+            }
+            let to_ref = task_commits[step_index - 1].0.clone();
+            let from_ref = if step_index > 1 {
+                task_commits[step_index - 2].0.clone()
+            } else {
+                format!("{}^", to_ref)
+            };
+            (from_ref, to_ref)
+        }
+        None => {
+            let first_hash = &task_commits[0].0;
+            let last_hash = &task_commits[task_commits.len() - 1].0;
+            (format!("{}^", first_hash), last_hash.clone())
+        }
+    };
 
-    let (_, first_step_idx, last_step_idx) = *mapping;
+    // Primary: libgit2 — plain pathspecs are supported in-process
+    match super::git_backend::diff_refs_for_path(git_dir, &from_ref, &to_ref, file_path) {
+        Ok((files, patch)) => {
+            log::info!(
+                "File diff for task {} path {} via libgit2: {} → {} ({} bytes patch)",
+                task_id, file_path, from_ref, to_ref, patch.len()
+            );
+            let git_commands =
+                vec![format!("libgit2: diff {}..{} -- {}", from_ref, to_ref, file_path)];
+            return Ok(super::types::DiffResult {
+                files,
+                patch,
+                from_ref,
+                to_ref,
+                git_commands,
+                intraline: None,
+            });
+        }
+        Err(e) => log::warn!("libgit2 file diff failed ({}) — falling back to git CLI", e),
+    }
 
-    // 4. Compute diff boundaries
-    let to_ref = steps[last_step_idx].hash.clone();
-    let from_ref = if first_step_idx > 0 {
+    let git_dir_str = git_dir.to_string_lossy().to_string();
+    let mut git_commands: Vec<String> = Vec::new();
+
+    // Get --numstat for file-level stats, limited to the one path
+    let numstat_args = [
+        "--git-dir", &git_dir_str,
+        "diff", "--numstat", "-M", "-C",
+        &from_ref, &to_ref,
+        "--", file_path,
+    ];
+    git_commands.push(format!("git {}", numstat_args.join(" ")));
+
+    let numstat_output = Command::new("git")
+        .args(numstat_args)
+        .output()
+        .map_err(|e| format!("Failed to run git diff --numstat: {}", e))?;
+
+    let files = if numstat_output.status.success() {
+        parse_numstat(&String::from_utf8_lossy(&numstat_output.stdout))
+    } else {
+        // Might be root commit — try diff-tree
+        let dt_args = [
+            "--git-dir", &git_dir_str,
+            "diff-tree", "--numstat", "--no-commit-id", "-r", &to_ref,
+            "--", file_path,
+        ];
+        git_commands.push(format!("git {} (fallback)", dt_args.join(" ")));
+        let dt_out = Command::new("git")
+            .args(dt_args)
+            .output()
+            .map_err(|e| format!("Failed to run git diff-tree: {}", e))?;
+        parse_numstat(&String::from_utf8_lossy(&dt_out.stdout))
+    };
+
+    // Get unified diff patch text for the one path
+    let patch_args = [
+        "--git-dir", &git_dir_str,
+        "diff", "-M", "-C", &from_ref, &to_ref,
+        "--", file_path,
+    ];
+    git_commands.push(format!("git {}", patch_args.join(" ")));
+
+    let patch_output = Command::new("git")
+        .args(patch_args)
+        .output()
+        .map_err(|e| format!("Failed to run git diff: {}", e))?;
+
+    let patch = if patch_output.status.success() {
+        String::from_utf8_lossy(&patch_output.stdout).to_string()
+    } else {
+        // Try diff-tree for root commits
+        let dt_patch_args = [
+            "--git-dir", &git_dir_str,
+            "diff-tree", "-p", "--no-commit-id", "-r", &to_ref,
+            "--", file_path,
+        ];
+        git_commands.push(format!("git {} (fallback)", dt_patch_args.join(" ")));
+        let dt_out = Command::new("git")
+            .args(dt_patch_args)
+            .output()
+            .unwrap_or(patch_output);
+        String::from_utf8_lossy(&dt_out.stdout).to_string()
+    };
+
+    log::info!(
+        "File diff for task {} path {}: {} → {} ({} bytes patch)",
+        task_id, file_path, from_ref, to_ref, patch.len()
+    );
+
+    Ok(super::types::DiffResult {
+        files,
+        patch,
+        from_ref,
+        to_ref,
+        git_commands,
+        intraline: None,
+    })
+}
+
+/// One touching step in a file's history:
+/// (step_index, hash, iso_timestamp, lines_added, lines_removed, status)
+pub type FileHistoryStep = (usize, String, String, usize, usize, String);
+
+/// List every checkpoint step where a given file changed, with per-step
+/// line counts — the data behind the file evolution view.
+///
+/// Steps that didn't touch the file are skipped. Line stats come from an
+/// in-process libgit2 diff per touching step; the CLI fallback runs
+/// `git diff --numstat` for the same range. Returns the touching steps
+/// plus the task's total step count.
+pub fn get_file_history(
+    task_id: &str,
+    file_path: &str,
+    git_dir: &PathBuf,
+) -> Result<(Vec<FileHistoryStep>, usize), String> {
+    // Verify git_dir exists on disk (Cline may rename .git ↔ .git_disabled during tasks)
+    if !git_dir.exists() {
+        return Err(format!(
+            "Git directory does not exist (Cline may have disabled it): {}",
+            git_dir.display()
+        ));
+    }
+
+    // One pass gives us which steps touched the file; line stats are only
+    // computed for those steps.
+    let commits = parse_checkpoint_commits_with_files(git_dir);
+
+    let mut task_commits: Vec<CheckpointCommitFiles> = commits
+        .into_iter()
+        .filter(|(_, tid, _, _)| tid == task_id)
+        .collect();
+    task_commits.reverse();
+
+    if task_commits.is_empty() {
+        return Err(format!("No checkpoint commits found for task '{}'", task_id));
+    }
+
+    let mut entries = Vec::new();
+
+    for (i, (hash, _, timestamp, paths)) in task_commits.iter().enumerate() {
+        if !paths.iter().any(|p| p == file_path) {
+            continue;
+        }
+
+        let to_ref = hash.clone();
+        let from_ref = if i > 0 {
+            task_commits[i - 1].0.clone()
+        } else {
+            format!("{}^", to_ref)
+        };
+
+        // Stats for just this file in this step
+        let (lines_added, lines_removed, status) =
+            match super::git_backend::diff_refs_for_path(git_dir, &from_ref, &to_ref, file_path) {
+                Ok((files, _)) => match files.first() {
+                    Some(f) => (f.lines_added, f.lines_removed, f.status.clone()),
+                    None => (0, 0, "modified".to_string()),
+                },
+                Err(e) => {
+                    log::warn!(
+                        "libgit2 file history stats failed for step {} ({}) — falling back to git CLI",
+                        i + 1, e
+                    );
+                    file_step_stats_cli(git_dir, &from_ref, &to_ref, file_path)
+                }
+            };
+
+        entries.push((
+            i + 1,
+            hash.clone(),
+            timestamp.clone(),
+            lines_added,
+            lines_removed,
+            status,
+        ));
+    }
+
+    log::info!(
+        "File history for task {} path {}: {} of {} steps touched the file",
+        task_id, file_path, entries.len(), task_commits.len()
+    );
+
+    Ok((entries, task_commits.len()))
+}
+
+/// CLI fallback for per-step single-file stats: `git diff --numstat -- <path>`
+/// with a diff-tree fallback for root commits.
+fn file_step_stats_cli(
+    git_dir: &std::path::Path,
+    from_ref: &str,
+    to_ref: &str,
+    file_path: &str,
+) -> (usize, usize, String) {
+    let git_dir_str = git_dir.to_string_lossy().to_string();
+
+    let output = Command::new("git")
+        .args([
+            "--git-dir", &git_dir_str,
+            "diff", "--numstat", "-M", "-C",
+            from_ref, to_ref,
+            "--", file_path,
+        ])
+        .output();
+
+    let stdout = match output {
+        Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout).to_string(),
+        _ => {
+            // Root commit — diff-tree fallback
+            let dt = Command::new("git")
+                .args([
+                    "--git-dir", &git_dir_str,
+                    "diff-tree", "--numstat", "--no-commit-id", "-r", to_ref,
+                    "--", file_path,
+                ])
+                .output();
+            match dt {
+                Ok(out) => String::from_utf8_lossy(&out.stdout).to_string(),
+                Err(_) => String::new(),
+            }
+        }
+    };
+
+    match parse_numstat(&stdout).into_iter().next() {
+        Some(f) => (f.lines_added, f.lines_removed, f.status),
+        None => (0, 0, "modified".to_string()),
+    }
+}
+
+/// List one directory level of the project snapshot at a checkpoint ref.
+///
+/// `path` is the subdirectory to list ("" for the repo root). Entries come
+/// back directories-first, alphabetical within each group. libgit2 primary,
+/// `git ls-tree -l` fallback.
+pub fn get_tree(
+    git_dir: &std::path::Path,
+    git_ref: &str,
+    path: &str,
+) -> Result<Vec<super::types::TreeEntry>, String> {
+    let raw = match super::git_backend::ls_tree(git_dir, git_ref, path) {
+        Ok(raw) => raw,
+        Err(e) => {
+            // Path/ref errors are user errors, not backend failures — don't
+            // mask them behind the CLI fallback's less specific messages
+            if e.starts_with("Path '") {
+                return Err(e);
+            }
+            log::warn!("libgit2 ls-tree failed ({}) — falling back to git CLI", e);
+            ls_tree_cli(git_dir, git_ref, path)?
+        }
+    };
+
+    let mut entries: Vec<super::types::TreeEntry> = raw
+        .into_iter()
+        .map(|(name, mode, is_dir, size)| super::types::TreeEntry {
+            path: if path.is_empty() {
+                name.clone()
+            } else {
+                format!("{}/{}", path, name)
+            },
+            kind: if is_dir { "dir" } else { "file" }.to_string(),
+            name,
+            mode,
+            size,
+        })
+        .collect();
+
+    // Directories first, then files, alphabetical within each group
+    entries.sort_by(|a, b| a.kind.cmp(&b.kind).then_with(|| a.name.cmp(&b.name)));
+
+    log::info!(
+        "Tree at {}:{} — {} entries",
+        git_ref, if path.is_empty() { "(root)" } else { path }, entries.len()
+    );
+
+    Ok(entries)
+}
+
+/// CLI fallback: `git ls-tree -l <ref>[:<path>]`.
+/// Lines look like `<mode> <type> <hash> <size>\t<name>` (size is "-" for trees).
+fn ls_tree_cli(
+    git_dir: &std::path::Path,
+    git_ref: &str,
+    path: &str,
+) -> Result<Vec<super::git_backend::TreeEntryRaw>, String> {
+    let git_dir_str = git_dir.to_string_lossy().to_string();
+    let spec = if path.is_empty() {
+        git_ref.to_string()
+    } else {
+        format!("{}:{}", git_ref, path)
+    };
+
+    let output = Command::new("git")
+        .args(["--git-dir", &git_dir_str, "ls-tree", "-l", &spec])
+        .output()
+        .map_err(|e| format!("Failed to run git ls-tree: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git ls-tree failed: {}", stderr.trim()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut entries = Vec::new();
+
+    for line in stdout.lines() {
+        let (meta, name) = match line.split_once('\t') {
+            Some(parts) => parts,
+            None => continue,
+        };
+        let fields: Vec<&str> = meta.split_whitespace().collect();
+        if fields.len() < 4 {
+            continue;
+        }
+        let mode = fields[0].to_string();
+        let is_dir = fields[1] == "tree";
+        let size = fields[3].parse::<usize>().ok();
+        entries.push((name.to_string(), mode, is_dir, size));
+    }
+
+    Ok(entries)
+}
+
+/// Fixed-string content search within a checkpoint snapshot.
+///
+/// Returns (matches, truncated) — truncated is true when `max_matches` was
+/// hit. libgit2 tree-walk primary, `git grep -n -F` fallback.
+pub fn search_snapshot(
+    git_dir: &std::path::Path,
+    git_ref: &str,
+    query: &str,
+    max_matches: usize,
+) -> Result<(Vec<super::types::SearchMatch>, bool), String> {
+    let (raw, truncated) =
+        match super::git_backend::grep_tree(git_dir, git_ref, query, max_matches) {
+            Ok(result) => result,
+            Err(e) => {
+                log::warn!("libgit2 grep failed ({}) — falling back to git grep", e);
+                grep_cli(git_dir, git_ref, query, max_matches)?
+            }
+        };
+
+    let matches = raw
+        .into_iter()
+        .map(|(path, line_number, line)| super::types::SearchMatch {
+            path,
+            line_number,
+            line,
+        })
+        .collect();
+
+    Ok((matches, truncated))
+}
+
+/// CLI fallback: `git grep -n -F -e <query> <ref>`.
+/// Output lines look like `<ref>:<path>:<line>:<text>`.
+fn grep_cli(
+    git_dir: &std::path::Path,
+    git_ref: &str,
+    query: &str,
+    max_matches: usize,
+) -> Result<(Vec<super::git_backend::GrepMatch>, bool), String> {
+    let git_dir_str = git_dir.to_string_lossy().to_string();
+
+    let output = Command::new("git")
+        .args([
+            "--git-dir", &git_dir_str,
+            "grep", "-n", "-I", "-F",
+            "-e", query,
+            git_ref,
+        ])
+        .output()
+        .map_err(|e| format!("Failed to run git grep: {}", e))?;
+
+    // Exit code 1 with nothing on stderr just means "no matches"
+    if !output.status.success() && !output.stderr.is_empty() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git grep failed: {}", stderr.trim()));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let prefix = format!("{}:", git_ref);
+    let mut matches = Vec::new();
+    let mut truncated = false;
+
+    for line in stdout.lines() {
+        if matches.len() >= max_matches {
+            truncated = true;
+            break;
+        }
+        let rest = match line.strip_prefix(&prefix) {
+            Some(r) => r,
+            None => continue,
+        };
+        // <path>:<line>:<text>
+        let (path, rest) = match rest.split_once(':') {
+            Some(parts) => parts,
+            None => continue,
+        };
+        let (line_no, text) = match rest.split_once(':') {
+            Some(parts) => parts,
+            None => continue,
+        };
+        if let Ok(n) = line_no.parse::<usize>() {
+            let text: String = text.trim_end().chars().take(500).collect();
+            matches.push((path.to_string(), n, text));
+        }
+    }
+
+    Ok((matches, truncated))
+}
+
+/// Create a `git bundle` archiving a task's checkpoint history.
+///
+/// The bundle head is the task's newest checkpoint commit; ancestor commits
+/// are included as required for reachability, so the bundle is standalone
+/// (`git clone <file>.bundle` works). Returns the raw bundle bytes.
+///
+/// This stays on the git CLI — libgit2 has no bundle support. A temporary
+/// ref is created for the bundle head (bundles can only record refs, not
+/// bare hashes) and deleted afterwards.
+pub fn create_task_bundle(task_id: &str, git_dir: &PathBuf) -> Result<Vec<u8>, String> {
+    if !git_dir.exists() {
+        return Err(format!(
+            "Git directory does not exist (Cline may have disabled it): {}",
+            git_dir.display()
+        ));
+    }
+
+    let commits = parse_checkpoint_commits(git_dir);
+
+    // Newest checkpoint commit for the task (commits come newest-first)
+    let last_hash = commits
+        .into_iter()
+        .find(|(_, tid, _)| tid == task_id)
+        .map(|(hash, _, _)| hash)
+        .ok_or_else(|| format!("No checkpoint commits found for task '{}'", task_id))?;
+
+    let git_dir_str = git_dir.to_string_lossy().to_string();
+    let ref_name = format!("refs/cline-xray/bundle-{}", task_id);
+    let bundle_path = std::env::temp_dir().join(format!("cline-xray-{}.bundle", task_id));
+    let bundle_path_str = bundle_path.to_string_lossy().to_string();
+
+    // Point a temporary ref at the bundle head
+    let update = Command::new("git")
+        .args(["--git-dir", &git_dir_str, "update-ref", &ref_name, &last_hash])
+        .output()
+        .map_err(|e| format!("Failed to run git update-ref: {}", e))?;
+    if !update.status.success() {
+        let stderr = String::from_utf8_lossy(&update.stderr);
+        return Err(format!("git update-ref failed: {}", stderr.trim()));
+    }
+
+    let bundle = Command::new("git")
+        .args([
+            "--git-dir", &git_dir_str,
+            "bundle", "create", &bundle_path_str,
+            &ref_name,
+        ])
+        .output();
+
+    // Always drop the temporary ref, even when bundling failed
+    let _ = Command::new("git")
+        .args(["--git-dir", &git_dir_str, "update-ref", "-d", &ref_name])
+        .output();
+
+    let bundle = bundle.map_err(|e| format!("Failed to run git bundle: {}", e))?;
+    if !bundle.status.success() {
+        let stderr = String::from_utf8_lossy(&bundle.stderr);
+        let _ = std::fs::remove_file(&bundle_path);
+        return Err(format!("git bundle failed: {}", stderr.trim()));
+    }
+
+    let bytes = std::fs::read(&bundle_path)
+        .map_err(|e| format!("Failed to read bundle file: {}", e));
+    let _ = std::fs::remove_file(&bundle_path);
+    let bytes = bytes?;
+
+    log::info!(
+        "Bundled task {} ({} → {} bytes)",
+        task_id, &last_hash[..8.min(last_hash.len())], bytes.len()
+    );
+
+    Ok(bytes)
+}
+
+/// Parse an ISO 8601 / RFC 3339 timestamp into epoch milliseconds for comparison.
+/// Handles both chrono rfc3339 (with fractional seconds) and git %aI (without).
+/// Falls back to string comparison if parsing fails.
+fn parse_timestamp_ms(ts: &str) -> i64 {
+    // Try chrono parsing (handles both formats)
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(ts) {
+        return dt.timestamp_millis();
+    }
+    // Fallback: try without fractional seconds
+    if let Ok(dt) = chrono::DateTime::parse_from_str(ts, "%Y-%m-%dT%H:%M:%S%:z") {
+        return dt.timestamp_millis();
+    }
+    log::warn!("Failed to parse timestamp for comparison: {}", ts);
+    0
+}
+
+/// Map subtask time boundaries to checkpoint step ranges.
+///
+/// Given subtask timestamps (from conversation_history) and step timestamps (from git),
+/// returns Vec of (subtask_index, first_step_array_idx, last_step_array_idx).
+/// Steps array must be in chronological order (oldest first).
+///
+/// Uses proper datetime parsing instead of lexicographic string comparison
+/// to handle format differences (chrono rfc3339 with fractional seconds vs
+/// git %aI without fractional seconds).
+pub fn map_subtasks_to_steps(
+    subtasks: &crate::conversation_history::types::SubtasksResponse,
+    steps: &[super::types::CheckpointStep],
+) -> Vec<(usize, usize, usize)> {
+    let mut mappings = Vec::new();
+
+    // Pre-parse step timestamps to epoch_ms for efficient comparison
+    let step_times: Vec<i64> = steps.iter()
+        .map(|s| parse_timestamp_ms(&s.timestamp))
+        .collect();
+
+    for (si, subtask) in subtasks.subtasks.iter().enumerate() {
+        let subtask_start_ms = parse_timestamp_ms(&subtask.timestamp);
+        let subtask_end_ms = subtasks.subtasks
+            .get(si + 1)
+            .map(|next| parse_timestamp_ms(&next.timestamp));
+
+        let mut first_step: Option<usize> = None;
+        let mut last_step: Option<usize> = None;
+
+        for (i, step_ms) in step_times.iter().enumerate() {
+            let in_range = *step_ms >= subtask_start_ms
+                && subtask_end_ms.map_or(true, |end| *step_ms < end);
+            if in_range {
+                if first_step.is_none() {
+                    first_step = Some(i);
+                }
+                last_step = Some(i);
+            }
+        }
+
+        if let (Some(first), Some(last)) = (first_step, last_step) {
+            log::debug!(
+                "Subtask #{} mapped to steps {}..{} (step hashes: {}..{})",
+                si, first, last,
+                &steps[first].hash[..8], &steps[last].hash[..8]
+            );
+            mappings.push((si, first, last));
+        } else {
+            log::warn!(
+                "Subtask #{} (start={}, end={:?}) has no matching checkpoint steps",
+                si, subtask.timestamp,
+                subtasks.subtasks.get(si + 1).map(|n| &n.timestamp)
+            );
+        }
+    }
+
+    mappings
+}
+
+/// Compute the diff for a single subtask phase.
+///
+/// Maps conversation history subtask boundaries to checkpoint step ranges,
+/// then computes git diff between the boundary steps.
+/// `subtask_index` is 0-based (0 = initial task, 1+ = feedback subtasks).
+pub fn get_subtask_diff(
+    task_id: &str,
+    subtask_index: usize,
+    workspace_id: &str,
+    git_dir: &PathBuf,
+    excludes: &[String],
+) -> Result<super::types::DiffResult, String> {
+    // Verify git_dir exists on disk (Cline may rename .git ↔ .git_disabled during tasks)
+    if !git_dir.exists() {
+        return Err(format!(
+            "Git directory does not exist (Cline may have disabled it): {}",
+            git_dir.display()
+        ));
+    }
+
+    // 1. Get subtask boundaries from conversation_history
+    let subtasks = crate::conversation_history::subtasks::parse_task_subtasks(task_id)
+        .ok_or_else(|| format!("No subtask data for task '{}' (ui_messages.json not found or no task entry)", task_id))?;
+
+    if subtask_index >= subtasks.total_subtasks {
+        return Err(format!(
+            "Subtask index {} out of range (task has {} subtasks)",
+            subtask_index, subtasks.total_subtasks
+        ));
+    }
+
+    // 2. Get checkpoint steps (chronological order)
+    let steps = list_steps_for_task(task_id, workspace_id, git_dir);
+    if steps.is_empty() {
+        return Err(format!("No checkpoint steps for task '{}'", task_id));
+    }
+
+    // 3. Map subtasks to step ranges
+    let mappings = map_subtasks_to_steps(&subtasks, &steps);
+    let mapping = mappings.iter()
+        .find(|(si, _, _)| *si == subtask_index)
+        .ok_or_else(|| format!(
+            "No checkpoint steps found within subtask #{}'s time window",
+            subtask_index
+        ))?;
+
+    let (_, first_step_idx, last_step_idx) = *mapping;
+
+    // 4. Compute diff boundaries
+    let to_ref = steps[last_step_idx].hash.clone();
+    let from_ref = if first_step_idx > 0 {
         // Previous step's commit = baseline for this subtask
         steps[first_step_idx - 1].hash.clone()
     } else {
@@ -718,6 +1514,29 @@ pub fn get_subtask_diff(
         format!("{}^", steps[first_step_idx].hash)
     };
 
+    // Primary: libgit2 — but only without excludes, since libgit2 has no
+    // support for `:(exclude)` pathspec magic
+    if excludes.is_empty() {
+        match super::git_backend::diff_refs(git_dir, &from_ref, &to_ref) {
+            Ok((files, patch)) => {
+                log::info!(
+                    "Subtask diff for task {} subtask #{} via libgit2: {} → {} ({} files, {} bytes patch)",
+                    task_id, subtask_index, from_ref, to_ref, files.len(), patch.len()
+                );
+                let git_commands = vec![format!("libgit2: diff {}..{}", from_ref, to_ref)];
+                return Ok(super::types::DiffResult {
+                    files,
+                    patch,
+                    from_ref,
+                    to_ref,
+                    git_commands,
+                    intraline: None,
+                });
+            }
+            Err(e) => log::warn!("libgit2 subtask diff failed ({}) — falling back to git CLI", e),
+        }
+    }
+
     let git_dir_str = git_dir.to_string_lossy().to_string();
     let mut git_commands: Vec<String> = Vec::new();
 
@@ -731,6 +1550,7 @@ pub fn get_subtask_diff(
     let mut numstat_args = vec![
         "--git-dir".to_string(), git_dir_str.clone(),
         "diff".to_string(), "--numstat".to_string(),
+        "-M".to_string(), "-C".to_string(),
         from_ref.clone(), to_ref.clone(),
     ];
     if !excludes.is_empty() {
@@ -793,7 +1613,7 @@ pub fn get_subtask_diff(
     // 6. Build patch args with exclude patterns
     let mut patch_args = vec![
         "--git-dir".to_string(), git_dir_str.clone(),
-        "diff".to_string(),
+        "diff".to_string(), "-M".to_string(), "-C".to_string(),
         from_ref.clone(), to_ref.clone(),
     ];
     if !excludes.is_empty() {
@@ -844,6 +1664,7 @@ pub fn get_subtask_diff(
         from_ref,
         to_ref,
         git_commands,
+        intraline: None,
     })
 }
 
@@ -883,47 +1704,401 @@ pub fn get_file_contents(
     git_ref: &str,
     paths: &[String],
 ) -> Vec<super::types::FileContent> {
-    let git_dir_str = git_dir.to_string_lossy().to_string();
-
     paths.iter().map(|path| {
-        let ref_path = format!("{}:{}", git_ref, path);
-        let output = Command::new("git")
-            .args(["--git-dir", &git_dir_str, "show", &ref_path])
-            .output();
-
-        match output {
-            Ok(out) if out.status.success() => {
-                let content = String::from_utf8_lossy(&out.stdout).to_string();
+        // Primary: libgit2 blob lookup (Ok(None) = path absent at ref)
+        match super::git_backend::show_file(git_dir, git_ref, path) {
+            Ok(Some(content)) => {
                 let size = content.len();
-                super::types::FileContent {
+                return super::types::FileContent {
                     path: path.clone(),
                     content: Some(content),
                     error: None,
                     size: Some(size),
-                }
+                };
             }
-            Ok(out) => {
-                let stderr = String::from_utf8_lossy(&out.stderr).to_string();
-                log::debug!("git show {} failed for {}: {}", ref_path, path, stderr.trim());
-                super::types::FileContent {
+            Ok(None) => {
+                return super::types::FileContent {
                     path: path.clone(),
                     content: None,
-                    error: Some(stderr.trim().to_string()),
+                    error: Some(format!("path '{}' does not exist at {}", path, git_ref)),
                     size: None,
-                }
+                };
             }
             Err(e) => {
-                super::types::FileContent {
-                    path: path.clone(),
-                    content: None,
-                    error: Some(format!("Failed to execute git: {}", e)),
-                    size: None,
-                }
+                log::debug!("libgit2 show failed for {} ({}) — falling back to git CLI", path, e);
             }
         }
+        show_file_cli(git_dir, git_ref, path)
     }).collect()
 }
 
+/// CLI fallback: file content via `git show <ref>:<path>`.
+fn show_file_cli(git_dir: &PathBuf, git_ref: &str, path: &str) -> super::types::FileContent {
+    let git_dir_str = git_dir.to_string_lossy().to_string();
+    let ref_path = format!("{}:{}", git_ref, path);
+    let output = Command::new("git")
+        .args(["--git-dir", &git_dir_str, "show", &ref_path])
+        .output();
+
+    match output {
+        Ok(out) if out.status.success() => {
+            let content = String::from_utf8_lossy(&out.stdout).to_string();
+            let size = content.len();
+            super::types::FileContent {
+                path: path.to_string(),
+                content: Some(content),
+                error: None,
+                size: Some(size),
+            }
+        }
+        Ok(out) => {
+            let stderr = String::from_utf8_lossy(&out.stderr).to_string();
+            log::debug!("git show {} failed for {}: {}", ref_path, path, stderr.trim());
+            super::types::FileContent {
+                path: path.to_string(),
+                content: None,
+                error: Some(stderr.trim().to_string()),
+                size: None,
+            }
+        }
+        Err(e) => super::types::FileContent {
+            path: path.to_string(),
+            content: None,
+            error: Some(format!("Failed to execute git: {}", e)),
+            size: None,
+        },
+    }
+}
+
+/// Default per-file cap on patch text in the JSON diff endpoints.
+const DEFAULT_MAX_PATCH_SECTION_BYTES: usize = 262_144;
+
+/// Per-file patch size cap, overridable via XRAY_MAX_PATCH_BYTES.
+pub fn max_patch_section_bytes() -> usize {
+    std::env::var("XRAY_MAX_PATCH_BYTES")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_MAX_PATCH_SECTION_BYTES)
+}
+
+/// Replace oversized per-file sections of a unified patch with a placeholder.
+///
+/// A "section" is everything from one `diff --git` header to the next. Any
+/// section larger than `limit` bytes is collapsed to its header plus a note
+/// naming the size, the limit, and a fetch-on-demand URL built from
+/// `fetch_base` (the single-file diff endpoint) — so huge generated files or
+/// binaries never bloat the JSON response, while the full patch stays one
+/// request away.
+pub fn cap_patch_sections(patch: &str, limit: usize, fetch_base: &str) -> String {
+    if patch.len() <= limit {
+        return patch.to_string();
+    }
+
+    let mut out = String::with_capacity(patch.len().min(limit * 4));
+    let mut section = String::new();
+
+    let flush = |section: &mut String, out: &mut String| {
+        if section.is_empty() {
+            return;
+        }
+        if section.len() > limit {
+            let header = section.lines().next().unwrap_or("").to_string();
+            // "diff --git a/<path> b/<path>" — take the b/ side
+            let path = header
+                .rsplit_once(" b/")
+                .map(|(_, p)| p.to_string())
+                .unwrap_or_default();
+            let sep = if fetch_base.contains('?') { '&' } else { '?' };
+            out.push_str(&header);
+            out.push('\n');
+            out.push_str(&format!(
+                "[patch omitted: {} bytes exceeds the {} byte limit — fetch it from {}{}path={}]\n",
+                section.len(),
+                limit,
+                fetch_base,
+                sep,
+                urlencoding::encode(&path)
+            ));
+        } else {
+            out.push_str(section);
+        }
+        section.clear();
+    };
+
+    for line in patch.split_inclusive('\n') {
+        if line.starts_with("diff --git ") {
+            flush(&mut section, &mut out);
+        }
+        section.push_str(line);
+    }
+    flush(&mut section, &mut out);
+
+    out
+}
+
+/// Bucket a workspace's checkpoint commits into per-day activity totals.
+///
+/// Only checkpoint commits (subject `checkpoint-<ws>-<task>`) count; the
+/// day comes from the author timestamp's local date. Days without any
+/// checkpoints are omitted, so sparse histories stay compact.
+pub fn get_workspace_activity(
+    workspace_id: &str,
+    git_dir: &std::path::Path,
+) -> Result<super::types::ActivityResponse, String> {
+    if !git_dir.exists() {
+        return Err(format!(
+            "Git directory does not exist (Cline may have disabled it): {}",
+            git_dir.display()
+        ));
+    }
+
+    // Primary: libgit2 single-pass walk with per-commit stats
+    let commits = match super::git_backend::log_all_with_stats(git_dir) {
+        Ok(c) => c,
+        Err(e) => {
+            log::warn!("libgit2 activity scan failed ({}) — falling back to git CLI", e);
+            activity_cli(git_dir)?
+        }
+    };
+
+    // date → (commits, task ids, added, removed)
+    let mut buckets: HashMap<String, (usize, std::collections::HashSet<String>, usize, usize)> =
+        HashMap::new();
+
+    for (_hash, subject, timestamp, added, removed) in commits {
+        let Some(task_id) = parse_checkpoint_subject(&subject) else {
+            continue;
+        };
+        if timestamp.len() < 10 {
+            continue;
+        }
+        let date = timestamp[..10].to_string();
+        let bucket = buckets.entry(date).or_default();
+        bucket.0 += 1;
+        bucket.1.insert(task_id);
+        bucket.2 += added;
+        bucket.3 += removed;
+    }
+
+    let mut days: Vec<super::types::ActivityDay> = buckets
+        .into_iter()
+        .map(|(date, (commits, tasks, lines_added, lines_removed))| {
+            super::types::ActivityDay {
+                date,
+                commits,
+                tasks: tasks.len(),
+                lines_added,
+                lines_removed,
+            }
+        })
+        .collect();
+    days.sort_by(|a, b| a.date.cmp(&b.date));
+
+    let total_commits = days.iter().map(|d| d.commits).sum();
+    let total_lines_added = days.iter().map(|d| d.lines_added).sum();
+    let total_lines_removed = days.iter().map(|d| d.lines_removed).sum();
+
+    Ok(super::types::ActivityResponse {
+        workspace_id: workspace_id.to_string(),
+        days,
+        total_commits,
+        total_lines_added,
+        total_lines_removed,
+    })
+}
+
+/// CLI fallback for the activity scan — one `git log --all --numstat`
+/// pass with the per-file numbers summed per commit.
+fn activity_cli(
+    git_dir: &std::path::Path,
+) -> Result<Vec<super::git_backend::CommitWithStats>, String> {
+    let git_dir_str = git_dir.to_string_lossy().to_string();
+
+    let output = Command::new("git")
+        .args([
+            "--git-dir",
+            &git_dir_str,
+            "log",
+            "--all",
+            "--numstat",
+            "--pretty=format:%H|%s|%aI",
+        ])
+        .output()
+        .map_err(|e| format!("Failed to execute git log: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "git log failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let mut commits: Vec<super::git_backend::CommitWithStats> = Vec::new();
+
+    for line in stdout.lines() {
+        if line.is_empty() {
+            continue;
+        }
+        // Header lines contain '|' but never a tab; numstat lines are
+        // "<added>\t<removed>\t<path>"
+        if line.contains('|') && !line.contains('\t') {
+            let parts: Vec<&str> = line.splitn(3, '|').collect();
+            if parts.len() == 3 {
+                commits.push((
+                    parts[0].to_string(),
+                    parts[1].to_string(),
+                    parts[2].to_string(),
+                    0,
+                    0,
+                ));
+            }
+        } else if let Some(current) = commits.last_mut() {
+            let parts: Vec<&str> = line.split('\t').collect();
+            if parts.len() >= 3 {
+                current.3 += parts[0].parse::<usize>().unwrap_or(0);
+                current.4 += parts[1].parse::<usize>().unwrap_or(0);
+            }
+        }
+    }
+
+    Ok(commits)
+}
+
+/// Attribute each line of a file's final state to the checkpoint step —
+/// and, when subtask data exists, the subtask — that introduced it.
+///
+/// Blames the file at the task's last step. Lines whose introducing commit
+/// is not one of the task's checkpoints (inherited from an earlier task or
+/// the initial import) get `step_index: None`. Subtask attribution reuses
+/// the same time-window mapping as the subtask diff and is best-effort:
+/// tasks without `ui_messages.json` simply get `subtask: None` throughout.
+pub fn get_blame(
+    task_id: &str,
+    file_path: &str,
+    workspace_id: &str,
+    git_dir: &PathBuf,
+) -> Result<super::types::BlameResponse, String> {
+    if !git_dir.exists() {
+        return Err(format!(
+            "Git directory does not exist (Cline may have disabled it): {}",
+            git_dir.display()
+        ));
+    }
+
+    let steps = list_steps_for_task(task_id, workspace_id, git_dir);
+    if steps.is_empty() {
+        return Err(format!("No checkpoint steps for task '{}'", task_id));
+    }
+    let last_hash = steps.last().map(|s| s.hash.clone()).unwrap_or_default();
+
+    // hash → 1-based step index
+    let step_by_hash: HashMap<&str, usize> =
+        steps.iter().map(|s| (s.hash.as_str(), s.index)).collect();
+
+    // Subtask step ranges (0-based step positions) — best-effort
+    let subtask_ranges: Vec<(usize, usize, usize)> =
+        crate::conversation_history::subtasks::parse_task_subtasks(task_id)
+            .map(|st| map_subtasks_to_steps(&st, &steps))
+            .unwrap_or_default();
+
+    // Primary: libgit2 blame. "Path '" errors are user errors (missing or
+    // binary file) — don't bother the CLI with those.
+    let raw = match super::git_backend::blame_file(git_dir, &last_hash, file_path) {
+        Ok(r) => {
+            log::info!(
+                "Blame for task {} path {} via libgit2: {} lines",
+                task_id, file_path, r.len()
+            );
+            r
+        }
+        Err(e) if e.starts_with("Path '") => return Err(e),
+        Err(e) => {
+            log::warn!("libgit2 blame failed ({}) — falling back to git CLI", e);
+            blame_cli(git_dir, &last_hash, file_path)?
+        }
+    };
+
+    let lines = raw
+        .into_iter()
+        .map(|(line_number, hash, content)| {
+            let step_index = step_by_hash.get(hash.as_str()).copied();
+            let subtask = step_index.and_then(|si| {
+                let pos = si - 1; // ranges hold 0-based step positions
+                subtask_ranges
+                    .iter()
+                    .find(|(_, first, last)| pos >= *first && pos <= *last)
+                    .map(|(s, _, _)| *s)
+            });
+            super::types::BlameLine {
+                line_number,
+                content,
+                hash,
+                step_index,
+                subtask,
+            }
+        })
+        .collect();
+
+    Ok(super::types::BlameResponse {
+        task_id: task_id.to_string(),
+        workspace_id: workspace_id.to_string(),
+        path: file_path.to_string(),
+        git_ref: last_hash,
+        total_steps: steps.len(),
+        lines,
+    })
+}
+
+/// CLI fallback for blame — parses `git blame --line-porcelain` output.
+fn blame_cli(
+    git_dir: &std::path::Path,
+    git_ref: &str,
+    file_path: &str,
+) -> Result<Vec<super::git_backend::BlameLineRaw>, String> {
+    let git_dir_str = git_dir.to_string_lossy().to_string();
+
+    let output = Command::new("git")
+        .args([
+            "--git-dir", &git_dir_str,
+            "blame", "-l", "--line-porcelain",
+            git_ref,
+            "--", file_path,
+        ])
+        .output()
+        .map_err(|e| format!("Failed to execute git blame: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "git blame failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    // --line-porcelain: per line, a "<sha> <orig> <final>" header, a block
+    // of metadata lines, then the content prefixed with a tab
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let mut lines = Vec::new();
+    let mut current_hash = String::new();
+    let mut current_line = 0usize;
+
+    for line in stdout.lines() {
+        if let Some(content) = line.strip_prefix('\t') {
+            lines.push((current_line, current_hash.clone(), content.to_string()));
+        } else {
+            let mut parts = line.split(' ');
+            if let (Some(sha), Some(_orig), Some(fin)) = (parts.next(), parts.next(), parts.next()) {
+                if sha.len() == 40 && sha.chars().all(|c| c.is_ascii_hexdigit()) {
+                    current_hash = sha.to_string();
+                    current_line = fin.parse().unwrap_or(current_line + 1);
+                }
+            }
+        }
+    }
+
+    Ok(lines)
+}
+
 /// Parse git --numstat output into DiffFile vec.
 /// Format: <added>\t<removed>\t<path>
 fn parse_numstat(output: &str) -> Vec<super::types::DiffFile> {
@@ -935,13 +2110,18 @@ fn parse_numstat(output: &str) -> Vec<super::types::DiffFile> {
             if parts.len() < 3 {
                 return None;
             }
+            // Binary files show up as "-\t-\t<path>"
+            let binary = parts[0] == "-" || parts[1] == "-";
             let added = parts[0].parse::<usize>().unwrap_or(0);
             let removed = parts[1].parse::<usize>().unwrap_or(0);
-            let path = parts[2].to_string();
+            let (old_path, path) = parse_numstat_path(parts[2]);
 
-            let status = if added > 0 && removed == 0 && parts[0] != "-" {
+            let status = if old_path.is_some() {
+                // numstat can't distinguish a copy from a rename
+                "renamed".to_string()
+            } else if added > 0 && removed == 0 && parts[0] != "-" {
                 "added".to_string()
-            } else if removed > 0 && added == 0 {
+            } else if removed > 0 && added == 0 && !binary {
                 "deleted".to_string()
             } else {
                 "modified".to_string()
@@ -952,7 +2132,33 @@ fn parse_numstat(output: &str) -> Vec<super::types::DiffFile> {
                 lines_added: added,
                 lines_removed: removed,
                 status,
+                old_path,
+                binary,
+                bytes_delta: None,
             })
         })
         .collect()
 }
+
+/// Split a numstat path field into (old_path, new_path).
+///
+/// With `-M -C`, renames show up as `old => new` or in the brace form
+/// `prefix/{old => new}/suffix` (either side of the arrow may be empty
+/// when a path component was inserted or dropped). Plain paths return
+/// (None, path).
+fn parse_numstat_path(raw: &str) -> (Option<String>, String) {
+    if let Some(brace_start) = raw.find('{') {
+        if let Some((left, right)) = raw[brace_start + 1..].split_once(" => ") {
+            if let Some((right_mid, suffix)) = right.split_once('}') {
+                let prefix = &raw[..brace_start];
+                let old = format!("{}{}{}", prefix, left, suffix).replace("//", "/");
+                let new = format!("{}{}{}", prefix, right_mid, suffix).replace("//", "/");
+                return (Some(old), new);
+            }
+        }
+    }
+    if let Some((old, new)) = raw.split_once(" => ") {
+        return (Some(old.to_string()), new.to_string());
+    }
+    (None, raw.to_string())
+}
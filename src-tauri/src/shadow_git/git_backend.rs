@@ -0,0 +1,541 @@
+//! libgit2-backed git operations for shadow repos.
+//!
+//! Primary backend for commit enumeration, numstat, patch generation and
+//! file retrieval. Running in-process via the `git2` crate avoids spawning
+//! a `git` subprocess per operation and keeps working when git isn't on
+//! PATH. Every function returns `Err` with a descriptive message on any
+//! libgit2 failure — callers in `discovery` fall back to the git CLI in
+//! that case, so an exotic repo state never reduces functionality below
+//! what the CLI path offered.
+//!
+//! The checkpoint repos are bare: `git_dir` is the `.git` / `.git_disabled`
+//! directory itself, which `Repository::open` handles directly.
+
+use git2::{Diff, DiffFormat, Repository, Sort};
+use std::path::Path;
+
+use super::types::DiffFile;
+
+/// Open the shadow repo (bare — git_dir is the repo directory itself).
+fn open_repo(git_dir: &Path) -> Result<Repository, String> {
+    Repository::open(git_dir)
+        .map_err(|e| format!("libgit2 open {:?}: {}", git_dir, e.message()))
+}
+
+/// Format a git2 time as ISO 8601 with offset — matches `git log --pretty=%aI`.
+fn format_time(time: git2::Time) -> String {
+    use chrono::{FixedOffset, TimeZone};
+    let offset = FixedOffset::east_opt(time.offset_minutes() * 60)
+        .unwrap_or_else(|| FixedOffset::east_opt(0).unwrap());
+    match offset.timestamp_opt(time.seconds(), 0) {
+        chrono::LocalResult::Single(dt) => dt.format("%Y-%m-%dT%H:%M:%S%:z").to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Enumerate all commits across all refs, newest first.
+///
+/// Equivalent to `git log --all --pretty=format:%H|%s|%aI` — returns
+/// (hash, subject, author_date_iso) tuples.
+pub fn log_all(git_dir: &Path) -> Result<Vec<(String, String, String)>, String> {
+    let repo = open_repo(git_dir)?;
+
+    let mut walk = repo
+        .revwalk()
+        .map_err(|e| format!("libgit2 revwalk: {}", e.message()))?;
+    // "*" is expanded to "refs/*" — all branches and tags, like --all
+    walk.push_glob("*")
+        .map_err(|e| format!("libgit2 push_glob: {}", e.message()))?;
+    walk.set_sorting(Sort::TIME)
+        .map_err(|e| format!("libgit2 set_sorting: {}", e.message()))?;
+
+    let mut commits = Vec::new();
+    for oid in walk {
+        let oid = oid.map_err(|e| format!("libgit2 walk: {}", e.message()))?;
+        let commit = repo
+            .find_commit(oid)
+            .map_err(|e| format!("libgit2 find_commit {}: {}", oid, e.message()))?;
+        let subject = commit.summary().unwrap_or("").to_string();
+        let timestamp = format_time(commit.author().when());
+        commits.push((oid.to_string(), subject, timestamp));
+    }
+
+    Ok(commits)
+}
+
+/// Diff one commit against its first parent (empty tree for roots) and
+/// collect the touched paths.
+fn changed_paths_for_commit(
+    repo: &Repository,
+    commit: &git2::Commit,
+) -> Result<Vec<String>, String> {
+    let to_tree = commit
+        .tree()
+        .map_err(|e| format!("libgit2 commit tree: {}", e.message()))?;
+    let from_tree = match commit.parent(0) {
+        Ok(parent) => Some(
+            parent
+                .tree()
+                .map_err(|e| format!("libgit2 parent tree: {}", e.message()))?,
+        ),
+        Err(_) => None, // root commit — diff against empty tree
+    };
+
+    let diff = repo
+        .diff_tree_to_tree(from_tree.as_ref(), Some(&to_tree), None)
+        .map_err(|e| format!("libgit2 diff: {}", e.message()))?;
+
+    Ok(diff
+        .deltas()
+        .filter_map(|d| {
+            d.new_file()
+                .path()
+                .or_else(|| d.old_file().path())
+                .map(|p| p.to_string_lossy().to_string())
+        })
+        .collect())
+}
+
+/// A commit plus the paths it touched: (hash, subject, iso_timestamp, paths)
+pub type CommitWithPaths = (String, String, String, Vec<String>);
+
+/// Like [`log_all`] but also returns the paths each commit touched —
+/// the in-process equivalent of one `git log --all --numstat` pass.
+///
+/// This walks the repo exactly once; per-commit tree diffs happen in
+/// memory, so enumerating a large workspace costs zero subprocesses
+/// instead of one per commit.
+pub fn log_all_with_paths(git_dir: &Path) -> Result<Vec<CommitWithPaths>, String> {
+    let repo = open_repo(git_dir)?;
+
+    let mut walk = repo
+        .revwalk()
+        .map_err(|e| format!("libgit2 revwalk: {}", e.message()))?;
+    walk.push_glob("*")
+        .map_err(|e| format!("libgit2 push_glob: {}", e.message()))?;
+    walk.set_sorting(Sort::TIME)
+        .map_err(|e| format!("libgit2 set_sorting: {}", e.message()))?;
+
+    let mut commits = Vec::new();
+    for oid in walk {
+        let oid = oid.map_err(|e| format!("libgit2 walk: {}", e.message()))?;
+        let commit = repo
+            .find_commit(oid)
+            .map_err(|e| format!("libgit2 find_commit {}: {}", oid, e.message()))?;
+        let subject = commit.summary().unwrap_or("").to_string();
+        let timestamp = format_time(commit.author().when());
+        let paths = changed_paths_for_commit(&repo, &commit)?;
+        commits.push((oid.to_string(), subject, timestamp, paths));
+    }
+
+    Ok(commits)
+}
+
+/// One commit with aggregate line stats: (hash, subject, timestamp,
+/// lines_added, lines_removed)
+pub type CommitWithStats = (String, String, String, usize, usize);
+
+/// Like [`log_all`] but with per-commit insertion/deletion totals —
+/// the in-process equivalent of one `git log --all --numstat` pass with
+/// the per-file numbers summed up.
+pub fn log_all_with_stats(git_dir: &Path) -> Result<Vec<CommitWithStats>, String> {
+    let repo = open_repo(git_dir)?;
+
+    let mut walk = repo
+        .revwalk()
+        .map_err(|e| format!("libgit2 revwalk: {}", e.message()))?;
+    walk.push_glob("*")
+        .map_err(|e| format!("libgit2 push_glob: {}", e.message()))?;
+    walk.set_sorting(Sort::TIME)
+        .map_err(|e| format!("libgit2 set_sorting: {}", e.message()))?;
+
+    let mut commits = Vec::new();
+    for oid in walk {
+        let oid = oid.map_err(|e| format!("libgit2 walk: {}", e.message()))?;
+        let commit = repo
+            .find_commit(oid)
+            .map_err(|e| format!("libgit2 find_commit {}: {}", oid, e.message()))?;
+        let subject = commit.summary().unwrap_or("").to_string();
+        let timestamp = format_time(commit.author().when());
+
+        let tree = commit
+            .tree()
+            .map_err(|e| format!("libgit2 tree {}: {}", oid, e.message()))?;
+        let parent_tree = commit.parent(0).ok().and_then(|p| p.tree().ok());
+        let diff = repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+            .map_err(|e| format!("libgit2 diff {}: {}", oid, e.message()))?;
+        let stats = diff
+            .stats()
+            .map_err(|e| format!("libgit2 diff stats {}: {}", oid, e.message()))?;
+
+        commits.push((
+            oid.to_string(),
+            subject,
+            timestamp,
+            stats.insertions(),
+            stats.deletions(),
+        ));
+    }
+
+    Ok(commits)
+}
+
+/// Diff two revisions: file-level numstat plus the unified patch text.
+///
+/// `from_ref` / `to_ref` accept anything `git rev-parse` would (hashes,
+/// `<hash>^`, refs). A `from_ref` that doesn't resolve — the `<hash>^` of a
+/// root commit — is treated as the empty tree, mirroring the CLI's
+/// `diff-tree` fallback.
+///
+/// Note: pathspec exclusions (`:(exclude)...`) are NOT supported by libgit2 —
+/// callers with exclude patterns must use the CLI path.
+pub fn diff_refs(
+    git_dir: &Path,
+    from_ref: &str,
+    to_ref: &str,
+) -> Result<(Vec<DiffFile>, String), String> {
+    diff_refs_inner(git_dir, from_ref, to_ref, None)
+}
+
+/// Like [`diff_refs`] but limited to a single file path.
+///
+/// Plain pathspecs ARE supported by libgit2 (unlike `:(exclude)` magic), so
+/// this stays in-process: the diff only visits deltas matching `path`.
+pub fn diff_refs_for_path(
+    git_dir: &Path,
+    from_ref: &str,
+    to_ref: &str,
+    path: &str,
+) -> Result<(Vec<DiffFile>, String), String> {
+    diff_refs_inner(git_dir, from_ref, to_ref, Some(path))
+}
+
+fn diff_refs_inner(
+    git_dir: &Path,
+    from_ref: &str,
+    to_ref: &str,
+    pathspec: Option<&str>,
+) -> Result<(Vec<DiffFile>, String), String> {
+    let repo = open_repo(git_dir)?;
+
+    let to_tree = repo
+        .revparse_single(to_ref)
+        .map_err(|e| format!("libgit2 revparse '{}': {}", to_ref, e.message()))?
+        .peel_to_tree()
+        .map_err(|e| format!("libgit2 peel '{}': {}", to_ref, e.message()))?;
+
+    let from_tree = match repo.revparse_single(from_ref) {
+        Ok(obj) => Some(
+            obj.peel_to_tree()
+                .map_err(|e| format!("libgit2 peel '{}': {}", from_ref, e.message()))?,
+        ),
+        Err(_) => None, // e.g. "<root>^" — diff against empty tree
+    };
+
+    let mut opts = git2::DiffOptions::new();
+    if let Some(p) = pathspec {
+        opts.pathspec(p);
+    }
+
+    let mut diff = repo
+        .diff_tree_to_tree(from_tree.as_ref(), Some(&to_tree), Some(&mut opts))
+        .map_err(|e| format!("libgit2 diff: {}", e.message()))?;
+
+    // Rename and copy detection — equivalent to `git diff -M -C`
+    let mut find_opts = git2::DiffFindOptions::new();
+    find_opts.renames(true).copies(true);
+    if let Err(e) = diff.find_similar(Some(&mut find_opts)) {
+        log::debug!("libgit2 find_similar failed (continuing): {}", e.message());
+    }
+
+    let files = collect_files(&repo, &diff)?;
+    let patch = render_patch(&diff)?;
+
+    Ok((files, patch))
+}
+
+/// Build the per-file stats list from a computed diff.
+fn collect_files(repo: &Repository, diff: &Diff) -> Result<Vec<DiffFile>, String> {
+    let mut files = Vec::new();
+
+    for (idx, delta) in diff.deltas().enumerate() {
+        let path = delta
+            .new_file()
+            .path()
+            .or_else(|| delta.old_file().path())
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        // Binary files have no line stats — report 0/0 like numstat's "-\t-"
+        let (lines_added, lines_removed) = match git2::Patch::from_diff(diff, idx) {
+            Ok(Some(patch)) => match patch.line_stats() {
+                Ok((_context, additions, deletions)) => (additions, deletions),
+                Err(_) => (0, 0),
+            },
+            _ => (0, 0),
+        };
+
+        // Binary detection via the blobs themselves (git's NUL heuristic) —
+        // the delta's is_binary flag is only set once patch content loads
+        let old_blob = repo.find_blob(delta.old_file().id()).ok();
+        let new_blob = repo.find_blob(delta.new_file().id()).ok();
+        let binary = old_blob.as_ref().map(|b| b.is_binary()).unwrap_or(false)
+            || new_blob.as_ref().map(|b| b.is_binary()).unwrap_or(false);
+
+        // For binary files the line counts say nothing — report the byte
+        // delta between the two blob versions instead (0 for absent sides)
+        let bytes_delta = if binary {
+            let old_size = old_blob.map(|b| b.size() as i64).unwrap_or(0);
+            let new_size = new_blob.map(|b| b.size() as i64).unwrap_or(0);
+            Some(new_size - old_size)
+        } else {
+            None
+        };
+
+        let status = match delta.status() {
+            git2::Delta::Added => "added",
+            git2::Delta::Deleted => "deleted",
+            git2::Delta::Renamed => "renamed",
+            git2::Delta::Copied => "copied",
+            _ => "modified",
+        };
+
+        // Renames/copies keep both sides so review views don't show a
+        // full delete + add pair
+        let old_path = match delta.status() {
+            git2::Delta::Renamed | git2::Delta::Copied => delta
+                .old_file()
+                .path()
+                .map(|p| p.to_string_lossy().to_string()),
+            _ => None,
+        };
+
+        files.push(DiffFile {
+            path,
+            lines_added,
+            lines_removed,
+            status: status.to_string(),
+            old_path,
+            binary,
+            bytes_delta,
+        });
+    }
+
+    Ok(files)
+}
+
+/// Render the unified patch text from a computed diff.
+fn render_patch(diff: &Diff) -> Result<String, String> {
+    let mut buf = String::new();
+
+    diff.print(DiffFormat::Patch, |_delta, _hunk, line| {
+        // Content lines carry their +/-/space marker in origin(); header
+        // lines ('F', 'H', ...) already include their own text
+        match line.origin() {
+            '+' | '-' | ' ' => buf.push(line.origin()),
+            _ => {}
+        }
+        buf.push_str(&String::from_utf8_lossy(line.content()));
+        true
+    })
+    .map_err(|e| format!("libgit2 patch print: {}", e.message()))?;
+
+    Ok(buf)
+}
+
+/// One raw tree listing entry: (name, mode_octal, is_dir, blob_size)
+pub type TreeEntryRaw = (String, String, bool, Option<usize>);
+
+/// List one directory level of the tree at a ref — equivalent to
+/// `git ls-tree -l <ref> <path>`.
+///
+/// `path` is the subdirectory to list ("" for the repo root). Returns
+/// (name, mode_octal, is_dir, blob_size) per entry; size is None for
+/// directories. Errs when the ref doesn't resolve or the path isn't a
+/// directory at that ref.
+pub fn ls_tree(
+    git_dir: &Path,
+    git_ref: &str,
+    path: &str,
+) -> Result<Vec<TreeEntryRaw>, String> {
+    let repo = open_repo(git_dir)?;
+
+    let root = repo
+        .revparse_single(git_ref)
+        .map_err(|e| format!("libgit2 revparse '{}': {}", git_ref, e.message()))?
+        .peel_to_tree()
+        .map_err(|e| format!("libgit2 peel '{}': {}", git_ref, e.message()))?;
+
+    let tree = if path.is_empty() {
+        root
+    } else {
+        let entry = root
+            .get_path(Path::new(path))
+            .map_err(|_| format!("Path '{}' does not exist at {}", path, git_ref))?;
+        let obj = entry
+            .to_object(&repo)
+            .map_err(|e| format!("libgit2 tree entry '{}': {}", path, e.message()))?;
+        match obj.into_tree() {
+            Ok(t) => t,
+            Err(_) => return Err(format!("Path '{}' is not a directory at {}", path, git_ref)),
+        }
+    };
+
+    let mut entries = Vec::new();
+    for entry in tree.iter() {
+        let name = entry.name().unwrap_or("").to_string();
+        let mode = format!("{:06o}", entry.filemode());
+        let is_dir = entry.kind() == Some(git2::ObjectType::Tree);
+        let size = if is_dir {
+            None
+        } else {
+            repo.find_blob(entry.id()).ok().map(|b| b.size())
+        };
+        entries.push((name, mode, is_dir, size));
+    }
+
+    Ok(entries)
+}
+
+/// One blamed line: (line_number_1based, commit_hash, content)
+pub type BlameLineRaw = (usize, String, String);
+
+/// Blame a file at a ref — equivalent to `git blame <ref> -- <path>`.
+///
+/// Returns one entry per line of the file as it exists at `git_ref`, with
+/// the commit that introduced the line. Errs on binary files and paths
+/// that don't exist at the ref (message starts with "Path '" so callers
+/// can skip the CLI fallback for user errors).
+pub fn blame_file(
+    git_dir: &Path,
+    git_ref: &str,
+    path: &str,
+) -> Result<Vec<BlameLineRaw>, String> {
+    let repo = open_repo(git_dir)?;
+
+    let commit = repo
+        .revparse_single(git_ref)
+        .map_err(|e| format!("libgit2 revparse '{}': {}", git_ref, e.message()))?
+        .peel_to_commit()
+        .map_err(|e| format!("libgit2 peel '{}': {}", git_ref, e.message()))?;
+
+    let tree = commit
+        .tree()
+        .map_err(|e| format!("libgit2 tree: {}", e.message()))?;
+    let entry = tree
+        .get_path(Path::new(path))
+        .map_err(|_| format!("Path '{}' does not exist at {}", path, git_ref))?;
+    let blob = repo
+        .find_blob(entry.id())
+        .map_err(|_| format!("Path '{}' is not a file at {}", path, git_ref))?;
+    if blob.is_binary() {
+        return Err(format!("Path '{}' is binary — blame is not supported", path));
+    }
+    let text = String::from_utf8_lossy(blob.content()).to_string();
+
+    let mut opts = git2::BlameOptions::new();
+    opts.newest_commit(commit.id());
+    let blame = repo
+        .blame_file(Path::new(path), Some(&mut opts))
+        .map_err(|e| format!("libgit2 blame '{}': {}", path, e.message()))?;
+
+    let mut out = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let line_no = i + 1;
+        let hash = blame
+            .get_line(line_no)
+            .map(|h| h.final_commit_id().to_string())
+            .unwrap_or_default();
+        out.push((line_no, hash, line.to_string()));
+    }
+
+    Ok(out)
+}
+
+/// One raw search match: (path, line_number_1_based, line_text)
+pub type GrepMatch = (String, usize, String);
+
+/// Fixed-string content search across the whole tree at a ref — the
+/// in-process equivalent of `git grep -n -F <query> <ref>`.
+///
+/// Binary blobs are skipped and matched lines are truncated to 500 chars.
+/// Returns (matches, truncated) — truncated is true when `max_matches` was
+/// hit and the walk stopped early.
+pub fn grep_tree(
+    git_dir: &Path,
+    git_ref: &str,
+    query: &str,
+    max_matches: usize,
+) -> Result<(Vec<GrepMatch>, bool), String> {
+    let repo = open_repo(git_dir)?;
+
+    let tree = repo
+        .revparse_single(git_ref)
+        .map_err(|e| format!("libgit2 revparse '{}': {}", git_ref, e.message()))?
+        .peel_to_tree()
+        .map_err(|e| format!("libgit2 peel '{}': {}", git_ref, e.message()))?;
+
+    let mut matches: Vec<GrepMatch> = Vec::new();
+    let mut truncated = false;
+
+    let walk_result = tree.walk(git2::TreeWalkMode::PreOrder, |dir, entry| {
+        if matches.len() >= max_matches {
+            truncated = true;
+            return git2::TreeWalkResult::Abort;
+        }
+        if entry.kind() != Some(git2::ObjectType::Blob) {
+            return git2::TreeWalkResult::Ok;
+        }
+        let blob = match repo.find_blob(entry.id()) {
+            Ok(b) => b,
+            Err(_) => return git2::TreeWalkResult::Ok,
+        };
+        if blob.is_binary() {
+            return git2::TreeWalkResult::Ok;
+        }
+        let content = String::from_utf8_lossy(blob.content());
+        let path = format!("{}{}", dir, entry.name().unwrap_or(""));
+
+        for (i, line) in content.lines().enumerate() {
+            if line.contains(query) {
+                let text: String = line.trim_end().chars().take(500).collect();
+                matches.push((path.clone(), i + 1, text));
+                if matches.len() >= max_matches {
+                    truncated = true;
+                    return git2::TreeWalkResult::Abort;
+                }
+            }
+        }
+        git2::TreeWalkResult::Ok
+    });
+
+    // Abort (hit max_matches) surfaces as a walk error — only propagate
+    // errors from walks we didn't stop ourselves
+    if let Err(e) = walk_result {
+        if !truncated {
+            return Err(format!("libgit2 tree walk: {}", e.message()));
+        }
+    }
+
+    Ok((matches, truncated))
+}
+
+/// File content at `<ref>:<path>` — equivalent to `git show`.
+///
+/// Returns Ok(None) when the path doesn't exist at that ref (deleted file),
+/// Err for anything else (bad ref, not a blob, repo error).
+pub fn show_file(git_dir: &Path, git_ref: &str, path: &str) -> Result<Option<String>, String> {
+    let repo = open_repo(git_dir)?;
+    let spec = format!("{}:{}", git_ref, path);
+
+    let result = match repo.revparse_single(&spec) {
+        Ok(obj) => match obj.into_blob() {
+            Ok(blob) => Ok(Some(String::from_utf8_lossy(blob.content()).to_string())),
+            Err(_) => Err(format!("'{}' is not a blob", spec)),
+        },
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(format!("libgit2 revparse '{}': {}", spec, e.message())),
+    };
+    result
+}
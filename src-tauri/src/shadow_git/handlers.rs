@@ -2,13 +2,14 @@ use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::Json;
 use parking_lot::RwLock;
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 use std::sync::Arc;
 
+use crate::api::pagination::{resolve_window, Page};
 use crate::state::AppState;
-use super::{cache, cleanup, discovery};
-use super::types::{DiffResult, FileContentsRequest, FileContentsResponse, StepsResponse, TasksResponse, WorkspacesResponse};
-use super::cleanup::NukeWorkspaceResponse;
+use super::{apply, cache, changesignore, cleanup, discovery, intraline, restore, summarize};
+use super::types::{ActivityResponse, BlameResponse, ClineTaskSummary, DiffResult, FileContentsRequest, FileContentsResponse, FileHistoryEntry, FileHistoryResponse, SearchResponse, StepsResponse, SummarizeRequest, SummarizeResponse, TasksResponse, TreeResponse, WorkspacesResponse};
+use super::cleanup::{GcWorkspaceResponse, NukeTaskResponse, NukeWorkspaceResponse};
 
 // ============ In-memory caches ============
 
@@ -36,12 +37,8 @@ static STEPS_CACHE: once_cell::sync::Lazy<RwLock<std::collections::HashMap<Strin
 
 // ============ Types ============
 
-/// Error response for changes endpoints
-#[derive(Debug, Serialize, utoipa::ToSchema)]
-pub struct ChangesErrorResponse {
-    pub error: String,
-    pub code: u16,
-}
+/// Error response for changes endpoints — alias for the crate-wide structured error.
+pub type ChangesErrorResponse = crate::api::error::ApiError;
 
 /// Query parameters for /changes/workspaces
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
@@ -59,13 +56,23 @@ pub struct TasksQuery {
     /// Set to true to force re-enumeration (bypass cache)
     #[serde(default)]
     pub refresh: Option<bool>,
+    /// Maximum number of tasks to return (default: all)
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// Zero-based offset into the task list (ignored when `cursor` is set)
+    #[serde(default)]
+    pub offset: Option<usize>,
+    /// Opaque continuation token from a previous page's `next_cursor`
+    #[serde(default)]
+    pub cursor: Option<String>,
 }
 
 /// Query parameters for /changes/tasks/:taskId/steps
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
 pub struct StepsQuery {
-    /// Workspace ID (required to locate the git repo)
-    pub workspace: String,
+    /// Workspace ID (optional — auto-linked from the task when omitted)
+    #[serde(default)]
+    pub workspace: Option<String>,
     /// Set to true to force re-enumeration (bypass cache)
     #[serde(default)]
     pub refresh: Option<bool>,
@@ -74,28 +81,145 @@ pub struct StepsQuery {
 /// Query parameters for /changes/tasks/:taskId/steps/:index/diff
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
 pub struct StepDiffQuery {
-    /// Workspace ID (required to locate the git repo)
-    pub workspace: String,
+    /// Workspace ID (optional — auto-linked from the task when omitted)
+    #[serde(default)]
+    pub workspace: Option<String>,
+    /// Diff granularity: "line" (default) or "word" — word adds intraline
+    /// change ranges to the response
+    #[serde(default)]
+    pub granularity: Option<String>,
 }
 
 /// Query parameters for /changes/tasks/:taskId/diff
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
 pub struct TaskDiffQuery {
-    /// Workspace ID (required to locate the git repo)
-    pub workspace: String,
+    /// Workspace ID (optional — auto-linked from the task when omitted)
+    #[serde(default)]
+    pub workspace: Option<String>,
     /// Pathspec exclusion patterns (repeated), e.g. ?exclude=node_modules&exclude=target
     #[serde(default)]
     pub exclude: Vec<String>,
+    /// Diff granularity: "line" (default) or "word" — word adds intraline
+    /// change ranges to the response
+    #[serde(default)]
+    pub granularity: Option<String>,
+}
+
+/// Query parameters for /changes/tasks/:taskId/bundle
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct BundleQuery {
+    /// Workspace ID (optional — auto-linked from the task when omitted)
+    #[serde(default)]
+    pub workspace: Option<String>,
+}
+
+/// Query parameters for /changes/tasks/:taskId/nuke
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct NukeTaskQuery {
+    /// Workspace ID (optional — auto-linked from the task when omitted)
+    #[serde(default)]
+    pub workspace: Option<String>,
+}
+
+/// Query parameters for /changes/tasks/:taskId/diff/file
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct FileDiffQuery {
+    /// Workspace ID (optional — auto-linked from the task when omitted)
+    #[serde(default)]
+    pub workspace: Option<String>,
+    /// File path (relative to repo root) to diff
+    pub path: String,
+    /// Step index (1-based) — when given, diffs only that step's change to the file
+    #[serde(default)]
+    pub step: Option<usize>,
+    /// Diff granularity: "line" (default) or "word" — word adds intraline
+    /// change ranges to the response
+    #[serde(default)]
+    pub granularity: Option<String>,
+}
+
+/// Query parameters for /changes/tasks/:taskId/blame
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct BlameQuery {
+    /// Workspace ID (optional — auto-linked from the task when omitted)
+    #[serde(default)]
+    pub workspace: Option<String>,
+    /// File path (relative to repo root) to blame
+    pub path: String,
+}
+
+/// Query parameters for /changes/tasks/:taskId/range-diff
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct RangeDiffQuery {
+    /// Workspace ID (optional — auto-linked from the task when omitted)
+    #[serde(default)]
+    pub workspace: Option<String>,
+    /// Starting step index (1-based) — the diff covers changes AFTER this step
+    pub from_step: usize,
+    /// Ending step index (1-based, inclusive) — must be greater than from_step
+    pub to_step: usize,
+    /// Diff granularity: "line" (default) or "word" — word adds intraline
+    /// change ranges to the response
+    #[serde(default)]
+    pub granularity: Option<String>,
+}
+
+/// Query parameters for /changes/tasks/:taskId/files/:path/history
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct FileHistoryQuery {
+    /// Workspace ID (optional — auto-linked from the task when omitted)
+    #[serde(default)]
+    pub workspace: Option<String>,
+}
+
+/// Path parameters for the file history endpoint
+#[derive(Debug, Deserialize)]
+pub struct FileHistoryPath {
+    pub task_id: String,
+    pub path: String,
 }
 
 /// Query parameters for /changes/tasks/:taskId/subtasks/:subtaskIndex/diff
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
 pub struct SubtaskDiffQuery {
-    /// Workspace ID (required to locate the git repo)
-    pub workspace: String,
+    /// Workspace ID (optional — auto-linked from the task when omitted)
+    #[serde(default)]
+    pub workspace: Option<String>,
     /// Pathspec exclusion patterns (repeated)
     #[serde(default)]
     pub exclude: Vec<String>,
+    /// Diff granularity: "line" (default) or "word" — word adds intraline
+    /// change ranges to the response
+    #[serde(default)]
+    pub granularity: Option<String>,
+}
+
+/// Query parameters for /changes/tree
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct TreeQuery {
+    /// Workspace ID (required)
+    pub workspace: String,
+    /// Git ref of the checkpoint snapshot (e.g. a commit hash)
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    /// Subdirectory to list (omitted or empty for the repo root)
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+/// Maximum matches returned by /changes/search before truncating
+const SEARCH_MAX_MATCHES: usize = 500;
+
+/// Query parameters for /changes/search
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct SearchQuery {
+    /// Workspace ID (required)
+    pub workspace: String,
+    /// Git ref of the checkpoint snapshot to search (e.g. a commit hash)
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    /// Search string (matched as a fixed string, not a regex)
+    pub q: String,
 }
 
 /// Path parameters for subtask diff endpoint
@@ -204,13 +328,14 @@ pub async fn list_workspaces_handler(
 /// Results are cached in memory and persisted to disk.
 /// On cold start, disk cache is loaded per-workspace on first request.
 /// Pass `?refresh=true` to re-scan from git.
+/// Returns the standard `{items, total, next_cursor}` list envelope.
 #[utoipa::path(
     get,
     path = "/changes/tasks",
     params(TasksQuery),
     responses(
-        (status = 200, description = "List of tasks for the workspace", body = TasksResponse),
-        (status = 400, description = "Missing or invalid workspace parameter", body = ChangesErrorResponse),
+        (status = 200, description = "Paginated list of tasks for the workspace", body = Page<ClineTaskSummary>),
+        (status = 400, description = "Missing or invalid workspace parameter or cursor", body = ChangesErrorResponse),
         (status = 500, description = "Internal server error", body = ChangesErrorResponse)
     ),
     security(("bearerAuth" = [])),
@@ -219,7 +344,7 @@ pub async fn list_workspaces_handler(
 pub async fn list_tasks_handler(
     State(_state): State<Arc<AppState>>,
     Query(params): Query<TasksQuery>,
-) -> Result<Json<TasksResponse>, (StatusCode, Json<ChangesErrorResponse>)> {
+) -> Result<Json<Page<ClineTaskSummary>>, (StatusCode, Json<ChangesErrorResponse>)> {
     let workspace_id = params.workspace.clone();
     let force_refresh = params.refresh.unwrap_or(false);
 
@@ -233,6 +358,14 @@ pub async fn list_tasks_handler(
         ));
     }
 
+    let window = resolve_window(params.limit, params.offset, params.cursor.as_deref())
+        .map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ChangesErrorResponse { error: e, code: 400 }),
+            )
+        })?;
+
     // Return memory-cached data if available and not refreshing
     if !force_refresh {
         let mem_cache = TASKS_CACHE.read();
@@ -242,7 +375,7 @@ pub async fn list_tasks_handler(
                 cached.tasks.len(),
                 workspace_id
             );
-            return Ok(Json(cached.clone()));
+            return Ok(Json(window.paginate(cached.tasks.clone())));
         }
         drop(mem_cache);
 
@@ -255,7 +388,7 @@ pub async fn list_tasks_handler(
             );
             // Promote to memory cache
             TASKS_CACHE.write().insert(workspace_id.clone(), disk_cached.clone());
-            return Ok(Json(disk_cached));
+            return Ok(Json(window.paginate(disk_cached.tasks)));
         }
     }
 
@@ -344,7 +477,7 @@ pub async fn list_tasks_handler(
             TASKS_CACHE.write().insert(workspace_id.clone(), response.clone());
             // Persist to disk
             cache::save_tasks(&workspace_id, &response);
-            Ok(Json(response))
+            Ok(Json(window.paginate(response.tasks)))
         }
         Err(e) => {
             log::error!("REST API: Failed to enumerate tasks: {}", e);
@@ -392,13 +525,98 @@ async fn resolve_git_dir(workspace_id: &str) -> Result<String, (StatusCode, Json
     ))
 }
 
+/// Helper: resolve (workspace_id, git_dir) for a task.
+///
+/// An explicit non-empty `?workspace=` param wins; otherwise the task is
+/// auto-linked via the persisted task → workspace link store (cwd parse +
+/// commit-subject scan on first resolution).
+pub(super) async fn resolve_workspace_for_request(
+    task_id: &str,
+    explicit: Option<String>,
+) -> Result<(String, String), (StatusCode, Json<ChangesErrorResponse>)> {
+    if let Some(ws_id) = explicit.filter(|w| !w.is_empty()) {
+        let git_dir = resolve_git_dir(&ws_id).await?;
+        return Ok((ws_id, git_dir));
+    }
+
+    let tid = task_id.to_string();
+    let found = tokio::task::spawn_blocking(move || super::autolink::resolve_workspace_for_task(&tid))
+        .await
+        .map_err(|e| (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ChangesErrorResponse { error: format!("Workspace auto-link failed: {}", e), code: 500 }),
+        ))?;
+
+    found
+        .map(|(ws_id, git_dir)| (ws_id, git_dir.to_string_lossy().to_string()))
+        .ok_or_else(|| (
+            StatusCode::BAD_REQUEST,
+            Json(ChangesErrorResponse {
+                error: format!(
+                    "Could not auto-link task '{}' to a checkpoint workspace — pass ?workspace= explicitly",
+                    task_id
+                ),
+                code: 400,
+            }),
+        ))
+}
+
+/// Seed the in-memory caches from the background cache warmer.
+///
+/// The warmer lives in [`super::warmup`] but the memory caches are module
+/// statics here — these setters keep them private while letting warmed
+/// results land in both memory and (via [`super::cache`]) disk.
+pub(super) fn seed_workspaces_cache(data: &WorkspacesResponse) {
+    *WORKSPACES_CACHE.write() = Some(data.clone());
+}
+
+pub(super) fn seed_tasks_cache(workspace_id: &str, data: &TasksResponse) {
+    TASKS_CACHE.write().insert(workspace_id.to_string(), data.clone());
+}
+
+pub(super) fn seed_steps_cache(workspace_id: &str, task_id: &str, data: &StepsResponse) {
+    STEPS_CACHE
+        .write()
+        .insert(cache::steps_cache_key(workspace_id, task_id), data.clone());
+}
+
+/// Helper: apply the `?granularity=` param to a computed diff.
+///
+/// "line" (or absent) leaves the result untouched; "word" fills
+/// `DiffResult.intraline` with per-line change ranges. Anything else is a
+/// 400. Runs on the final (capped) patch text so the ranges always match
+/// what the response carries.
+fn apply_granularity(
+    diff: &mut DiffResult,
+    granularity: Option<&str>,
+) -> Result<(), (StatusCode, Json<ChangesErrorResponse>)> {
+    match granularity {
+        None | Some("") | Some("line") => Ok(()),
+        Some("word") => {
+            diff.intraline = Some(intraline::annotate_patch(&diff.patch));
+            Ok(())
+        }
+        Some(other) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(ChangesErrorResponse {
+                error: format!(
+                    "Invalid granularity '{}' — expected 'line' or 'word'",
+                    other
+                ),
+                code: 400,
+            }),
+        )),
+    }
+}
+
 /// List checkpoint steps for a task
 ///
 /// Returns the individual checkpoint commits (steps) for a given task,
 /// in chronological order (oldest first). Each step includes the commit hash,
 /// timestamp, and number of files changed vs its parent commit.
 ///
-/// The `workspace` query parameter is required to locate the git repo.
+/// The `workspace` query parameter is optional — when omitted, the task is
+/// auto-linked to its checkpoint workspace via the link store.
 #[utoipa::path(
     get,
     path = "/changes/tasks/{task_id}/steps",
@@ -419,18 +637,11 @@ pub async fn list_steps_handler(
     Path(task_id): Path<String>,
     Query(params): Query<StepsQuery>,
 ) -> Result<Json<StepsResponse>, (StatusCode, Json<ChangesErrorResponse>)> {
-    let workspace_id = params.workspace.clone();
     let force_refresh = params.refresh.unwrap_or(false);
 
-    if workspace_id.is_empty() {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(ChangesErrorResponse {
-                error: "Missing required 'workspace' query parameter".to_string(),
-                code: 400,
-            }),
-        ));
-    }
+    // Resolve the workspace up front — the cache key needs it even on hits
+    let (workspace_id, git_dir) =
+        resolve_workspace_for_request(&task_id, params.workspace.clone()).await?;
 
     let cache_key = cache::steps_cache_key(&workspace_id, &task_id);
 
@@ -463,8 +674,6 @@ pub async fn list_steps_handler(
         task_id, workspace_id, force_refresh
     );
 
-    let git_dir = resolve_git_dir(&workspace_id).await?;
-
     let tid = task_id.clone();
     let ws_id = workspace_id.clone();
     let result = tokio::task::spawn_blocking(move || {
@@ -532,26 +741,17 @@ pub async fn task_diff_handler(
     Path(task_id): Path<String>,
     Query(params): Query<TaskDiffQuery>,
 ) -> Result<Json<DiffResult>, (StatusCode, Json<ChangesErrorResponse>)> {
-    let workspace_id = params.workspace.clone();
-    let excludes = params.exclude.clone();
+    let mut excludes = params.exclude.clone();
 
-    if workspace_id.is_empty() {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(ChangesErrorResponse {
-                error: "Missing required 'workspace' query parameter".to_string(),
-                code: 400,
-            }),
-        ));
-    }
+    let (workspace_id, git_dir) =
+        resolve_workspace_for_request(&task_id, params.workspace.clone()).await?;
+    changesignore::merge_into_excludes(&mut excludes, &workspace_id);
 
     log::info!(
         "REST API: GET /changes/tasks/{}/diff — workspace={}, excludes={:?}",
         task_id, workspace_id, excludes
     );
 
-    let git_dir = resolve_git_dir(&workspace_id).await?;
-
     let tid = task_id.clone();
     let result = tokio::task::spawn_blocking(move || {
         let git_path = std::path::PathBuf::from(&git_dir);
@@ -560,11 +760,19 @@ pub async fn task_diff_handler(
     .await;
 
     match result {
-        Ok(Ok(diff)) => {
+        Ok(Ok(mut diff)) => {
             log::info!(
                 "REST API: Task diff for {}: {} files, {} bytes patch",
                 task_id, diff.files.len(), diff.patch.len()
             );
+            // Cap oversized per-file sections — the full patch stays
+            // available via /diff.patch and /diff/file
+            diff.patch = discovery::cap_patch_sections(
+                &diff.patch,
+                discovery::max_patch_section_bytes(),
+                &format!("/changes/tasks/{}/diff/file", task_id),
+            );
+            apply_granularity(&mut diff, params.granularity.as_deref())?;
             Ok(Json(diff))
         }
         Ok(Err(e)) => {
@@ -587,86 +795,87 @@ pub async fn task_diff_handler(
     }
 }
 
-/// Get the diff for a single checkpoint step
+/// Get the diff for a single file within a task
 ///
-/// Returns the unified diff (patch) and file-level statistics for the specified
-/// step (by 1-based index) within a task. The diff is computed between the
-/// step's parent commit and the step commit itself.
+/// Returns the patch hunks and stats for one file only — across the whole
+/// task by default, or for a single step when `?step=` is given. This lets
+/// the UI and agents request focused diffs instead of the multi-megabyte
+/// whole-task patch.
 ///
-/// The `workspace` query parameter is required to locate the git repo.
+/// A file the range never touched yields an empty diff (no files, empty
+/// patch). The `workspace` query parameter is optional — when omitted, the
+/// task is auto-linked to its checkpoint workspace via the link store.
 #[utoipa::path(
     get,
-    path = "/changes/tasks/{task_id}/steps/{index}/diff",
+    path = "/changes/tasks/{task_id}/diff/file",
     params(
         ("task_id" = String, Path, description = "Task ID"),
-        ("index" = usize, Path, description = "Step index (1-based, chronological)"),
-        StepDiffQuery
+        FileDiffQuery
     ),
     responses(
-        (status = 200, description = "Diff result for the step", body = DiffResult),
+        (status = 200, description = "Diff result for the single file", body = DiffResult),
         (status = 400, description = "Invalid parameters", body = ChangesErrorResponse),
         (status = 500, description = "Internal server error", body = ChangesErrorResponse)
     ),
     security(("bearerAuth" = [])),
     tags = ["changes", "tool"]
 )]
-pub async fn step_diff_handler(
+pub async fn file_diff_handler(
     State(_state): State<Arc<AppState>>,
-    Path(path): Path<StepDiffPath>,
-    Query(params): Query<StepDiffQuery>,
+    Path(task_id): Path<String>,
+    Query(params): Query<FileDiffQuery>,
 ) -> Result<Json<DiffResult>, (StatusCode, Json<ChangesErrorResponse>)> {
-    let workspace_id = params.workspace.clone();
-    let task_id = path.task_id;
-    let step_index = path.index;
+    let file_path = params.path.clone();
+    let step = params.step;
 
-    if workspace_id.is_empty() {
+    if file_path.is_empty() {
         return Err((
             StatusCode::BAD_REQUEST,
             Json(ChangesErrorResponse {
-                error: "Missing required 'workspace' query parameter".to_string(),
+                error: "Missing required 'path' query parameter".to_string(),
                 code: 400,
             }),
         ));
     }
 
+    let (workspace_id, git_dir) =
+        resolve_workspace_for_request(&task_id, params.workspace.clone()).await?;
+
     log::info!(
-        "REST API: GET /changes/tasks/{}/steps/{}/diff — workspace={}",
-        task_id, step_index, workspace_id
+        "REST API: GET /changes/tasks/{}/diff/file — workspace={}, path={}, step={:?}",
+        task_id, workspace_id, file_path, step
     );
 
-    let git_dir = resolve_git_dir(&workspace_id).await?;
-
     let tid = task_id.clone();
+    let fp = file_path.clone();
     let result = tokio::task::spawn_blocking(move || {
         let git_path = std::path::PathBuf::from(&git_dir);
-        discovery::get_step_diff(&tid, step_index, &git_path)
+        discovery::get_file_diff(&tid, &fp, step, &git_path)
     })
     .await;
 
     match result {
-        Ok(Ok(diff)) => {
+        Ok(Ok(mut diff)) => {
             log::info!(
-                "REST API: Step diff for task {} step {}: {} files",
-                task_id, step_index, diff.files.len()
+                "REST API: File diff for task {} path {}: {} bytes patch",
+                task_id, file_path, diff.patch.len()
             );
+            apply_granularity(&mut diff, params.granularity.as_deref())?;
             Ok(Json(diff))
         }
         Ok(Err(e)) => {
-            log::warn!("REST API: Step diff error: {}", e);
+            log::warn!("REST API: File diff error: {}", e);
             Err((
                 StatusCode::BAD_REQUEST,
-                Json(ChangesErrorResponse {
-                    error: e,
-                    code: 400,
-                }),
+                Json(ChangesErrorResponse { error: e, code: 400 }),
             ))
         }
         Err(e) => {
-            log::error!("REST API: Failed to compute step diff: {}", e);
+            log::error!("REST API: Failed to compute file diff: {}", e);
             Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ChangesErrorResponse {
-                    error: format!("Failed to compute step diff: {}", e),
+                    error: format!("Failed to compute file diff: {}", e),
                     code: 500,
                 }),
             ))
@@ -674,88 +883,80 @@ pub async fn step_diff_handler(
     }
 }
 
-/// Get the diff for a single subtask phase
+/// Get the cumulative diff between two checkpoint steps
 ///
-/// Computes the diff for a specific subtask within a task by mapping
-/// conversation history feedback boundaries to checkpoint commit ranges.
-/// Subtask #0 is the initial task, #1+ are feedback-driven subtasks.
+/// Computes one diff from the repository state at `from_step` to the state
+/// at `to_step` (both 1-based, chronological) — "what changed since my last
+/// feedback" without the subtask boundary heuristic. `from_step` must be
+/// strictly less than `to_step`.
 ///
-/// This bridges the conversation_history module (subtask detection from
-/// `ui_messages.json`) with the shadow_git module (checkpoint commits).
-/// Each subtask's time window is mapped to the checkpoint steps that
-/// fall within it, and the diff is computed across that step range.
+/// The `workspace` query parameter is optional — when omitted, the task is
+/// auto-linked to its checkpoint workspace via the link store.
 #[utoipa::path(
     get,
-    path = "/changes/tasks/{task_id}/subtasks/{subtask_index}/diff",
+    path = "/changes/tasks/{task_id}/range-diff",
     params(
         ("task_id" = String, Path, description = "Task ID"),
-        ("subtask_index" = usize, Path, description = "Subtask index (0-based: 0=initial task, 1+=feedback subtasks)"),
-        SubtaskDiffQuery
+        RangeDiffQuery
     ),
     responses(
-        (status = 200, description = "Diff result for the subtask phase", body = DiffResult),
-        (status = 400, description = "Invalid parameters or no steps in subtask window", body = ChangesErrorResponse),
+        (status = 200, description = "Cumulative diff across the step range", body = DiffResult),
+        (status = 400, description = "Invalid parameters or step range", body = ChangesErrorResponse),
         (status = 500, description = "Internal server error", body = ChangesErrorResponse)
     ),
     security(("bearerAuth" = [])),
     tags = ["changes", "tool"]
 )]
-pub async fn subtask_diff_handler(
+pub async fn range_diff_handler(
     State(_state): State<Arc<AppState>>,
-    Path(path): Path<SubtaskDiffPath>,
-    Query(params): Query<SubtaskDiffQuery>,
+    Path(task_id): Path<String>,
+    Query(params): Query<RangeDiffQuery>,
 ) -> Result<Json<DiffResult>, (StatusCode, Json<ChangesErrorResponse>)> {
-    let workspace_id = params.workspace.clone();
-    let excludes = params.exclude.clone();
-    let task_id = path.task_id;
-    let subtask_index = path.subtask_index;
+    let from_step = params.from_step;
+    let to_step = params.to_step;
 
-    if workspace_id.is_empty() {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            Json(ChangesErrorResponse {
-                error: "Missing required 'workspace' query parameter".to_string(),
-                code: 400,
-            }),
-        ));
-    }
+    let (workspace_id, git_dir) =
+        resolve_workspace_for_request(&task_id, params.workspace.clone()).await?;
 
     log::info!(
-        "REST API: GET /changes/tasks/{}/subtasks/{}/diff — workspace={}, excludes={:?}",
-        task_id, subtask_index, workspace_id, excludes
+        "REST API: GET /changes/tasks/{}/range-diff — workspace={}, steps {}..{}",
+        task_id, workspace_id, from_step, to_step
     );
 
-    let git_dir = resolve_git_dir(&workspace_id).await?;
-
     let tid = task_id.clone();
-    let ws_id = workspace_id.clone();
     let result = tokio::task::spawn_blocking(move || {
         let git_path = std::path::PathBuf::from(&git_dir);
-        discovery::get_subtask_diff(&tid, subtask_index, &ws_id, &git_path, &excludes)
+        discovery::get_range_diff(&tid, from_step, to_step, &git_path)
     })
     .await;
 
     match result {
-        Ok(Ok(diff)) => {
+        Ok(Ok(mut diff)) => {
             log::info!(
-                "REST API: Subtask diff for task {} subtask #{}: {} files, {} bytes patch",
-                task_id, subtask_index, diff.files.len(), diff.patch.len()
+                "REST API: Range diff for task {} steps {}..{}: {} files, {} bytes patch",
+                task_id, from_step, to_step, diff.files.len(), diff.patch.len()
+            );
+            diff.patch = discovery::cap_patch_sections(
+                &diff.patch,
+                discovery::max_patch_section_bytes(),
+                &format!("/changes/tasks/{}/diff/file", task_id),
             );
+            apply_granularity(&mut diff, params.granularity.as_deref())?;
             Ok(Json(diff))
         }
         Ok(Err(e)) => {
-            log::warn!("REST API: Subtask diff error: {}", e);
+            log::warn!("REST API: Range diff error: {}", e);
             Err((
                 StatusCode::BAD_REQUEST,
                 Json(ChangesErrorResponse { error: e, code: 400 }),
             ))
         }
         Err(e) => {
-            log::error!("REST API: Failed to compute subtask diff: {}", e);
+            log::error!("REST API: Failed to compute range diff: {}", e);
             Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ChangesErrorResponse {
-                    error: format!("Failed to compute subtask diff: {}", e),
+                    error: format!("Failed to compute range diff: {}", e),
                     code: 500,
                 }),
             ))
@@ -763,100 +964,1466 @@ pub async fn subtask_diff_handler(
     }
 }
 
-/// Nuke a workspace's checkpoint history
-///
-/// Deletes ALL checkpoint history for the specified workspace by removing the
-/// `.git` directory and re-initializing it as an empty bare repo.
-/// The workspace ID stays the same, but all task/step commits are gone.
-/// Cline will recreate checkpoints when the next task runs.
+/// Get the change history of one file across a task's checkpoints
 ///
-/// **Safety:**
-/// - Cannot nuke if `.git_disabled` (Cline is actively running a task)
-/// - Returns the number of deleted commits and tasks
+/// Lists every step where the file changed, with per-step added/removed
+/// line counts and a ready-made `patchUrl` pointing at the per-step
+/// single-file diff — the data behind a file evolution view.
 ///
-/// **This operation cannot be undone.**
+/// The `path` segment must be URL-encoded (slashes as `%2F`). The
+/// `workspace` query parameter is optional — when omitted, the task is
+/// auto-linked to its checkpoint workspace via the link store.
 #[utoipa::path(
-    post,
-    path = "/changes/workspaces/{id}/nuke",
+    get,
+    path = "/changes/tasks/{task_id}/files/{path}/history",
     params(
-        ("id" = String, Path, description = "Workspace ID to nuke")
+        ("task_id" = String, Path, description = "Task ID"),
+        ("path" = String, Path, description = "File path relative to repo root (URL-encoded)"),
+        FileHistoryQuery
     ),
     responses(
-        (status = 200, description = "Workspace nuked successfully", body = NukeWorkspaceResponse),
-        (status = 400, description = "Cannot nuke (e.g. active task)", body = ChangesErrorResponse),
+        (status = 200, description = "Per-step history for the file", body = FileHistoryResponse),
+        (status = 400, description = "Invalid parameters", body = ChangesErrorResponse),
         (status = 500, description = "Internal server error", body = ChangesErrorResponse)
     ),
     security(("bearerAuth" = [])),
-    tags = ["changes"]
+    tags = ["changes", "tool"]
 )]
-pub async fn nuke_workspace_handler(
+pub async fn file_history_handler(
     State(_state): State<Arc<AppState>>,
-    Path(workspace_id): Path<String>,
-) -> Result<Json<NukeWorkspaceResponse>, (StatusCode, Json<ChangesErrorResponse>)> {
-    if workspace_id.is_empty() {
+    Path(path_params): Path<FileHistoryPath>,
+    Query(params): Query<FileHistoryQuery>,
+) -> Result<Json<FileHistoryResponse>, (StatusCode, Json<ChangesErrorResponse>)> {
+    let task_id = path_params.task_id;
+    let file_path = path_params.path;
+
+    if file_path.is_empty() {
         return Err((
             StatusCode::BAD_REQUEST,
             Json(ChangesErrorResponse {
-                error: "Missing workspace ID".to_string(),
+                error: "Missing file path".to_string(),
                 code: 400,
             }),
         ));
     }
 
+    let (workspace_id, git_dir) =
+        resolve_workspace_for_request(&task_id, params.workspace.clone()).await?;
+
     log::info!(
-        "REST API: POST /changes/workspaces/{}/nuke — nuking workspace",
-        workspace_id
+        "REST API: GET /changes/tasks/{}/files/{}/history — workspace={}",
+        task_id, file_path, workspace_id
     );
 
-    // Resolve git_dir for this workspace
-    let git_dir = resolve_git_dir(&workspace_id).await?;
-
-    let ws_id = workspace_id.clone();
-    let gd = git_dir.clone();
+    let tid = task_id.clone();
+    let fp = file_path.clone();
     let result = tokio::task::spawn_blocking(move || {
-        cleanup::nuke_workspace(&ws_id, &gd)
+        let git_path = std::path::PathBuf::from(&git_dir);
+        discovery::get_file_history(&tid, &fp, &git_path)
     })
     .await;
 
     match result {
-        Ok(Ok(response)) => {
+        Ok(Ok((steps, total_steps))) => {
+            let entries: Vec<FileHistoryEntry> = steps
+                .into_iter()
+                .map(|(step_index, hash, timestamp, lines_added, lines_removed, status)| {
+                    FileHistoryEntry {
+                        step_index,
+                        hash,
+                        timestamp,
+                        lines_added,
+                        lines_removed,
+                        status,
+                        patch_url: format!(
+                            "/changes/tasks/{}/diff/file?path={}&step={}",
+                            task_id,
+                            urlencoding::encode(&file_path),
+                            step_index
+                        ),
+                    }
+                })
+                .collect();
+
             log::info!(
-                "REST API: Nuked workspace {} — {} commits, {} tasks deleted",
-                workspace_id, response.deleted_commits, response.deleted_tasks
+                "REST API: File history for task {} path {}: {} touching steps",
+                task_id, file_path, entries.len()
             );
 
-            // Invalidate caches for this workspace
-            TASKS_CACHE.write().remove(&workspace_id);
-            // Remove all steps cache entries for this workspace
-            {
-                let mut steps = STEPS_CACHE.write();
-                let keys_to_remove: Vec<String> = steps
-                    .keys()
-                    .filter(|k| k.starts_with(&format!("{}:", workspace_id)))
-                    .cloned()
-                    .collect();
-                for k in keys_to_remove {
-                    steps.remove(&k);
-                }
-            }
-            // Invalidate workspaces cache to force re-discovery
-            *WORKSPACES_CACHE.write() = None;
-
-            Ok(Json(response))
+            Ok(Json(FileHistoryResponse {
+                task_id,
+                workspace_id,
+                path: file_path,
+                entries,
+                total_steps,
+            }))
         }
         Ok(Err(e)) => {
-            log::warn!("REST API: Nuke workspace error: {}", e);
+            log::warn!("REST API: File history error: {}", e);
             Err((
                 StatusCode::BAD_REQUEST,
                 Json(ChangesErrorResponse { error: e, code: 400 }),
             ))
         }
         Err(e) => {
-            log::error!("REST API: Failed to nuke workspace: {}", e);
+            log::error!("REST API: Failed to compute file history: {}", e);
             Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ChangesErrorResponse {
-                    error: format!("Failed to nuke workspace: {}", e),
+                    error: format!("Failed to compute file history: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
+
+/// Attribute each line of a file to the step and subtask that wrote it
+///
+/// Blames the file at the task's last checkpoint, restricted to the task's
+/// commit range, and maps each line's introducing commit to its step index
+/// and — via the same time-window heuristic as the subtask diff — to the
+/// subtask (and therefore the user prompt) responsible for it. Lines that
+/// predate the task come back with `stepIndex: null`.
+///
+/// The `workspace` query parameter is optional — when omitted, the task is
+/// auto-linked to its checkpoint workspace via the link store.
+#[utoipa::path(
+    get,
+    path = "/changes/tasks/{task_id}/blame",
+    params(
+        ("task_id" = String, Path, description = "Task ID"),
+        BlameQuery
+    ),
+    responses(
+        (status = 200, description = "Per-line attribution for the file", body = BlameResponse),
+        (status = 400, description = "Invalid parameters or binary/missing file", body = ChangesErrorResponse),
+        (status = 500, description = "Internal server error", body = ChangesErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["changes", "tool"]
+)]
+pub async fn blame_handler(
+    State(_state): State<Arc<AppState>>,
+    Path(task_id): Path<String>,
+    Query(params): Query<BlameQuery>,
+) -> Result<Json<BlameResponse>, (StatusCode, Json<ChangesErrorResponse>)> {
+    let file_path = params.path.clone();
+
+    if file_path.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ChangesErrorResponse {
+                error: "Missing required 'path' query parameter".to_string(),
+                code: 400,
+            }),
+        ));
+    }
+
+    let (workspace_id, git_dir) =
+        resolve_workspace_for_request(&task_id, params.workspace.clone()).await?;
+
+    log::info!(
+        "REST API: GET /changes/tasks/{}/blame — workspace={}, path={}",
+        task_id, workspace_id, file_path
+    );
+
+    let tid = task_id.clone();
+    let fp = file_path.clone();
+    let ws_id = workspace_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let git_path = std::path::PathBuf::from(&git_dir);
+        discovery::get_blame(&tid, &fp, &ws_id, &git_path)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(blame)) => {
+            log::info!(
+                "REST API: Blame for task {} path {}: {} lines",
+                task_id, file_path, blame.lines.len()
+            );
+            Ok(Json(blame))
+        }
+        Ok(Err(e)) => {
+            log::warn!("REST API: Blame error: {}", e);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(ChangesErrorResponse { error: e, code: 400 }),
+            ))
+        }
+        Err(e) => {
+            log::error!("REST API: Failed to compute blame: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ChangesErrorResponse {
+                    error: format!("Failed to compute blame: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
+
+/// Download the full task diff as a .patch file
+///
+/// Returns the same unified patch as `/changes/tasks/{task_id}/diff` but as
+/// a plain-text attachment (`text/x-patch` with a content-disposition
+/// filename), ready to be applied elsewhere with `git apply`.
+///
+/// Supports the same optional `workspace` auto-linking and `exclude`
+/// pathspec patterns as the JSON diff endpoint.
+#[utoipa::path(
+    get,
+    path = "/changes/tasks/{task_id}/diff.patch",
+    params(
+        ("task_id" = String, Path, description = "Task ID"),
+        TaskDiffQuery
+    ),
+    responses(
+        (status = 200, description = "Unified patch text (attachment)", body = String, content_type = "text/x-patch"),
+        (status = 400, description = "Invalid parameters", body = ChangesErrorResponse),
+        (status = 500, description = "Internal server error", body = ChangesErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["changes", "tool"]
+)]
+pub async fn task_diff_patch_handler(
+    State(_state): State<Arc<AppState>>,
+    Path(task_id): Path<String>,
+    Query(params): Query<TaskDiffQuery>,
+) -> Result<([(axum::http::HeaderName, String); 2], String), (StatusCode, Json<ChangesErrorResponse>)> {
+    let mut excludes = params.exclude.clone();
+
+    let (workspace_id, git_dir) =
+        resolve_workspace_for_request(&task_id, params.workspace.clone()).await?;
+    changesignore::merge_into_excludes(&mut excludes, &workspace_id);
+
+    log::info!(
+        "REST API: GET /changes/tasks/{}/diff.patch — workspace={}, excludes={:?}",
+        task_id, workspace_id, excludes
+    );
+
+    let tid = task_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let git_path = std::path::PathBuf::from(&git_dir);
+        discovery::get_task_diff(&tid, &git_path, &excludes)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(diff)) => {
+            log::info!(
+                "REST API: Patch download for task {}: {} bytes",
+                task_id, diff.patch.len()
+            );
+            let headers = [
+                (
+                    axum::http::header::CONTENT_TYPE,
+                    "text/x-patch; charset=utf-8".to_string(),
+                ),
+                (
+                    axum::http::header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"task-{}.patch\"", task_id),
+                ),
+            ];
+            Ok((headers, diff.patch))
+        }
+        Ok(Err(e)) => {
+            log::warn!("REST API: Patch download error: {}", e);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(ChangesErrorResponse { error: e, code: 400 }),
+            ))
+        }
+        Err(e) => {
+            log::error!("REST API: Failed to build patch download: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ChangesErrorResponse {
+                    error: format!("Failed to build patch download: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
+
+/// Download a task's checkpoint history as a git bundle
+///
+/// Produces a standalone `git bundle` whose head is the task's newest
+/// checkpoint commit, so a task's full change history can be archived or
+/// shared with a teammate (`git clone task-<id>.bundle`). Ancestor commits
+/// are included as needed for reachability.
+///
+/// The `workspace` query parameter is optional — when omitted, the task is
+/// auto-linked to its checkpoint workspace via the link store.
+#[utoipa::path(
+    get,
+    path = "/changes/tasks/{task_id}/bundle",
+    params(
+        ("task_id" = String, Path, description = "Task ID"),
+        BundleQuery
+    ),
+    responses(
+        (status = 200, description = "Git bundle bytes (attachment)", body = Vec<u8>, content_type = "application/octet-stream"),
+        (status = 400, description = "Invalid parameters", body = ChangesErrorResponse),
+        (status = 500, description = "Internal server error", body = ChangesErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["changes"]
+)]
+pub async fn task_bundle_handler(
+    State(_state): State<Arc<AppState>>,
+    Path(task_id): Path<String>,
+    Query(params): Query<BundleQuery>,
+) -> Result<([(axum::http::HeaderName, String); 2], Vec<u8>), (StatusCode, Json<ChangesErrorResponse>)> {
+    let (workspace_id, git_dir) =
+        resolve_workspace_for_request(&task_id, params.workspace.clone()).await?;
+
+    log::info!(
+        "REST API: GET /changes/tasks/{}/bundle — workspace={}",
+        task_id, workspace_id
+    );
+
+    let tid = task_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let git_path = std::path::PathBuf::from(&git_dir);
+        discovery::create_task_bundle(&tid, &git_path)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(bytes)) => {
+            log::info!(
+                "REST API: Bundle for task {}: {} bytes",
+                task_id, bytes.len()
+            );
+            let headers = [
+                (
+                    axum::http::header::CONTENT_TYPE,
+                    "application/octet-stream".to_string(),
+                ),
+                (
+                    axum::http::header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"task-{}.bundle\"", task_id),
+                ),
+            ];
+            Ok((headers, bytes))
+        }
+        Ok(Err(e)) => {
+            log::warn!("REST API: Bundle error: {}", e);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(ChangesErrorResponse { error: e, code: 400 }),
+            ))
+        }
+        Err(e) => {
+            log::error!("REST API: Failed to create bundle: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ChangesErrorResponse {
+                    error: format!("Failed to create bundle: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
+
+/// Get the diff for a single checkpoint step
+///
+/// Returns the unified diff (patch) and file-level statistics for the specified
+/// step (by 1-based index) within a task. The diff is computed between the
+/// step's parent commit and the step commit itself.
+///
+/// The `workspace` query parameter is optional — when omitted, the task is
+/// auto-linked to its checkpoint workspace via the link store.
+#[utoipa::path(
+    get,
+    path = "/changes/tasks/{task_id}/steps/{index}/diff",
+    params(
+        ("task_id" = String, Path, description = "Task ID"),
+        ("index" = usize, Path, description = "Step index (1-based, chronological)"),
+        StepDiffQuery
+    ),
+    responses(
+        (status = 200, description = "Diff result for the step", body = DiffResult),
+        (status = 400, description = "Invalid parameters", body = ChangesErrorResponse),
+        (status = 500, description = "Internal server error", body = ChangesErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["changes", "tool"]
+)]
+pub async fn step_diff_handler(
+    State(_state): State<Arc<AppState>>,
+    Path(path): Path<StepDiffPath>,
+    Query(params): Query<StepDiffQuery>,
+) -> Result<Json<DiffResult>, (StatusCode, Json<ChangesErrorResponse>)> {
+    let task_id = path.task_id;
+    let step_index = path.index;
+
+    let (workspace_id, git_dir) =
+        resolve_workspace_for_request(&task_id, params.workspace.clone()).await?;
+
+    log::info!(
+        "REST API: GET /changes/tasks/{}/steps/{}/diff — workspace={}",
+        task_id, step_index, workspace_id
+    );
+
+    let tid = task_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let git_path = std::path::PathBuf::from(&git_dir);
+        discovery::get_step_diff(&tid, step_index, &git_path)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(mut diff)) => {
+            log::info!(
+                "REST API: Step diff for task {} step {}: {} files",
+                task_id, step_index, diff.files.len()
+            );
+            diff.patch = discovery::cap_patch_sections(
+                &diff.patch,
+                discovery::max_patch_section_bytes(),
+                &format!("/changes/tasks/{}/diff/file?step={}", task_id, step_index),
+            );
+            apply_granularity(&mut diff, params.granularity.as_deref())?;
+            Ok(Json(diff))
+        }
+        Ok(Err(e)) => {
+            log::warn!("REST API: Step diff error: {}", e);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(ChangesErrorResponse {
+                    error: e,
+                    code: 400,
+                }),
+            ))
+        }
+        Err(e) => {
+            log::error!("REST API: Failed to compute step diff: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ChangesErrorResponse {
+                    error: format!("Failed to compute step diff: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
+
+/// Get the diff for a single subtask phase
+///
+/// Computes the diff for a specific subtask within a task by mapping
+/// conversation history feedback boundaries to checkpoint commit ranges.
+/// Subtask #0 is the initial task, #1+ are feedback-driven subtasks.
+///
+/// This bridges the conversation_history module (subtask detection from
+/// `ui_messages.json`) with the shadow_git module (checkpoint commits).
+/// Each subtask's time window is mapped to the checkpoint steps that
+/// fall within it, and the diff is computed across that step range.
+#[utoipa::path(
+    get,
+    path = "/changes/tasks/{task_id}/subtasks/{subtask_index}/diff",
+    params(
+        ("task_id" = String, Path, description = "Task ID"),
+        ("subtask_index" = usize, Path, description = "Subtask index (0-based: 0=initial task, 1+=feedback subtasks)"),
+        SubtaskDiffQuery
+    ),
+    responses(
+        (status = 200, description = "Diff result for the subtask phase", body = DiffResult),
+        (status = 400, description = "Invalid parameters or no steps in subtask window", body = ChangesErrorResponse),
+        (status = 500, description = "Internal server error", body = ChangesErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["changes", "tool"]
+)]
+pub async fn subtask_diff_handler(
+    State(_state): State<Arc<AppState>>,
+    Path(path): Path<SubtaskDiffPath>,
+    Query(params): Query<SubtaskDiffQuery>,
+) -> Result<Json<DiffResult>, (StatusCode, Json<ChangesErrorResponse>)> {
+    let mut excludes = params.exclude.clone();
+    let task_id = path.task_id;
+    let subtask_index = path.subtask_index;
+
+    let (workspace_id, git_dir) =
+        resolve_workspace_for_request(&task_id, params.workspace.clone()).await?;
+    changesignore::merge_into_excludes(&mut excludes, &workspace_id);
+
+    log::info!(
+        "REST API: GET /changes/tasks/{}/subtasks/{}/diff — workspace={}, excludes={:?}",
+        task_id, subtask_index, workspace_id, excludes
+    );
+
+    let tid = task_id.clone();
+    let ws_id = workspace_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let git_path = std::path::PathBuf::from(&git_dir);
+        discovery::get_subtask_diff(&tid, subtask_index, &ws_id, &git_path, &excludes)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(mut diff)) => {
+            log::info!(
+                "REST API: Subtask diff for task {} subtask #{}: {} files, {} bytes patch",
+                task_id, subtask_index, diff.files.len(), diff.patch.len()
+            );
+            diff.patch = discovery::cap_patch_sections(
+                &diff.patch,
+                discovery::max_patch_section_bytes(),
+                &format!("/changes/tasks/{}/diff/file", task_id),
+            );
+            apply_granularity(&mut diff, params.granularity.as_deref())?;
+            Ok(Json(diff))
+        }
+        Ok(Err(e)) => {
+            log::warn!("REST API: Subtask diff error: {}", e);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(ChangesErrorResponse { error: e, code: 400 }),
+            ))
+        }
+        Err(e) => {
+            log::error!("REST API: Failed to compute subtask diff: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ChangesErrorResponse {
+                    error: format!("Failed to compute subtask diff: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
+
+/// Browse the file tree of a checkpoint snapshot
+///
+/// Lists one directory level of the project as captured by any checkpoint
+/// commit (backed by `git ls-tree`), with file sizes and modes. Pass `path`
+/// to descend into subdirectories; omit it for the repo root. Entries come
+/// back directories-first, alphabetical.
+#[utoipa::path(
+    get,
+    path = "/changes/tree",
+    params(TreeQuery),
+    responses(
+        (status = 200, description = "Directory listing at the ref", body = TreeResponse),
+        (status = 400, description = "Invalid workspace, ref or path", body = ChangesErrorResponse),
+        (status = 500, description = "Internal server error", body = ChangesErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["changes", "tool"]
+)]
+pub async fn tree_handler(
+    State(_state): State<Arc<AppState>>,
+    Query(params): Query<TreeQuery>,
+) -> Result<Json<TreeResponse>, (StatusCode, Json<ChangesErrorResponse>)> {
+    let workspace_id = params.workspace.clone();
+    let git_ref = params.git_ref.clone();
+    let path = params.path.clone().unwrap_or_default();
+    // Normalize: no leading/trailing slashes in tree paths
+    let path = path.trim_matches('/').to_string();
+
+    if workspace_id.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ChangesErrorResponse {
+                error: "Missing required 'workspace' query parameter".to_string(),
+                code: 400,
+            }),
+        ));
+    }
+
+    if git_ref.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ChangesErrorResponse {
+                error: "Missing required 'ref' query parameter".to_string(),
+                code: 400,
+            }),
+        ));
+    }
+
+    log::info!(
+        "REST API: GET /changes/tree — workspace={}, ref={}, path={}",
+        workspace_id, &git_ref[..std::cmp::min(8, git_ref.len())],
+        if path.is_empty() { "(root)" } else { &path }
+    );
+
+    let git_dir = resolve_git_dir(&workspace_id).await?;
+
+    let gr = git_ref.clone();
+    let p = path.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let git_path = std::path::PathBuf::from(&git_dir);
+        discovery::get_tree(&git_path, &gr, &p)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(entries)) => {
+            log::info!(
+                "REST API: Tree for workspace {} at {}: {} entries",
+                workspace_id, &git_ref[..std::cmp::min(8, git_ref.len())], entries.len()
+            );
+            Ok(Json(TreeResponse {
+                workspace_id,
+                git_ref,
+                path,
+                entries,
+            }))
+        }
+        Ok(Err(e)) => {
+            log::warn!("REST API: Tree error: {}", e);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(ChangesErrorResponse { error: e, code: 400 }),
+            ))
+        }
+        Err(e) => {
+            log::error!("REST API: Failed to list tree: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ChangesErrorResponse {
+                    error: format!("Failed to list tree: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
+
+/// Search file contents within a checkpoint snapshot
+///
+/// Runs a fixed-string content search (`git grep` semantics) across the
+/// project as captured by any checkpoint commit, returning file/line
+/// matches. This lets agents locate code that existed at a given point
+/// even if it has since been deleted. Results are capped at 500 matches
+/// (`truncated: true` when the cap was hit).
+#[utoipa::path(
+    get,
+    path = "/changes/search",
+    params(SearchQuery),
+    responses(
+        (status = 200, description = "Matches at the ref", body = SearchResponse),
+        (status = 400, description = "Invalid workspace, ref or query", body = ChangesErrorResponse),
+        (status = 500, description = "Internal server error", body = ChangesErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["changes", "tool"]
+)]
+pub async fn search_handler(
+    State(_state): State<Arc<AppState>>,
+    Query(params): Query<SearchQuery>,
+) -> Result<Json<SearchResponse>, (StatusCode, Json<ChangesErrorResponse>)> {
+    let workspace_id = params.workspace.clone();
+    let git_ref = params.git_ref.clone();
+    let query = params.q.clone();
+
+    if workspace_id.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ChangesErrorResponse {
+                error: "Missing required 'workspace' query parameter".to_string(),
+                code: 400,
+            }),
+        ));
+    }
+
+    if git_ref.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ChangesErrorResponse {
+                error: "Missing required 'ref' query parameter".to_string(),
+                code: 400,
+            }),
+        ));
+    }
+
+    if query.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ChangesErrorResponse {
+                error: "Missing required 'q' query parameter".to_string(),
+                code: 400,
+            }),
+        ));
+    }
+
+    log::info!(
+        "REST API: GET /changes/search — workspace={}, ref={}, q={}",
+        workspace_id, &git_ref[..std::cmp::min(8, git_ref.len())], query
+    );
+
+    let git_dir = resolve_git_dir(&workspace_id).await?;
+
+    let gr = git_ref.clone();
+    let q = query.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let git_path = std::path::PathBuf::from(&git_dir);
+        discovery::search_snapshot(&git_path, &gr, &q, SEARCH_MAX_MATCHES)
+    })
+    .await;
+
+    match result {
+        Ok(Ok((matches, truncated))) => {
+            log::info!(
+                "REST API: Search for workspace {} at {}: {} matches (truncated={})",
+                workspace_id, &git_ref[..std::cmp::min(8, git_ref.len())], matches.len(), truncated
+            );
+            Ok(Json(SearchResponse {
+                workspace_id,
+                git_ref,
+                query,
+                matches,
+                truncated,
+            }))
+        }
+        Ok(Err(e)) => {
+            log::warn!("REST API: Search error: {}", e);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(ChangesErrorResponse { error: e, code: 400 }),
+            ))
+        }
+        Err(e) => {
+            log::error!("REST API: Failed to search snapshot: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ChangesErrorResponse {
+                    error: format!("Failed to search snapshot: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
+
+/// Apply a task's checkpoint diff onto a target repository
+///
+/// Replays the task's full patch onto a real working tree with `git apply`
+/// — "apply this Cline change onto a fresh clone". Supports three-way
+/// merge (`threeWay: true`, leaves conflict markers) and dry runs
+/// (`checkOnly: true`, no files modified). A patch that doesn't apply
+/// cleanly is a 200 response with `applied: false` and the conflicting
+/// paths listed; only setup problems (bad target, empty patch) are errors.
+#[utoipa::path(
+    post,
+    path = "/changes/tasks/{task_id}/apply",
+    params(
+        ("task_id" = String, Path, description = "Task ID")
+    ),
+    request_body = apply::ApplyRequest,
+    responses(
+        (status = 200, description = "Apply result (check `applied` and `conflicts`)", body = apply::ApplyResponse),
+        (status = 400, description = "Invalid target, workspace or empty patch", body = ChangesErrorResponse),
+        (status = 500, description = "Internal server error", body = ChangesErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["changes"]
+)]
+pub async fn apply_task_handler(
+    State(_state): State<Arc<AppState>>,
+    Path(task_id): Path<String>,
+    Json(body): Json<apply::ApplyRequest>,
+) -> Result<Json<apply::ApplyResponse>, (StatusCode, Json<ChangesErrorResponse>)> {
+    if body.target_path.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ChangesErrorResponse {
+                error: "Missing required 'targetPath' field".to_string(),
+                code: 400,
+            }),
+        ));
+    }
+
+    let (workspace_id, git_dir) =
+        resolve_workspace_for_request(&task_id, body.workspace.clone()).await?;
+
+    log::info!(
+        "REST API: POST /changes/tasks/{}/apply — workspace={}, target={}, threeWay={}, checkOnly={}",
+        task_id, workspace_id, body.target_path, body.three_way, body.check_only
+    );
+
+    let tid = task_id.clone();
+    let ws_id = workspace_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let git_path = std::path::PathBuf::from(&git_dir);
+        apply::apply_task_diff(&tid, &ws_id, git_path, &body)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(response)) => {
+            log::info!(
+                "REST API: Apply task {}: applied={}, {} conflicts",
+                task_id, response.applied, response.conflicts.len()
+            );
+            crate::audit::record(
+                "changes.apply",
+                serde_json::json!({
+                    "taskId": task_id,
+                    "workspace": workspace_id,
+                    "checkOnly": response.check_only,
+                }),
+                true,
+                &format!("applied={}, {} conflicts", response.applied, response.conflicts.len()),
+            );
+            Ok(Json(response))
+        }
+        Ok(Err(e)) => {
+            log::warn!("REST API: Apply error: {}", e);
+            crate::audit::record(
+                "changes.apply",
+                serde_json::json!({ "taskId": task_id, "workspace": workspace_id }),
+                false,
+                &e,
+            );
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(ChangesErrorResponse { error: e, code: 400 }),
+            ))
+        }
+        Err(e) => {
+            log::error!("REST API: Failed to apply task diff: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ChangesErrorResponse {
+                    error: format!("Failed to apply task diff: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
+
+/// Summarize a task's diff with the configured LLM provider
+///
+/// Chunks the task's full patch, runs it through the Gemini provider and
+/// returns a structured summary: a task overview plus per-file intent and
+/// risk flags. The result is cached on disk — repeat calls return the
+/// cached summary (`cached: true`) until `refresh: true` regenerates it.
+/// The cache is also where /latest and Jira reporting pick the summary up.
+#[utoipa::path(
+    post,
+    path = "/changes/tasks/{task_id}/summarize",
+    params(
+        ("task_id" = String, Path, description = "Task ID")
+    ),
+    request_body = SummarizeRequest,
+    responses(
+        (status = 200, description = "Structured diff summary", body = SummarizeResponse),
+        (status = 400, description = "Invalid parameters, empty diff or missing API key", body = ChangesErrorResponse),
+        (status = 500, description = "Provider or internal error", body = ChangesErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["changes"]
+)]
+pub async fn summarize_task_handler(
+    State(state): State<Arc<AppState>>,
+    Path(task_id): Path<String>,
+    Json(body): Json<SummarizeRequest>,
+) -> Result<Json<SummarizeResponse>, (StatusCode, Json<ChangesErrorResponse>)> {
+    let model = body.model.clone().unwrap_or_else(|| "gemini-2.0-flash".to_string());
+
+    let (workspace_id, git_dir) =
+        resolve_workspace_for_request(&task_id, body.workspace.clone()).await?;
+
+    log::info!(
+        "REST API: POST /changes/tasks/{}/summarize — workspace={}, model={}, refresh={}",
+        task_id, workspace_id, model, body.refresh
+    );
+
+    if !body.refresh {
+        if let Some(summary) = cache::load_task_summary(&workspace_id, &task_id) {
+            log::info!("REST API: Returning cached diff summary for task {}", task_id);
+            return Ok(Json(SummarizeResponse { cached: true, summary }));
+        }
+    }
+
+    // Past the cache: the rest needs the provider, which offline mode forbids
+    if crate::offline::is_offline() {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ChangesErrorResponse {
+                error: "offline: no cached summary and offline mode is active".to_string(),
+                code: 503,
+            }),
+        ));
+    }
+
+    let gemini_api_key = state.gemini_api_key();
+    if gemini_api_key.is_empty() || gemini_api_key == "YOUR_GEMINI_API_KEY_HERE" {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ChangesErrorResponse {
+                error: "Gemini API key not configured. Please set GEMINI_API_KEY in .env file.".to_string(),
+                code: 400,
+            }),
+        ));
+    }
+
+    let tid = task_id.clone();
+    let diff = tokio::task::spawn_blocking(move || {
+        let git_path = std::path::PathBuf::from(&git_dir);
+        discovery::get_task_diff(&tid, &git_path, &[])
+    })
+    .await
+    .map_err(|e| (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ChangesErrorResponse {
+            error: format!("Failed to compute task diff: {}", e),
+            code: 500,
+        }),
+    ))?
+    .map_err(|e| (
+        StatusCode::BAD_REQUEST,
+        Json(ChangesErrorResponse { error: e, code: 400 }),
+    ))?;
+
+    if diff.patch.trim().is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ChangesErrorResponse {
+                error: format!("Task '{}' has an empty diff — nothing to summarize", task_id),
+                code: 400,
+            }),
+        ));
+    }
+
+    match summarize::summarize_task_diff(&state, &task_id, &workspace_id, &model, &diff.patch).await {
+        Ok(summary) => {
+            cache::save_task_summary(&workspace_id, &task_id, &summary);
+            log::info!(
+                "REST API: Summarized task {} diff: {} files, {} chars overview",
+                task_id, summary.files.len(), summary.overview.len()
+            );
+            Ok(Json(SummarizeResponse { cached: false, summary }))
+        }
+        Err(e) => {
+            log::error!("REST API: Diff summarization failed: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ChangesErrorResponse { error: e, code: 500 }),
+            ))
+        }
+    }
+}
+
+/// Restore files from a checkpoint back into the project
+///
+/// Reads file bodies from the shadow repo at the given ref (`git show`
+/// semantics) and writes them back to their original location, so users
+/// can recover code that Cline later overwrote. Files that already exist
+/// are backed up next to themselves (`<name>.<timestamp>.bak`) first.
+///
+/// `projectPath` is optional — when omitted, the original project
+/// directory is looked up from the task → workspace link store. Per-file
+/// failures are reported in the response rather than aborting the batch.
+#[utoipa::path(
+    post,
+    path = "/changes/restore",
+    request_body = restore::RestoreRequest,
+    responses(
+        (status = 200, description = "Per-file restore outcomes", body = restore::RestoreResponse),
+        (status = 400, description = "Invalid workspace, ref or project path", body = ChangesErrorResponse),
+        (status = 500, description = "Internal server error", body = ChangesErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["changes"]
+)]
+pub async fn restore_files_handler(
+    State(_state): State<Arc<AppState>>,
+    Json(body): Json<restore::RestoreRequest>,
+) -> Result<Json<restore::RestoreResponse>, (StatusCode, Json<ChangesErrorResponse>)> {
+    let workspace_id = body.workspace.clone();
+    let git_ref = body.git_ref.clone();
+
+    if workspace_id.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ChangesErrorResponse {
+                error: "Missing required 'workspace' field".to_string(),
+                code: 400,
+            }),
+        ));
+    }
+
+    if git_ref.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ChangesErrorResponse {
+                error: "Missing required 'gitRef' field".to_string(),
+                code: 400,
+            }),
+        ));
+    }
+
+    if body.paths.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ChangesErrorResponse {
+                error: "No paths to restore".to_string(),
+                code: 400,
+            }),
+        ));
+    }
+
+    // Resolve the destination project directory: explicit > link store
+    let project_path = match body.project_path.clone().filter(|p| !p.is_empty()) {
+        Some(p) => p,
+        None => {
+            let ws_id = workspace_id.clone();
+            let found = tokio::task::spawn_blocking(move || {
+                super::autolink::project_path_for_workspace(&ws_id)
+            })
+            .await
+            .map_err(|e| (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ChangesErrorResponse {
+                    error: format!("Project path lookup failed: {}", e),
+                    code: 500,
+                }),
+            ))?;
+            found.ok_or_else(|| (
+                StatusCode::BAD_REQUEST,
+                Json(ChangesErrorResponse {
+                    error: format!(
+                        "No known project path for workspace '{}' — pass 'projectPath' explicitly",
+                        workspace_id
+                    ),
+                    code: 400,
+                }),
+            ))?
+        }
+    };
+
+    log::info!(
+        "REST API: POST /changes/restore — workspace={}, ref={}, {} paths → {}",
+        workspace_id, &git_ref[..std::cmp::min(8, git_ref.len())], body.paths.len(), project_path
+    );
+
+    let git_dir = resolve_git_dir(&workspace_id).await?;
+
+    let ws_id = workspace_id.clone();
+    let gr = git_ref.clone();
+    let paths = body.paths.clone();
+    let pp = project_path.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let git_path = std::path::PathBuf::from(&git_dir);
+        restore::restore_files(&ws_id, git_path, &gr, &paths, &pp)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(response)) => {
+            log::info!(
+                "REST API: Restore for workspace {}: {} restored, {} failed",
+                workspace_id, response.restored, response.failed
+            );
+            crate::audit::record(
+                "changes.restore",
+                serde_json::json!({ "workspace": workspace_id, "gitRef": git_ref }),
+                true,
+                &format!("{} restored, {} failed", response.restored, response.failed),
+            );
+            Ok(Json(response))
+        }
+        Ok(Err(e)) => {
+            log::warn!("REST API: Restore error: {}", e);
+            crate::audit::record(
+                "changes.restore",
+                serde_json::json!({ "workspace": workspace_id, "gitRef": git_ref }),
+                false,
+                &e,
+            );
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(ChangesErrorResponse { error: e, code: 400 }),
+            ))
+        }
+        Err(e) => {
+            log::error!("REST API: Failed to restore files: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ChangesErrorResponse {
+                    error: format!("Failed to restore files: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
+
+/// Nuke a workspace's checkpoint history
+///
+/// Deletes ALL checkpoint history for the specified workspace by removing the
+/// `.git` directory and re-initializing it as an empty bare repo.
+/// The workspace ID stays the same, but all task/step commits are gone.
+/// Cline will recreate checkpoints when the next task runs.
+///
+/// **Safety:**
+/// - Cannot nuke if `.git_disabled` (Cline is actively running a task)
+/// - Returns the number of deleted commits and tasks
+///
+/// **This operation cannot be undone.**
+#[utoipa::path(
+    post,
+    path = "/changes/workspaces/{id}/nuke",
+    params(
+        ("id" = String, Path, description = "Workspace ID to nuke")
+    ),
+    responses(
+        (status = 200, description = "Workspace nuked successfully", body = NukeWorkspaceResponse),
+        (status = 400, description = "Cannot nuke (e.g. active task)", body = ChangesErrorResponse),
+        (status = 500, description = "Internal server error", body = ChangesErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["changes"]
+)]
+pub async fn nuke_workspace_handler(
+    State(_state): State<Arc<AppState>>,
+    Path(workspace_id): Path<String>,
+) -> Result<Json<NukeWorkspaceResponse>, (StatusCode, Json<ChangesErrorResponse>)> {
+    if workspace_id.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ChangesErrorResponse {
+                error: "Missing workspace ID".to_string(),
+                code: 400,
+            }),
+        ));
+    }
+
+    log::info!(
+        "REST API: POST /changes/workspaces/{}/nuke — nuking workspace",
+        workspace_id
+    );
+
+    // Resolve git_dir for this workspace
+    let git_dir = resolve_git_dir(&workspace_id).await?;
+
+    let ws_id = workspace_id.clone();
+    let gd = git_dir.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        cleanup::nuke_workspace(&ws_id, &gd)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(response)) => {
+            log::info!(
+                "REST API: Nuked workspace {} — {} commits, {} tasks deleted",
+                workspace_id, response.deleted_commits, response.deleted_tasks
+            );
+
+            // Invalidate caches for this workspace
+            TASKS_CACHE.write().remove(&workspace_id);
+            // Remove all steps cache entries for this workspace
+            {
+                let mut steps = STEPS_CACHE.write();
+                let keys_to_remove: Vec<String> = steps
+                    .keys()
+                    .filter(|k| k.starts_with(&format!("{}:", workspace_id)))
+                    .cloned()
+                    .collect();
+                for k in keys_to_remove {
+                    steps.remove(&k);
+                }
+            }
+            // Invalidate workspaces cache to force re-discovery
+            *WORKSPACES_CACHE.write() = None;
+
+            crate::audit::record(
+                "changes.nuke_workspace",
+                serde_json::json!({ "workspace": workspace_id }),
+                true,
+                &format!(
+                    "{} commits, {} tasks deleted",
+                    response.deleted_commits, response.deleted_tasks
+                ),
+            );
+            Ok(Json(response))
+        }
+        Ok(Err(e)) => {
+            log::warn!("REST API: Nuke workspace error: {}", e);
+            crate::audit::record(
+                "changes.nuke_workspace",
+                serde_json::json!({ "workspace": workspace_id }),
+                false,
+                &e,
+            );
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(ChangesErrorResponse { error: e, code: 400 }),
+            ))
+        }
+        Err(e) => {
+            log::error!("REST API: Failed to nuke workspace: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ChangesErrorResponse {
+                    error: format!("Failed to nuke workspace: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
+
+/// Nuke a single task's checkpoints
+///
+/// Drops only the checkpoint commits belonging to one task by rewinding
+/// (or deleting) the refs whose tips they occupy, then expires reflogs and
+/// runs `git gc --prune=now` to reclaim the objects. Other tasks' history
+/// is untouched — if the task's commits are buried under later work, the
+/// operation refuses and nothing is modified.
+///
+/// The `workspace` query parameter is optional — when omitted, the task is
+/// auto-linked to its checkpoint workspace via the link store.
+///
+/// **This operation cannot be undone.**
+#[utoipa::path(
+    post,
+    path = "/changes/tasks/{task_id}/nuke",
+    params(
+        ("task_id" = String, Path, description = "Task ID to nuke"),
+        NukeTaskQuery
+    ),
+    responses(
+        (status = 200, description = "Task checkpoints nuked successfully", body = NukeTaskResponse),
+        (status = 400, description = "Cannot nuke (active task, buried commits, unknown task)", body = ChangesErrorResponse),
+        (status = 500, description = "Internal server error", body = ChangesErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["changes"]
+)]
+pub async fn nuke_task_handler(
+    State(_state): State<Arc<AppState>>,
+    Path(task_id): Path<String>,
+    Query(params): Query<NukeTaskQuery>,
+) -> Result<Json<NukeTaskResponse>, (StatusCode, Json<ChangesErrorResponse>)> {
+    let (workspace_id, git_dir) =
+        resolve_workspace_for_request(&task_id, params.workspace.clone()).await?;
+
+    log::info!(
+        "REST API: POST /changes/tasks/{}/nuke — workspace={}",
+        task_id, workspace_id
+    );
+
+    let tid = task_id.clone();
+    let ws_id = workspace_id.clone();
+    let gd = git_dir.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        cleanup::nuke_task(&ws_id, &tid, &gd)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(response)) => {
+            log::info!(
+                "REST API: Nuked task {} — {} commits dropped, {} objects reclaimed",
+                task_id, response.deleted_commits, response.reclaimed_objects
+            );
+
+            // Invalidate caches touching this workspace/task
+            TASKS_CACHE.write().remove(&workspace_id);
+            STEPS_CACHE
+                .write()
+                .remove(&cache::steps_cache_key(&workspace_id, &task_id));
+            *WORKSPACES_CACHE.write() = None;
+
+            crate::audit::record(
+                "changes.nuke_task",
+                serde_json::json!({ "taskId": task_id, "workspace": workspace_id }),
+                true,
+                &format!(
+                    "{} commits dropped, {} objects reclaimed",
+                    response.deleted_commits, response.reclaimed_objects
+                ),
+            );
+            Ok(Json(response))
+        }
+        Ok(Err(e)) => {
+            log::warn!("REST API: Nuke task error: {}", e);
+            crate::audit::record(
+                "changes.nuke_task",
+                serde_json::json!({ "taskId": task_id, "workspace": workspace_id }),
+                false,
+                &e,
+            );
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(ChangesErrorResponse { error: e, code: 400 }),
+            ))
+        }
+        Err(e) => {
+            log::error!("REST API: Failed to nuke task: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ChangesErrorResponse {
+                    error: format!("Failed to nuke task: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
+
+/// Garbage-collect and repack a workspace's shadow repo
+///
+/// Expires reflogs and runs `git gc --aggressive --prune=now` on the
+/// checkpoint repo, reporting on-disk size and object counts before and
+/// after so users can see how much a bloated workspace shrank. Combine
+/// with the `sizeBytes` field in the workspaces listing to find candidates.
+///
+/// Requires an active `.git` dir — repacking while Cline is mid-task
+/// (`.git_disabled`) is refused.
+#[utoipa::path(
+    post,
+    path = "/changes/workspaces/{id}/gc",
+    params(
+        ("id" = String, Path, description = "Workspace ID to gc")
+    ),
+    responses(
+        (status = 200, description = "GC completed", body = GcWorkspaceResponse),
+        (status = 400, description = "Cannot gc (e.g. active task)", body = ChangesErrorResponse),
+        (status = 500, description = "Internal server error", body = ChangesErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["changes"]
+)]
+pub async fn gc_workspace_handler(
+    State(_state): State<Arc<AppState>>,
+    Path(workspace_id): Path<String>,
+) -> Result<Json<GcWorkspaceResponse>, (StatusCode, Json<ChangesErrorResponse>)> {
+    if workspace_id.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ChangesErrorResponse {
+                error: "Missing workspace ID".to_string(),
+                code: 400,
+            }),
+        ));
+    }
+
+    log::info!(
+        "REST API: POST /changes/workspaces/{}/gc — repacking workspace",
+        workspace_id
+    );
+
+    let git_dir = resolve_git_dir(&workspace_id).await?;
+
+    let ws_id = workspace_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        cleanup::gc_workspace(&ws_id, &git_dir)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(response)) => {
+            log::info!(
+                "REST API: GC workspace {} — {} bytes reclaimed ({} → {} objects)",
+                workspace_id, response.reclaimed_bytes,
+                response.objects_before, response.objects_after
+            );
+            // Sizes changed — force re-discovery on the next listing
+            *WORKSPACES_CACHE.write() = None;
+            Ok(Json(response))
+        }
+        Ok(Err(e)) => {
+            log::warn!("REST API: GC workspace error: {}", e);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(ChangesErrorResponse { error: e, code: 400 }),
+            ))
+        }
+        Err(e) => {
+            log::error!("REST API: Failed to gc workspace: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ChangesErrorResponse {
+                    error: format!("Failed to gc workspace: {}", e),
+                    code: 500,
+                }),
+            ))
+        }
+    }
+}
+
+/// Checkpoint activity over time for a workspace
+///
+/// Returns checkpoint commit counts, distinct task counts and changed-line
+/// totals bucketed by day — the data behind an "how much did Cline churn
+/// this project" chart. Days without any checkpoints are omitted.
+#[utoipa::path(
+    get,
+    path = "/changes/workspaces/{id}/activity",
+    params(
+        ("id" = String, Path, description = "Workspace ID")
+    ),
+    responses(
+        (status = 200, description = "Per-day checkpoint activity", body = ActivityResponse),
+        (status = 400, description = "Unknown workspace", body = ChangesErrorResponse),
+        (status = 500, description = "Internal server error", body = ChangesErrorResponse)
+    ),
+    security(("bearerAuth" = [])),
+    tags = ["changes", "tool"]
+)]
+pub async fn workspace_activity_handler(
+    State(_state): State<Arc<AppState>>,
+    Path(workspace_id): Path<String>,
+) -> Result<Json<ActivityResponse>, (StatusCode, Json<ChangesErrorResponse>)> {
+    if workspace_id.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ChangesErrorResponse {
+                error: "Missing workspace ID".to_string(),
+                code: 400,
+            }),
+        ));
+    }
+
+    let git_dir = resolve_git_dir(&workspace_id).await?;
+
+    log::info!(
+        "REST API: GET /changes/workspaces/{}/activity",
+        workspace_id
+    );
+
+    let ws_id = workspace_id.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let git_path = std::path::PathBuf::from(&git_dir);
+        discovery::get_workspace_activity(&ws_id, &git_path)
+    })
+    .await;
+
+    match result {
+        Ok(Ok(activity)) => {
+            log::info!(
+                "REST API: Activity for workspace {}: {} days, {} commits",
+                workspace_id, activity.days.len(), activity.total_commits
+            );
+            Ok(Json(activity))
+        }
+        Ok(Err(e)) => {
+            log::warn!("REST API: Activity error: {}", e);
+            Err((
+                StatusCode::BAD_REQUEST,
+                Json(ChangesErrorResponse { error: e, code: 400 }),
+            ))
+        }
+        Err(e) => {
+            log::error!("REST API: Failed to compute activity: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ChangesErrorResponse {
+                    error: format!("Failed to compute activity: {}", e),
                     code: 500,
                 }),
             ))
@@ -0,0 +1,230 @@
+//! Intraline (word-level) diff annotation.
+//!
+//! Post-processes unified patch text into per-line change ranges so the
+//! frontend can highlight the exact changed tokens without re-diffing in
+//! JavaScript. Opt-in via `?granularity=word` on the diff endpoints — the
+//! patch text itself is never modified, the ranges ride along in
+//! `DiffResult.intraline`.
+//!
+//! Within each hunk, a run of removed lines followed by a run of added
+//! lines is paired index-wise (the same heuristic git's own `--word-diff`
+//! rendering uses); paired lines get token-level ranges from an LCS over
+//! their words, unpaired lines get a single whole-line range.
+
+use super::types::{IntralineLine, IntralineRange};
+
+/// Lines with more tokens than this skip the LCS and fall back to a
+/// whole-line range — keeps the O(n*m) table bounded on minified files.
+const MAX_TOKENS_PER_LINE: usize = 200;
+
+/// Compute intraline change ranges for every changed line in a patch.
+pub fn annotate_patch(patch: &str) -> Vec<IntralineLine> {
+    let mut out = Vec::new();
+    let mut path = String::new();
+    let mut old_line = 0usize;
+    let mut new_line = 0usize;
+    // Pending runs of removed/added lines within the current hunk
+    let mut removed: Vec<(usize, String)> = Vec::new();
+    let mut added: Vec<(usize, String)> = Vec::new();
+
+    for line in patch.lines() {
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            flush_runs(&mut removed, &mut added, &path, &mut out);
+            // "a/<path> b/<path>" — take the b/ side
+            path = rest
+                .rsplit_once(" b/")
+                .map(|(_, p)| p.to_string())
+                .unwrap_or_default();
+        } else if line.starts_with("@@ ") {
+            flush_runs(&mut removed, &mut added, &path, &mut out);
+            if let Some((o, n)) = parse_hunk_header(line) {
+                old_line = o;
+                new_line = n;
+            }
+        } else if line.starts_with("+++") || line.starts_with("---") {
+            // File headers — not content lines
+        } else if let Some(text) = line.strip_prefix('+') {
+            added.push((new_line, text.to_string()));
+            new_line += 1;
+        } else if let Some(text) = line.strip_prefix('-') {
+            removed.push((old_line, text.to_string()));
+            old_line += 1;
+        } else if line.starts_with(' ') {
+            flush_runs(&mut removed, &mut added, &path, &mut out);
+            old_line += 1;
+            new_line += 1;
+        }
+        // Anything else ("\ No newline at end of file", omitted-section
+        // placeholders, ...) is ignored
+    }
+    flush_runs(&mut removed, &mut added, &path, &mut out);
+
+    out
+}
+
+/// Parse "@@ -a[,b] +c[,d] @@" into the starting (old, new) line numbers.
+fn parse_hunk_header(line: &str) -> Option<(usize, usize)> {
+    let mut parts = line.split(' ');
+    parts.next(); // "@@"
+    let old = parts.next()?.strip_prefix('-')?;
+    let new = parts.next()?.strip_prefix('+')?;
+    let old_start = old.split(',').next()?.parse().ok()?;
+    let new_start = new.split(',').next()?.parse().ok()?;
+    Some((old_start, new_start))
+}
+
+/// Pair up the pending removed/added runs and emit their range entries.
+fn flush_runs(
+    removed: &mut Vec<(usize, String)>,
+    added: &mut Vec<(usize, String)>,
+    path: &str,
+    out: &mut Vec<IntralineLine>,
+) {
+    let pairs = removed.len().min(added.len());
+
+    for i in 0..pairs {
+        let (old_no, old_text) = &removed[i];
+        let (new_no, new_text) = &added[i];
+        let (old_ranges, new_ranges) = word_ranges(old_text, new_text);
+        out.push(IntralineLine {
+            path: path.to_string(),
+            side: "old".to_string(),
+            line_number: *old_no,
+            ranges: old_ranges,
+        });
+        out.push(IntralineLine {
+            path: path.to_string(),
+            side: "new".to_string(),
+            line_number: *new_no,
+            ranges: new_ranges,
+        });
+    }
+
+    for (no, text) in removed.iter().skip(pairs) {
+        out.push(IntralineLine {
+            path: path.to_string(),
+            side: "old".to_string(),
+            line_number: *no,
+            ranges: whole_line(text),
+        });
+    }
+    for (no, text) in added.iter().skip(pairs) {
+        out.push(IntralineLine {
+            path: path.to_string(),
+            side: "new".to_string(),
+            line_number: *no,
+            ranges: whole_line(text),
+        });
+    }
+
+    removed.clear();
+    added.clear();
+}
+
+fn whole_line(text: &str) -> Vec<IntralineRange> {
+    if text.is_empty() {
+        Vec::new()
+    } else {
+        vec![IntralineRange { start: 0, end: text.len() }]
+    }
+}
+
+/// One token: (byte_start, byte_end) within its line.
+type Token = (usize, usize);
+
+/// Tokenize a line into words, symbol characters and whitespace runs,
+/// keeping byte offsets so ranges map straight back into the line.
+fn tokenize(line: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut kind = None; // 0 = word, 1 = whitespace; symbols are singletons
+
+    for (i, c) in line.char_indices() {
+        let k = if c.is_alphanumeric() || c == '_' {
+            Some(0)
+        } else if c.is_whitespace() {
+            Some(1)
+        } else {
+            None
+        };
+        if k.is_none() || k != kind {
+            if start < i {
+                tokens.push((start, i));
+            }
+            start = i;
+            kind = k;
+            if k.is_none() {
+                tokens.push((i, i + c.len_utf8()));
+                start = i + c.len_utf8();
+            }
+        }
+    }
+    if start < line.len() {
+        tokens.push((start, line.len()));
+    }
+    tokens
+}
+
+/// Token-level diff of two lines — returns (old_ranges, new_ranges) of the
+/// bytes NOT shared between them, adjacent ranges merged.
+fn word_ranges(old: &str, new: &str) -> (Vec<IntralineRange>, Vec<IntralineRange>) {
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+
+    if old_tokens.len() > MAX_TOKENS_PER_LINE || new_tokens.len() > MAX_TOKENS_PER_LINE {
+        return (whole_line(old), whole_line(new));
+    }
+
+    // Classic LCS table over token contents
+    let n = old_tokens.len();
+    let m = new_tokens.len();
+    let mut table = vec![0usize; (n + 1) * (m + 1)];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            table[i * (m + 1) + j] = if old[old_tokens[i].0..old_tokens[i].1]
+                == new[new_tokens[j].0..new_tokens[j].1]
+            {
+                table[(i + 1) * (m + 1) + j + 1] + 1
+            } else {
+                table[(i + 1) * (m + 1) + j].max(table[i * (m + 1) + j + 1])
+            };
+        }
+    }
+
+    // Walk the table, marking tokens that are not part of the LCS
+    let mut old_changed = vec![true; n];
+    let mut new_changed = vec![true; m];
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[old_tokens[i].0..old_tokens[i].1] == new[new_tokens[j].0..new_tokens[j].1] {
+            old_changed[i] = false;
+            new_changed[j] = false;
+            i += 1;
+            j += 1;
+        } else if table[(i + 1) * (m + 1) + j] >= table[i * (m + 1) + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    (
+        merge_ranges(&old_tokens, &old_changed),
+        merge_ranges(&new_tokens, &new_changed),
+    )
+}
+
+/// Turn the changed-token flags into merged byte ranges.
+fn merge_ranges(tokens: &[Token], changed: &[bool]) -> Vec<IntralineRange> {
+    let mut ranges: Vec<IntralineRange> = Vec::new();
+    for (token, is_changed) in tokens.iter().zip(changed) {
+        if !is_changed {
+            continue;
+        }
+        match ranges.last_mut() {
+            Some(last) if last.end == token.0 => last.end = token.1,
+            _ => ranges.push(IntralineRange { start: token.0, end: token.1 }),
+        }
+    }
+    ranges
+}
@@ -7,10 +7,19 @@
 
 pub mod types;
 pub mod discovery;
+pub mod git_backend;
+pub mod autolink;
+pub mod apply;
+pub mod intraline;
+pub mod restore;
 pub mod cache;
+pub mod changesignore;
 pub mod cleanup;
+pub mod summarize;
+pub mod warmup;
 pub mod handlers;
 
 pub use types::*;
 pub use discovery::{list_tasks_for_workspace, list_steps_for_task, get_task_diff, get_subtask_diff, find_workspace_for_task, get_file_contents};
+pub use autolink::resolve_workspace_for_task;
 pub use handlers::*;
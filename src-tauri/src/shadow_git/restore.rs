@@ -0,0 +1,171 @@
+//! Restore checkpoint file contents back into the original project.
+//!
+//! Recovers code that Cline later overwrote: file bodies are read from the
+//! shadow repo at a given ref (`git show` semantics) and written back to
+//! their original location in the project working directory. Existing files
+//! are backed up next to themselves before being replaced.
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Request body for POST /changes/restore
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreRequest {
+    /// Workspace ID (required to locate the shadow git repo)
+    pub workspace: String,
+    /// Git ref to restore from (e.g. a checkpoint commit hash)
+    pub git_ref: String,
+    /// File paths (relative to repo root) to restore
+    pub paths: Vec<String>,
+    /// Project directory to restore into. Optional — when omitted, the
+    /// original project path is looked up from the task → workspace link
+    /// store (populated whenever a task in this workspace was auto-linked).
+    #[serde(default)]
+    pub project_path: Option<String>,
+}
+
+/// Outcome of restoring a single file
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoredFile {
+    /// File path relative to repo root
+    pub path: String,
+    /// Absolute destination the file was written to
+    pub dest: String,
+    /// True when the file was written successfully
+    pub restored: bool,
+    /// Absolute path of the backup copy (None when the destination was new)
+    pub backup: Option<String>,
+    /// Error message when restoration failed
+    pub error: Option<String>,
+}
+
+/// Response for POST /changes/restore
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreResponse {
+    /// Workspace ID
+    pub workspace_id: String,
+    /// The git ref files were restored from
+    pub git_ref: String,
+    /// The project directory files were restored into
+    pub project_path: String,
+    /// Per-file outcomes
+    pub files: Vec<RestoredFile>,
+    /// Number of files restored successfully
+    pub restored: usize,
+    /// Number of files that failed
+    pub failed: usize,
+}
+
+/// Restore file contents from a checkpoint ref into a project directory.
+///
+/// Returns Err for setup problems (missing project dir); individual file
+/// failures (path absent at the ref, write errors) are reported per-file
+/// so one bad path doesn't abort the rest.
+pub fn restore_files(
+    workspace_id: &str,
+    git_dir: std::path::PathBuf,
+    git_ref: &str,
+    paths: &[String],
+    project_path: &str,
+) -> Result<RestoreResponse, String> {
+    let project_root = Path::new(project_path);
+    if !project_root.is_dir() {
+        return Err(format!(
+            "Project path '{}' does not exist or is not a directory",
+            project_path
+        ));
+    }
+
+    // One shared suffix per run so a repeated restore doesn't clobber the
+    // previous run's backups
+    let backup_suffix = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
+
+    let contents = super::discovery::get_file_contents(&git_dir, git_ref, paths);
+
+    let mut files = Vec::new();
+    for file in contents {
+        let dest = project_root.join(&file.path);
+        let dest_str = dest.to_string_lossy().to_string();
+
+        let content = match file.content {
+            Some(c) => c,
+            None => {
+                files.push(RestoredFile {
+                    path: file.path,
+                    dest: dest_str,
+                    restored: false,
+                    backup: None,
+                    error: file
+                        .error
+                        .or_else(|| Some("File not found at ref".to_string())),
+                });
+                continue;
+            }
+        };
+
+        // Back up the current file before overwriting it
+        let mut backup = None;
+        if dest.exists() {
+            let backup_path = format!("{}.{}.bak", dest_str, backup_suffix);
+            if let Err(e) = std::fs::copy(&dest, &backup_path) {
+                files.push(RestoredFile {
+                    path: file.path,
+                    dest: dest_str,
+                    restored: false,
+                    backup: None,
+                    error: Some(format!("Failed to create backup: {}", e)),
+                });
+                continue;
+            }
+            backup = Some(backup_path);
+        } else if let Some(parent) = dest.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                files.push(RestoredFile {
+                    path: file.path,
+                    dest: dest_str,
+                    restored: false,
+                    backup: None,
+                    error: Some(format!("Failed to create parent directory: {}", e)),
+                });
+                continue;
+            }
+        }
+
+        match std::fs::write(&dest, &content) {
+            Ok(_) => files.push(RestoredFile {
+                path: file.path,
+                dest: dest_str,
+                restored: true,
+                backup,
+                error: None,
+            }),
+            Err(e) => files.push(RestoredFile {
+                path: file.path,
+                dest: dest_str,
+                restored: false,
+                backup,
+                error: Some(format!("Failed to write file: {}", e)),
+            }),
+        }
+    }
+
+    let restored = files.iter().filter(|f| f.restored).count();
+    let failed = files.len() - restored;
+
+    log::info!(
+        "Restore from {} into {}: {} restored, {} failed",
+        git_ref, project_path, restored, failed
+    );
+
+    Ok(RestoreResponse {
+        workspace_id: workspace_id.to_string(),
+        git_ref: git_ref.to_string(),
+        project_path: project_path.to_string(),
+        files,
+        restored,
+        failed,
+    })
+}
@@ -0,0 +1,262 @@
+//! LLM diff summarization.
+//!
+//! Chunks a task's unified patch into whole `diff --git` sections, runs
+//! each chunk through the configured Gemini provider and merges the
+//! replies into one structured summary (per-file intent + risk flags).
+//! The result is cached on disk via [`super::cache`] so /latest and Jira
+//! reporting can reuse it without re-running inference.
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use serde::Deserialize;
+
+use super::types::{FileSummary, TaskDiffSummary};
+use crate::api::retry;
+use crate::state::AppState;
+
+/// Target upper bound per inference call — keeps chunks well inside the
+/// model's context window while batching small files together.
+const MAX_CHUNK_BYTES: usize = 60_000;
+
+/// The JSON shape we ask the model to reply with (per chunk).
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ChunkReply {
+    #[serde(default)]
+    overview: String,
+    #[serde(default)]
+    files: Vec<FileSummary>,
+}
+
+/// Summarize a task's full patch via the configured provider.
+///
+/// Runs one inference call per chunk and merges the results; the chunk
+/// overviews are joined into the task overview. Errors are provider or
+/// parse failures — an empty patch should be rejected by the caller
+/// before getting here.
+pub async fn summarize_task_diff(
+    state: &Arc<AppState>,
+    task_id: &str,
+    workspace_id: &str,
+    model: &str,
+    patch: &str,
+) -> Result<TaskDiffSummary, String> {
+    let chunks = chunk_patch(patch);
+    log::info!(
+        "Summarizing task {} diff: {} bytes patch in {} chunk(s) via {}",
+        task_id,
+        patch.len(),
+        chunks.len(),
+        model
+    );
+
+    let mut overviews: Vec<String> = Vec::new();
+    let mut files: Vec<FileSummary> = Vec::new();
+
+    for (i, chunk) in chunks.iter().enumerate() {
+        let reply = call_provider(state, model, task_id, i, chunks.len(), chunk).await?;
+        if !reply.overview.trim().is_empty() {
+            overviews.push(reply.overview.trim().to_string());
+        }
+        files.extend(reply.files);
+    }
+
+    Ok(TaskDiffSummary {
+        task_id: task_id.to_string(),
+        workspace_id: workspace_id.to_string(),
+        model: model.to_string(),
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        overview: overviews.join(" "),
+        files,
+    })
+}
+
+/// Split a patch into chunks of whole `diff --git` sections, batching
+/// sections together up to [`MAX_CHUNK_BYTES`]. A single oversized section
+/// becomes its own (truncated) chunk rather than being dropped.
+fn chunk_patch(patch: &str) -> Vec<String> {
+    let mut sections: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for line in patch.split_inclusive('\n') {
+        if line.starts_with("diff --git ") && !current.is_empty() {
+            sections.push(std::mem::take(&mut current));
+        }
+        current.push_str(line);
+    }
+    if !current.is_empty() {
+        sections.push(current);
+    }
+
+    let mut chunks: Vec<String> = Vec::new();
+    let mut chunk = String::new();
+    for mut section in sections {
+        if section.len() > MAX_CHUNK_BYTES {
+            // Cut at a char boundary and note the truncation for the model
+            let mut cut = MAX_CHUNK_BYTES;
+            while !section.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            section.truncate(cut);
+            section.push_str("\n[... section truncated ...]\n");
+        }
+        if !chunk.is_empty() && chunk.len() + section.len() > MAX_CHUNK_BYTES {
+            chunks.push(std::mem::take(&mut chunk));
+        }
+        chunk.push_str(&section);
+    }
+    if !chunk.is_empty() {
+        chunks.push(chunk);
+    }
+    chunks
+}
+
+/// Run one chunk through the Gemini API and parse the structured reply.
+async fn call_provider(
+    state: &Arc<AppState>,
+    model: &str,
+    task_id: &str,
+    chunk_index: usize,
+    chunk_count: usize,
+    chunk: &str,
+) -> Result<ChunkReply, String> {
+    let start_time = Instant::now();
+
+    let prompt = format!(
+        "You are reviewing part {} of {} of a unified diff produced by an AI coding assistant.\n\
+         For each file in the diff, describe in one sentence what the change does (its intent), \
+         and list any risk flags (short kebab-case tags such as \"deletes-code\", \"touches-auth\", \
+         \"schema-change\", \"large-change\", \"binary-file\"; empty list when nothing stands out).\n\
+         Reply with STRICT JSON only, no markdown, in exactly this shape:\n\
+         {{\"overview\": \"<one or two sentences covering this part>\", \
+         \"files\": [{{\"path\": \"<path>\", \"intent\": \"<sentence>\", \"riskFlags\": []}}]}}\n\n\
+         {}",
+        chunk_index + 1,
+        chunk_count,
+        chunk
+    );
+
+    let body = serde_json::json!({
+        "contents": [{
+            "role": "user",
+            "parts": [{ "text": prompt }]
+        }]
+    });
+
+    let client = crate::http::outbound_client();
+    let url = format!(
+        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
+        model,
+        state.gemini_api_key()
+    );
+
+    let retry_policy = retry::RetryPolicy::from_config();
+    let outcome = retry::send_with_retry(
+        || {
+            client
+                .post(&url)
+                .header("Content-Type", "application/json")
+                .json(&body)
+        },
+        &retry_policy,
+    )
+    .await;
+    let retry_count = Some(outcome.retries);
+
+    let log_failure = |error: String, status: Option<u16>, elapsed: u64| {
+        state.add_inference_log(
+            "gemini".to_string(),
+            model.to_string(),
+            "summarize".to_string(),
+            false,
+            status,
+            elapsed,
+            None, None, None,
+            Some(error),
+            None,
+            Some(format!("task {} chunk {}/{}", task_id, chunk_index + 1, chunk_count)),
+            None,
+            retry_count,
+        );
+    };
+
+    let response = outcome.result.map_err(|e| {
+        let msg = format!("Failed to call Gemini API: {}", e);
+        log_failure(msg.clone(), None, start_time.elapsed().as_millis() as u64);
+        msg
+    })?;
+
+    let status = response.status();
+    let response_text = response.text().await.map_err(|e| {
+        let msg = format!("Failed to read Gemini response: {}", e);
+        log_failure(msg.clone(), Some(status.as_u16()), start_time.elapsed().as_millis() as u64);
+        msg
+    })?;
+
+    if !status.is_success() {
+        let msg = format!("Gemini API error ({}): {}", status, response_text);
+        log_failure(msg.clone(), Some(status.as_u16()), start_time.elapsed().as_millis() as u64);
+        return Err(msg);
+    }
+
+    let value: serde_json::Value = serde_json::from_str(&response_text).map_err(|e| {
+        let msg = format!("Failed to parse Gemini response: {}", e);
+        log_failure(msg.clone(), Some(status.as_u16()), start_time.elapsed().as_millis() as u64);
+        msg
+    })?;
+
+    let text = value["candidates"][0]["content"]["parts"]
+        .as_array()
+        .map(|parts| {
+            parts
+                .iter()
+                .filter_map(|p| p["text"].as_str())
+                .collect::<Vec<_>>()
+                .join("")
+        })
+        .unwrap_or_default();
+
+    let reply = parse_reply(&text).ok_or_else(|| {
+        let msg = format!(
+            "Model did not return parseable JSON for task {} chunk {}/{}",
+            task_id,
+            chunk_index + 1,
+            chunk_count
+        );
+        log_failure(msg.clone(), Some(status.as_u16()), start_time.elapsed().as_millis() as u64);
+        msg
+    })?;
+
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+    state.add_inference_log(
+        "gemini".to_string(),
+        model.to_string(),
+        "summarize".to_string(),
+        true,
+        Some(200),
+        duration_ms,
+        None, None, None,
+        None,
+        None,
+        Some(format!("task {} chunk {}/{}", task_id, chunk_index + 1, chunk_count)),
+        Some(serde_json::json!({
+            "chunk_bytes": chunk.len(),
+            "files_summarized": reply.files.len(),
+        })),
+        retry_count,
+    );
+
+    Ok(reply)
+}
+
+/// Parse the model's reply, tolerating markdown code fences around the JSON.
+fn parse_reply(text: &str) -> Option<ChunkReply> {
+    let trimmed = text.trim();
+    let inner = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|s| s.strip_suffix("```"))
+        .unwrap_or(trimmed)
+        .trim();
+    serde_json::from_str::<ChunkReply>(inner).ok()
+}
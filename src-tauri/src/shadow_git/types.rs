@@ -14,6 +14,10 @@ pub struct WorkspaceInfo {
     pub task_count: usize,
     /// ISO 8601 timestamp of the most recent checkpoint commit in this workspace
     pub last_modified: String,
+    /// On-disk size of the shadow repo in bytes (0 in listings cached
+    /// before this field existed)
+    #[serde(default)]
+    pub size_bytes: u64,
 }
 
 /// Response for GET /changes/workspaces
@@ -91,7 +95,18 @@ pub struct DiffFile {
     /// Lines removed
     pub lines_removed: usize,
     /// File status
-    pub status: String, // "added" | "modified" | "deleted" | "renamed"
+    pub status: String, // "added" | "modified" | "deleted" | "renamed" | "copied"
+    /// Previous path for renamed or copied files (None otherwise);
+    /// `path` always holds the current/new path
+    #[serde(default)]
+    pub old_path: Option<String>,
+    /// True for binary files (numstat reports `-` instead of line counts)
+    #[serde(default)]
+    pub binary: bool,
+    /// Byte-size delta (new - old) for binary files; None for text files
+    /// and on the CLI fallback path, where numstat carries no sizes
+    #[serde(default)]
+    pub bytes_delta: Option<i64>,
 }
 
 /// Full diff result for a step or task
@@ -108,6 +123,260 @@ pub struct DiffResult {
     pub to_ref: String,
     /// The actual git commands that were executed to produce this diff
     pub git_commands: Vec<String>,
+    /// Intraline change ranges per changed line — only populated when the
+    /// request asked for `?granularity=word`, null otherwise
+    #[serde(default)]
+    pub intraline: Option<Vec<IntralineLine>>,
+}
+
+/// One changed character range within a line (byte offsets, end exclusive)
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IntralineRange {
+    /// Start offset within the line
+    pub start: usize,
+    /// End offset within the line (exclusive)
+    pub end: usize,
+}
+
+/// Intraline (word-level) highlight data for one changed line in a diff
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IntralineLine {
+    /// File path the line belongs to
+    pub path: String,
+    /// Which version the line number refers to: "old" or "new"
+    pub side: String,
+    /// Line number (1-based) in the old file for removed lines, in the
+    /// new file for added lines
+    pub line_number: usize,
+    /// Changed token ranges within the line (whole line when the line has
+    /// no counterpart to compare against)
+    pub ranges: Vec<IntralineRange>,
+}
+
+/// One step in a file's evolution (a checkpoint commit that touched the file)
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FileHistoryEntry {
+    /// Step index (1-based, chronological order)
+    pub step_index: usize,
+    /// 40-char commit SHA of the step
+    pub hash: String,
+    /// ISO 8601 timestamp of the step commit
+    pub timestamp: String,
+    /// Lines added to the file in this step
+    pub lines_added: usize,
+    /// Lines removed from the file in this step
+    pub lines_removed: usize,
+    /// File status in this step ("added" | "modified" | "deleted" | "renamed")
+    pub status: String,
+    /// API path for the per-step patch of this file
+    pub patch_url: String,
+}
+
+/// Response for GET /changes/tasks/:taskId/files/:path/history
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FileHistoryResponse {
+    /// Task ID
+    pub task_id: String,
+    /// Workspace ID
+    pub workspace_id: String,
+    /// File path relative to repo root
+    pub path: String,
+    /// Steps where the file changed (chronological, oldest first)
+    pub entries: Vec<FileHistoryEntry>,
+    /// Total steps in the task (for context — entries only cover touching steps)
+    pub total_steps: usize,
+}
+
+/// Checkpoint activity totals for one calendar day
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityDay {
+    /// The day (YYYY-MM-DD, commit author local date)
+    pub date: String,
+    /// Checkpoint commits made that day
+    pub commits: usize,
+    /// Distinct tasks with at least one checkpoint that day
+    pub tasks: usize,
+    /// Lines added across that day's checkpoints
+    pub lines_added: usize,
+    /// Lines removed across that day's checkpoints
+    pub lines_removed: usize,
+}
+
+/// Response for GET /changes/workspaces/:id/activity
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityResponse {
+    /// Workspace ID
+    pub workspace_id: String,
+    /// Per-day buckets, oldest first (days without checkpoints are omitted)
+    pub days: Vec<ActivityDay>,
+    /// Total checkpoint commits across all days
+    pub total_commits: usize,
+    /// Total lines added
+    pub total_lines_added: usize,
+    /// Total lines removed
+    pub total_lines_removed: usize,
+}
+
+/// One line of a blamed file, attributed to the step that introduced it
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BlameLine {
+    /// Line number (1-based) in the file's final state
+    pub line_number: usize,
+    /// The line text
+    pub content: String,
+    /// Commit hash that introduced the line
+    pub hash: String,
+    /// Step index (1-based) that introduced the line — None when the line
+    /// predates the task (inherited from an earlier task or import)
+    pub step_index: Option<usize>,
+    /// Subtask index (0-based) whose step range contains the introducing
+    /// step — None without subtask data or for pre-task lines
+    pub subtask: Option<usize>,
+}
+
+/// Response for GET /changes/tasks/:taskId/blame
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BlameResponse {
+    /// Task ID
+    pub task_id: String,
+    /// Workspace ID
+    pub workspace_id: String,
+    /// File path relative to repo root
+    pub path: String,
+    /// The commit the file state was taken from (the task's last step)
+    pub git_ref: String,
+    /// Total steps in the task
+    pub total_steps: usize,
+    /// One entry per line of the file
+    pub lines: Vec<BlameLine>,
+}
+
+/// One entry in a tree listing (file or directory at a checkpoint ref)
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeEntry {
+    /// Entry name (no path components)
+    pub name: String,
+    /// Full path relative to repo root
+    pub path: String,
+    /// Entry kind: "dir" or "file"
+    pub kind: String,
+    /// Git file mode in octal (e.g. "100644", "040000")
+    pub mode: String,
+    /// Blob size in bytes (None for directories)
+    pub size: Option<usize>,
+}
+
+/// Response for GET /changes/tree
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TreeResponse {
+    /// Workspace ID
+    pub workspace_id: String,
+    /// The git ref the snapshot was read from
+    pub git_ref: String,
+    /// The directory that was listed ("" for the repo root)
+    pub path: String,
+    /// Entries at this level (directories first, then files, alphabetical)
+    pub entries: Vec<TreeEntry>,
+}
+
+/// One content search match within a checkpoint snapshot
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchMatch {
+    /// File path relative to repo root
+    pub path: String,
+    /// Line number of the match (1-based)
+    pub line_number: usize,
+    /// The matched line (trimmed, truncated to 500 chars)
+    pub line: String,
+}
+
+/// Response for GET /changes/search
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResponse {
+    /// Workspace ID
+    pub workspace_id: String,
+    /// The git ref the snapshot was searched at
+    pub git_ref: String,
+    /// The query string (matched as a fixed string, not a regex)
+    pub query: String,
+    /// Matches in tree order
+    pub matches: Vec<SearchMatch>,
+    /// True when the match cap was hit and the search stopped early
+    pub truncated: bool,
+}
+
+/// LLM-generated summary of one file's change within a task diff
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FileSummary {
+    /// File path relative to repo root
+    pub path: String,
+    /// One-sentence description of what the change to this file does
+    #[serde(default)]
+    pub intent: String,
+    /// Risk flags raised by the model (e.g. "deletes-code", "touches-auth",
+    /// "schema-change") — empty when nothing stood out
+    #[serde(default)]
+    pub risk_flags: Vec<String>,
+}
+
+/// Structured LLM summary of a whole task diff
+///
+/// Cached on disk next to the other shadow_git caches so /latest and Jira
+/// reporting can reuse it without re-running inference.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskDiffSummary {
+    /// Task ID the summary describes
+    pub task_id: String,
+    /// Workspace ID
+    pub workspace_id: String,
+    /// Model that produced the summary
+    pub model: String,
+    /// ISO 8601 timestamp of when the summary was generated
+    pub generated_at: String,
+    /// High-level description of what the task changed
+    pub overview: String,
+    /// Per-file intent and risk flags
+    pub files: Vec<FileSummary>,
+}
+
+/// Request body for POST /changes/tasks/:taskId/summarize
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SummarizeRequest {
+    /// Workspace ID (optional — auto-linked from the task when omitted)
+    #[serde(default)]
+    pub workspace: Option<String>,
+    /// Model to use (defaults to "gemini-2.0-flash")
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Regenerate even when a cached summary exists
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+/// Response for POST /changes/tasks/:taskId/summarize
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SummarizeResponse {
+    /// True when the summary came from the disk cache instead of a fresh
+    /// inference run
+    pub cached: bool,
+    /// The structured summary
+    pub summary: TaskDiffSummary,
 }
 
 /// Content of a single file retrieved from the shadow git repo
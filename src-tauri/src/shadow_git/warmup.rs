@@ -0,0 +1,127 @@
+//! Background cache warming for shadow git discovery and history.
+//!
+//! Runs once shortly after REST server startup: discovers checkpoint
+//! workspaces, enumerates tasks for the most recently modified ones,
+//! pre-parses steps for the newest tasks, then primes the conversation
+//! history task index. First UI load and /latest then hit warm caches
+//! instead of triggering cold scans.
+//!
+//! Configurable via environment:
+//! - `XRAY_CACHE_WARM=0` disables warming entirely
+//! - `XRAY_CACHE_WARM_WORKSPACES` — newest workspaces to enumerate tasks
+//!   for (default 3)
+//! - `XRAY_CACHE_WARM_TASKS` — newest tasks per workspace to pre-parse
+//!   steps for (default 5)
+
+use super::types::{StepsResponse, TasksResponse, WorkspacesResponse};
+use super::{cache, discovery};
+
+const DEFAULT_WARM_WORKSPACES: usize = 3;
+const DEFAULT_WARM_TASKS: usize = 5;
+
+fn env_usize(name: &str, default: usize) -> usize {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(default)
+}
+
+/// Spawn the one-shot background cache warmer.
+///
+/// Spawned once at REST server startup, next to the retention scheduler.
+pub fn spawn_cache_warmer() {
+    let disabled = std::env::var("XRAY_CACHE_WARM")
+        .map(|v| v == "0" || v.eq_ignore_ascii_case("false"))
+        .unwrap_or(false);
+    if disabled {
+        log::info!("Cache warmer: disabled via XRAY_CACHE_WARM");
+        return;
+    }
+    if !crate::config::current().cache.warmup_enabled {
+        log::info!("Cache warmer: disabled via config.toml ([cache] warmup_enabled)");
+        return;
+    }
+
+    tokio::spawn(async {
+        // Let startup finish before hitting the filesystem
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+
+        match tokio::task::spawn_blocking(warm_shadow_git).await {
+            Ok((workspaces, tasks, steps)) => log::info!(
+                "Cache warmer: warmed {} workspaces, {} task lists, steps for {} tasks",
+                workspaces, tasks, steps
+            ),
+            Err(e) => log::error!("Cache warmer: shadow git warming panicked: {}", e),
+        }
+
+        // Prime the history task index (instant when the disk cache is
+        // already current)
+        match crate::conversation_history::handlers::index::get_or_refresh_task_index(false).await
+        {
+            Ok(index) => log::info!(
+                "Cache warmer: history task index ready ({} tasks)",
+                index.total_tasks
+            ),
+            Err((_, e)) => {
+                log::warn!("Cache warmer: history index refresh failed: {}", e.error)
+            }
+        }
+    });
+    log::info!("Cache warmer: spawned (one-shot)");
+}
+
+/// Warm the shadow git caches — returns (workspaces, task lists, step
+/// lists) counts for the log line. Also run periodically by the
+/// scheduler's `cache_warm` job.
+pub(crate) fn warm_shadow_git() -> (usize, usize, usize) {
+    let workspaces = discovery::find_workspaces();
+    let root = discovery::checkpoints_root()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|| "N/A".to_string());
+    let response = WorkspacesResponse {
+        workspaces,
+        checkpoints_root: root,
+    };
+    super::handlers::seed_workspaces_cache(&response);
+    cache::save_workspaces(&response);
+
+    // Newest workspaces first — ISO 8601 strings compare chronologically
+    let mut ws_sorted: Vec<_> = response.workspaces.iter().filter(|w| w.active).collect();
+    ws_sorted.sort_by(|a, b| b.last_modified.cmp(&a.last_modified));
+
+    let warm_workspaces = env_usize("XRAY_CACHE_WARM_WORKSPACES", DEFAULT_WARM_WORKSPACES);
+    let warm_tasks = env_usize("XRAY_CACHE_WARM_TASKS", DEFAULT_WARM_TASKS);
+
+    let mut task_lists = 0usize;
+    let mut step_lists = 0usize;
+
+    for ws in ws_sorted.into_iter().take(warm_workspaces) {
+        let git_dir = std::path::PathBuf::from(&ws.git_dir);
+        let mut tasks = discovery::list_tasks_for_workspace(&ws.id, &git_dir);
+        let tasks_response = TasksResponse {
+            workspace_id: ws.id.clone(),
+            tasks: tasks.clone(),
+        };
+        super::handlers::seed_tasks_cache(&ws.id, &tasks_response);
+        cache::save_tasks(&ws.id, &tasks_response);
+        task_lists += 1;
+
+        tasks.sort_by(|a, b| b.last_modified.cmp(&a.last_modified));
+        for task in tasks.into_iter().take(warm_tasks) {
+            let steps = discovery::list_steps_for_task(&task.task_id, &ws.id, &git_dir);
+            if steps.is_empty() {
+                continue;
+            }
+            let steps_response = StepsResponse {
+                task_id: task.task_id.clone(),
+                workspace_id: ws.id.clone(),
+                steps,
+            };
+            super::handlers::seed_steps_cache(&ws.id, &task.task_id, &steps_response);
+            cache::save_steps(&ws.id, &task.task_id, &steps_response);
+            step_lists += 1;
+        }
+    }
+
+    (response.workspaces.len(), task_lists, step_lists)
+}
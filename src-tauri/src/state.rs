@@ -14,6 +14,12 @@ pub struct AccessLogEntry {
     pub status_code: u16,
     pub duration_ms: u64,
     pub client_ip: String,
+    /// User-Agent header of the request, when present
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    /// Short fingerprint of the bearer token used (never the token itself)
+    #[serde(default)]
+    pub client_key_id: Option<String>,
 }
 
 /// Single inference log entry for AI model calls
@@ -36,6 +42,63 @@ pub struct InferenceLogEntry {
     pub system_prompt: Option<String>,
     pub user_message_preview: Option<String>, // First 100 chars of user message
     pub metadata: Option<serde_json::Value>,  // For any additional details
+    /// Retries spent before this outcome (0 = first attempt)
+    #[serde(default)]
+    pub retry_count: Option<u32>,
+}
+
+/// Cap on each stored request/response body (bytes). Bodies beyond this are
+/// truncated with the corresponding `*Truncated` flag set.
+const MAX_EXCHANGE_BODY_BYTES: usize = 64 * 1024;
+/// Full request/response capture for one inference log entry.
+///
+/// Stored separately from [`InferenceLogEntry`] (which keeps only previews)
+/// and only when `logging.capture_inference_bodies` is enabled. Bodies are
+/// size-capped and the provider API key is redacted before storage.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct InferenceExchange {
+    /// ID of the inference log entry this exchange belongs to
+    pub log_id: u64,
+    /// Request URL with any API key query parameter redacted
+    pub request_url: String,
+    /// Full request body sent to the provider (JSON)
+    pub request_body: String,
+    /// True if the request body was clipped at the size cap
+    pub request_truncated: bool,
+    /// Full response body returned by the provider
+    pub response_body: String,
+    /// True if the response body was clipped at the size cap
+    pub response_truncated: bool,
+}
+
+/// Clip a body at [`MAX_EXCHANGE_BODY_BYTES`] on a char boundary.
+fn cap_exchange_body(mut body: String) -> (String, bool) {
+    if body.len() <= MAX_EXCHANGE_BODY_BYTES {
+        return (body, false);
+    }
+    let mut cut = MAX_EXCHANGE_BODY_BYTES;
+    while !body.is_char_boundary(cut) {
+        cut -= 1;
+    }
+    body.truncate(cut);
+    (body, true)
+}
+
+/// Redact the value of a `key=` query parameter (Gemini passes the API key
+/// in the URL).
+fn redact_url_key(url: &str) -> String {
+    match url.find("key=") {
+        Some(start) => {
+            let value_start = start + "key=".len();
+            let value_end = url[value_start..]
+                .find('&')
+                .map(|i| value_start + i)
+                .unwrap_or(url.len());
+            format!("{}***{}", &url[..value_start], &url[value_end..])
+        }
+        None => url.to_string(),
+    }
 }
 
 /// Shared application state for the REST server
@@ -49,9 +112,10 @@ pub struct AppState {
     pub jira_email: String,
     pub jira_api_token: String,
     
-    // Gemini API configuration
-    pub gemini_api_key: String,
-    
+    // Gemini API configuration. Behind a lock so PUT /system/providers can
+    // rotate the key at runtime; read via [`gemini_api_key`](Self::gemini_api_key).
+    gemini_api_key: RwLock<String>,
+
     // Access log storage
     access_log: RwLock<Vec<AccessLogEntry>>,
     log_counter: RwLock<u64>,
@@ -59,6 +123,9 @@ pub struct AppState {
     // Inference log storage
     inference_log: RwLock<Vec<InferenceLogEntry>>,
     inference_log_counter: RwLock<u64>,
+
+    // Full request/response captures, keyed by inference log entry ID
+    inference_exchanges: RwLock<Vec<InferenceExchange>>,
 }
 
 impl AppState {
@@ -76,17 +143,30 @@ impl AppState {
             jira_base_url,
             jira_email,
             jira_api_token,
-            gemini_api_key,
+            gemini_api_key: RwLock::new(gemini_api_key),
             access_log: RwLock::new(Vec::new()),
             log_counter: RwLock::new(0),
             inference_log: RwLock::new(Vec::new()),
             inference_log_counter: RwLock::new(0),
+            inference_exchanges: RwLock::new(Vec::new()),
         })
     }
 
     /// Verify Bearer token
+    /// Verify a bearer token in constant time.
+    ///
+    /// Accumulates byte differences instead of short-circuiting so response
+    /// timing doesn't leak how many leading bytes matched. Length is checked
+    /// after the scan for the same reason.
     pub fn verify_token(&self, token: &str) -> bool {
-        self.auth_token == token
+        let expected = self.auth_token.as_bytes();
+        let presented = token.as_bytes();
+        let mut diff: u8 = (expected.len() != presented.len()) as u8;
+        for i in 0..expected.len() {
+            let b = presented.get(i % presented.len().max(1)).copied().unwrap_or(0);
+            diff |= expected[i] ^ b;
+        }
+        diff == 0 && !expected.is_empty()
     }
 
     /// Get server uptime in seconds
@@ -94,17 +174,31 @@ impl AppState {
         self.start_time.elapsed().as_secs()
     }
 
-    /// Create a JiraClient from app state
-    pub fn create_jira_client(&self) -> crate::jira::JiraClient {
+    /// The currently effective Gemini API key
+    pub fn gemini_api_key(&self) -> String {
+        self.gemini_api_key.read().clone()
+    }
+
+    /// Swap in a new Gemini API key. Takes effect for every request built
+    /// after the swap; in-flight requests finish with the old key.
+    pub fn set_gemini_api_key(&self, key: String) {
+        *self.gemini_api_key.write() = key;
+    }
+
+    /// Create a JiraClient from app state, with per-call latency recorded
+    /// in the access log
+    pub fn create_jira_client(self: &Arc<Self>) -> crate::jira::JiraClient {
         crate::jira::JiraClient::new(
             self.jira_base_url.clone(),
             self.jira_email.clone(),
             self.jira_api_token.clone(),
         )
+        .with_access_log(self.clone())
     }
 
     /// Add an access log entry
-    pub fn add_access_log(&self, method: String, path: String, status_code: u16, duration_ms: u64, client_ip: String) {
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_access_log(&self, method: String, path: String, status_code: u16, duration_ms: u64, client_ip: String, user_agent: Option<String>, client_key_id: Option<String>) {
         let mut counter = self.log_counter.write();
         *counter += 1;
         let id = *counter;
@@ -117,15 +211,18 @@ impl AppState {
             status_code,
             duration_ms,
             client_ip,
+            user_agent,
+            client_key_id,
         };
 
         let mut log = self.access_log.write();
         log.push(entry);
         
-        // Keep only the last 1000 entries to prevent memory bloat
+        // Keep only the configured number of entries to prevent memory bloat
+        let cap = crate::config::current().logging.access_log_entries;
         let len = log.len();
-        if len > 1000 {
-            log.drain(0..len - 1000);
+        if len > cap {
+            log.drain(0..len - cap);
         }
     }
 
@@ -134,12 +231,29 @@ impl AppState {
         self.access_log.read().clone()
     }
 
+    /// Requests served in the last 60 seconds (from the access log)
+    pub fn requests_last_minute(&self) -> usize {
+        let cutoff = chrono::Utc::now() - chrono::Duration::seconds(60);
+        self.access_log
+            .read()
+            .iter()
+            .rev()
+            .take_while(|e| {
+                chrono::DateTime::parse_from_rfc3339(&e.timestamp)
+                    .map(|t| t.with_timezone(&chrono::Utc) >= cutoff)
+                    .unwrap_or(false)
+            })
+            .count()
+    }
+
     /// Clear access log
     pub fn clear_access_logs(&self) {
         self.access_log.write().clear();
     }
 
-    /// Add an inference log entry
+    /// Add an inference log entry, returning its assigned ID (for attaching
+    /// a full exchange capture via [`add_inference_exchange`](Self::add_inference_exchange))
+    #[allow(clippy::too_many_arguments)]
     pub fn add_inference_log(
         &self,
         provider: String,
@@ -155,7 +269,8 @@ impl AppState {
         system_prompt: Option<String>,
         user_message_preview: Option<String>,
         metadata: Option<serde_json::Value>,
-    ) {
+        retry_count: Option<u32>,
+    ) -> u64 {
         let mut counter = self.inference_log_counter.write();
         *counter += 1;
         let id = *counter;
@@ -176,16 +291,20 @@ impl AppState {
             system_prompt,
             user_message_preview,
             metadata,
+            retry_count,
         };
 
         let mut log = self.inference_log.write();
         log.push(entry);
         
-        // Keep only the last 500 entries to prevent memory bloat
+        // Keep only the configured number of entries to prevent memory bloat
+        let cap = crate::config::current().logging.inference_log_entries;
         let len = log.len();
-        if len > 500 {
-            log.drain(0..len - 500);
+        if len > cap {
+            log.drain(0..len - cap);
         }
+
+        id
     }
 
     /// Get all inference log entries
@@ -193,8 +312,62 @@ impl AppState {
         self.inference_log.read().clone()
     }
 
+    /// Get a single inference log entry by ID
+    pub fn get_inference_log(&self, id: u64) -> Option<InferenceLogEntry> {
+        self.inference_log.read().iter().find(|e| e.id == id).cloned()
+    }
+
     /// Clear inference log
     pub fn clear_inference_logs(&self) {
         self.inference_log.write().clear();
+        self.inference_exchanges.write().clear();
+    }
+
+    /// Store the full request/response bodies for an inference log entry.
+    ///
+    /// Bodies are clipped at the size cap and the API key is stripped from
+    /// the URL and bodies before anything is stored.
+    pub fn add_inference_exchange(
+        &self,
+        log_id: u64,
+        request_url: &str,
+        request_body: String,
+        response_body: String,
+    ) {
+        let api_key = self.gemini_api_key();
+        let redact = |body: String| {
+            if api_key.is_empty() {
+                body
+            } else {
+                body.replace(&api_key, "***")
+            }
+        };
+        let (request_body, request_truncated) = cap_exchange_body(redact(request_body));
+        let (response_body, response_truncated) = cap_exchange_body(redact(response_body));
+
+        let mut exchanges = self.inference_exchanges.write();
+        exchanges.push(InferenceExchange {
+            log_id,
+            request_url: redact_url_key(request_url),
+            request_body,
+            request_truncated,
+            response_body,
+            response_truncated,
+        });
+
+        let cap = crate::config::current().logging.inference_exchanges;
+        let len = exchanges.len();
+        if len > cap {
+            exchanges.drain(0..len - cap);
+        }
+    }
+
+    /// Get the full exchange for an inference log entry, if it was captured
+    pub fn get_inference_exchange(&self, log_id: u64) -> Option<InferenceExchange> {
+        self.inference_exchanges
+            .read()
+            .iter()
+            .find(|e| e.log_id == log_id)
+            .cloned()
     }
 }
@@ -0,0 +1,205 @@
+//! Per-tool rate budgets and quotas for ToolRuntime
+//!
+//! Budgets cap how often and how long a tool may run: max calls per minute,
+//! max calls per hour, and max cumulative execution time. They are checked in
+//! `ToolRuntime::call` before execution and tracked per tool. Unlike the
+//! circuit breaker (which reacts to failures), budgets bound successful usage.
+
+use super::{ToolRuntime, ToolRuntimeError};
+use serde::{Deserialize, Serialize};
+
+/// Budget limits for a tool (part of [`super::ToolConfig`])
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolBudget {
+    /// Maximum calls per rolling minute (None = unlimited)
+    pub max_calls_per_minute: Option<u32>,
+    /// Maximum calls per rolling hour (None = unlimited)
+    pub max_calls_per_hour: Option<u32>,
+    /// Maximum cumulative execution time in milliseconds (None = unlimited)
+    pub max_total_duration_ms: Option<u64>,
+}
+
+impl ToolBudget {
+    /// Whether any limit is actually set
+    pub fn is_limited(&self) -> bool {
+        self.max_calls_per_minute.is_some()
+            || self.max_calls_per_hour.is_some()
+            || self.max_total_duration_ms.is_some()
+    }
+}
+
+/// Tracked usage for one tool's budget
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetState {
+    /// Epoch-millisecond timestamps of calls within the last hour
+    #[serde(skip)]
+    pub call_times_ms: Vec<i64>,
+    /// Calls in the last rolling minute
+    pub calls_last_minute: u32,
+    /// Calls in the last rolling hour
+    pub calls_last_hour: u32,
+    /// Cumulative execution time across all calls (milliseconds)
+    pub total_duration_ms: u64,
+    /// Total calls recorded since startup or last reset
+    pub total_calls: u64,
+    /// ISO 8601 timestamp of the most recent call
+    pub last_call_at: Option<String>,
+}
+
+impl BudgetState {
+    /// Drop call timestamps older than one hour and refresh window counts.
+    pub fn refresh(&mut self, now_ms: i64) {
+        self.call_times_ms.retain(|t| now_ms - t < 3_600_000);
+        self.calls_last_hour = self.call_times_ms.len() as u32;
+        self.calls_last_minute = self
+            .call_times_ms
+            .iter()
+            .filter(|t| now_ms - **t < 60_000)
+            .count() as u32;
+    }
+
+    /// Check the refreshed state against a budget's limits.
+    pub fn check(&self, budget: &ToolBudget) -> Result<(), String> {
+        if let Some(max) = budget.max_calls_per_minute {
+            if self.calls_last_minute >= max {
+                return Err(format!("{} calls in the last minute (max {})", self.calls_last_minute, max));
+            }
+        }
+        if let Some(max) = budget.max_calls_per_hour {
+            if self.calls_last_hour >= max {
+                return Err(format!("{} calls in the last hour (max {})", self.calls_last_hour, max));
+            }
+        }
+        if let Some(max) = budget.max_total_duration_ms {
+            if self.total_duration_ms >= max {
+                return Err(format!("{}ms cumulative execution time (max {}ms)", self.total_duration_ms, max));
+            }
+        }
+        Ok(())
+    }
+
+    /// Record one call and its duration.
+    pub fn record(&mut self, now_ms: i64, duration_ms: u64) {
+        self.call_times_ms.push(now_ms);
+        self.total_calls += 1;
+        self.total_duration_ms += duration_ms;
+        self.last_call_at = Some(chrono::Utc::now().to_rfc3339());
+        self.refresh(now_ms);
+    }
+
+    /// Reset all tracked usage.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+impl ToolRuntime {
+    /// Check a tool's budget before execution
+    pub fn check_budget(&self, operation_id: &str) -> Result<(), ToolRuntimeError> {
+        let tool_config = self.get_tool_config(operation_id);
+        let Some(budget) = tool_config.budget else {
+            return Ok(());
+        };
+        if !budget.is_limited() {
+            return Ok(());
+        }
+
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let mut budgets = self.budgets.write();
+        let state = budgets.entry(operation_id.to_string()).or_default();
+        state.refresh(now_ms);
+
+        state.check(&budget).map_err(|reason| {
+            tracing::warn!("Budget exceeded for {}: {}", operation_id, reason);
+            ToolRuntimeError::BudgetExceeded(format!("{}: {}", operation_id, reason))
+        })
+    }
+
+    /// Record budget usage after a tool executed
+    pub fn record_budget_usage(&self, operation_id: &str, duration_ms: u64) {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let mut budgets = self.budgets.write();
+        let state = budgets.entry(operation_id.to_string()).or_default();
+        state.record(now_ms, duration_ms);
+    }
+
+    /// Get budget state for all tools (refreshed to the current windows)
+    pub fn get_budget_status(&self) -> std::collections::HashMap<String, BudgetState> {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let mut budgets = self.budgets.write();
+        for state in budgets.values_mut() {
+            state.refresh(now_ms);
+        }
+        budgets.clone()
+    }
+
+    /// Reset all budget usage counters
+    pub fn reset_budgets(&self) {
+        self.budgets.write().clear();
+    }
+
+    /// Reset budget usage for a specific tool
+    pub fn reset_budget(&self, operation_id: &str) {
+        if let Some(state) = self.budgets.write().get_mut(operation_id) {
+            state.reset();
+            tracing::info!("Budget for {} manually reset", operation_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_allows_under_limit() {
+        let budget = ToolBudget {
+            max_calls_per_minute: Some(3),
+            ..Default::default()
+        };
+        let mut state = BudgetState::default();
+        let now = 1_000_000;
+
+        state.record(now, 10);
+        state.record(now + 1, 10);
+        assert!(state.check(&budget).is_ok());
+    }
+
+    #[test]
+    fn test_budget_blocks_per_minute() {
+        let budget = ToolBudget {
+            max_calls_per_minute: Some(2),
+            ..Default::default()
+        };
+        let mut state = BudgetState::default();
+        let now = 1_000_000;
+
+        state.record(now, 10);
+        state.record(now + 1, 10);
+        assert!(state.check(&budget).is_err());
+
+        // Calls age out of the rolling minute
+        state.refresh(now + 61_000);
+        assert!(state.check(&budget).is_ok());
+    }
+
+    #[test]
+    fn test_budget_blocks_cumulative_duration() {
+        let budget = ToolBudget {
+            max_total_duration_ms: Some(100),
+            ..Default::default()
+        };
+        let mut state = BudgetState::default();
+
+        state.record(1_000, 60);
+        assert!(state.check(&budget).is_ok());
+
+        state.record(2_000, 60);
+        assert!(state.check(&budget).is_err());
+
+        state.reset();
+        assert!(state.check(&budget).is_ok());
+    }
+}
@@ -1,6 +1,9 @@
 //! Circuit breaker and rate limiting for ToolRuntime
 //!
-//! Protects against cascading failures and rate limit exhaustion.
+//! Protects against cascading failures and rate limit exhaustion. Failure
+//! threshold, cool-down and half-open probe count are configurable per tool
+//! (falling back to the global config), and state transitions are published
+//! to the `/latest/stream` event stream.
 
 use super::{ToolRuntime, ToolRuntimeError};
 use serde::{Deserialize, Serialize};
@@ -80,8 +83,9 @@ impl CircuitBreakerState {
         }
     }
 
-    /// Record a successful call
-    pub fn record_success(&mut self) {
+    /// Record a successful call; close the breaker after `half_open_probes`
+    /// consecutive successes in the half-open state
+    pub fn record_success(&mut self, half_open_probes: u32) {
         self.total_calls += 1;
         
         match self.state {
@@ -90,8 +94,8 @@ impl CircuitBreakerState {
             }
             CircuitState::HalfOpen => {
                 self.success_count += 1;
-                // After a few successes, close the circuit
-                if self.success_count >= 3 {
+                // After enough successful probes, close the circuit
+                if self.success_count >= half_open_probes {
                     self.state = CircuitState::Closed;
                     self.failure_count = 0;
                     self.success_count = 0;
@@ -177,24 +181,32 @@ impl CircuitBreakerState {
 }
 
 impl ToolRuntime {
+    /// Resolve the breaker cool-down for a tool (per-tool override or global)
+    fn breaker_cooldown_ms(&self, operation_id: &str) -> u64 {
+        self.get_tool_config(operation_id)
+            .cooldown_ms
+            .unwrap_or_else(|| self.get_global_config().circuit_breaker_reset_ms)
+    }
+
     /// Check if a tool call should be allowed (circuit breaker)
     pub fn check_circuit_breaker(&self, operation_id: &str) -> Result<(), ToolRuntimeError> {
         let circuit_breakers = self.circuit_breakers.read();
-        let global_config = self.get_global_config();
+        let cooldown_ms = self.breaker_cooldown_ms(operation_id);
         
         if let Some(state) = circuit_breakers.get(operation_id) {
-            if !state.should_allow(global_config.circuit_breaker_reset_ms) {
+            if !state.should_allow(cooldown_ms) {
                 return Err(ToolRuntimeError::CircuitBreakerOpen(operation_id.to_string()));
             }
             
             // Transition from open to half-open if reset time passed
-            if state.state == CircuitState::Open && state.should_allow(global_config.circuit_breaker_reset_ms) {
+            if state.state == CircuitState::Open && state.should_allow(cooldown_ms) {
                 drop(circuit_breakers);
                 let mut circuit_breakers = self.circuit_breakers.write();
                 if let Some(state) = circuit_breakers.get_mut(operation_id) {
                     state.state = CircuitState::HalfOpen;
                     state.success_count = 0;
                     tracing::info!("Circuit breaker for {} transitioned to half-open", operation_id);
+                    Self::publish_transition(operation_id, CircuitState::Open, CircuitState::HalfOpen);
                 }
             }
         }
@@ -204,24 +216,7 @@ impl ToolRuntime {
 
     /// Update circuit breaker state after a call
     pub fn update_circuit_breaker(&self, operation_id: &str, success: bool) {
-        let tool_config = self.get_tool_config(operation_id);
-        let global_config = self.get_global_config();
-        
-        let max_failures = tool_config
-            .max_failures
-            .unwrap_or(global_config.default_max_failures);
-        let reset_ms = global_config.circuit_breaker_reset_ms;
-
-        let mut circuit_breakers = self.circuit_breakers.write();
-        let state = circuit_breakers
-            .entry(operation_id.to_string())
-            .or_insert_with(CircuitBreakerState::new);
-
-        if success {
-            state.record_success();
-        } else {
-            state.record_failure(max_failures, reset_ms, None);
-        }
+        self.update_circuit_breaker_with_reason(operation_id, success, None);
     }
 
     /// Update circuit breaker with failure reason
@@ -237,18 +232,48 @@ impl ToolRuntime {
         let max_failures = tool_config
             .max_failures
             .unwrap_or(global_config.default_max_failures);
-        let reset_ms = global_config.circuit_breaker_reset_ms;
+        let reset_ms = tool_config
+            .cooldown_ms
+            .unwrap_or(global_config.circuit_breaker_reset_ms);
+        let half_open_probes = tool_config
+            .half_open_probes
+            .unwrap_or(global_config.half_open_probes);
 
         let mut circuit_breakers = self.circuit_breakers.write();
         let state = circuit_breakers
             .entry(operation_id.to_string())
             .or_insert_with(CircuitBreakerState::new);
 
+        let before = state.state;
         if success {
-            state.record_success();
+            state.record_success(half_open_probes);
         } else {
             state.record_failure(max_failures, reset_ms, reason);
         }
+        if state.state != before {
+            Self::publish_transition(operation_id, before, state.state);
+        }
+    }
+
+    /// Publish a breaker transition to the event stream
+    fn publish_transition(operation_id: &str, from: CircuitState, to: CircuitState) {
+        if to == CircuitState::Open {
+            crate::notify::emit(
+                "circuit_breaker.opened",
+                serde_json::json!({
+                    "operationId": operation_id,
+                    "from": format!("{:?}", from),
+                    "summary": format!("Circuit breaker opened for {}", operation_id),
+                }),
+            );
+        }
+        crate::latest::stream::publish(crate::latest::stream::LatestStreamEvent {
+            kind: "breaker".to_string(),
+            task_id: None,
+            workspace_id: None,
+            detail: Some(format!("{}: {:?} -> {:?}", operation_id, from, to)),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        });
     }
 
     /// Reset circuit breaker for a specific tool
@@ -277,7 +302,7 @@ mod tests {
         assert!(state.should_allow(60000));
         assert_eq!(state.state, CircuitState::Closed);
         
-        state.record_success();
+        state.record_success(3);
         assert_eq!(state.total_calls, 1);
         assert_eq!(state.failure_count, 0);
     }
@@ -311,13 +336,23 @@ mod tests {
         state.success_count = 0;
         
         // Record successes to close the circuit
-        state.record_success();
+        state.record_success(3);
         assert_eq!(state.state, CircuitState::HalfOpen);
         
-        state.record_success();
+        state.record_success(3);
         assert_eq!(state.state, CircuitState::HalfOpen);
         
-        state.record_success();
+        state.record_success(3);
+        assert_eq!(state.state, CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_circuit_breaker_configurable_probe_count() {
+        let mut state = CircuitBreakerState::new();
+        state.state = CircuitState::HalfOpen;
+
+        // A single probe closes the circuit when configured to 1
+        state.record_success(1);
         assert_eq!(state.state, CircuitState::Closed);
     }
 
@@ -325,8 +360,8 @@ mod tests {
     fn test_circuit_breaker_stats() {
         let mut state = CircuitBreakerState::new();
         
-        state.record_success();
-        state.record_success();
+        state.record_success(3);
+        state.record_success(3);
         state.record_failure(5, 60000, Some("Test error".to_string()));
         
         let stats = state.stats();
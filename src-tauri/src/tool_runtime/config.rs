@@ -33,6 +33,34 @@ pub struct ToolConfig {
     #[serde(default)]
     pub max_failures: Option<u32>,
 
+    /// Circuit breaker cool-down override, in milliseconds (None = global)
+    #[serde(default)]
+    pub cooldown_ms: Option<u64>,
+
+    /// Successful half-open probes required to close the breaker (None = global)
+    #[serde(default)]
+    pub half_open_probes: Option<u32>,
+
+    /// Rate/quota budget for this tool (None = unlimited)
+    #[serde(default)]
+    pub budget: Option<super::ToolBudget>,
+
+    /// Match strategy used when recording fixtures (None = exact args)
+    #[serde(default)]
+    pub fixture_strategy: Option<super::FixtureStrategy>,
+
+    /// TTL applied to newly recorded fixtures, in milliseconds (None = no expiry)
+    #[serde(default)]
+    pub fixture_ttl_ms: Option<u64>,
+
+    /// Maximum response size in bytes before truncation (None = unlimited)
+    #[serde(default)]
+    pub max_response_bytes: Option<usize>,
+
+    /// How to shrink over-budget responses (None = structural)
+    #[serde(default)]
+    pub truncation_strategy: Option<super::TruncationStrategy>,
+
     /// Custom metadata for this tool
     #[serde(default)]
     pub metadata: HashMap<String, serde_json::Value>,
@@ -48,6 +76,13 @@ impl Default for ToolConfig {
             arg_clamps: HashMap::new(),
             timeout_ms: None,
             max_failures: None,
+            cooldown_ms: None,
+            half_open_probes: None,
+            budget: None,
+            fixture_strategy: None,
+            fixture_ttl_ms: None,
+            max_response_bytes: None,
+            truncation_strategy: None,
             metadata: HashMap::new(),
         }
     }
@@ -187,6 +222,10 @@ pub struct GlobalRuntimeConfig {
     /// Circuit breaker reset time (milliseconds)
     pub circuit_breaker_reset_ms: u64,
 
+    /// Successful half-open probes required to close a breaker
+    #[serde(default = "default_half_open_probes")]
+    pub half_open_probes: u32,
+
     /// Enable detailed logging
     pub verbose_logging: bool,
 
@@ -194,6 +233,10 @@ pub struct GlobalRuntimeConfig {
     pub rate_limit_per_minute: u32,
 }
 
+fn default_half_open_probes() -> u32 {
+    3
+}
+
 impl Default for GlobalRuntimeConfig {
     fn default() -> Self {
         Self {
@@ -205,6 +248,7 @@ impl Default for GlobalRuntimeConfig {
             default_timeout_ms: 30_000, // 30 seconds
             default_max_failures: 5,
             circuit_breaker_reset_ms: 60_000, // 1 minute
+            half_open_probes: 3,
             verbose_logging: false,
             rate_limit_per_minute: 0, // unlimited
         }
@@ -0,0 +1,487 @@
+//! Agent evaluation harness
+//!
+//! `POST /agent/eval` runs a suite of stored prompts against one or more
+//! Gemini models and scores the result per case: did the model pick the
+//! expected tools (with the expected arguments), and how close is its
+//! answer to the reference answer?
+//!
+//! Predicted tool calls are executed through the ToolRuntime choke-point
+//! with [`ToolCallSource::AutomatedTest`], so an active fixture set serves
+//! recorded responses and an eval run never has to touch live Jira or
+//! mutate anything. Running the same suite before and after a prompt or
+//! tool change gives a regression signal.
+
+use super::{ToolCallSource, ToolErrorResponse, ToolRuntime};
+use crate::api::retry;
+use axum::{extract::State, http::StatusCode, Json};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Default model when the request names none
+const DEFAULT_EVAL_MODEL: &str = "gemini-2.0-flash";
+/// Hard cap so one request can't spin the provider for minutes
+const MAX_CASES: usize = 50;
+const MAX_MODELS: usize = 5;
+
+/// One stored prompt with its expectations
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EvalCase {
+    /// Case name, unique within the suite
+    pub name: String,
+    /// The user prompt to evaluate
+    pub prompt: String,
+    /// Tool calls the model is expected to make (order-insensitive)
+    #[serde(default)]
+    pub expected_tool_calls: Vec<ExpectedToolCall>,
+    /// Reference answer for similarity scoring
+    #[serde(default)]
+    pub expected_answer: Option<String>,
+}
+
+/// An expected tool call: operation plus the argument subset that must match
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpectedToolCall {
+    /// Operation ID, e.g. "get_jira_list"
+    pub operation_id: String,
+    /// Arguments that must be present (subset match; extra args are fine)
+    #[serde(default)]
+    pub args: serde_json::Value,
+}
+
+/// Request body for the eval endpoint
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EvalRequest {
+    /// The suite of cases to run
+    pub cases: Vec<EvalCase>,
+    /// Models to compare (defaults to one entry, "gemini-2.0-flash")
+    #[serde(default)]
+    pub models: Vec<String>,
+}
+
+/// A tool call the model predicted
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PredictedToolCall {
+    pub operation_id: String,
+    #[serde(default)]
+    pub args: serde_json::Value,
+}
+
+/// Result of one case against one model
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EvalCaseResult {
+    pub name: String,
+    /// Fraction of expected tool calls the model made (1.0 when none expected)
+    pub tool_call_score: f64,
+    /// Word-overlap similarity to the reference answer (1.0 when none given)
+    pub answer_score: f64,
+    /// Tool calls the model predicted
+    pub predicted_tool_calls: Vec<PredictedToolCall>,
+    /// How many predicted calls were answered from fixtures
+    pub fixture_hits: usize,
+    /// The model's answer text
+    pub answer: String,
+    /// Error for this case, if the model call or parsing failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// All case results for one model
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelEvalReport {
+    pub model: String,
+    pub cases: Vec<EvalCaseResult>,
+    /// Mean tool-call score across cases
+    pub avg_tool_call_score: f64,
+    /// Mean answer score across cases
+    pub avg_answer_score: f64,
+}
+
+/// Comparison report across all requested models
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct EvalResponse {
+    pub reports: Vec<ModelEvalReport>,
+    pub total_cases: usize,
+    pub duration_ms: u64,
+}
+
+/// Subset match: every key in `expected` must be present in `actual` with
+/// an equal value, recursing into objects. Non-object expectations compare
+/// by equality; an expected `null` matches anything.
+fn args_subset_matches(expected: &serde_json::Value, actual: &serde_json::Value) -> bool {
+    match expected {
+        serde_json::Value::Null => true,
+        serde_json::Value::Object(expected_map) => match actual.as_object() {
+            Some(actual_map) => expected_map.iter().all(|(key, expected_value)| {
+                actual_map
+                    .get(key)
+                    .map(|actual_value| args_subset_matches(expected_value, actual_value))
+                    .unwrap_or(false)
+            }),
+            None => expected_map.is_empty(),
+        },
+        _ => expected == actual,
+    }
+}
+
+/// Fraction of expected calls that appear among the predictions (each
+/// prediction may satisfy only one expectation). 1.0 when nothing expected.
+fn score_tool_calls(expected: &[ExpectedToolCall], predicted: &[PredictedToolCall]) -> f64 {
+    if expected.is_empty() {
+        return 1.0;
+    }
+    let mut used = vec![false; predicted.len()];
+    let mut matched = 0usize;
+    for expectation in expected {
+        let found = predicted.iter().enumerate().find(|(i, call)| {
+            !used[*i]
+                && call.operation_id == expectation.operation_id
+                && args_subset_matches(&expectation.args, &call.args)
+        });
+        if let Some((i, _)) = found {
+            used[i] = true;
+            matched += 1;
+        }
+    }
+    matched as f64 / expected.len() as f64
+}
+
+/// Word-overlap (Jaccard) similarity between two answers, case-insensitive
+fn answer_similarity(expected: &str, actual: &str) -> f64 {
+    let words = |text: &str| -> HashSet<String> {
+        text.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(|w| w.to_string())
+            .collect()
+    };
+    let expected_words = words(expected);
+    let actual_words = words(actual);
+    if expected_words.is_empty() && actual_words.is_empty() {
+        return 1.0;
+    }
+    let intersection = expected_words.intersection(&actual_words).count();
+    let union = expected_words.union(&actual_words).count();
+    intersection as f64 / union as f64
+}
+
+/// What the model is asked to return for each case
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ModelPrediction {
+    #[serde(default)]
+    tool_calls: Vec<PredictedToolCall>,
+    #[serde(default)]
+    answer: String,
+}
+
+/// Ask the model which tools it would call and what it would answer.
+/// Returns the parsed prediction or an error string.
+async fn predict(
+    api_key: &str,
+    model: &str,
+    tool_catalog: &str,
+    prompt: &str,
+) -> Result<ModelPrediction, String> {
+    let instruction = format!(
+        "You are evaluated on tool selection for a Jira dashboard assistant. \
+         Available tools:\n{}\n\nFor the user request below, reply with ONLY a \
+         JSON object of the form {{\"toolCalls\": [{{\"operationId\": \"...\", \
+         \"args\": {{...}}}}], \"answer\": \"...\"}} — the tools you would call \
+         (in order, possibly none) and the answer you would give.\n\n\
+         User request: {}",
+        tool_catalog, prompt
+    );
+
+    let body = serde_json::json!({
+        "contents": [{
+            "role": "user",
+            "parts": [{ "text": instruction }]
+        }]
+    });
+
+    let client = crate::http::outbound_client();
+    let url = format!(
+        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
+        model, api_key
+    );
+
+    let policy = retry::RetryPolicy::from_config();
+    let outcome = retry::send_with_retry(
+        || {
+            client
+                .post(&url)
+                .header("Content-Type", "application/json")
+                .json(&body)
+        },
+        &policy,
+    )
+    .await;
+
+    let response = outcome
+        .result
+        .map_err(|e| format!("Failed to call Gemini API: {}", e))?;
+    let status = response.status();
+    let text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read Gemini response: {}", e))?;
+    if !status.is_success() {
+        return Err(format!("Gemini API error ({}): {}", status, text));
+    }
+
+    let value: serde_json::Value =
+        serde_json::from_str(&text).map_err(|e| format!("Failed to parse Gemini response: {}", e))?;
+    let reply = value["candidates"][0]["content"]["parts"]
+        .as_array()
+        .map(|parts| {
+            parts
+                .iter()
+                .filter_map(|p| p["text"].as_str())
+                .collect::<Vec<_>>()
+                .join("")
+        })
+        .unwrap_or_default();
+
+    parse_prediction(&reply).ok_or_else(|| "Model did not return parseable JSON".to_string())
+}
+
+/// Extract the prediction JSON from the model reply, tolerating code fences
+/// and surrounding prose.
+fn parse_prediction(reply: &str) -> Option<ModelPrediction> {
+    let start = reply.find('{')?;
+    let end = reply.rfind('}')?;
+    if end < start {
+        return None;
+    }
+    serde_json::from_str(&reply[start..=end]).ok()
+}
+
+/// Run an evaluation suite
+///
+/// Runs each case's prompt against each requested model, executes the
+/// predicted tool calls through the runtime (fixtures answer them when a
+/// fixture set is active), and scores tool-call correctness plus answer
+/// similarity. Compare reports across models or across prompt revisions.
+#[utoipa::path(
+    post,
+    path = "/agent/eval",
+    request_body = EvalRequest,
+    responses(
+        (status = 200, description = "Evaluation report", body = EvalResponse),
+        (status = 400, description = "Invalid suite", body = ToolErrorResponse),
+        (status = 500, description = "Gemini API key not configured", body = ToolErrorResponse)
+    ),
+    tag = "agent"
+)]
+pub async fn run_eval_handler(
+    State(runtime): State<Arc<ToolRuntime>>,
+    Json(request): Json<EvalRequest>,
+) -> Result<Json<EvalResponse>, (StatusCode, Json<ToolErrorResponse>)> {
+    let start = Instant::now();
+
+    if request.cases.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ToolErrorResponse {
+                error: "Eval suite needs at least one case".to_string(),
+                code: 400,
+            }),
+        ));
+    }
+    if request.cases.len() > MAX_CASES {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ToolErrorResponse {
+                error: format!("Eval suite is limited to {} cases", MAX_CASES),
+                code: 400,
+            }),
+        ));
+    }
+
+    let models = if request.models.is_empty() {
+        vec![DEFAULT_EVAL_MODEL.to_string()]
+    } else {
+        request.models.clone()
+    };
+    if models.len() > MAX_MODELS {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ToolErrorResponse {
+                error: format!("Eval is limited to {} models per run", MAX_MODELS),
+                code: 400,
+            }),
+        ));
+    }
+
+    let api_key = runtime.app_state().gemini_api_key();
+    if api_key.is_empty() || api_key == "YOUR_GEMINI_API_KEY_HERE" {
+        return Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ToolErrorResponse {
+                error: "Gemini API key not configured".to_string(),
+                code: 500,
+            }),
+        ));
+    }
+
+    // One tool catalog for the whole run
+    let tool_catalog = runtime
+        .list_tools()
+        .iter()
+        .map(|t| format!("- {}: {}", t.operation_id, t.description))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut reports = Vec::new();
+    for model in &models {
+        let mut cases = Vec::new();
+        for case in &request.cases {
+            let case_start = Instant::now();
+            match predict(&api_key, model, &tool_catalog, &case.prompt).await {
+                Ok(prediction) => {
+                    // Execute predicted calls through the choke-point so
+                    // fixtures (and budgets/breakers) apply
+                    let mut fixture_hits = 0usize;
+                    for call in &prediction.tool_calls {
+                        let result = runtime
+                            .call(
+                                &call.operation_id,
+                                call.args.clone(),
+                                ToolCallSource::AutomatedTest,
+                            )
+                            .await;
+                        if result.from_fixture {
+                            fixture_hits += 1;
+                        }
+                    }
+
+                    let answer_score = case
+                        .expected_answer
+                        .as_deref()
+                        .map(|expected| answer_similarity(expected, &prediction.answer))
+                        .unwrap_or(1.0);
+                    cases.push(EvalCaseResult {
+                        name: case.name.clone(),
+                        tool_call_score: score_tool_calls(
+                            &case.expected_tool_calls,
+                            &prediction.tool_calls,
+                        ),
+                        answer_score,
+                        predicted_tool_calls: prediction.tool_calls,
+                        fixture_hits,
+                        answer: prediction.answer,
+                        error: None,
+                        duration_ms: case_start.elapsed().as_millis() as u64,
+                    });
+                }
+                Err(e) => {
+                    tracing::warn!("Eval case '{}' on {} failed: {}", case.name, model, e);
+                    cases.push(EvalCaseResult {
+                        name: case.name.clone(),
+                        tool_call_score: 0.0,
+                        answer_score: 0.0,
+                        predicted_tool_calls: Vec::new(),
+                        fixture_hits: 0,
+                        answer: String::new(),
+                        error: Some(e),
+                        duration_ms: case_start.elapsed().as_millis() as u64,
+                    });
+                }
+            }
+        }
+
+        let count = cases.len() as f64;
+        reports.push(ModelEvalReport {
+            model: model.clone(),
+            avg_tool_call_score: cases.iter().map(|c| c.tool_call_score).sum::<f64>() / count,
+            avg_answer_score: cases.iter().map(|c| c.answer_score).sum::<f64>() / count,
+            cases,
+        });
+    }
+
+    Ok(Json(EvalResponse {
+        reports,
+        total_cases: request.cases.len(),
+        duration_ms: start.elapsed().as_millis() as u64,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn expected(operation_id: &str, args: serde_json::Value) -> ExpectedToolCall {
+        ExpectedToolCall {
+            operation_id: operation_id.to_string(),
+            args,
+        }
+    }
+
+    fn predicted(operation_id: &str, args: serde_json::Value) -> PredictedToolCall {
+        PredictedToolCall {
+            operation_id: operation_id.to_string(),
+            args,
+        }
+    }
+
+    #[test]
+    fn test_args_subset_matches() {
+        assert!(args_subset_matches(&json!(null), &json!({"a": 1})));
+        assert!(args_subset_matches(&json!({}), &json!({"a": 1})));
+        assert!(args_subset_matches(&json!({"a": 1}), &json!({"a": 1, "b": 2})));
+        assert!(!args_subset_matches(&json!({"a": 1}), &json!({"a": 2})));
+        assert!(!args_subset_matches(&json!({"a": 1}), &json!({"b": 1})));
+        assert!(args_subset_matches(
+            &json!({"filter": {"jql": "x"}}),
+            &json!({"filter": {"jql": "x", "max": 5}})
+        ));
+    }
+
+    #[test]
+    fn test_score_tool_calls() {
+        let exp = vec![
+            expected("get_jira_list", json!({})),
+            expected("get_health", json!({})),
+        ];
+        let pred = vec![predicted("get_jira_list", json!({"jql": "x"}))];
+        assert!((score_tool_calls(&exp, &pred) - 0.5).abs() < f64::EPSILON);
+
+        // No expectations: perfect score regardless of predictions
+        assert!((score_tool_calls(&[], &pred) - 1.0).abs() < f64::EPSILON);
+
+        // One prediction can't satisfy two identical expectations
+        let exp = vec![
+            expected("get_health", json!({})),
+            expected("get_health", json!({})),
+        ];
+        let pred = vec![predicted("get_health", json!({}))];
+        assert!((score_tool_calls(&exp, &pred) - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_answer_similarity() {
+        assert!((answer_similarity("three open bugs", "three open bugs") - 1.0).abs() < f64::EPSILON);
+        assert!(answer_similarity("three open bugs", "completely different words") < 0.2);
+        assert!((answer_similarity("", "") - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_parse_prediction_tolerates_fences() {
+        let reply = "Sure! ```json\n{\"toolCalls\": [{\"operationId\": \"get_health\", \"args\": {}}], \"answer\": \"ok\"}\n```";
+        let prediction = parse_prediction(reply).unwrap();
+        assert_eq!(prediction.tool_calls.len(), 1);
+        assert_eq!(prediction.tool_calls[0].operation_id, "get_health");
+        assert_eq!(prediction.answer, "ok");
+    }
+}
@@ -1,10 +1,19 @@
 //! Fixtures replay/record for ToolRuntime
 //!
 //! Allows recording tool responses and replaying them for testing.
+//!
+//! Fixtures are persisted to disk as one JSON file per operation under
+//! `%APPDATA%/jira-dashboard/fixtures/`, loaded at startup, and written on
+//! every mutation. Individual fixtures may carry a TTL after which they stop
+//! matching; the recording match strategy is configurable per tool via
+//! `ToolConfig::fixture_strategy`.
 
 use super::ToolRuntime;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
+
+const FIXTURES_DIR: &str = "jira-dashboard/fixtures";
 
 /// Storage for recorded fixtures
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -59,6 +68,108 @@ impl FixturesStorage {
     pub fn count(&self) -> usize {
         self.fixtures.values().map(|v| v.len()).sum()
     }
+
+    /// Drop expired fixtures from every operation.
+    pub fn prune_expired(&mut self) {
+        for fixtures in self.fixtures.values_mut() {
+            fixtures.retain(|f| !f.is_expired());
+        }
+        self.fixtures.retain(|_, v| !v.is_empty());
+    }
+}
+
+// ============ Disk persistence ============
+
+/// Return the fixtures directory, creating it if needed.
+fn fixtures_dir() -> Option<PathBuf> {
+    let appdata = std::env::var("APPDATA").ok()?;
+    let dir = PathBuf::from(appdata).join(FIXTURES_DIR);
+    if !dir.exists() {
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            log::warn!("Failed to create fixtures dir {:?}: {}", dir, e);
+            return None;
+        }
+    }
+    Some(dir)
+}
+
+/// Per-operation fixture file name (operation IDs are path slugs, but
+/// sanitize separators defensively).
+fn fixture_file(operation_id: &str) -> String {
+    let safe = operation_id.replace(['/', '\\'], "_");
+    format!("{}.json", safe)
+}
+
+/// Load all persisted fixtures from disk, pruning expired entries.
+pub fn load_fixtures_from_disk() -> FixturesStorage {
+    let mut storage = FixturesStorage::new();
+    let Some(dir) = fixtures_dir() else {
+        return storage;
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return storage;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(operation_id) = path.file_stem().and_then(|n| n.to_str()).map(String::from)
+        else {
+            continue;
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(json) => match serde_json::from_str::<Vec<Fixture>>(&json) {
+                Ok(fixtures) => {
+                    storage.fixtures.insert(operation_id, fixtures);
+                }
+                Err(e) => log::warn!("Failed to parse fixtures file {:?}: {}", path, e),
+            },
+            Err(e) => log::warn!("Failed to read fixtures file {:?}: {}", path, e),
+        }
+    }
+    storage.prune_expired();
+    log::info!("Loaded {} fixtures from disk", storage.count());
+    storage
+}
+
+/// Write one operation's fixtures to disk (removes the file when empty).
+pub fn save_operation_fixtures(operation_id: &str, fixtures: &[Fixture]) {
+    let Some(dir) = fixtures_dir() else {
+        return;
+    };
+    let path = dir.join(fixture_file(operation_id));
+    if fixtures.is_empty() {
+        let _ = std::fs::remove_file(&path);
+        return;
+    }
+    match serde_json::to_string_pretty(fixtures) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                log::warn!("Failed to write fixtures file {:?}: {}", path, e);
+            }
+        }
+        Err(e) => log::warn!("Failed to serialize fixtures for {}: {}", operation_id, e),
+    }
+}
+
+/// Replace all on-disk fixture files with the given storage's contents.
+pub fn save_all_fixtures(storage: &FixturesStorage) {
+    let Some(dir) = fixtures_dir() else {
+        return;
+    };
+    // Remove stale files first so deleted operations disappear
+    if let Ok(entries) = std::fs::read_dir(&dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) == Some("json") {
+                let _ = std::fs::remove_file(&path);
+            }
+        }
+    }
+    for (operation_id, fixtures) in &storage.fixtures {
+        save_operation_fixtures(operation_id, fixtures);
+    }
 }
 
 /// A single recorded fixture
@@ -76,6 +187,9 @@ pub struct Fixture {
     /// Tags for filtering/organizing
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Time-to-live in milliseconds from recorded_at (None = never expires)
+    #[serde(default)]
+    pub ttl_ms: Option<u64>,
 }
 
 impl Fixture {
@@ -87,6 +201,7 @@ impl Fixture {
             recorded_at: chrono::Local::now().to_rfc3339(),
             name: None,
             tags: Vec::new(),
+            ttl_ms: None,
         }
     }
 
@@ -98,6 +213,7 @@ impl Fixture {
             recorded_at: chrono::Local::now().to_rfc3339(),
             name: None,
             tags: Vec::new(),
+            ttl_ms: None,
         }
     }
 
@@ -113,9 +229,29 @@ impl Fixture {
         self
     }
 
-    /// Check if this fixture matches the given args
+    /// Set time-to-live
+    pub fn with_ttl(mut self, ttl_ms: u64) -> Self {
+        self.ttl_ms = Some(ttl_ms);
+        self
+    }
+
+    /// Whether this fixture's TTL has elapsed
+    pub fn is_expired(&self) -> bool {
+        let Some(ttl_ms) = self.ttl_ms else {
+            return false;
+        };
+        match chrono::DateTime::parse_from_rfc3339(&self.recorded_at) {
+            Ok(recorded) => {
+                let expires = recorded + chrono::Duration::milliseconds(ttl_ms as i64);
+                chrono::Utc::now() >= expires
+            }
+            Err(_) => false, // unparseable timestamp — treat as fresh
+        }
+    }
+
+    /// Check if this fixture matches the given args (expired fixtures never match)
     pub fn matches(&self, args: &serde_json::Value) -> bool {
-        self.args.matches(args)
+        !self.is_expired() && self.args.matches(args)
     }
 }
 
@@ -131,6 +267,11 @@ pub enum FixtureMatch {
     Contains(serde_json::Value),
     /// Match if args match this regex pattern (for string values)
     Regex { patterns: HashMap<String, String> },
+    /// Match exact arguments, ignoring the listed keys on both sides
+    ExactIgnoring {
+        args: serde_json::Value,
+        ignore_keys: Vec<String>,
+    },
 }
 
 impl FixtureMatch {
@@ -165,8 +306,141 @@ impl FixtureMatch {
                     false
                 }
             }
+            FixtureMatch::ExactIgnoring { args: expected, ignore_keys } => {
+                match (args.as_object(), expected.as_object()) {
+                    (Some(actual_obj), Some(expected_obj)) => {
+                        let stripped = |obj: &serde_json::Map<String, serde_json::Value>| {
+                            obj.iter()
+                                .filter(|(k, _)| !ignore_keys.contains(k))
+                                .map(|(k, v)| (k.clone(), v.clone()))
+                                .collect::<serde_json::Map<_, _>>()
+                        };
+                        stripped(actual_obj) == stripped(expected_obj)
+                    }
+                    _ => args == expected,
+                }
+            }
+        }
+    }
+}
+
+/// Per-tool strategy used when recording fixtures
+/// (how the recorded args become a match pattern).
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FixtureStrategy {
+    /// Record the exact args (default)
+    Exact,
+    /// Record the args but ignore the listed keys when matching
+    IgnoreKeys { keys: Vec<String> },
+    /// Match future calls by regex on string-valued args
+    Regex { patterns: HashMap<String, String> },
+}
+
+impl FixtureStrategy {
+    /// Build the match pattern for a set of recorded args.
+    pub fn to_match(&self, args: &serde_json::Value) -> FixtureMatch {
+        match self {
+            FixtureStrategy::Exact => FixtureMatch::Exact(args.clone()),
+            FixtureStrategy::IgnoreKeys { keys } => FixtureMatch::ExactIgnoring {
+                args: args.clone(),
+                ignore_keys: keys.clone(),
+            },
+            FixtureStrategy::Regex { patterns } => FixtureMatch::Regex {
+                patterns: patterns.clone(),
+            },
+        }
+    }
+}
+
+// ============ Recording sessions ============
+
+/// An in-progress named recording session capturing all tool responses.
+#[derive(Debug, Clone, Default)]
+pub struct RecordingSession {
+    /// Session name (also the saved set's file name)
+    pub name: String,
+    /// ISO 8601 timestamp when recording started
+    pub started_at: String,
+    /// Responses captured so far
+    pub storage: FixturesStorage,
+}
+
+/// Summary of a saved fixture set for listing.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FixtureSetInfo {
+    /// Set name
+    pub name: String,
+    /// Number of fixtures in the set
+    pub count: usize,
+}
+
+/// Return the saved fixture sets directory, creating it if needed.
+fn sessions_dir() -> Option<PathBuf> {
+    let appdata = std::env::var("APPDATA").ok()?;
+    let dir = PathBuf::from(appdata).join(FIXTURES_DIR).join("sessions");
+    if !dir.exists() {
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            log::warn!("Failed to create fixture sessions dir {:?}: {}", dir, e);
+            return None;
         }
     }
+    Some(dir)
+}
+
+/// Validate a session/set name (file-name safe).
+pub fn valid_session_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Save a named fixture set to disk.
+fn save_session_set(name: &str, storage: &FixturesStorage) -> Result<(), String> {
+    let dir = sessions_dir().ok_or("Fixture sessions directory unavailable")?;
+    let path = dir.join(format!("{}.json", name));
+    let json = serde_json::to_string_pretty(storage)
+        .map_err(|e| format!("Failed to serialize fixture set: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {:?}: {}", path, e))
+}
+
+/// Load a named fixture set from disk.
+fn load_session_set(name: &str) -> Result<FixturesStorage, String> {
+    let dir = sessions_dir().ok_or("Fixture sessions directory unavailable")?;
+    let path = dir.join(format!("{}.json", name));
+    let json = std::fs::read_to_string(&path)
+        .map_err(|_| format!("Fixture set '{}' not found", name))?;
+    serde_json::from_str(&json).map_err(|e| format!("Failed to parse fixture set '{}': {}", name, e))
+}
+
+/// List saved fixture sets on disk.
+pub fn list_session_sets() -> Vec<FixtureSetInfo> {
+    let Some(dir) = sessions_dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+    let mut sets = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(name) = path.file_stem().and_then(|n| n.to_str()).map(String::from) else {
+            continue;
+        };
+        let count = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|json| serde_json::from_str::<FixturesStorage>(&json).ok())
+            .map(|s| s.count())
+            .unwrap_or(0);
+        sets.push(FixtureSetInfo { name, count });
+    }
+    sets.sort_by(|a, b| a.name.cmp(&b.name));
+    sets
 }
 
 impl ToolRuntime {
@@ -180,32 +454,49 @@ impl ToolRuntime {
         fixtures.find_match(operation_id, args).cloned()
     }
 
-    /// Record a fixture
+    /// Record a fixture using the tool's configured match strategy
     pub fn record_fixture(
         &self,
         operation_id: &str,
         args: &serde_json::Value,
         response: serde_json::Value,
     ) {
-        let fixture = Fixture::new(args.clone(), response);
-        
+        let tool_config = self.get_tool_config(operation_id);
+        let mut fixture = Fixture::new(args.clone(), response);
+        if let Some(ref strategy) = tool_config.fixture_strategy {
+            fixture.args = strategy.to_match(args);
+        }
+        if let Some(ttl_ms) = tool_config.fixture_ttl_ms {
+            fixture.ttl_ms = Some(ttl_ms);
+        }
+
         tracing::debug!(
             "Recording fixture for {} with args: {:?}",
             operation_id,
             args
         );
-        
-        self.fixtures.write().add(operation_id, fixture);
+
+        let mut fixtures = self.fixtures.write();
+        fixtures.add(operation_id, fixture);
+        save_operation_fixtures(operation_id, fixtures.get(operation_id).unwrap());
     }
 
     /// Add a manual fixture
     pub fn add_fixture(&self, operation_id: &str, fixture: Fixture) {
-        self.fixtures.write().add(operation_id, fixture);
+        let mut fixtures = self.fixtures.write();
+        fixtures.add(operation_id, fixture);
+        save_operation_fixtures(operation_id, fixtures.get(operation_id).unwrap());
     }
 
     /// Remove all fixtures for an operation
     pub fn remove_fixtures(&self, operation_id: &str) {
         self.fixtures.write().fixtures.remove(operation_id);
+        save_operation_fixtures(operation_id, &[]);
+    }
+
+    /// Load persisted fixtures from disk (called once at startup)
+    pub fn load_persisted_fixtures(&self) {
+        *self.fixtures.write() = load_fixtures_from_disk();
     }
 
     /// Get fixture count for an operation
@@ -217,6 +508,81 @@ impl ToolRuntime {
             .map(|v| v.len())
             .unwrap_or(0)
     }
+
+    /// Start a named recording session
+    pub fn start_recording_session(&self, name: &str) -> Result<(), String> {
+        if !valid_session_name(name) {
+            return Err(format!(
+                "Invalid session name '{}': use letters, digits, '-' or '_'",
+                name
+            ));
+        }
+        let mut session = self.recording_session.write();
+        if let Some(ref active) = *session {
+            return Err(format!(
+                "Recording session '{}' is already active",
+                active.name
+            ));
+        }
+        *session = Some(RecordingSession {
+            name: name.to_string(),
+            started_at: chrono::Utc::now().to_rfc3339(),
+            storage: FixturesStorage::new(),
+        });
+        Ok(())
+    }
+
+    /// Stop the active recording session and save it as a named set.
+    /// Returns the set name and number of fixtures captured.
+    pub fn stop_recording_session(&self) -> Result<(String, usize), String> {
+        let session = self
+            .recording_session
+            .write()
+            .take()
+            .ok_or("No recording session is active")?;
+        let count = session.storage.count();
+        save_session_set(&session.name, &session.storage)?;
+        Ok((session.name, count))
+    }
+
+    /// Name and start time of the active recording session, if any
+    pub fn active_recording_session(&self) -> Option<(String, String)> {
+        self.recording_session
+            .read()
+            .as_ref()
+            .map(|s| (s.name.clone(), s.started_at.clone()))
+    }
+
+    /// Capture a response into the active recording session (no-op when idle)
+    pub fn record_session_fixture(
+        &self,
+        operation_id: &str,
+        args: &serde_json::Value,
+        response: serde_json::Value,
+    ) {
+        let mut session = self.recording_session.write();
+        if let Some(ref mut session) = *session {
+            session
+                .storage
+                .add(operation_id, Fixture::new(args.clone(), response));
+        }
+    }
+
+    /// Activate a saved fixture set: load it as the live fixtures and enable
+    /// global fixture replay. Returns the number of fixtures loaded.
+    pub fn activate_fixture_set(&self, name: &str) -> Result<usize, String> {
+        let storage = load_session_set(name)?;
+        let count = storage.count();
+        *self.fixtures.write() = storage;
+        save_all_fixtures(&self.fixtures.read());
+
+        let mut config = self.get_global_config();
+        config.replay_fixtures = true;
+        self.set_global_config(config);
+
+        tracing::info!("Activated fixture set '{}' ({} fixtures)", name, count);
+        Ok(count)
+    }
 }
 
 #[cfg(test)]
@@ -251,12 +617,53 @@ mod tests {
             recorded_at: "2024-01-01".to_string(),
             name: None,
             tags: Vec::new(),
+            ttl_ms: None,
         };
 
         assert!(fixture.matches(&serde_json::json!({"jql": "test", "extra": "ignored"})));
         assert!(!fixture.matches(&serde_json::json!({"jql": "different"})));
     }
 
+    #[test]
+    fn test_fixture_expiry() {
+        let mut fixture = Fixture::any(serde_json::json!({"result": "ok"})).with_ttl(3_600_000);
+        assert!(!fixture.is_expired());
+
+        // Recorded two hours ago with a one-hour TTL — expired, never matches
+        fixture.recorded_at = (chrono::Utc::now() - chrono::Duration::hours(2)).to_rfc3339();
+        assert!(fixture.is_expired());
+        assert!(!fixture.matches(&serde_json::json!({})));
+    }
+
+    #[test]
+    fn test_fixture_exact_ignoring_match() {
+        let fixture = Fixture {
+            args: FixtureMatch::ExactIgnoring {
+                args: serde_json::json!({"jql": "test", "requestId": "abc"}),
+                ignore_keys: vec!["requestId".to_string()],
+            },
+            response: serde_json::json!({"result": "ok"}),
+            recorded_at: chrono::Utc::now().to_rfc3339(),
+            name: None,
+            tags: Vec::new(),
+            ttl_ms: None,
+        };
+
+        assert!(fixture.matches(&serde_json::json!({"jql": "test", "requestId": "xyz"})));
+        assert!(fixture.matches(&serde_json::json!({"jql": "test"})));
+        assert!(!fixture.matches(&serde_json::json!({"jql": "other", "requestId": "abc"})));
+    }
+
+    #[test]
+    fn test_fixture_strategy_to_match() {
+        let strategy = FixtureStrategy::IgnoreKeys {
+            keys: vec!["timestamp".to_string()],
+        };
+        let m = strategy.to_match(&serde_json::json!({"jql": "test", "timestamp": 1}));
+        assert!(m.matches(&serde_json::json!({"jql": "test", "timestamp": 2})));
+        assert!(!m.matches(&serde_json::json!({"jql": "nope", "timestamp": 1})));
+    }
+
     #[test]
     fn test_fixtures_storage() {
         let mut storage = FixturesStorage::new();
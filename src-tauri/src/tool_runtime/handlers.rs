@@ -6,8 +6,9 @@ use super::{
     GlobalRuntimeConfig, ToolCallSource, ToolConfig, ToolExecutionLog, ToolInfo, ToolInvokeRequest,
     ToolRuntime,
 };
+use crate::api::pagination::{Page, PageQuery};
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     Json,
 };
@@ -31,38 +32,103 @@ pub struct ToolInvokeResponse {
     pub validation: Option<super::ValidationResult>,
 }
 
-/// Response listing all tools
+/// Response for runtime configuration
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 #[serde(rename_all = "camelCase")]
-pub struct ToolsListResponse {
-    pub tools: Vec<ToolInfo>,
+pub struct RuntimeConfigResponse {
+    pub config: GlobalRuntimeConfig,
+    pub tool_configs: HashMap<String, ToolConfig>,
+}
+
+/// Response for circuit breaker status
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CircuitBreakerStatusResponse {
+    pub breakers: HashMap<String, super::CircuitBreakerState>,
     pub total: usize,
 }
 
-/// Response for tool execution logs
+/// Response for budget status
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 #[serde(rename_all = "camelCase")]
-pub struct ToolExecutionLogsResponse {
-    pub logs: Vec<ToolExecutionLog>,
+pub struct BudgetStatusResponse {
+    pub budgets: HashMap<String, super::BudgetState>,
     pub total: usize,
 }
 
-/// Response for runtime configuration
+/// Response listing registered macro tools
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 #[serde(rename_all = "camelCase")]
-pub struct RuntimeConfigResponse {
-    pub config: GlobalRuntimeConfig,
-    pub tool_configs: HashMap<String, ToolConfig>,
+pub struct MacrosResponse {
+    /// Registered macros
+    pub macros: Vec<super::MacroTool>,
+    /// Total count
+    pub total: usize,
 }
 
-/// Response for circuit breaker status
+/// Request to save the current configuration as a named profile
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveProfileRequest {
+    /// Profile name (letters, digits, '-' or '_')
+    pub name: String,
+    /// Human-readable description
+    #[serde(default)]
+    pub description: String,
+}
+
+/// Response listing available profiles
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 #[serde(rename_all = "camelCase")]
-pub struct CircuitBreakerStatusResponse {
-    pub breakers: HashMap<String, super::CircuitBreakerState>,
+pub struct ProfilesResponse {
+    /// Built-in and saved profiles
+    pub profiles: Vec<super::ProfileInfo>,
+    /// Total count
     pub total: usize,
 }
 
+/// Request to replay a logged tool call
+#[derive(Debug, Default, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayLogRequest {
+    /// Force the replay into dry-run mode (defaults to the tool's config)
+    #[serde(default)]
+    pub dry_run: Option<bool>,
+}
+
+/// Response for a replayed tool call
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayLogResponse {
+    /// The log entry that was replayed
+    pub log_id: u64,
+    /// The operation that was re-executed
+    pub operation_id: String,
+    /// Result of the replayed call
+    pub result: super::ToolCallResult,
+    /// Whether the new response matches the recorded one
+    pub matches: bool,
+    /// JSON-pointer paths where the new response differs from the recorded one
+    pub differences: Vec<String>,
+}
+
+/// Query parameters for runtime metrics
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsQuery {
+    /// Rolling window in minutes (omit to aggregate the whole retained log)
+    pub window_minutes: Option<u64>,
+}
+
+/// Response for runtime metrics
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeMetricsResponse {
+    pub metrics: HashMap<String, super::ToolMetrics>,
+    pub total: usize,
+    pub window_minutes: Option<u64>,
+}
+
 /// Response for fixtures
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -71,11 +137,29 @@ pub struct FixturesResponse {
     pub count: usize,
 }
 
-/// Error response
+/// Error response — alias for the crate-wide structured error.
+pub type ToolErrorResponse = crate::api::error::ApiError;
+
+/// Response for fixture session state
 #[derive(Debug, Serialize, utoipa::ToSchema)]
-pub struct ToolErrorResponse {
-    pub error: String,
-    pub code: u16,
+#[serde(rename_all = "camelCase")]
+pub struct FixtureSessionsResponse {
+    /// Name of the active recording session, if any
+    pub active: Option<String>,
+    /// ISO 8601 start time of the active session, if any
+    pub active_since: Option<String>,
+    /// Saved fixture sets available for activation
+    pub sets: Vec<super::FixtureSetInfo>,
+}
+
+/// Response after stopping a session or activating a set
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct FixtureSetResponse {
+    /// Set name
+    pub name: String,
+    /// Number of fixtures in the set
+    pub count: usize,
 }
 
 // ============ Request Types ============
@@ -94,6 +178,25 @@ pub struct ConfigureToolRequest {
     pub config: ToolConfig,
 }
 
+/// Request to start a fixture recording session
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StartSessionRequest {
+    /// Session name (letters, digits, '-' or '_')
+    pub name: String,
+}
+
+/// Request to replace the full runtime configuration (global + per-tool)
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRuntimeConfigRequest {
+    /// Global runtime settings
+    pub config: GlobalRuntimeConfig,
+    /// Per-tool configuration keyed by operation_id (omitted keys are removed)
+    #[serde(default)]
+    pub tool_configs: HashMap<String, ToolConfig>,
+}
+
 // ============ Handlers ============
 
 /// Invoke a tool
@@ -153,34 +256,48 @@ pub async fn invoke_tool_handler(
 #[utoipa::path(
     get,
     path = "/tools",
+    params(PageQuery),
     responses(
-        (status = 200, description = "List of available tools", body = ToolsListResponse)
+        (status = 200, description = "Paginated list of available tools", body = Page<ToolInfo>),
+        (status = 400, description = "Invalid cursor", body = ToolErrorResponse)
     ),
     tag = "tools"
 )]
 pub async fn list_tools_handler(
     State(runtime): State<Arc<ToolRuntime>>,
-) -> Json<ToolsListResponse> {
-    let tools = runtime.list_tools();
-    let total = tools.len();
-    Json(ToolsListResponse { tools, total })
+    Query(page): Query<PageQuery>,
+) -> Result<Json<Page<ToolInfo>>, (StatusCode, Json<ToolErrorResponse>)> {
+    let window = page.window().map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ToolErrorResponse { error: e, code: 400 }),
+        )
+    })?;
+    Ok(Json(window.paginate(runtime.list_tools())))
 }
 
 /// Get tool execution logs
 #[utoipa::path(
     get,
     path = "/tools/logs",
+    params(PageQuery),
     responses(
-        (status = 200, description = "Tool execution logs", body = ToolExecutionLogsResponse)
+        (status = 200, description = "Paginated tool execution logs", body = Page<ToolExecutionLog>),
+        (status = 400, description = "Invalid cursor", body = ToolErrorResponse)
     ),
     tag = "tools"
 )]
 pub async fn get_tool_logs_handler(
     State(runtime): State<Arc<ToolRuntime>>,
-) -> Json<ToolExecutionLogsResponse> {
-    let logs = runtime.get_execution_logs();
-    let total = logs.len();
-    Json(ToolExecutionLogsResponse { logs, total })
+    Query(page): Query<PageQuery>,
+) -> Result<Json<Page<ToolExecutionLog>>, (StatusCode, Json<ToolErrorResponse>)> {
+    let window = page.window().map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ToolErrorResponse { error: e, code: 400 }),
+        )
+    })?;
+    Ok(Json(window.paginate(runtime.get_execution_logs())))
 }
 
 /// Clear tool execution logs
@@ -239,6 +356,72 @@ pub async fn update_config_handler(
     Json(RuntimeConfigResponse { config, tool_configs })
 }
 
+/// Get the full runtime configuration (global + per-tool)
+///
+/// Same payload as GET /tools/config, exposed under /runtime for consumers
+/// that manage the persisted configuration as a whole.
+#[utoipa::path(
+    get,
+    path = "/runtime/config",
+    responses(
+        (status = 200, description = "Runtime configuration", body = RuntimeConfigResponse)
+    ),
+    tag = "tools"
+)]
+pub async fn get_runtime_config_handler(
+    State(runtime): State<Arc<ToolRuntime>>,
+) -> Json<RuntimeConfigResponse> {
+    let config = runtime.get_global_config();
+    let tool_configs = runtime.get_all_tool_configs();
+    Json(RuntimeConfigResponse { config, tool_configs })
+}
+
+/// Replace the full runtime configuration (global + per-tool)
+///
+/// Unlike PUT /tools/config (global only), this replaces the per-tool config
+/// map as well. The result is persisted to disk and survives restarts.
+#[utoipa::path(
+    put,
+    path = "/runtime/config",
+    request_body = UpdateRuntimeConfigRequest,
+    responses(
+        (status = 200, description = "Configuration replaced", body = RuntimeConfigResponse)
+    ),
+    tag = "tools"
+)]
+pub async fn put_runtime_config_handler(
+    State(runtime): State<Arc<ToolRuntime>>,
+    Json(request): Json<UpdateRuntimeConfigRequest>,
+) -> Json<RuntimeConfigResponse> {
+    runtime.set_full_config(request.config, request.tool_configs);
+    tracing::info!("Tools Console: Full runtime config replaced");
+
+    let config = runtime.get_global_config();
+    let tool_configs = runtime.get_all_tool_configs();
+    Json(RuntimeConfigResponse { config, tool_configs })
+}
+
+/// Get configuration for a specific tool
+///
+/// Returns the stored config, or the defaults if the tool was never configured.
+#[utoipa::path(
+    get,
+    path = "/tools/{operation_id}/config",
+    params(
+        ("operation_id" = String, Path, description = "Tool operation ID")
+    ),
+    responses(
+        (status = 200, description = "Tool configuration", body = ToolConfig)
+    ),
+    tag = "tools"
+)]
+pub async fn get_tool_config_handler(
+    State(runtime): State<Arc<ToolRuntime>>,
+    Path(operation_id): Path<String>,
+) -> Json<ToolConfig> {
+    Json(runtime.get_tool_config(&operation_id))
+}
+
 /// Configure a specific tool
 #[utoipa::path(
     put,
@@ -317,6 +500,64 @@ pub async fn reset_tool_circuit_breaker_handler(
     StatusCode::OK
 }
 
+/// Get budget usage for all tools
+///
+/// Rolling-window call counts and cumulative durations, refreshed at read
+/// time. Tools without budget limits still appear once they have been called.
+#[utoipa::path(
+    get,
+    path = "/runtime/budgets",
+    responses(
+        (status = 200, description = "Budget usage per tool", body = BudgetStatusResponse)
+    ),
+    tag = "tools"
+)]
+pub async fn get_budgets_handler(
+    State(runtime): State<Arc<ToolRuntime>>,
+) -> Json<BudgetStatusResponse> {
+    let budgets = runtime.get_budget_status();
+    let total = budgets.len();
+    Json(BudgetStatusResponse { budgets, total })
+}
+
+/// Reset budget usage for all tools
+#[utoipa::path(
+    delete,
+    path = "/runtime/budgets",
+    responses(
+        (status = 200, description = "All budgets reset")
+    ),
+    tag = "tools"
+)]
+pub async fn reset_budgets_handler(
+    State(runtime): State<Arc<ToolRuntime>>,
+) -> StatusCode {
+    runtime.reset_budgets();
+    tracing::info!("Tools Console: All budgets reset");
+    StatusCode::OK
+}
+
+/// Reset budget usage for a specific tool
+#[utoipa::path(
+    delete,
+    path = "/runtime/tools/{operation_id}/budget",
+    params(
+        ("operation_id" = String, Path, description = "Tool operation ID")
+    ),
+    responses(
+        (status = 200, description = "Budget reset")
+    ),
+    tag = "tools"
+)]
+pub async fn reset_tool_budget_handler(
+    State(runtime): State<Arc<ToolRuntime>>,
+    Path(operation_id): Path<String>,
+) -> StatusCode {
+    runtime.reset_budget(&operation_id);
+    tracing::info!("Tools Console: Budget for {} reset", operation_id);
+    StatusCode::OK
+}
+
 /// Get all fixtures
 #[utoipa::path(
     get,
@@ -353,6 +594,12 @@ pub async fn import_fixtures_handler(
     
     let fixtures = runtime.export_fixtures();
     let count = runtime.get_fixtures().count();
+    crate::audit::record(
+        "tools.fixtures_import",
+        serde_json::json!({ "count": count }),
+        true,
+        "Fixtures imported",
+    );
     Json(FixturesResponse { fixtures, count })
 }
 
@@ -373,6 +620,474 @@ pub async fn clear_fixtures_handler(
     StatusCode::OK
 }
 
+/// List registered macro tools
+#[utoipa::path(
+    get,
+    path = "/runtime/macros",
+    responses(
+        (status = 200, description = "Registered composite macro tools", body = MacrosResponse)
+    ),
+    tag = "tools"
+)]
+pub async fn list_macros_handler(
+    State(runtime): State<Arc<ToolRuntime>>,
+) -> Json<MacrosResponse> {
+    let macros = runtime.list_macros();
+    let total = macros.len();
+    Json(MacrosResponse { macros, total })
+}
+
+/// Register (or replace) a composite macro tool
+///
+/// The macro becomes callable like any other tool via its name.
+#[utoipa::path(
+    post,
+    path = "/runtime/macros",
+    request_body = super::MacroTool,
+    responses(
+        (status = 200, description = "Macro registered"),
+        (status = 400, description = "Invalid macro definition", body = ToolErrorResponse)
+    ),
+    tag = "tools"
+)]
+pub async fn register_macro_handler(
+    State(runtime): State<Arc<ToolRuntime>>,
+    Json(macro_tool): Json<super::MacroTool>,
+) -> Result<StatusCode, (StatusCode, Json<ToolErrorResponse>)> {
+    let name = macro_tool.name.clone();
+    match runtime.register_macro(macro_tool) {
+        Ok(()) => {
+            tracing::info!("Tools Console: Registered macro tool '{}'", name);
+            Ok(StatusCode::OK)
+        }
+        Err(e) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(ToolErrorResponse { error: e, code: 400 }),
+        )),
+    }
+}
+
+/// Delete a registered macro tool
+#[utoipa::path(
+    delete,
+    path = "/runtime/macros/{name}",
+    params(
+        ("name" = String, Path, description = "Macro name")
+    ),
+    responses(
+        (status = 200, description = "Macro deleted"),
+        (status = 404, description = "Macro not found", body = ToolErrorResponse)
+    ),
+    tag = "tools"
+)]
+pub async fn delete_macro_handler(
+    State(runtime): State<Arc<ToolRuntime>>,
+    Path(name): Path<String>,
+) -> Result<StatusCode, (StatusCode, Json<ToolErrorResponse>)> {
+    match runtime.remove_macro(&name) {
+        Ok(()) => {
+            tracing::info!("Tools Console: Deleted macro tool '{}'", name);
+            Ok(StatusCode::OK)
+        }
+        Err(e) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ToolErrorResponse { error: e, code: 404 }),
+        )),
+    }
+}
+
+/// Reset one tool's circuit breaker (Dev Console alias)
+#[utoipa::path(
+    post,
+    path = "/runtime/breakers/{operation_id}/reset",
+    params(
+        ("operation_id" = String, Path, description = "The operation ID whose breaker to reset")
+    ),
+    responses(
+        (status = 200, description = "Circuit breaker reset")
+    ),
+    tag = "tools"
+)]
+pub async fn reset_breaker_handler(
+    State(runtime): State<Arc<ToolRuntime>>,
+    Path(operation_id): Path<String>,
+) -> StatusCode {
+    runtime.reset_circuit_breaker(&operation_id);
+    tracing::info!("Tools Console: Reset circuit breaker for {}", operation_id);
+    StatusCode::OK
+}
+
+/// List available runtime profiles
+#[utoipa::path(
+    get,
+    path = "/runtime/profiles",
+    responses(
+        (status = 200, description = "Built-in and saved runtime profiles", body = ProfilesResponse)
+    ),
+    tag = "tools"
+)]
+pub async fn list_profiles_handler(
+    State(runtime): State<Arc<ToolRuntime>>,
+) -> Json<ProfilesResponse> {
+    let profiles = runtime.list_profiles();
+    let total = profiles.len();
+    Json(ProfilesResponse { profiles, total })
+}
+
+/// Save the current runtime configuration as a named profile
+#[utoipa::path(
+    post,
+    path = "/runtime/profiles",
+    request_body = SaveProfileRequest,
+    responses(
+        (status = 200, description = "Profile saved"),
+        (status = 400, description = "Invalid or reserved profile name", body = ToolErrorResponse)
+    ),
+    tag = "tools"
+)]
+pub async fn save_profile_handler(
+    State(runtime): State<Arc<ToolRuntime>>,
+    Json(request): Json<SaveProfileRequest>,
+) -> Result<StatusCode, (StatusCode, Json<ToolErrorResponse>)> {
+    match runtime.save_current_profile(&request.name, &request.description) {
+        Ok(()) => {
+            tracing::info!("Tools Console: Saved runtime profile '{}'", request.name);
+            Ok(StatusCode::OK)
+        }
+        Err(e) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(ToolErrorResponse { error: e, code: 400 }),
+        )),
+    }
+}
+
+/// Activate a runtime profile by name
+///
+/// Replaces the current global and per-tool configuration with the profile's.
+#[utoipa::path(
+    post,
+    path = "/runtime/profiles/{name}/activate",
+    params(
+        ("name" = String, Path, description = "Profile name (built-in or saved)")
+    ),
+    responses(
+        (status = 200, description = "Profile activated"),
+        (status = 404, description = "Profile not found", body = ToolErrorResponse)
+    ),
+    tag = "tools"
+)]
+pub async fn activate_profile_handler(
+    State(runtime): State<Arc<ToolRuntime>>,
+    Path(name): Path<String>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<ToolErrorResponse>)> {
+    match runtime.activate_profile(&name) {
+        Ok(description) => {
+            tracing::info!("Tools Console: Activated runtime profile '{}'", name);
+            Ok(Json(serde_json::json!({
+                "name": name,
+                "description": description,
+            })))
+        }
+        Err(e) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ToolErrorResponse { error: e, code: 404 }),
+        )),
+    }
+}
+
+/// Delete a saved custom profile
+#[utoipa::path(
+    delete,
+    path = "/runtime/profiles/{name}",
+    params(
+        ("name" = String, Path, description = "Saved profile name")
+    ),
+    responses(
+        (status = 200, description = "Profile deleted"),
+        (status = 404, description = "Profile not found", body = ToolErrorResponse)
+    ),
+    tag = "tools"
+)]
+pub async fn delete_profile_handler(
+    Path(name): Path<String>,
+) -> Result<StatusCode, (StatusCode, Json<ToolErrorResponse>)> {
+    match super::delete_profile(&name) {
+        Ok(()) => {
+            tracing::info!("Tools Console: Deleted runtime profile '{}'", name);
+            Ok(StatusCode::OK)
+        }
+        Err(e) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ToolErrorResponse { error: e, code: 404 }),
+        )),
+    }
+}
+
+/// Export execution logs as JSONL
+///
+/// One `ToolExecutionLog` JSON object per line, suitable for archiving or
+/// piping into jq.
+#[utoipa::path(
+    get,
+    path = "/runtime/logs/export",
+    responses(
+        (status = 200, description = "Execution logs as JSON Lines", content_type = "application/x-ndjson")
+    ),
+    tag = "tools"
+)]
+pub async fn export_logs_handler(
+    State(runtime): State<Arc<ToolRuntime>>,
+) -> impl axum::response::IntoResponse {
+    let logs = runtime.get_execution_logs();
+    let mut body = String::new();
+    for entry in &logs {
+        if let Ok(line) = serde_json::to_string(entry) {
+            body.push_str(&line);
+            body.push('\n');
+        }
+    }
+    (
+        [(axum::http::header::CONTENT_TYPE, "application/x-ndjson")],
+        body,
+    )
+}
+
+/// Replay a logged tool call and diff against the recorded response
+///
+/// Re-executes the call with the same arguments (optionally forced into
+/// dry-run) and reports every JSON-pointer path where the fresh response
+/// differs from the recorded one — useful for spotting regressions.
+#[utoipa::path(
+    post,
+    path = "/runtime/logs/{id}/replay",
+    params(
+        ("id" = u64, Path, description = "Execution log entry id")
+    ),
+    request_body = ReplayLogRequest,
+    responses(
+        (status = 200, description = "Replay result with response diff", body = ReplayLogResponse),
+        (status = 404, description = "Log entry not found", body = ToolErrorResponse)
+    ),
+    tag = "tools"
+)]
+pub async fn replay_log_handler(
+    State(runtime): State<Arc<ToolRuntime>>,
+    Path(id): Path<u64>,
+    request: Option<Json<ReplayLogRequest>>,
+) -> Result<Json<ReplayLogResponse>, (StatusCode, Json<ToolErrorResponse>)> {
+    let Some(entry) = runtime.find_execution_log(id) else {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ToolErrorResponse {
+                error: format!("Log entry {} not found", id),
+                code: 404,
+            }),
+        ));
+    };
+
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    if let Some(dry_run) = request.dry_run {
+        let mut config = runtime.get_tool_config(&entry.operation_id);
+        config.dry_run = dry_run;
+        runtime.configure_tool(&entry.operation_id, config);
+    }
+
+    tracing::info!(
+        "Tools Console: Replaying log entry {} ({})",
+        id,
+        entry.operation_id
+    );
+    let result = runtime
+        .call(&entry.operation_id, entry.args.clone(), ToolCallSource::UiConsole)
+        .await;
+
+    let mut differences = Vec::new();
+    diff_json(
+        entry.response.as_ref().unwrap_or(&serde_json::Value::Null),
+        result.data.as_ref().unwrap_or(&serde_json::Value::Null),
+        "",
+        &mut differences,
+    );
+
+    Ok(Json(ReplayLogResponse {
+        log_id: id,
+        operation_id: entry.operation_id,
+        result,
+        matches: differences.is_empty(),
+        differences,
+    }))
+}
+
+/// Collect JSON-pointer paths where `new` differs from `old`
+fn diff_json(old: &serde_json::Value, new: &serde_json::Value, path: &str, out: &mut Vec<String>) {
+    use serde_json::Value;
+    match (old, new) {
+        (Value::Object(old_map), Value::Object(new_map)) => {
+            for (key, old_value) in old_map {
+                let child = format!("{}/{}", path, key);
+                match new_map.get(key) {
+                    Some(new_value) => diff_json(old_value, new_value, &child, out),
+                    None => out.push(format!("{}: removed", child)),
+                }
+            }
+            for key in new_map.keys() {
+                if !old_map.contains_key(key) {
+                    out.push(format!("{}/{}: added", path, key));
+                }
+            }
+        }
+        (Value::Array(old_arr), Value::Array(new_arr)) => {
+            for (i, old_value) in old_arr.iter().enumerate() {
+                let child = format!("{}/{}", path, i);
+                match new_arr.get(i) {
+                    Some(new_value) => diff_json(old_value, new_value, &child, out),
+                    None => out.push(format!("{}: removed", child)),
+                }
+            }
+            for i in old_arr.len()..new_arr.len() {
+                out.push(format!("{}/{}: added", path, i));
+            }
+        }
+        (old, new) if old != new => {
+            let root = if path.is_empty() { "/" } else { path };
+            out.push(format!("{}: {} -> {}", root, old, new));
+        }
+        _ => {}
+    }
+}
+
+/// Get per-tool execution metrics
+///
+/// Aggregates the execution log into count, success rate, p50/p95/p99
+/// latency and last error per tool, over an optional rolling window.
+#[utoipa::path(
+    get,
+    path = "/runtime/metrics",
+    params(MetricsQuery),
+    responses(
+        (status = 200, description = "Per-tool execution metrics", body = RuntimeMetricsResponse)
+    ),
+    tag = "tools"
+)]
+pub async fn get_runtime_metrics_handler(
+    State(runtime): State<Arc<ToolRuntime>>,
+    Query(query): Query<MetricsQuery>,
+) -> Json<RuntimeMetricsResponse> {
+    let metrics = runtime.get_metrics(query.window_minutes);
+    let total = metrics.len();
+    Json(RuntimeMetricsResponse {
+        metrics,
+        total,
+        window_minutes: query.window_minutes,
+    })
+}
+
+/// List fixture sessions and saved sets
+#[utoipa::path(
+    get,
+    path = "/runtime/fixtures/sessions",
+    responses(
+        (status = 200, description = "Active session and saved fixture sets", body = FixtureSessionsResponse)
+    ),
+    tag = "tools"
+)]
+pub async fn list_fixture_sessions_handler(
+    State(runtime): State<Arc<ToolRuntime>>,
+) -> Json<FixtureSessionsResponse> {
+    let active = runtime.active_recording_session();
+    Json(FixtureSessionsResponse {
+        active: active.as_ref().map(|(name, _)| name.clone()),
+        active_since: active.map(|(_, started_at)| started_at),
+        sets: super::list_session_sets(),
+    })
+}
+
+/// Start a named fixture recording session
+///
+/// While a session is active, every tool response is captured into the named
+/// set regardless of per-tool record flags. Stop the session to persist it.
+#[utoipa::path(
+    post,
+    path = "/runtime/fixtures/sessions",
+    request_body = StartSessionRequest,
+    responses(
+        (status = 200, description = "Recording session started"),
+        (status = 400, description = "Invalid name or session already active", body = ToolErrorResponse)
+    ),
+    tag = "tools"
+)]
+pub async fn start_fixture_session_handler(
+    State(runtime): State<Arc<ToolRuntime>>,
+    Json(request): Json<StartSessionRequest>,
+) -> Result<StatusCode, (StatusCode, Json<ToolErrorResponse>)> {
+    match runtime.start_recording_session(&request.name) {
+        Ok(()) => {
+            tracing::info!("Tools Console: Recording session '{}' started", request.name);
+            Ok(StatusCode::OK)
+        }
+        Err(e) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(ToolErrorResponse { error: e, code: 400 }),
+        )),
+    }
+}
+
+/// Stop the active recording session and save it as a named set
+#[utoipa::path(
+    delete,
+    path = "/runtime/fixtures/sessions",
+    responses(
+        (status = 200, description = "Session stopped and saved", body = FixtureSetResponse),
+        (status = 400, description = "No active session", body = ToolErrorResponse)
+    ),
+    tag = "tools"
+)]
+pub async fn stop_fixture_session_handler(
+    State(runtime): State<Arc<ToolRuntime>>,
+) -> Result<Json<FixtureSetResponse>, (StatusCode, Json<ToolErrorResponse>)> {
+    match runtime.stop_recording_session() {
+        Ok((name, count)) => {
+            tracing::info!("Tools Console: Recording session '{}' saved ({} fixtures)", name, count);
+            Ok(Json(FixtureSetResponse { name, count }))
+        }
+        Err(e) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(ToolErrorResponse { error: e, code: 400 }),
+        )),
+    }
+}
+
+/// Activate a saved fixture set for deterministic demo/offline mode
+///
+/// Loads the set as the live fixtures and turns on global fixture replay.
+#[utoipa::path(
+    post,
+    path = "/runtime/fixtures/sessions/{name}/activate",
+    params(
+        ("name" = String, Path, description = "Saved fixture set name")
+    ),
+    responses(
+        (status = 200, description = "Fixture set activated", body = FixtureSetResponse),
+        (status = 400, description = "Set not found or unreadable", body = ToolErrorResponse)
+    ),
+    tag = "tools"
+)]
+pub async fn activate_fixture_set_handler(
+    State(runtime): State<Arc<ToolRuntime>>,
+    Path(name): Path<String>,
+) -> Result<Json<FixtureSetResponse>, (StatusCode, Json<ToolErrorResponse>)> {
+    match runtime.activate_fixture_set(&name) {
+        Ok(count) => {
+            tracing::info!("Tools Console: Fixture set '{}' activated", name);
+            Ok(Json(FixtureSetResponse { name, count }))
+        }
+        Err(e) => Err((
+            StatusCode::BAD_REQUEST,
+            Json(ToolErrorResponse { error: e, code: 400 }),
+        )),
+    }
+}
+
 /// Enable all tools
 #[utoipa::path(
     post,
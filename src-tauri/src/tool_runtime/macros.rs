@@ -0,0 +1,297 @@
+//! Composite "macro" tools for ToolRuntime
+//!
+//! A macro tool is a named sequence of existing operations executed as one
+//! tool call (e.g. `latest_review` = `GET /latest` then `POST
+//! /changes/.../summarize`). Step arguments support mapping from the macro's
+//! own invocation args and from earlier step responses via JSON pointers:
+//! a string value `"$args/taskId"` resolves against the invocation args and
+//! `"$0/data/taskId"` against step 0's response. Macros are registered into
+//! `list_tools`, callable through the normal `call()` choke-point, and
+//! persisted to `%APPDATA%/jira-dashboard/macros.json`.
+
+use super::{ToolCallResult, ToolCallSource, ToolRuntime, ToolRuntimeError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Instant;
+
+/// One step of a macro tool
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MacroStep {
+    /// The operation to invoke (e.g. "get_latest")
+    pub operation_id: String,
+    /// Arguments for the step; string values starting with `$args/` or
+    /// `$<step-index>/` are resolved as JSON pointers before the call
+    #[serde(default)]
+    pub args: serde_json::Value,
+}
+
+/// A composite tool made of a sequence of existing operations
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct MacroTool {
+    /// Macro name (its operation_id for agents)
+    pub name: String,
+    /// Human-readable description shown in list_tools
+    #[serde(default)]
+    pub description: String,
+    /// Steps executed in order; a failing step aborts the macro
+    pub steps: Vec<MacroStep>,
+}
+
+/// Return the macros file path, creating the directory if needed.
+fn macros_path() -> Option<PathBuf> {
+    let appdata = std::env::var("APPDATA").ok()?;
+    let dir = PathBuf::from(appdata).join("jira-dashboard");
+    if !dir.exists() {
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            log::warn!("Failed to create macros dir {:?}: {}", dir, e);
+            return None;
+        }
+    }
+    Some(dir.join("macros.json"))
+}
+
+/// Load persisted macros from disk.
+fn load_macros() -> HashMap<String, MacroTool> {
+    let Some(path) = macros_path() else {
+        return HashMap::new();
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+            log::warn!("Failed to parse macros file {:?}: {}", path, e);
+            HashMap::new()
+        }),
+        Err(_) => HashMap::new(), // first run — no file yet
+    }
+}
+
+/// Save all macros to disk.
+fn save_macros(macros: &HashMap<String, MacroTool>) {
+    let Some(path) = macros_path() else {
+        return;
+    };
+    match serde_json::to_string_pretty(macros) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                log::warn!("Failed to write macros file {:?}: {}", path, e);
+            }
+        }
+        Err(e) => log::warn!("Failed to serialize macros: {}", e),
+    }
+}
+
+/// Resolve mapping placeholders in a step's args.
+///
+/// String values `"$args/<pointer>"` are looked up in the macro invocation
+/// args; `"$<index>/<pointer>"` in the given step's response. Unresolvable
+/// references become `null`. Objects and arrays are resolved recursively.
+fn resolve_step_args(
+    template: &serde_json::Value,
+    invocation_args: &serde_json::Value,
+    step_results: &[serde_json::Value],
+) -> serde_json::Value {
+    match template {
+        serde_json::Value::String(s) if s.starts_with('$') => {
+            let reference = &s[1..];
+            let (source, pointer) = match reference.split_once('/') {
+                Some((head, rest)) => (head, format!("/{}", rest)),
+                None => (reference, String::new()),
+            };
+            let root = if source == "args" {
+                Some(invocation_args)
+            } else {
+                source.parse::<usize>().ok().and_then(|i| step_results.get(i))
+            };
+            root.and_then(|v| v.pointer(&pointer))
+                .cloned()
+                .unwrap_or(serde_json::Value::Null)
+        }
+        serde_json::Value::Object(map) => serde_json::Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), resolve_step_args(v, invocation_args, step_results)))
+                .collect(),
+        ),
+        serde_json::Value::Array(items) => serde_json::Value::Array(
+            items
+                .iter()
+                .map(|v| resolve_step_args(v, invocation_args, step_results))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+impl ToolRuntime {
+    /// Whether an operation_id names a registered macro
+    pub fn is_macro(&self, operation_id: &str) -> bool {
+        self.macros.read().contains_key(operation_id)
+    }
+
+    /// List registered macros
+    pub fn list_macros(&self) -> Vec<MacroTool> {
+        let mut macros: Vec<MacroTool> = self.macros.read().values().cloned().collect();
+        macros.sort_by(|a, b| a.name.cmp(&b.name));
+        macros
+    }
+
+    /// Register (or replace) a macro tool
+    pub fn register_macro(&self, macro_tool: MacroTool) -> Result<(), String> {
+        if !super::valid_session_name(&macro_tool.name) {
+            return Err(format!(
+                "Invalid macro name '{}': use letters, digits, '-' or '_'",
+                macro_tool.name
+            ));
+        }
+        if macro_tool.steps.is_empty() {
+            return Err("A macro needs at least one step".to_string());
+        }
+        if macro_tool.steps.iter().any(|s| s.operation_id == macro_tool.name) {
+            return Err("A macro cannot invoke itself".to_string());
+        }
+        let mut macros = self.macros.write();
+        macros.insert(macro_tool.name.clone(), macro_tool);
+        save_macros(&macros);
+        Ok(())
+    }
+
+    /// Remove a registered macro
+    pub fn remove_macro(&self, name: &str) -> Result<(), String> {
+        let mut macros = self.macros.write();
+        if macros.remove(name).is_none() {
+            return Err(format!("Macro '{}' not found", name));
+        }
+        save_macros(&macros);
+        Ok(())
+    }
+
+    /// Load persisted macros from disk (called once at startup)
+    pub fn load_persisted_macros(&self) {
+        let macros = load_macros();
+        if !macros.is_empty() {
+            log::info!("Loaded {} macro tools from disk", macros.len());
+        }
+        *self.macros.write() = macros;
+    }
+
+    /// Execute a macro: run its steps in order, mapping arguments between
+    /// them, and return the last step's data with all step responses attached.
+    pub(super) async fn call_macro(
+        &self,
+        name: &str,
+        args: serde_json::Value,
+        source: ToolCallSource,
+    ) -> ToolCallResult {
+        let start = Instant::now();
+        let Some(macro_tool) = self.macros.read().get(name).cloned() else {
+            return self.log_and_return(
+                name,
+                source,
+                &args,
+                Err(ToolRuntimeError::ToolNotFound(name.to_string())),
+                start,
+                false,
+                false,
+                None,
+            );
+        };
+
+        let mut step_results: Vec<serde_json::Value> = Vec::new();
+        for (i, step) in macro_tool.steps.iter().enumerate() {
+            let step_args = resolve_step_args(&step.args, &args, &step_results);
+            // Box the recursive call so the macro future stays finitely sized
+            let result =
+                Box::pin(self.call(&step.operation_id, step_args, source.clone())).await;
+            if !result.success {
+                let reason = result.error.unwrap_or_else(|| "unknown error".to_string());
+                return self.log_and_return(
+                    name,
+                    source,
+                    &args,
+                    Err(ToolRuntimeError::InternalError(format!(
+                        "Macro step {} ({}) failed: {}",
+                        i, step.operation_id, reason
+                    ))),
+                    start,
+                    false,
+                    false,
+                    None,
+                );
+            }
+            step_results.push(result.data.unwrap_or(serde_json::Value::Null));
+        }
+
+        let response = serde_json::json!({
+            "macro": name,
+            "data": step_results.last().cloned().unwrap_or(serde_json::Value::Null),
+            "steps": step_results,
+        });
+        self.log_and_return(name, source, &args, Ok(response), start, false, false, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::AppState;
+    use std::sync::Arc;
+
+    fn create_test_runtime() -> Arc<ToolRuntime> {
+        let state = AppState::new(
+            "test-token".to_string(),
+            "https://jira.test".to_string(),
+            "test@test.com".to_string(),
+            "api-token".to_string(),
+            "gemini-key".to_string(),
+        );
+        ToolRuntime::new(state)
+    }
+
+    #[test]
+    fn test_resolve_step_args_mapping() {
+        let template = serde_json::json!({
+            "taskId": "$args/taskId",
+            "summary": "$0/latest/prompt",
+            "nested": {"workspace": "$0/workspaceId"},
+            "literal": 42,
+            "missing": "$1/nope"
+        });
+        let invocation_args = serde_json::json!({"taskId": "abc123"});
+        let step_results = vec![serde_json::json!({
+            "latest": {"prompt": "Fix the bug"},
+            "workspaceId": "ws-1"
+        })];
+
+        let resolved = resolve_step_args(&template, &invocation_args, &step_results);
+        assert_eq!(resolved["taskId"], "abc123");
+        assert_eq!(resolved["summary"], "Fix the bug");
+        assert_eq!(resolved["nested"]["workspace"], "ws-1");
+        assert_eq!(resolved["literal"], 42);
+        assert!(resolved["missing"].is_null());
+    }
+
+    #[test]
+    fn test_register_macro_validation() {
+        let runtime = create_test_runtime();
+
+        // Empty steps rejected
+        let empty = MacroTool {
+            name: "empty".to_string(),
+            description: String::new(),
+            steps: Vec::new(),
+        };
+        assert!(runtime.register_macro(empty).is_err());
+
+        // Self-referencing macros rejected
+        let recursive = MacroTool {
+            name: "loop".to_string(),
+            description: String::new(),
+            steps: vec![MacroStep {
+                operation_id: "loop".to_string(),
+                args: serde_json::Value::Null,
+            }],
+        };
+        assert!(runtime.register_macro(recursive).is_err());
+    }
+}
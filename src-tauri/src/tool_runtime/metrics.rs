@@ -0,0 +1,118 @@
+//! Per-tool execution metrics for ToolRuntime
+//!
+//! Aggregates the in-memory `ToolExecutionLog` into per-tool summaries:
+//! call count, success rate, p50/p95/p99 latency and the most recent error.
+//! The aggregation window is configurable per request (rolling minutes over
+//! the retained log), so slow or flaky tools are visible at a glance via
+//! `GET /runtime/metrics`.
+
+use super::ToolRuntime;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Aggregated metrics for one tool
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolMetrics {
+    /// Total calls in the window
+    pub count: usize,
+    /// Successful calls in the window
+    pub success_count: usize,
+    /// Success rate in the window (0.0 - 1.0)
+    pub success_rate: f64,
+    /// Median duration in milliseconds
+    pub p50_ms: u64,
+    /// 95th percentile duration in milliseconds
+    pub p95_ms: u64,
+    /// 99th percentile duration in milliseconds
+    pub p99_ms: u64,
+    /// Mean duration in milliseconds
+    pub avg_ms: u64,
+    /// Most recent error message in the window, if any
+    pub last_error: Option<String>,
+    /// ISO 8601 timestamp of the most recent call in the window
+    pub last_call_at: Option<String>,
+}
+
+/// Pick a percentile from a sorted duration list (nearest-rank on n-1)
+fn percentile(sorted: &[u64], pct: f64) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+    let idx = ((pct / 100.0) * (sorted.len() - 1) as f64).round() as usize;
+    sorted[idx.min(sorted.len() - 1)]
+}
+
+impl ToolRuntime {
+    /// Aggregate execution logs into per-tool metrics.
+    ///
+    /// `window_minutes = None` aggregates everything still in the log;
+    /// otherwise only entries newer than the rolling window count.
+    pub fn get_metrics(&self, window_minutes: Option<u64>) -> HashMap<String, ToolMetrics> {
+        let cutoff = window_minutes
+            .map(|m| chrono::Utc::now() - chrono::Duration::minutes(m as i64));
+
+        // Group durations and outcomes per tool
+        let mut per_tool: HashMap<String, Vec<&super::ToolExecutionLog>> = HashMap::new();
+        let log = self.execution_log.read();
+        for entry in log.iter() {
+            if let Some(cutoff) = cutoff {
+                match chrono::DateTime::parse_from_rfc3339(&entry.timestamp) {
+                    Ok(ts) if ts.with_timezone(&chrono::Utc) < cutoff => continue,
+                    Ok(_) => {}
+                    Err(_) => {} // unparseable timestamps stay included
+                }
+            }
+            per_tool
+                .entry(entry.operation_id.clone())
+                .or_default()
+                .push(entry);
+        }
+
+        per_tool
+            .into_iter()
+            .map(|(operation_id, entries)| {
+                let count = entries.len();
+                let success_count = entries.iter().filter(|e| e.success).count();
+                let mut durations: Vec<u64> = entries.iter().map(|e| e.duration_ms).collect();
+                durations.sort_unstable();
+                let total: u64 = durations.iter().sum();
+
+                // Log entries are appended in order, so the last one is newest
+                let last_error = entries
+                    .iter()
+                    .rev()
+                    .find_map(|e| e.error.clone());
+                let last_call_at = entries.last().map(|e| e.timestamp.clone());
+
+                let metrics = ToolMetrics {
+                    count,
+                    success_count,
+                    success_rate: success_count as f64 / count as f64,
+                    p50_ms: percentile(&durations, 50.0),
+                    p95_ms: percentile(&durations, 95.0),
+                    p99_ms: percentile(&durations, 99.0),
+                    avg_ms: total / count as u64,
+                    last_error,
+                    last_call_at,
+                };
+                (operation_id, metrics)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile_nearest_rank() {
+        let sorted: Vec<u64> = (1..=100).collect();
+        assert_eq!(percentile(&sorted, 50.0), 51);
+        assert_eq!(percentile(&sorted, 95.0), 95);
+        assert_eq!(percentile(&sorted, 99.0), 99);
+        assert_eq!(percentile(&[42], 95.0), 42);
+        assert_eq!(percentile(&[], 50.0), 0);
+    }
+}
@@ -36,6 +36,13 @@ mod executor;
 mod validator;
 mod fixtures;
 mod circuit_breaker;
+mod budgets;
+mod metrics;
+mod profiles;
+mod eval;
+mod macros;
+mod truncation;
+pub mod persistence;
 pub mod handlers;
 
 pub use types::*;
@@ -44,6 +51,12 @@ pub use executor::*;
 pub use validator::*;
 pub use fixtures::*;
 pub use circuit_breaker::*;
+pub use budgets::*;
+pub use metrics::*;
+pub use profiles::*;
+pub use eval::*;
+pub use macros::*;
+pub use truncation::*;
 pub use handlers::*;
 
 use crate::state::AppState;
@@ -83,8 +96,14 @@ pub struct ToolRuntime {
     log_counter: RwLock<u64>,
     /// Circuit breaker per tool
     circuit_breakers: RwLock<HashMap<String, CircuitBreakerState>>,
+    /// Budget usage per tool
+    budgets: RwLock<HashMap<String, BudgetState>>,
     /// Fixtures storage
     fixtures: RwLock<FixturesStorage>,
+    /// Registered composite macro tools
+    macros: RwLock<HashMap<String, MacroTool>>,
+    /// Active named fixture recording session
+    recording_session: RwLock<Option<RecordingSession>>,
     /// OpenAPI spec cache for validation
     openapi_spec: RwLock<Option<serde_json::Value>>,
 }
@@ -99,7 +118,10 @@ impl ToolRuntime {
             execution_log: RwLock::new(Vec::new()),
             log_counter: RwLock::new(0),
             circuit_breakers: RwLock::new(HashMap::new()),
+            budgets: RwLock::new(HashMap::new()),
             fixtures: RwLock::new(FixturesStorage::default()),
+            macros: RwLock::new(HashMap::new()),
+            recording_session: RwLock::new(None),
             openapi_spec: RwLock::new(None),
         })
     }
@@ -114,9 +136,23 @@ impl ToolRuntime {
         *self.openapi_spec.write() = Some(spec);
     }
 
+    /// Load persisted configuration from disk (called once at startup)
+    pub fn load_persisted_config(&self) {
+        if let Some(persisted) = persistence::load() {
+            *self.global_config.write() = persisted.config;
+            *self.tool_configs.write() = persisted.tool_configs;
+        }
+    }
+
+    /// Write the current global + per-tool configuration to disk
+    fn persist_config(&self) {
+        persistence::save(&self.global_config.read(), &self.tool_configs.read());
+    }
+
     /// Configure a specific tool
     pub fn configure_tool(&self, operation_id: &str, config: ToolConfig) {
         self.tool_configs.write().insert(operation_id.to_string(), config);
+        self.persist_config();
     }
 
     /// Get tool configuration (with defaults)
@@ -131,6 +167,18 @@ impl ToolRuntime {
     /// Update global configuration
     pub fn set_global_config(&self, config: GlobalRuntimeConfig) {
         *self.global_config.write() = config;
+        self.persist_config();
+    }
+
+    /// Replace the entire configuration (global + all per-tool configs)
+    pub fn set_full_config(
+        &self,
+        config: GlobalRuntimeConfig,
+        tool_configs: HashMap<String, ToolConfig>,
+    ) {
+        *self.global_config.write() = config;
+        *self.tool_configs.write() = tool_configs;
+        self.persist_config();
     }
 
     /// Get global configuration
@@ -153,6 +201,11 @@ impl ToolRuntime {
         args: serde_json::Value,
         source: ToolCallSource,
     ) -> ToolCallResult {
+        // Step 0: Composite macro tools run their own step pipeline
+        if self.is_macro(operation_id) {
+            return self.call_macro(operation_id, args, source).await;
+        }
+
         let start = Instant::now();
         let tool_config = self.get_tool_config(operation_id);
         let global_config = self.get_global_config();
@@ -185,6 +238,20 @@ impl ToolRuntime {
             );
         }
 
+        // Step 2b: Check budget (rate/quota limits)
+        if let Err(e) = self.check_budget(operation_id) {
+            return self.log_and_return(
+                operation_id,
+                source,
+                &args,
+                Err(e),
+                start,
+                false,
+                false,
+                None,
+            );
+        }
+
         // Step 3: Apply arg clamps
         let clamped_args = self.apply_arg_clamps(operation_id, args.clone(), &tool_config);
 
@@ -244,14 +311,26 @@ impl ToolRuntime {
         // Step 7: Execute the actual tool
         let result = self.execute_tool(operation_id, &clamped_args).await;
 
-        // Step 8: Update circuit breaker state
+        // Step 7b: Clamp over-sized responses before they reach the agent
+        let result = match (result, tool_config.max_response_bytes) {
+            (Ok(response), Some(max_bytes)) => Ok(truncation::truncate_response(
+                response,
+                max_bytes,
+                tool_config.truncation_strategy.unwrap_or_default(),
+            )),
+            (other, _) => other,
+        };
+
+        // Step 8: Update circuit breaker state and budget usage
         self.update_circuit_breaker(operation_id, result.is_ok());
+        self.record_budget_usage(operation_id, start.elapsed().as_millis() as u64);
 
-        // Step 9: Record fixture if enabled
-        if tool_config.record_fixtures && result.is_ok() {
-            if let Ok(ref response) = result {
+        // Step 9: Record fixture if enabled (named sessions capture everything)
+        if let Ok(ref response) = result {
+            if tool_config.record_fixtures {
                 self.record_fixture(operation_id, &clamped_args, response.clone());
             }
+            self.record_session_fixture(operation_id, &clamped_args, response.clone());
         }
 
         // Step 10: Validate response against OpenAPI schema (if enabled)
@@ -330,10 +409,11 @@ impl ToolRuntime {
         let mut log = self.execution_log.write();
         log.push(log_entry);
 
-        // Keep only last 500 entries
+        // Keep only the configured number of entries
+        let cap = crate::config::current().logging.execution_log_entries;
         let len = log.len();
-        if len > 500 {
-            log.drain(0..len - 500);
+        if len > cap {
+            log.drain(0..len - cap);
         }
 
         // Build result
@@ -358,6 +438,11 @@ impl ToolRuntime {
         self.execution_log.write().clear();
     }
 
+    /// Find a single execution log entry by id
+    pub fn find_execution_log(&self, id: u64) -> Option<ToolExecutionLog> {
+        self.execution_log.read().iter().find(|e| e.id == id).cloned()
+    }
+
     /// List all available tools from OpenAPI spec
     pub fn list_tools(&self) -> Vec<ToolInfo> {
         let spec = self.openapi_spec.read();
@@ -416,14 +501,122 @@ impl ToolRuntime {
             }
         }
 
+        // Macro tools appear alongside spec-derived tools
+        for macro_tool in self.list_macros() {
+            let config = self.get_tool_config(&macro_tool.name);
+            tools.push(ToolInfo {
+                operation_id: macro_tool.name.clone(),
+                method: "MACRO".to_string(),
+                path: format!("(macro, {} steps)", macro_tool.steps.len()),
+                description: macro_tool.description.clone(),
+                tags: vec!["macro".to_string()],
+                config,
+            });
+        }
+
         tools
     }
 
+    /// Build Gemini `functionDeclarations` for every enabled tool.
+    ///
+    /// Each declaration merges the operation's query parameters and
+    /// requestBody properties into a single object schema, resolved against
+    /// the spec's components and reduced to the schema subset the Gemini
+    /// function-calling API accepts. Disabled tools are omitted so the model
+    /// never sees a tool it is not allowed to call.
+    pub fn function_declarations(&self) -> Vec<serde_json::Value> {
+        let spec = match self.openapi_spec.read().clone() {
+            Some(spec) => spec,
+            None => return Vec::new(),
+        };
+
+        let mut declarations = Vec::new();
+        for tool in self.list_tools() {
+            if !tool.config.enabled {
+                continue;
+            }
+
+            // Macro tools have no spec entry; declare them without parameters
+            let operation = spec
+                .get("paths")
+                .and_then(|p| p.get(&tool.path))
+                .and_then(|methods| methods.get(tool.method.to_lowercase()));
+
+            let mut properties = serde_json::Map::new();
+            let mut required: Vec<String> = Vec::new();
+
+            if let Some(operation) = operation {
+                if let Some(parameters) = operation.get("parameters").and_then(|p| p.as_array()) {
+                    for param in parameters {
+                        let name = match param.get("name").and_then(|n| n.as_str()) {
+                            Some(name) => name,
+                            None => continue,
+                        };
+                        let mut schema = sanitize_declaration_schema(
+                            param.get("schema").unwrap_or(&serde_json::json!({})),
+                            &spec,
+                            0,
+                        );
+                        if let (Some(obj), Some(description)) = (
+                            schema.as_object_mut(),
+                            param.get("description").and_then(|d| d.as_str()),
+                        ) {
+                            obj.entry("description".to_string())
+                                .or_insert_with(|| description.into());
+                        }
+                        properties.insert(name.to_string(), schema);
+                        if param.get("required").and_then(|r| r.as_bool()).unwrap_or(false) {
+                            required.push(name.to_string());
+                        }
+                    }
+                }
+
+                let body_schema = operation
+                    .get("requestBody")
+                    .and_then(|b| b.get("content"))
+                    .and_then(|c| c.get("application/json"))
+                    .and_then(|j| j.get("schema"));
+                if let Some(body_schema) = body_schema {
+                    let resolved = sanitize_declaration_schema(body_schema, &spec, 0);
+                    if let Some(props) = resolved.get("properties").and_then(|p| p.as_object()) {
+                        for (name, prop) in props {
+                            properties.insert(name.clone(), prop.clone());
+                        }
+                    }
+                    if let Some(names) = resolved.get("required").and_then(|r| r.as_array()) {
+                        required.extend(
+                            names.iter().filter_map(|n| n.as_str().map(String::from)),
+                        );
+                    }
+                }
+            }
+
+            let mut declaration = serde_json::json!({
+                "name": tool.operation_id,
+                "description": tool.description,
+            });
+            if !properties.is_empty() {
+                let mut parameters = serde_json::json!({
+                    "type": "object",
+                    "properties": properties,
+                });
+                if !required.is_empty() {
+                    parameters["required"] = required.into();
+                }
+                declaration["parameters"] = parameters;
+            }
+            declarations.push(declaration);
+        }
+
+        declarations
+    }
+
     /// Enable all tools
     pub fn enable_all_tools(&self) {
         for (_, config) in self.tool_configs.write().iter_mut() {
             config.enabled = true;
         }
+        self.persist_config();
     }
 
     /// Disable all tools
@@ -431,6 +624,7 @@ impl ToolRuntime {
         for (_, config) in self.tool_configs.write().iter_mut() {
             config.enabled = false;
         }
+        self.persist_config();
     }
 
     /// Reset all circuit breakers
@@ -445,7 +639,9 @@ impl ToolRuntime {
 
     /// Clear all fixtures
     pub fn clear_fixtures(&self) {
-        self.fixtures.write().clear();
+        let mut fixtures = self.fixtures.write();
+        fixtures.clear();
+        fixtures::save_all_fixtures(&fixtures);
     }
 
     /// Get all fixtures
@@ -455,6 +651,7 @@ impl ToolRuntime {
 
     /// Import fixtures from JSON
     pub fn import_fixtures(&self, fixtures: FixturesStorage) {
+        fixtures::save_all_fixtures(&fixtures);
         *self.fixtures.write() = fixtures;
     }
 
@@ -464,6 +661,78 @@ impl ToolRuntime {
     }
 }
 
+/// Reduce an OpenAPI schema to the subset the Gemini function-calling API
+/// accepts (type/format/description/nullable/enum/items/properties/required),
+/// resolving `#/components/schemas/...` references along the way. Depth-limited
+/// so self-referential schemas cannot recurse forever.
+fn sanitize_declaration_schema(
+    schema: &serde_json::Value,
+    spec: &serde_json::Value,
+    depth: usize,
+) -> serde_json::Value {
+    if depth > 6 {
+        return serde_json::json!({ "type": "string" });
+    }
+
+    if let Some(reference) = schema.get("$ref").and_then(|r| r.as_str()) {
+        let resolved = reference
+            .strip_prefix("#/components/schemas/")
+            .and_then(|name| spec.pointer(&format!("/components/schemas/{}", name)));
+        return match resolved {
+            Some(target) => sanitize_declaration_schema(target, spec, depth + 1),
+            None => serde_json::json!({ "type": "string" }),
+        };
+    }
+
+    // Combinators aren't supported by Gemini; the first variant is the best
+    // hint available
+    for combinator in ["allOf", "anyOf", "oneOf"] {
+        if let Some(first) = schema
+            .get(combinator)
+            .and_then(|v| v.as_array())
+            .and_then(|a| a.first())
+        {
+            return sanitize_declaration_schema(first, spec, depth + 1);
+        }
+    }
+
+    let mut out = serde_json::Map::new();
+    for key in ["type", "format", "description", "nullable", "enum", "required"] {
+        if let Some(value) = schema.get(key) {
+            out.insert(key.to_string(), value.clone());
+        }
+    }
+    if let Some(items) = schema.get("items") {
+        out.insert(
+            "items".to_string(),
+            sanitize_declaration_schema(items, spec, depth + 1),
+        );
+    }
+    if let Some(props) = schema.get("properties").and_then(|p| p.as_object()) {
+        let sanitized: serde_json::Map<String, serde_json::Value> = props
+            .iter()
+            .map(|(name, prop)| {
+                (
+                    name.clone(),
+                    sanitize_declaration_schema(prop, spec, depth + 1),
+                )
+            })
+            .collect();
+        out.insert("properties".to_string(), sanitized.into());
+    }
+    if !out.contains_key("type") {
+        let inferred = if out.contains_key("properties") {
+            "object"
+        } else if out.contains_key("items") {
+            "array"
+        } else {
+            "string"
+        };
+        out.insert("type".to_string(), inferred.into());
+    }
+    serde_json::Value::Object(out)
+}
+
 /// Information about a tool
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -476,3 +745,109 @@ pub struct ToolInfo {
     pub tags: Vec<String>,
     pub config: ToolConfig,
 }
+
+#[cfg(test)]
+mod function_declaration_tests {
+    use super::*;
+
+    fn runtime_with_spec() -> Arc<ToolRuntime> {
+        let state = AppState::new(
+            "test-token".to_string(),
+            "https://jira.test".to_string(),
+            "test@test.com".to_string(),
+            "api-token".to_string(),
+            "gemini-key".to_string(),
+        );
+        let runtime = ToolRuntime::new(state);
+        runtime.set_openapi_spec(serde_json::json!({
+            "paths": {
+                "/jira/list": {
+                    "get": {
+                        "summary": "List Jira issues",
+                        "parameters": [
+                            {
+                                "name": "jql",
+                                "in": "query",
+                                "required": true,
+                                "description": "JQL query string",
+                                "schema": {"type": "string"}
+                            }
+                        ]
+                    }
+                },
+                "/jira/comment": {
+                    "post": {
+                        "summary": "Add a comment",
+                        "requestBody": {
+                            "content": {
+                                "application/json": {
+                                    "schema": {"$ref": "#/components/schemas/CommentRequest"}
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "CommentRequest": {
+                        "type": "object",
+                        "required": ["issueKey"],
+                        "properties": {
+                            "issueKey": {"type": "string"},
+                            "body": {"type": "string", "example": "dropped"}
+                        }
+                    }
+                }
+            }
+        }));
+        runtime
+    }
+
+    #[test]
+    fn test_declarations_merge_params_and_body() {
+        let runtime = runtime_with_spec();
+        let declarations = runtime.function_declarations();
+        assert_eq!(declarations.len(), 2);
+
+        let list = declarations
+            .iter()
+            .find(|d| d["name"] == "get_jira_list")
+            .unwrap();
+        assert_eq!(list["description"], "List Jira issues");
+        assert_eq!(list["parameters"]["properties"]["jql"]["type"], "string");
+        assert_eq!(
+            list["parameters"]["properties"]["jql"]["description"],
+            "JQL query string"
+        );
+        assert_eq!(list["parameters"]["required"][0], "jql");
+
+        // requestBody $ref resolved, unsupported keys ("example") stripped
+        let comment = declarations
+            .iter()
+            .find(|d| d["name"] == "post_jira_comment")
+            .unwrap();
+        assert_eq!(
+            comment["parameters"]["properties"]["issueKey"]["type"],
+            "string"
+        );
+        assert!(comment["parameters"]["properties"]["body"]
+            .get("example")
+            .is_none());
+        assert_eq!(comment["parameters"]["required"][0], "issueKey");
+    }
+
+    #[test]
+    fn test_disabled_tools_are_omitted() {
+        let runtime = runtime_with_spec();
+        runtime.configure_tool(
+            "get_jira_list",
+            ToolConfig {
+                enabled: false,
+                ..Default::default()
+            },
+        );
+        let declarations = runtime.function_declarations();
+        assert!(declarations.iter().all(|d| d["name"] != "get_jira_list"));
+    }
+}
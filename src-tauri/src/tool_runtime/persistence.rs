@@ -0,0 +1,79 @@
+//! Disk persistence for ToolRuntime configuration.
+//!
+//! Tool enable/disable flags, clamps and the global runtime config are
+//! serialized to `%APPDATA%/jira-dashboard/tool_runtime_config.json` on every
+//! mutation and loaded once at startup, so Tools Console settings survive
+//! restarts. Missing or corrupt files fall back to defaults.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use super::{GlobalRuntimeConfig, ToolConfig};
+
+const CONFIG_DIR: &str = "jira-dashboard";
+const CONFIG_FILE: &str = "tool_runtime_config.json";
+
+/// The on-disk shape: global config plus all per-tool configs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersistedRuntimeConfig {
+    /// Global runtime settings
+    pub config: GlobalRuntimeConfig,
+    /// Per-tool configuration keyed by operation_id
+    #[serde(default)]
+    pub tool_configs: HashMap<String, ToolConfig>,
+}
+
+/// Return the config file path, creating the directory if needed.
+fn config_path() -> Option<PathBuf> {
+    let appdata = std::env::var("APPDATA").ok()?;
+    let dir = PathBuf::from(appdata).join(CONFIG_DIR);
+    if !dir.exists() {
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            log::warn!("Failed to create tool runtime config dir {:?}: {}", dir, e);
+            return None;
+        }
+    }
+    Some(dir.join(CONFIG_FILE))
+}
+
+/// Load the persisted runtime configuration from disk.
+pub fn load() -> Option<PersistedRuntimeConfig> {
+    let path = config_path()?;
+    match std::fs::read_to_string(&path) {
+        Ok(json) => match serde_json::from_str::<PersistedRuntimeConfig>(&json) {
+            Ok(data) => {
+                log::info!(
+                    "Loaded tool runtime config from disk ({} tool configs)",
+                    data.tool_configs.len()
+                );
+                Some(data)
+            }
+            Err(e) => {
+                log::warn!("Failed to parse tool runtime config {:?}: {}", path, e);
+                None
+            }
+        },
+        Err(_) => None, // first run — no file yet
+    }
+}
+
+/// Save the runtime configuration to disk.
+pub fn save(config: &GlobalRuntimeConfig, tool_configs: &HashMap<String, ToolConfig>) {
+    let Some(path) = config_path() else {
+        return;
+    };
+    let data = PersistedRuntimeConfig {
+        config: config.clone(),
+        tool_configs: tool_configs.clone(),
+    };
+    match serde_json::to_string_pretty(&data) {
+        Ok(json) => {
+            if let Err(e) = std::fs::write(&path, json) {
+                log::warn!("Failed to write tool runtime config {:?}: {}", path, e);
+            }
+        }
+        Err(e) => log::warn!("Failed to serialize tool runtime config: {}", e),
+    }
+}
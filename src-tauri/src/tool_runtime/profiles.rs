@@ -0,0 +1,265 @@
+//! Named runtime profiles for ToolRuntime
+//!
+//! A profile captures a full runtime configuration — global flags plus
+//! per-tool configs — under a name so whole setups can be switched in one
+//! call (`POST /runtime/profiles/{name}/activate`). Three profiles are built
+//! in: `read-only` (mutating tools disabled), `demo` (fixture replay +
+//! dry-run everywhere) and `full-access` (everything enabled with defaults).
+//! Custom profiles snapshot the current configuration and are persisted
+//! under `%APPDATA%/jira-dashboard/profiles/`.
+
+use super::{GlobalRuntimeConfig, ToolConfig, ToolRuntime};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// A named, self-contained runtime configuration
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeProfile {
+    /// Profile name (also the saved file name)
+    pub name: String,
+    /// Human-readable description
+    #[serde(default)]
+    pub description: String,
+    /// Global runtime settings to apply
+    pub config: GlobalRuntimeConfig,
+    /// Per-tool configuration keyed by operation_id
+    #[serde(default)]
+    pub tool_configs: HashMap<String, ToolConfig>,
+}
+
+/// Summary of an available profile for listing
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileInfo {
+    /// Profile name
+    pub name: String,
+    /// Human-readable description
+    pub description: String,
+    /// Whether this is a built-in profile (not deletable)
+    pub builtin: bool,
+}
+
+/// Return the saved profiles directory, creating it if needed.
+fn profiles_dir() -> Option<PathBuf> {
+    let appdata = std::env::var("APPDATA").ok()?;
+    let dir = PathBuf::from(appdata).join("jira-dashboard").join("profiles");
+    if !dir.exists() {
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            log::warn!("Failed to create profiles dir {:?}: {}", dir, e);
+            return None;
+        }
+    }
+    Some(dir)
+}
+
+/// Save a profile to disk.
+fn save_profile(profile: &RuntimeProfile) -> Result<(), String> {
+    let dir = profiles_dir().ok_or("Profiles directory unavailable")?;
+    let path = dir.join(format!("{}.json", profile.name));
+    let json = serde_json::to_string_pretty(profile)
+        .map_err(|e| format!("Failed to serialize profile: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write {:?}: {}", path, e))
+}
+
+/// Load a saved profile from disk.
+fn load_profile(name: &str) -> Result<RuntimeProfile, String> {
+    let dir = profiles_dir().ok_or("Profiles directory unavailable")?;
+    let path = dir.join(format!("{}.json", name));
+    let json = std::fs::read_to_string(&path)
+        .map_err(|_| format!("Profile '{}' not found", name))?;
+    serde_json::from_str(&json).map_err(|e| format!("Failed to parse profile '{}': {}", name, e))
+}
+
+/// Delete a saved profile from disk.
+pub fn delete_profile(name: &str) -> Result<(), String> {
+    let dir = profiles_dir().ok_or("Profiles directory unavailable")?;
+    let path = dir.join(format!("{}.json", name));
+    if !path.exists() {
+        return Err(format!("Profile '{}' not found", name));
+    }
+    std::fs::remove_file(&path).map_err(|e| format!("Failed to delete {:?}: {}", path, e))
+}
+
+/// List saved (custom) profiles on disk.
+fn list_saved_profiles() -> Vec<ProfileInfo> {
+    let Some(dir) = profiles_dir() else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+    let mut profiles = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let Ok(json) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let Ok(profile) = serde_json::from_str::<RuntimeProfile>(&json) else {
+            continue;
+        };
+        profiles.push(ProfileInfo {
+            name: profile.name,
+            description: profile.description,
+            builtin: false,
+        });
+    }
+    profiles.sort_by(|a, b| a.name.cmp(&b.name));
+    profiles
+}
+
+const BUILTIN_PROFILES: &[(&str, &str)] = &[
+    ("read-only", "Only GET tools enabled; all mutating tools disabled"),
+    ("demo", "Fixture replay on and every tool in dry-run mode"),
+    ("full-access", "All tools enabled with default settings"),
+];
+
+impl ToolRuntime {
+    /// Build one of the built-in profiles against the current tool list
+    fn builtin_profile(&self, name: &str) -> Option<RuntimeProfile> {
+        let description = BUILTIN_PROFILES
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, d)| d.to_string())?;
+
+        let mut config = GlobalRuntimeConfig::default();
+        let mut tool_configs: HashMap<String, ToolConfig> = HashMap::new();
+
+        match name {
+            "read-only" => {
+                for tool in self.list_tools() {
+                    if tool.method != "GET" {
+                        tool_configs.insert(
+                            tool.operation_id,
+                            ToolConfig {
+                                enabled: false,
+                                ..Default::default()
+                            },
+                        );
+                    }
+                }
+            }
+            "demo" => {
+                config.replay_fixtures = true;
+                for tool in self.list_tools() {
+                    tool_configs.insert(
+                        tool.operation_id,
+                        ToolConfig {
+                            dry_run: true,
+                            ..Default::default()
+                        },
+                    );
+                }
+            }
+            "full-access" => {} // defaults: everything enabled
+            _ => return None,
+        }
+
+        Some(RuntimeProfile {
+            name: name.to_string(),
+            description,
+            config,
+            tool_configs,
+        })
+    }
+
+    /// List available profiles: built-ins first, then saved custom ones
+    pub fn list_profiles(&self) -> Vec<ProfileInfo> {
+        let mut profiles: Vec<ProfileInfo> = BUILTIN_PROFILES
+            .iter()
+            .map(|(name, description)| ProfileInfo {
+                name: name.to_string(),
+                description: description.to_string(),
+                builtin: true,
+            })
+            .collect();
+        profiles.extend(list_saved_profiles());
+        profiles
+    }
+
+    /// Snapshot the current configuration as a named custom profile
+    pub fn save_current_profile(&self, name: &str, description: &str) -> Result<(), String> {
+        if !super::valid_session_name(name) {
+            return Err(format!(
+                "Invalid profile name '{}': use letters, digits, '-' or '_'",
+                name
+            ));
+        }
+        if BUILTIN_PROFILES.iter().any(|(n, _)| *n == name) {
+            return Err(format!("'{}' is a built-in profile name", name));
+        }
+        let profile = RuntimeProfile {
+            name: name.to_string(),
+            description: description.to_string(),
+            config: self.get_global_config(),
+            tool_configs: self.get_all_tool_configs(),
+        };
+        save_profile(&profile)
+    }
+
+    /// Activate a profile by name (built-in or saved), replacing the current
+    /// runtime configuration. Returns the profile's description.
+    pub fn activate_profile(&self, name: &str) -> Result<String, String> {
+        let profile = match self.builtin_profile(name) {
+            Some(p) => p,
+            None => load_profile(name)?,
+        };
+        let description = profile.description.clone();
+        self.set_full_config(profile.config, profile.tool_configs);
+        tracing::info!("Activated runtime profile '{}'", name);
+        Ok(description)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::AppState;
+    use std::sync::Arc;
+
+    fn create_test_runtime() -> Arc<ToolRuntime> {
+        let state = AppState::new(
+            "test-token".to_string(),
+            "https://jira.test".to_string(),
+            "test@test.com".to_string(),
+            "api-token".to_string(),
+            "gemini-key".to_string(),
+        );
+        let runtime = ToolRuntime::new(state);
+        runtime.set_openapi_spec(serde_json::json!({
+            "paths": {
+                "/jira/list": {"get": {"summary": "List issues"}},
+                "/jira/comment": {"post": {"summary": "Add comment"}}
+            }
+        }));
+        runtime
+    }
+
+    #[test]
+    fn test_read_only_profile_disables_mutating_tools() {
+        let runtime = create_test_runtime();
+        runtime.activate_profile("read-only").unwrap();
+
+        assert!(runtime.get_tool_config("get_jira_list").enabled);
+        assert!(!runtime.get_tool_config("post_jira_comment").enabled);
+    }
+
+    #[test]
+    fn test_demo_profile_enables_replay_and_dry_run() {
+        let runtime = create_test_runtime();
+        runtime.activate_profile("demo").unwrap();
+
+        assert!(runtime.get_global_config().replay_fixtures);
+        assert!(runtime.get_tool_config("get_jira_list").dry_run);
+    }
+
+    #[test]
+    fn test_unknown_profile_errors() {
+        let runtime = create_test_runtime();
+        assert!(runtime.activate_profile("no-such-profile").is_err());
+    }
+}
@@ -0,0 +1,163 @@
+//! Response size clamps and truncation for ToolRuntime
+//!
+//! Large tool responses (a 5 MB diff, say) can blow an agent's context
+//! window. Each tool can set `max_response_bytes` with a truncation strategy:
+//! `head`/`tail` keep the first/last part of the serialized response, while
+//! `structural` drops known-heavy fields (`patch` first) before falling back
+//! to head truncation. Truncated responses carry a `_truncated` annotation
+//! with the original size and what was cut.
+
+use serde::{Deserialize, Serialize};
+
+/// Fields dropped by the structural strategy, heaviest first
+const HEAVY_FIELDS: &[&str] = &["patch", "content", "diff"];
+
+/// How to shrink a response that exceeds its byte budget
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TruncationStrategy {
+    /// Keep the beginning of the serialized response
+    Head,
+    /// Keep the end of the serialized response
+    Tail,
+    /// Drop heavy fields (`patch` first), then fall back to head
+    #[default]
+    Structural,
+}
+
+/// Clamp a response to `max_bytes` using the given strategy.
+///
+/// Responses already within budget come back unchanged; anything truncated
+/// gains a `_truncated` object describing the original size and strategy.
+pub fn truncate_response(
+    response: serde_json::Value,
+    max_bytes: usize,
+    strategy: TruncationStrategy,
+) -> serde_json::Value {
+    let serialized = response.to_string();
+    if serialized.len() <= max_bytes {
+        return response;
+    }
+    let original_bytes = serialized.len();
+
+    if strategy == TruncationStrategy::Structural {
+        let mut stripped = response;
+        let mut dropped = Vec::new();
+        for field in HEAVY_FIELDS {
+            drop_field(&mut stripped, field, &mut dropped);
+            if !dropped.is_empty() && stripped.to_string().len() <= max_bytes {
+                break;
+            }
+        }
+        if stripped.to_string().len() <= max_bytes {
+            if let Some(obj) = stripped.as_object_mut() {
+                obj.insert(
+                    "_truncated".to_string(),
+                    serde_json::json!({
+                        "strategy": "structural",
+                        "originalBytes": original_bytes,
+                        "droppedFields": dropped,
+                    }),
+                );
+            }
+            return stripped;
+        }
+        // Still too big — fall back to head truncation of the stripped value
+        return clip_serialized(&stripped.to_string(), max_bytes, TruncationStrategy::Head, original_bytes);
+    }
+
+    clip_serialized(&serialized, max_bytes, strategy, original_bytes)
+}
+
+/// Replace every `field` value in the tree with a marker string.
+fn drop_field(value: &mut serde_json::Value, field: &str, dropped: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if key == field && !child.is_null() {
+                    *child = serde_json::json!("[truncated]");
+                    if !dropped.contains(&field.to_string()) {
+                        dropped.push(field.to_string());
+                    }
+                } else {
+                    drop_field(child, field, dropped);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                drop_field(item, field, dropped);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Keep the head or tail of a serialized response, wrapped with metadata.
+fn clip_serialized(
+    serialized: &str,
+    max_bytes: usize,
+    strategy: TruncationStrategy,
+    original_bytes: usize,
+) -> serde_json::Value {
+    let snippet = match strategy {
+        TruncationStrategy::Tail => {
+            let mut start = serialized.len().saturating_sub(max_bytes);
+            while start < serialized.len() && !serialized.is_char_boundary(start) {
+                start += 1;
+            }
+            &serialized[start..]
+        }
+        _ => {
+            let mut end = max_bytes.min(serialized.len());
+            while end > 0 && !serialized.is_char_boundary(end) {
+                end -= 1;
+            }
+            &serialized[..end]
+        }
+    };
+
+    serde_json::json!({
+        "_truncated": {
+            "strategy": strategy,
+            "originalBytes": original_bytes,
+            "keptBytes": snippet.len(),
+        },
+        "content": snippet,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_within_budget_unchanged() {
+        let response = serde_json::json!({"small": true});
+        let result = truncate_response(response.clone(), 10_000, TruncationStrategy::Structural);
+        assert_eq!(result, response);
+    }
+
+    #[test]
+    fn test_structural_drops_patch_first() {
+        let response = serde_json::json!({
+            "files": [{"path": "a.rs", "patch": "x".repeat(500)}],
+            "total": 1
+        });
+        let result = truncate_response(response, 200, TruncationStrategy::Structural);
+
+        assert_eq!(result["files"][0]["patch"], "[truncated]");
+        assert_eq!(result["total"], 1);
+        assert_eq!(result["_truncated"]["droppedFields"][0], "patch");
+    }
+
+    #[test]
+    fn test_head_truncation_wraps_with_metadata() {
+        let response = serde_json::json!({"data": "y".repeat(1000)});
+        let result = truncate_response(response, 100, TruncationStrategy::Head);
+
+        assert!(result["content"].as_str().unwrap().len() <= 100);
+        assert_eq!(result["_truncated"]["strategy"], "head");
+        assert!(result["_truncated"]["originalBytes"].as_u64().unwrap() > 1000);
+    }
+}
@@ -147,6 +147,9 @@ pub enum ToolRuntimeError {
     #[error("Rate limit exceeded")]
     RateLimited,
 
+    #[error("Budget exceeded for tool '{0}'")]
+    BudgetExceeded(String),
+
     #[error("Invalid arguments: {0}")]
     InvalidArguments(String),
 
@@ -195,26 +198,6 @@ pub struct ToolConfigRequest {
     pub config: super::ToolConfig,
 }
 
-/// Response listing all tools
-#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
-#[serde(rename_all = "camelCase")]
-pub struct ToolsListResponse {
-    /// List of available tools
-    pub tools: Vec<super::ToolInfo>,
-    /// Total count
-    pub total: usize,
-}
-
-/// Response for tool execution logs
-#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
-#[serde(rename_all = "camelCase")]
-pub struct ToolExecutionLogsResponse {
-    /// List of log entries
-    pub logs: Vec<super::ToolExecutionLog>,
-    /// Total count
-    pub total: usize,
-}
-
 /// Response for global runtime config
 #[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -1,6 +1,11 @@
 //! OpenAPI schema validation for ToolRuntime
 //!
-//! Validates requests and responses against the OpenAPI specification.
+//! Validates requests and responses against the OpenAPI specification using
+//! real JSON Schema validation (the `jsonschema` crate). Schemas come from
+//! the operation's parameters, requestBody and response definitions; `$ref`
+//! pointers into `#/components/schemas` are resolved by embedding the spec's
+//! components alongside the schema being compiled. Every violation is
+//! reported with its JSON-pointer path into the offending value.
 
 use super::{ToolRuntime, ValidationResult};
 
@@ -99,18 +104,18 @@ impl ToolRuntime {
         // Validate 200 response schema
         if let Some(responses) = operation.get("responses").and_then(|r| r.as_object()) {
             if let Some(ok_response) = responses.get("200").or_else(|| responses.get("201")) {
-                if let Some(content) = ok_response.get("content") {
-                    if let Some(json_content) = content.get("application/json") {
-                        if let Some(schema) = json_content.get("schema") {
-                            self.validate_value_against_schema(
-                                response,
-                                schema,
-                                "response",
-                                &mut result,
-                                spec,
-                            );
-                        }
-                    }
+                if let Some(schema) = ok_response
+                    .get("content")
+                    .and_then(|c| c.get("application/json"))
+                    .and_then(|j| j.get("schema"))
+                {
+                    self.validate_value_against_schema(
+                        response,
+                        schema,
+                        "response",
+                        &mut result,
+                        spec,
+                    );
                 }
             }
         }
@@ -130,7 +135,7 @@ impl ToolRuntime {
             .get(method)
     }
 
-    /// Validate parameters against schema
+    /// Validate parameters against their schemas
     fn validate_parameters(
         &self,
         parameters: &[serde_json::Value],
@@ -147,7 +152,7 @@ impl ToolRuntime {
 
             // Check if required parameter is present
             let value = args_obj.and_then(|obj| obj.get(name));
-            
+
             if required && value.is_none() {
                 result.add_error(format!("Missing required parameter: {}", name));
                 continue;
@@ -160,7 +165,7 @@ impl ToolRuntime {
         }
     }
 
-    /// Validate request body against schema
+    /// Validate request body against its schema
     fn validate_request_body(
         &self,
         request_body: &serde_json::Value,
@@ -174,145 +179,55 @@ impl ToolRuntime {
             .and_then(|j| j.get("schema"));
 
         if let Some(schema) = schema {
-            // Resolve $ref if present
-            let resolved_schema = if let Some(ref_path) = schema.get("$ref").and_then(|r| r.as_str()) {
-                self.resolve_ref(spec, ref_path)
-            } else {
-                Some(schema)
-            };
-
-            if let Some(resolved_schema) = resolved_schema {
-                // Check required fields
-                if let Some(required) = resolved_schema.get("required").and_then(|r| r.as_array()) {
-                    let args_obj = args.as_object();
-                    for req_field in required {
-                        if let Some(field_name) = req_field.as_str() {
-                            if args_obj.map(|obj| !obj.contains_key(field_name)).unwrap_or(true) {
-                                result.add_error(format!("Missing required field: {}", field_name));
-                            }
-                        }
-                    }
-                }
-
-                // Validate against schema
-                self.validate_value_against_schema(args, resolved_schema, "body", result, spec);
-            }
+            self.validate_value_against_schema(args, schema, "body", result, spec);
         }
     }
 
-    /// Validate a value against a JSON schema
+    /// Validate a value against a JSON schema with full `$ref` resolution.
+    ///
+    /// The schema is compiled with the spec's `components` embedded so that
+    /// `#/components/schemas/...` references resolve. Each violation is
+    /// reported as `<base><json-pointer>: <message>`.
     fn validate_value_against_schema(
         &self,
         value: &serde_json::Value,
         schema: &serde_json::Value,
-        path: &str,
+        base: &str,
         result: &mut ValidationResult,
         spec: &serde_json::Value,
     ) {
-        // Resolve $ref if present
-        let schema = if let Some(ref_path) = schema.get("$ref").and_then(|r| r.as_str()) {
-            match self.resolve_ref(spec, ref_path) {
-                Some(s) => s,
-                None => {
-                    result.add_warning(format!("Could not resolve $ref: {}", ref_path));
-                    return;
-                }
+        let root = Self::embed_components(schema, spec);
+        let validator = match jsonschema::validator_for(&root) {
+            Ok(v) => v,
+            Err(e) => {
+                result.add_warning(format!("{}: schema failed to compile: {}", base, e));
+                return;
             }
-        } else {
-            schema
         };
 
-        // Get expected type
-        let expected_type = schema.get("type").and_then(|t| t.as_str());
-
-        match expected_type {
-            Some("string") => {
-                if !value.is_string() && !value.is_null() {
-                    result.add_error(format!("{}: expected string, got {:?}", path, value));
-                }
-                // Check enum values
-                if let Some(enum_values) = schema.get("enum").and_then(|e| e.as_array()) {
-                    if let Some(str_val) = value.as_str() {
-                        let valid = enum_values.iter().any(|v| v.as_str() == Some(str_val));
-                        if !valid {
-                            result.add_error(format!(
-                                "{}: value '{}' not in enum {:?}",
-                                path, str_val, enum_values
-                            ));
-                        }
-                    }
-                }
-            }
-            Some("integer") | Some("number") => {
-                if !value.is_number() && !value.is_null() {
-                    result.add_error(format!("{}: expected number, got {:?}", path, value));
-                }
-                // Check min/max
-                if let Some(num) = value.as_f64() {
-                    if let Some(min) = schema.get("minimum").and_then(|m| m.as_f64()) {
-                        if num < min {
-                            result.add_error(format!("{}: {} is less than minimum {}", path, num, min));
-                        }
-                    }
-                    if let Some(max) = schema.get("maximum").and_then(|m| m.as_f64()) {
-                        if num > max {
-                            result.add_error(format!("{}: {} is greater than maximum {}", path, num, max));
-                        }
-                    }
-                }
-            }
-            Some("boolean") => {
-                if !value.is_boolean() && !value.is_null() {
-                    result.add_error(format!("{}: expected boolean, got {:?}", path, value));
-                }
-            }
-            Some("array") => {
-                if !value.is_array() && !value.is_null() {
-                    result.add_error(format!("{}: expected array, got {:?}", path, value));
-                }
-                // Validate array items
-                if let (Some(arr), Some(items_schema)) = (value.as_array(), schema.get("items")) {
-                    for (i, item) in arr.iter().enumerate() {
-                        let item_path = format!("{}[{}]", path, i);
-                        self.validate_value_against_schema(item, items_schema, &item_path, result, spec);
-                    }
-                }
-            }
-            Some("object") => {
-                if !value.is_object() && !value.is_null() {
-                    result.add_error(format!("{}: expected object, got {:?}", path, value));
-                }
-                // Validate object properties
-                if let (Some(obj), Some(properties)) = (value.as_object(), schema.get("properties").and_then(|p| p.as_object())) {
-                    for (prop_name, prop_schema) in properties {
-                        if let Some(prop_value) = obj.get(prop_name) {
-                            let prop_path = format!("{}.{}", path, prop_name);
-                            self.validate_value_against_schema(prop_value, prop_schema, &prop_path, result, spec);
-                        }
-                    }
-                }
-            }
-            None => {
-                // No type specified, skip validation
-                result.add_warning(format!("{}: no type specified in schema", path));
-            }
-            Some(t) => {
-                result.add_warning(format!("{}: unknown type '{}'", path, t));
+        for error in validator.iter_errors(value) {
+            let pointer = error.instance_path().to_string();
+            if pointer.is_empty() {
+                result.add_error(format!("{}: {}", base, error));
+            } else {
+                result.add_error(format!("{}{}: {}", base, pointer, error));
             }
         }
     }
 
-    /// Resolve a $ref pointer in the OpenAPI spec
-    fn resolve_ref<'a>(&self, spec: &'a serde_json::Value, ref_path: &str) -> Option<&'a serde_json::Value> {
-        if !ref_path.starts_with("#/") {
-            return None;
-        }
-        let parts: Vec<&str> = ref_path[2..].split('/').collect();
-        let mut current = spec;
-        for part in parts {
-            current = current.get(part)?;
+    /// Build a standalone schema document that still resolves the spec's
+    /// `#/components/schemas/...` pointers: the operation-level schema at the
+    /// root with the spec's `components` grafted in beside it.
+    fn embed_components(
+        schema: &serde_json::Value,
+        spec: &serde_json::Value,
+    ) -> serde_json::Value {
+        let mut root = schema.clone();
+        if let (Some(obj), Some(components)) = (root.as_object_mut(), spec.get("components")) {
+            obj.entry("components".to_string())
+                .or_insert_with(|| components.clone());
         }
-        Some(current)
+        root
     }
 }
 
@@ -331,7 +246,7 @@ mod tests {
             "gemini-key".to_string(),
         );
         let runtime = ToolRuntime::new(state);
-        
+
         // Set a minimal OpenAPI spec
         runtime.set_openapi_spec(serde_json::json!({
             "paths": {
@@ -367,22 +282,49 @@ mod tests {
                             }
                         }
                     }
+                },
+                "/jira/comment": {
+                    "post": {
+                        "requestBody": {
+                            "content": {
+                                "application/json": {
+                                    "schema": {"$ref": "#/components/schemas/CommentRequest"}
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "CommentRequest": {
+                        "type": "object",
+                        "required": ["issueKey", "body"],
+                        "properties": {
+                            "issueKey": {"type": "string"},
+                            "body": {"type": "string"},
+                            "mentions": {
+                                "type": "array",
+                                "items": {"type": "string"}
+                            }
+                        }
+                    }
                 }
             }
         }));
-        
+
         runtime
     }
 
     #[test]
     fn test_validate_request() {
         let runtime = create_test_runtime_with_spec();
-        
+
         let args = serde_json::json!({
             "jql": "assignee = currentUser()",
             "maxResults": 50
         });
-        
+
         let result = runtime.validate_request("get_jira_list", &args);
         assert!(result.valid);
         assert!(result.errors.is_empty());
@@ -391,26 +333,46 @@ mod tests {
     #[test]
     fn test_validate_request_invalid_type() {
         let runtime = create_test_runtime_with_spec();
-        
+
         let args = serde_json::json!({
             "maxResults": "not a number"
         });
-        
+
         let result = runtime.validate_request("get_jira_list", &args);
         assert!(!result.valid);
-        assert!(result.errors.iter().any(|e| e.contains("expected number")));
+        assert!(result.errors.iter().any(|e| e.contains("is not of type")));
     }
 
     #[test]
     fn test_validate_request_out_of_range() {
         let runtime = create_test_runtime_with_spec();
-        
+
         let args = serde_json::json!({
             "maxResults": 5000
         });
-        
+
         let result = runtime.validate_request("get_jira_list", &args);
         assert!(!result.valid);
-        assert!(result.errors.iter().any(|e| e.contains("greater than maximum")));
+        assert!(result.errors.iter().any(|e| e.contains("greater than the maximum")));
+    }
+
+    #[test]
+    fn test_validate_request_body_resolves_ref() {
+        let runtime = create_test_runtime_with_spec();
+
+        // Missing required "body", wrong type nested inside "mentions"
+        let args = serde_json::json!({
+            "issueKey": "PROJ-1",
+            "mentions": ["alice", 42]
+        });
+
+        let result = runtime.validate_request("post_jira_comment", &args);
+        assert!(!result.valid);
+        assert!(result
+            .errors
+            .iter()
+            .any(|e| e.contains("required") && e.contains("body")));
+        // Violations inside nested values carry JSON-pointer paths
+        assert!(result.errors.iter().any(|e| e.contains("body/mentions/1")));
     }
 }
@@ -0,0 +1,373 @@
+//! TypeScript client generation from the runtime OpenAPI spec.
+//!
+//! Generates a single self-contained `.ts` file containing:
+//!
+//! - An `export interface` / type alias per component schema
+//! - An `ApiClient` class with one typed method per operation, named after
+//!   its `operationId`, using `fetch` with the bearer token
+//!
+//! The generator walks the already-serialized spec (`serde_json::Value`) —
+//! the same document served at `/openapi.json` — so the emitted client can
+//! never drift from what the handlers actually expose. Regenerate via the
+//! `generate_ts_client` Tauri command after handler changes.
+//!
+//! The mapping is deliberately pragmatic: schemas the generator does not
+//! understand degrade to `unknown` rather than failing, so an exotic schema
+//! never blocks regeneration.
+
+use serde_json::Value;
+
+/// Default output location, relative to the `src-tauri` working directory
+/// used in dev builds: the frontend source tree.
+pub const DEFAULT_OUTPUT: &str = "../src/lib/api/client.generated.ts";
+
+/// Generate the full TypeScript client source for a spec.
+pub fn generate_typescript_client(spec: &Value) -> String {
+    let mut out = String::new();
+    out.push_str("// AUTO-GENERATED from the runtime OpenAPI spec — do not edit.\n");
+    out.push_str("// Regenerate with the `generate_ts_client` Tauri command.\n");
+    if let Some(hash) = spec["info"]["x-spec-hash"].as_str() {
+        out.push_str(&format!("// Spec hash: {}\n", hash));
+    }
+    out.push('\n');
+
+    emit_schemas(spec, &mut out);
+    emit_client(spec, &mut out);
+    out
+}
+
+/// Emit one `export interface`/`export type` per component schema.
+fn emit_schemas(spec: &Value, out: &mut String) {
+    let Some(schemas) = spec["components"]["schemas"].as_object() else {
+        return;
+    };
+    for (name, schema) in schemas {
+        let ts_name = sanitize_ident(name);
+        if let Some(props) = schema.get("properties").and_then(|p| p.as_object()) {
+            let required: Vec<&str> = schema["required"]
+                .as_array()
+                .map(|r| r.iter().filter_map(|v| v.as_str()).collect())
+                .unwrap_or_default();
+            out.push_str(&format!("export interface {} {{\n", ts_name));
+            for (prop, prop_schema) in props {
+                let optional = if required.contains(&prop.as_str()) { "" } else { "?" };
+                out.push_str(&format!(
+                    "  {}{}: {};\n",
+                    quote_prop(prop),
+                    optional,
+                    ts_type(prop_schema)
+                ));
+            }
+            out.push_str("}\n\n");
+        } else {
+            out.push_str(&format!("export type {} = {};\n\n", ts_name, ts_type(schema)));
+        }
+    }
+}
+
+/// Emit the `ApiClient` class with one method per operation.
+fn emit_client(spec: &Value, out: &mut String) {
+    out.push_str(
+        "export class ApiClient {\n\
+         \x20 constructor(private baseUrl: string, private token: string) {}\n\n\
+         \x20 private async request<T>(method: string, path: string, query?: Record<string, unknown>, body?: unknown): Promise<T> {\n\
+         \x20   const url = new URL(this.baseUrl + path);\n\
+         \x20   for (const [k, v] of Object.entries(query ?? {})) {\n\
+         \x20     if (v !== undefined && v !== null) url.searchParams.set(k, String(v));\n\
+         \x20   }\n\
+         \x20   const res = await fetch(url, {\n\
+         \x20     method,\n\
+         \x20     headers: {\n\
+         \x20       Authorization: `Bearer ${this.token}`,\n\
+         \x20       ...(body !== undefined ? { 'Content-Type': 'application/json' } : {}),\n\
+         \x20     },\n\
+         \x20     body: body !== undefined ? JSON.stringify(body) : undefined,\n\
+         \x20   });\n\
+         \x20   if (!res.ok) throw new Error(`${method} ${path} failed: ${res.status} ${await res.text()}`);\n\
+         \x20   return (await res.json()) as T;\n\
+         \x20 }\n\n",
+    );
+
+    if let Some(paths) = spec["paths"].as_object() {
+        for (path, item) in paths {
+            let Some(ops) = item.as_object() else { continue };
+            for (method, op) in ops {
+                if !matches!(
+                    method.as_str(),
+                    "get" | "put" | "post" | "delete" | "patch"
+                ) {
+                    continue;
+                }
+                emit_operation(path, method, op, out);
+            }
+        }
+    }
+
+    out.push_str("}\n");
+}
+
+/// Emit one typed method for an operation.
+fn emit_operation(path: &str, method: &str, op: &Value, out: &mut String) {
+    let name = op["operationId"]
+        .as_str()
+        .map(sanitize_ident)
+        .unwrap_or_else(|| sanitize_ident(&format!("{}_{}", method, path)));
+
+    let mut args: Vec<String> = Vec::new();
+    let mut query_params: Vec<(String, bool)> = Vec::new();
+
+    // Path params become leading arguments; the path string becomes a template.
+    let mut ts_path = path.to_string();
+    if let Some(params) = op["parameters"].as_array() {
+        for param in params {
+            let Some(pname) = param["name"].as_str() else { continue };
+            let ty = ts_type(&param["schema"]);
+            match param["in"].as_str() {
+                Some("path") => {
+                    let ident = sanitize_ident(pname);
+                    args.push(format!("{}: {}", ident, ty));
+                    ts_path = ts_path.replace(
+                        &format!("{{{}}}", pname),
+                        &format!("${{encodeURIComponent(String({}))}}", ident),
+                    );
+                }
+                Some("query") => {
+                    let required = param["required"].as_bool().unwrap_or(false);
+                    query_params.push((pname.to_string(), required));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // Query params are bundled into a single (usually optional) object arg.
+    let has_query = !query_params.is_empty();
+    if has_query {
+        let fields: Vec<String> = query_params
+            .iter()
+            .map(|(name, required)| {
+                format!("{}{}: unknown", quote_prop(name), if *required { "" } else { "?" })
+            })
+            .collect();
+        let all_optional = query_params.iter().all(|(_, required)| !required);
+        args.push(format!(
+            "query{}: {{ {} }}",
+            if all_optional { "?" } else { "" },
+            fields.join("; ")
+        ));
+    }
+
+    let has_body = op["requestBody"].is_object();
+    if has_body {
+        let body_ty = ts_type(&op["requestBody"]["content"]["application/json"]["schema"]);
+        args.push(format!("body: {}", body_ty));
+    }
+
+    let ok_schema = &op["responses"]["200"]["content"]["application/json"]["schema"];
+    let ret = if ok_schema.is_object() {
+        ts_type(ok_schema)
+    } else {
+        "unknown".to_string()
+    };
+
+    out.push_str(&format!(
+        "  {}({}): Promise<{}> {{\n    return this.request('{}', `{}`{}{});\n  }}\n\n",
+        name,
+        args.join(", "),
+        ret,
+        method.to_uppercase(),
+        ts_path,
+        if has_query { ", query" } else { ", undefined" },
+        if has_body { ", body" } else { "" },
+    ));
+}
+
+/// Map a JSON schema fragment to a TypeScript type expression.
+fn ts_type(schema: &Value) -> String {
+    if let Some(reference) = schema["$ref"].as_str() {
+        return sanitize_ident(reference.rsplit('/').next().unwrap_or("unknown"));
+    }
+    if let Some(variants) = schema["enum"].as_array() {
+        let literals: Vec<String> = variants
+            .iter()
+            .map(|v| match v.as_str() {
+                Some(s) => format!("'{}'", s),
+                None => v.to_string(),
+            })
+            .collect();
+        if !literals.is_empty() {
+            return literals.join(" | ");
+        }
+    }
+    for combinator in ["oneOf", "anyOf"] {
+        if let Some(variants) = schema[combinator].as_array() {
+            let types: Vec<String> = variants.iter().map(ts_type).collect();
+            if !types.is_empty() {
+                return types.join(" | ");
+            }
+        }
+    }
+    if let Some(parts) = schema["allOf"].as_array() {
+        let types: Vec<String> = parts.iter().map(ts_type).collect();
+        if !types.is_empty() {
+            return types.join(" & ");
+        }
+    }
+
+    // OpenAPI 3.1 uses `type: ["string", "null"]` for nullability
+    let (base_type, nullable) = match &schema["type"] {
+        Value::String(s) => (Some(s.as_str()), false),
+        Value::Array(types) => {
+            let non_null = types
+                .iter()
+                .filter_map(|t| t.as_str())
+                .find(|t| *t != "null");
+            (non_null, types.iter().any(|t| t == "null"))
+        }
+        _ => (None, false),
+    };
+
+    let base = match base_type {
+        Some("string") => "string".to_string(),
+        Some("integer") | Some("number") => "number".to_string(),
+        Some("boolean") => "boolean".to_string(),
+        Some("array") => format!("{}[]", ts_type(&schema["items"])),
+        Some("object") => {
+            if let Some(props) = schema["properties"].as_object() {
+                let required: Vec<&str> = schema["required"]
+                    .as_array()
+                    .map(|r| r.iter().filter_map(|v| v.as_str()).collect())
+                    .unwrap_or_default();
+                let fields: Vec<String> = props
+                    .iter()
+                    .map(|(name, prop)| {
+                        format!(
+                            "{}{}: {}",
+                            quote_prop(name),
+                            if required.contains(&name.as_str()) { "" } else { "?" },
+                            ts_type(prop)
+                        )
+                    })
+                    .collect();
+                format!("{{ {} }}", fields.join("; "))
+            } else if schema["additionalProperties"].is_object() {
+                format!("Record<string, {}>", ts_type(&schema["additionalProperties"]))
+            } else {
+                "Record<string, unknown>".to_string()
+            }
+        }
+        _ => "unknown".to_string(),
+    };
+
+    if nullable {
+        format!("{} | null", base)
+    } else {
+        base
+    }
+}
+
+/// Turn an arbitrary name (operationId, schema name, path) into a valid
+/// TypeScript identifier. Generic schema names like `Page_ToolInfo` pass
+/// through; separators become underscores.
+fn sanitize_ident(name: &str) -> String {
+    let mut ident: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+    if ident.chars().next().map(|c| c.is_ascii_digit()).unwrap_or(true) {
+        ident.insert(0, '_');
+    }
+    ident
+}
+
+/// Quote a property name when it is not a plain identifier.
+fn quote_prop(name: &str) -> String {
+    let plain = !name.is_empty()
+        && name.chars().next().map(|c| c.is_ascii_alphabetic() || c == '_').unwrap_or(false)
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
+        name.to_string()
+    } else {
+        format!("'{}'", name)
+    }
+}
+
+/// Generate the client and write it to `output` (or [`DEFAULT_OUTPUT`]),
+/// creating parent directories as needed. Returns the path written.
+pub fn write_typescript_client(
+    spec: &Value,
+    output: Option<&str>,
+) -> Result<String, String> {
+    let path = std::path::PathBuf::from(output.unwrap_or(DEFAULT_OUTPUT));
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+    }
+    let source = generate_typescript_client(spec);
+    std::fs::write(&path, &source)
+        .map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+    log::info!(
+        "TS client: wrote {} bytes to {:?} ({} interfaces)",
+        source.len(),
+        path,
+        source.matches("export interface").count()
+    );
+    Ok(path.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ts_type_primitives_and_refs() {
+        assert_eq!(ts_type(&serde_json::json!({"type": "string"})), "string");
+        assert_eq!(ts_type(&serde_json::json!({"type": "integer"})), "number");
+        assert_eq!(
+            ts_type(&serde_json::json!({"type": ["string", "null"]})),
+            "string | null"
+        );
+        assert_eq!(
+            ts_type(&serde_json::json!({"$ref": "#/components/schemas/ToolInfo"})),
+            "ToolInfo"
+        );
+        assert_eq!(
+            ts_type(&serde_json::json!({"type": "array", "items": {"type": "boolean"}})),
+            "boolean[]"
+        );
+    }
+
+    #[test]
+    fn test_enum_becomes_literal_union() {
+        let schema = serde_json::json!({"type": "string", "enum": ["head", "tail"]});
+        assert_eq!(ts_type(&schema), "'head' | 'tail'");
+    }
+
+    #[test]
+    fn test_generates_interface_and_method() {
+        let spec = serde_json::json!({
+            "paths": {
+                "/tools/{id}": {
+                    "get": {
+                        "operationId": "get_tool",
+                        "parameters": [
+                            {"name": "id", "in": "path", "schema": {"type": "string"}},
+                            {"name": "verbose", "in": "query", "schema": {"type": "boolean"}}
+                        ],
+                        "responses": {"200": {"content": {"application/json": {
+                            "schema": {"$ref": "#/components/schemas/Tool"}
+                        }}}}
+                    }
+                }
+            },
+            "components": {"schemas": {
+                "Tool": {"type": "object", "properties": {"name": {"type": "string"}}, "required": ["name"]}
+            }}
+        });
+        let ts = generate_typescript_client(&spec);
+        assert!(ts.contains("export interface Tool {"));
+        assert!(ts.contains("name: string;"));
+        assert!(ts.contains("get_tool(id: string, query?: { verbose?: unknown }): Promise<Tool>"));
+        assert!(ts.contains("${encodeURIComponent(String(id))}"));
+    }
+}